@@ -0,0 +1,24 @@
+#import kayak_ui::bindings::globals
+#import kayak_ui::sample_quad::sample_quad
+#import kayak_ui::vertex_output::VertexOutput
+
+struct GradientMaterial {
+    from_color: vec4<f32>,
+    to_color: vec4<f32>,
+    speed: f32,
+};
+
+@group(3) @binding(0)
+var<uniform> material: GradientMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Keep the shape (rounded corners) of the quad Kayak would have drawn
+    let shape = sample_quad(in);
+
+    // Goes from 0.0 to 1.0 across the quad
+    let uv = in.pos / in.size;
+    let t = 0.5 + 0.5 * sin(globals.time * material.speed + (uv.x + uv.y) * 3.14159);
+    let color = mix(material.from_color, material.to_color, t);
+    return vec4(color.rgb, color.a * shape.a);
+}
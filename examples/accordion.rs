@@ -30,7 +30,7 @@ fn startup(
             >
                 <ScrollContextProviderBundle>
                     <ScrollBoxBundle>
-                        <AccordionContextBundle accordion={AccordionContextProvider { allow_only_one: true, ..Default::default() }}>
+                        <AccordionContextBundle accordion={AccordionContextProvider { allow_multiple: false, ..Default::default() }}>
                             <AccordionSummaryBundle>
                                 <TextWidgetBundle
                                     text={TextProps {
@@ -55,6 +55,7 @@ fn current_count_render(
                 <KButtonBundle
                     button={KButton {
                         text: "Click me!".into(),
+                        ..Default::default()
                     }}
                     on_event={OnEvent::new(
                         move |In(_entity): In<Entity>,
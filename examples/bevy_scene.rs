@@ -233,6 +233,7 @@ fn startup(
                 <KButtonBundle
                     button={KButton {
                         text: "Change Tile Color".into(),
+                        ..Default::default()
                     }}
                     on_event={handle_change_color}
                     styles={button_styles}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, KStyle, *};
+
+const VALUES: [f32; 8] = [0.2, 0.5, 0.35, 0.8, 0.6, 0.9, 0.4, 0.7];
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let draw = CanvasDraw::new(|painter, rect| {
+        let bar_width = rect.width / VALUES.len() as f32;
+        let points = VALUES
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Vec2::new(
+                    (index as f32 + 0.5) * bar_width,
+                    rect.height * (1.0 - value),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (index, point) in points.iter().enumerate() {
+            painter.rounded_rect(
+                Vec2::new(index as f32 * bar_width + 4.0, point.y),
+                Vec2::new(bar_width - 8.0, rect.height - point.y),
+                4.0,
+                Color::rgb(0.25, 0.35, 0.6),
+            );
+        }
+
+        for line in points.windows(2) {
+            painter.line(line[0], line[1], 3.0, Color::rgb(0.9, 0.6, 0.2));
+        }
+
+        for point in points.iter() {
+            painter.circle(*point, 5.0, Color::WHITE);
+        }
+
+        painter.triangle(
+            Vec2::new(rect.width - 30.0, 10.0),
+            Vec2::new(rect.width - 10.0, 10.0),
+            Vec2::new(rect.width - 20.0, 30.0),
+            Color::rgb(0.8, 0.2, 0.3),
+        );
+    });
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Canvas".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(400.0, 300.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ClipBundle>
+                    <CanvasBundle canvas={CanvasProps { draw }} />
+                </ClipBundle>
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
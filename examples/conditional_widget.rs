@@ -47,6 +47,7 @@ fn my_widget_render(
                     }}
                     button={KButton {
                         text: "Show Window".into(),
+                        ..Default::default()
                     }}
                     on_event={OnEvent::new(
                         move |In(_entity): In<Entity>,
@@ -74,7 +75,7 @@ fn my_widget_render(
                             }}
                         >
                             <KButtonBundle
-                                button={KButton { text: "Hide Window".into() }}
+                                button={KButton { text: "Hide Window".into(), ..Default::default() }}
                                 on_event={OnEvent::new(
                                     move |In(_entity): In<Entity>,
                                     mut event: ResMut<KEvent>,
@@ -281,7 +281,7 @@ fn update_theme_demo(
                                 }}
                             />
                             <KButtonBundle
-                                button={KButton { text: "BUTTON".into() }}
+                                button={KButton { text: "BUTTON".into(), ..Default::default() }}
                                 styles={btn_style}
                             />
                             {
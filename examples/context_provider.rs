@@ -0,0 +1,184 @@
+//! This example demonstrates how to share a value with descendants using the generic
+//! `ContextProvider` widget instead of hand-rolling a provider like `WindowContextProvider`.
+//!
+//! The `Counter` widget provides its current count, which is read several levels down by the
+//! `CountLabel` widget without passing it through the props of every widget in between.
+
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+/// The value shared through the context
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+struct Count(u32);
+
+type CountProviderBundle = ContextProviderBundle<Count>;
+
+#[derive(Component, Default, PartialEq, Clone)]
+struct CountLabel;
+
+impl Widget for CountLabel {}
+
+#[derive(Bundle)]
+struct CountLabelBundle {
+    label: CountLabel,
+    styles: KStyle,
+    widget_name: WidgetName,
+}
+
+impl Default for CountLabelBundle {
+    fn default() -> Self {
+        Self {
+            label: CountLabel,
+            styles: KStyle::default(),
+            widget_name: CountLabel.get_name(),
+        }
+    }
+}
+
+fn count_label_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    count_query: Query<&Count>,
+) -> bool {
+    if let Some(count) = widget_context.get_context(entity, &count_query) {
+        let parent_id = Some(entity);
+        rsx! {
+            <TextWidgetBundle
+                text={TextProps {
+                    content: format!("Current Count: {}", count.0),
+                    size: 16.0,
+                    line_height: Some(40.0),
+                    ..Default::default()
+                }}
+            />
+        };
+    }
+
+    true
+}
+
+#[derive(Component, Default, PartialEq, Clone)]
+struct Counter;
+
+impl Widget for Counter {}
+
+#[derive(Component, Default, PartialEq, Clone)]
+struct CounterState {
+    count: u32,
+}
+
+#[derive(Bundle)]
+struct CounterBundle {
+    counter: Counter,
+    styles: KStyle,
+    widget_name: WidgetName,
+}
+
+impl Default for CounterBundle {
+    fn default() -> Self {
+        Self {
+            counter: Counter,
+            styles: KStyle::default(),
+            widget_name: Counter.get_name(),
+        }
+    }
+}
+
+fn counter_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    query: Query<&CounterState>,
+) -> bool {
+    let state_entity = widget_context.use_state(&mut commands, entity, CounterState::default());
+    if let Ok(state) = query.get(state_entity) {
+        let parent_id = Some(entity);
+        rsx! {
+            <CountProviderBundle
+                context_provider={ContextProvider {
+                    value: Count(state.count),
+                }}
+            >
+                <ElementBundle>
+                    <CountLabelBundle />
+                    <KButtonBundle
+                        button={KButton {
+                            text: "Click me!".into(),
+                            ..Default::default()
+                        }}
+                        on_event={OnEvent::new(
+                            move |In(_entity): In<Entity>,
+                            event: ResMut<KEvent>,
+                            mut query: Query<&mut CounterState>| {
+                                if let EventType::Click(..) = event.event_type {
+                                    if let Ok(mut state) = query.get_mut(state_entity) {
+                                        state.count += 1;
+                                    }
+                                }
+                            },
+                        )}
+                    />
+                </ElementBundle>
+            </CountProviderBundle>
+        };
+    }
+
+    true
+}
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    widget_context.add_plugin(ContextProviderPlugin::<Count>::default());
+    widget_context.add_widget_data::<Counter, CounterState>();
+    widget_context.add_widget_system(
+        Counter.get_name(),
+        widget_update::<Counter, CounterState>,
+        counter_render,
+    );
+    widget_context.add_widget_data::<CountLabel, EmptyState>();
+    widget_context.add_widget_system(
+        CountLabel.get_name(),
+        // Re-render the label whenever the provided count changes
+        widget_update_with_context::<CountLabel, EmptyState, Count>,
+        count_label_render,
+    );
+
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Context Provider Example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <CounterBundle />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+/// A global value shared by every widget that uses it
+#[derive(Resource, Default, PartialEq, Clone, Copy)]
+struct DarkMode(bool);
+
+#[derive(Component, Default, PartialEq, Clone)]
+struct ThemedPanel;
+
+impl Widget for ThemedPanel {}
+
+#[derive(Bundle)]
+struct ThemedPanelBundle {
+    panel: ThemedPanel,
+    styles: KStyle,
+    computed_styles: ComputedStyles,
+    widget_name: WidgetName,
+}
+
+impl Default for ThemedPanelBundle {
+    fn default() -> Self {
+        Self {
+            panel: ThemedPanel,
+            styles: KStyle::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: ThemedPanel.get_name(),
+        }
+    }
+}
+
+fn themed_panel_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    dark_mode: Option<Res<DarkMode>>,
+) -> bool {
+    // Re-renders this widget whenever `DarkMode` changes
+    widget_context.use_global::<DarkMode>(&mut commands, entity);
+    let is_dark = dark_mode.is_some_and(|dark_mode| dark_mode.0);
+    let (background_color, color) = if is_dark {
+        (Color::rgb(0.1, 0.1, 0.12), Color::WHITE)
+    } else {
+        (Color::rgb(0.9, 0.9, 0.88), Color::BLACK)
+    };
+
+    let parent_id = Some(entity);
+    rsx! {
+        <BackgroundBundle
+            styles={KStyle {
+                background_color: background_color.into(),
+                padding: Edge::all(Units::Pixels(10.0)).into(),
+                ..Default::default()
+            }}
+        >
+            <TextWidgetBundle
+                text={TextProps {
+                    content: if is_dark { "Dark mode" } else { "Light mode" }.into(),
+                    size: 16.0,
+                    ..Default::default()
+                }}
+                styles={KStyle {
+                    color: color.into(),
+                    ..Default::default()
+                }}
+            />
+            <KButtonBundle
+                button={KButton {
+                    text: "Toggle theme".into(),
+                    ..Default::default()
+                }}
+                on_event={OnEvent::new(
+                    move |In(_entity): In<Entity>,
+                          event: Res<KEvent>,
+                          mut dark_mode: ResMut<DarkMode>| {
+                        if let EventType::Click(..) = event.event_type {
+                            dark_mode.0 = !dark_mode.0;
+                        }
+                    },
+                )}
+            />
+        </BackgroundBundle>
+    };
+
+    true
+}
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("lato-light.kttf"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    widget_context.add_widget_data::<ThemedPanel, EmptyState>();
+    widget_context.add_widget_system(
+        ThemedPanel.get_name(),
+        widget_update::<ThemedPanel, EmptyState>,
+        themed_panel_render,
+    );
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Global State Window 1".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ThemedPanelBundle />
+            </WindowBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Global State Window 2".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(500.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ThemedPanelBundle />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
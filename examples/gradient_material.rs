@@ -0,0 +1,78 @@
+//! This example draws a widget with a custom material instead of Kayak's default quad.
+//!
+//! The material's shader (assets/gradient_material.wgsl) animates a gradient between two colors
+//! using the global time, while keeping the rounded corners of the widget it's attached to.
+
+use bevy::{prelude::*, reflect::TypePath, render::render_resource::AsBindGroup};
+use kayak_ui::prelude::{widgets::*, *};
+
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct GradientMaterial {
+    #[uniform(0)]
+    from_color: Color,
+    #[uniform(0)]
+    to_color: Color,
+    /// How fast the gradient moves
+    #[uniform(0)]
+    speed: f32,
+}
+
+impl MaterialUI for GradientMaterial {
+    fn fragment_shader() -> bevy::render::render_resource::ShaderRef {
+        "gradient_material.wgsl".into()
+    }
+}
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<GradientMaterial>>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let gradient = materials.add(GradientMaterial {
+        from_color: Color::rgb(0.298, 0.388, 0.949),
+        to_color: Color::rgb(0.949, 0.298, 0.592),
+        speed: 2.0,
+    });
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <BackgroundBundle
+                styles={KStyle {
+                    position_type: KPositionType::SelfDirected.into(),
+                    left: Units::Pixels(50.0).into(),
+                    top: Units::Pixels(50.0).into(),
+                    width: Units::Pixels(300.0).into(),
+                    height: Units::Pixels(150.0).into(),
+                    border_radius: Corner::all(20.0).into(),
+                    // Any widget drawn as a quad can be drawn with a material instead
+                    material: MaterialHandle::from_handle(gradient).into(),
+                    ..Default::default()
+                }}
+            />
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            KayakContextPlugin,
+            KayakWidgets,
+            MaterialUIPlugin::<GradientMaterial>::default(),
+        ))
+        .add_systems(Startup, startup)
+        .run()
+}
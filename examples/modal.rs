@@ -58,6 +58,7 @@ fn my_widget_render(
                     }}
                     button={KButton {
                         text: "Show Modal".into(),
+                        ..Default::default()
                     }}
                     on_event={OnEvent::new(
                         move |In(_entity): In<Entity>,
@@ -77,6 +78,15 @@ fn my_widget_render(
                     modal={Modal {
                         title: "Modal".into(),
                         visible: state.show_modal,
+                        dismiss_on_backdrop: true,
+                        dismiss_on_escape: true,
+                        on_request_close: Handler::new(
+                            move |In(_): In<(Entity, ())>, mut query: Query<&mut MyWidgetState>| {
+                                if let Ok(mut state) = query.get_mut(state_entity) {
+                                    state.show_modal = false;
+                                }
+                            },
+                        ),
                         ..Modal::default()
                     }}
                     styles={KStyle {
@@ -101,7 +111,7 @@ fn my_widget_render(
                         }}
                     />
                     <KButtonBundle
-                        button={KButton { text: "Hide Modal".into() }}
+                        button={KButton { text: "Hide Modal".into(), ..Default::default() }}
                         on_event={OnEvent::new(
                             move |In(_entity): In<Entity>,
                             mut event: ResMut<KEvent>,
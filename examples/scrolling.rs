@@ -49,7 +49,13 @@ Lorem ipsum dolor sit amet, consectetur adipiscing elit. Cras sed tellus neque.
                 }}
             >
                 <ScrollContextProviderBundle>
-                    <ScrollBoxBundle>
+                    <ScrollBoxBundle
+                        scroll_box_props={ScrollBoxProps {
+                            momentum: true,
+                            focusable: true,
+                            ..Default::default()
+                        }}
+                    >
                         <TextWidgetBundle
                             text={TextProps {
                                 content: lorem_ipsum,
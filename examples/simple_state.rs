@@ -55,6 +55,7 @@ fn current_count_render(
                 <KButtonBundle
                     button={KButton {
                         text: "Click me!".into(),
+                        ..Default::default()
                     }}
                     styles={KStyle {
                         font_size: (48.).into(),
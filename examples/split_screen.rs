@@ -0,0 +1,103 @@
+//! This example shows two independent UIs sharing one window, like the per-player HUDs of a
+//! split-screen game.
+//!
+//! Each UI has its own camera with a viewport covering half of the window. Widgets are laid out
+//! relative to their camera's viewport and input is mapped into it before being dispatched.
+
+use bevy::{prelude::*, render::camera::Viewport, window::WindowResized};
+use kayak_ui::prelude::{widgets::*, *};
+
+/// Marks the camera of the player shown on the left (0) or right (1) half of the window
+#[derive(Component)]
+struct PlayerCamera(u32);
+
+fn spawn_player_ui(commands: &mut Commands, player: u32) {
+    let camera_entity = commands
+        .spawn((
+            Camera2dBundle {
+                camera: Camera {
+                    order: player as isize,
+                    // Don't clear what the other player's camera has drawn
+                    clear_color: if player == 0 {
+                        ClearColorConfig::Default
+                    } else {
+                        ClearColorConfig::None
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            CameraUIKayak,
+            PlayerCamera(player),
+        ))
+        .id();
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: format!("Player {}", player + 1),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(250.0, 150.0),
+                    ..KWindow::default()
+                }}
+            >
+                <KButtonBundle
+                    button={KButton {
+                        text: "Ready!".into(),
+                        ..Default::default()
+                    }}
+                />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    spawn_player_ui(&mut commands, 0);
+    spawn_player_ui(&mut commands, 1);
+}
+
+/// Gives each player camera half of the window
+fn set_camera_viewports(
+    windows: Query<&Window>,
+    mut resize_events: EventReader<WindowResized>,
+    mut cameras: Query<(&mut Camera, &PlayerCamera)>,
+) {
+    for resize_event in resize_events.read() {
+        if let Ok(window) = windows.get(resize_event.window) {
+            let size = UVec2::new(
+                window.resolution.physical_width() / 2,
+                window.resolution.physical_height(),
+            );
+            for (mut camera, player) in cameras.iter_mut() {
+                camera.viewport = Some(Viewport {
+                    physical_position: UVec2::new(player.0 * size.x, 0),
+                    physical_size: size,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, set_camera_viewports)
+        .run()
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use kayak_ui::{
+    prelude::{widgets::*, *},
+    CameraUIKayak,
+};
+
+/// Keeps the stylesheet loaded so that its styles are updated when the file changes
+#[derive(Resource)]
+struct UiStyleSheet(#[allow(dead_code)] Handle<StyleSheet>);
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn(Camera2dBundle::default())
+        .insert(CameraUIKayak)
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    // The styles in this file are added to the `Theme` resource once it's loaded
+    commands.insert_resource(UiStyleSheet(asset_server.load("ui.style.ron")));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <BackgroundBundle
+                styles={KStyle {
+                    theme: "panel".to_string().into(),
+                    ..Default::default()
+                }}
+            >
+                <TextWidgetBundle
+                    styles={KStyle {
+                        theme: "title".to_string().into(),
+                        ..Default::default()
+                    }}
+                    text={TextProps {
+                        content: "Stylesheets".into(),
+                        ..Default::default()
+                    }}
+                />
+                <TextWidgetBundle
+                    styles={KStyle {
+                        theme: "body".to_string().into(),
+                        ..Default::default()
+                    }}
+                    text={TextProps {
+                        content: "Edit assets/ui.style.ron while this example is running to restyle these widgets.".into(),
+                        ..Default::default()
+                    }}
+                />
+            </BackgroundBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+// Run with `cargo run --example style_sheet --features bevy/file_watcher` to enable hot reloading
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(AssetPlugin {
+            watch_for_changes_override: Some(true),
+            ..Default::default()
+        }))
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
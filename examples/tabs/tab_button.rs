@@ -76,6 +76,7 @@ pub fn tab_button_render(
                     }}
                     button={KButton {
                         text: tab_button.title.clone(),
+                        ..Default::default()
                     }}
                     on_event={on_event}
                 />
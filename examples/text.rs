@@ -19,13 +19,22 @@ fn my_widget_1_render(
         // Which means when foo changes MyWidget will render twice!
         *computed_styles = KStyle {
             color: Color::RED.into(),
+            text_outline: (Color::BLACK, 0.2).into(),
+            text_shadow: TextShadow {
+                color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+                offset: Vec2::new(2.0, 2.0),
+                blur: 2.0,
+            }
+            .into(),
             render_command: StyleProp::Value(RenderCommand::Text {
                 content: format!("My number is: {}", my_widget.foo),
                 alignment: Alignment::Start,
+                direction: TextDirection::LeftToRight,
                 word_wrap: false,
                 subpixel: false,
                 text_layout: TextLayout::default(),
                 properties: TextProperties::default(),
+                spans: Vec::new(),
             }),
             ..Default::default()
         }
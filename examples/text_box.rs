@@ -8,6 +8,7 @@ struct TextBoxExample;
 struct TextBoxExampleState {
     pub value1: String,
     pub value2: String,
+    pub value3: f64,
 }
 
 impl Widget for TextBoxExample {}
@@ -41,24 +42,34 @@ fn update_text_box_example(
         TextBoxExampleState {
             value1: "Hello World".into(),
             value2: "Hello World2".into(),
+            value3: 5.0,
         },
     );
 
     if let Ok(textbox_state) = state_query.get(state_entity) {
         let on_change = OnChange::new(
-            move |In((_, value)): In<(Entity, String)>,
+            move |In((_, change)): In<(Entity, ValueChange<String>)>,
                   mut state_query: Query<&mut TextBoxExampleState>| {
                 if let Ok(mut state) = state_query.get_mut(state_entity) {
-                    state.value1 = value;
+                    state.value1 = change.value;
                 }
             },
         );
 
         let on_change2 = OnChange::new(
-            move |In((_, value)): In<(Entity, String)>,
+            move |In((_, change)): In<(Entity, ValueChange<String>)>,
                   mut state_query: Query<&mut TextBoxExampleState>| {
                 if let Ok(mut state) = state_query.get_mut(state_entity) {
-                    state.value2 = value;
+                    state.value2 = change.value;
+                }
+            },
+        );
+
+        let on_change3 = OnChange::new(
+            move |In((_, change)): In<(Entity, ValueChange<f64>)>,
+                  mut state_query: Query<&mut TextBoxExampleState>| {
+                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                    state.value3 = change.value;
                 }
             },
         );
@@ -75,9 +86,30 @@ fn update_text_box_example(
                     on_change={on_change}
                 />
                 <TextBoxBundle
-                    text_box={TextBoxProps { value: textbox_state.value2.clone(), ..Default::default()}}
+                    styles={KStyle {
+                        bottom: StyleProp::Value(Units::Pixels(10.0)),
+                        ..Default::default()
+                    }}
+                    text_box={TextBoxProps { value: textbox_state.value2.clone(), multiline: true, ..Default::default()}}
                     on_change={on_change2}
                 />
+                <TextBoxBundle
+                    styles={KStyle {
+                        bottom: StyleProp::Value(Units::Pixels(10.0)),
+                        ..Default::default()
+                    }}
+                    text_box={TextBoxProps { value: "Disabled".into(), disabled: true, ..Default::default()}}
+                />
+                <NumberInputBundle
+                    number_input={NumberInputProps {
+                        value: textbox_state.value3,
+                        min: 0.0,
+                        max: 10.0,
+                        step: 0.5,
+                        on_change: on_change3,
+                        ..Default::default()
+                    }}
+                />
             </ElementBundle>
         };
     }
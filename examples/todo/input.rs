@@ -43,8 +43,9 @@ pub fn render_todo_input(
     todo_list: Res<TodoList>,
 ) -> bool {
     let on_change = OnChange::new(
-        move |In((_, value)): In<(Entity, String)>, mut todo_list: ResMut<TodoList>| {
-            todo_list.new_item = value;
+        move |In((_, change)): In<(Entity, ValueChange<String>)>,
+              mut todo_list: ResMut<TodoList>| {
+            todo_list.new_item = change.value;
         },
     );
 
@@ -97,6 +98,7 @@ pub fn render_todo_input(
                 }}
                 button={KButton {
                     text: "+".into(),
+                    ..Default::default()
                 }}
                 on_event={handle_click}
             />
@@ -90,6 +90,7 @@ pub fn render_todo_items(
                             }}
                             button={KButton {
                                 text: "X".into(),
+                                ..Default::default()
                             }}
                             on_event={handle_click}
                         />
@@ -0,0 +1,70 @@
+//! Compares measuring a screen full of static labels with and without the layout cache.
+//!
+//! Run with `cargo bench -p kayak_font --bench layout_cache`.
+
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "bevy_renderer")]
+use kayak_font::ImageType;
+use kayak_font::{Alignment, KayakFont, Sdf, TextDirection, TextProperties};
+
+const LABELS: usize = 500;
+const FRAMES: usize = 100;
+
+fn make_font() -> KayakFont {
+    let bytes = std::fs::read("assets/roboto.kayak_font")
+        .expect("a `roboto.kayak_font` file in the `assets/` directory of this crate");
+
+    #[cfg(feature = "bevy_renderer")]
+    return KayakFont::new(
+        Sdf::from_bytes(&bytes),
+        ImageType::Atlas(bevy::asset::Handle::default()),
+    );
+
+    #[cfg(not(feature = "bevy_renderer"))]
+    return KayakFont::new(Sdf::from_bytes(&bytes));
+}
+
+fn run_frames(measure: impl Fn(&str, TextProperties)) -> Duration {
+    let labels = (0..LABELS)
+        .map(|i| format!("Label number {} of a screen full of static text", i))
+        .collect::<Vec<_>>();
+    let properties = TextProperties {
+        font_size: 16.0,
+        line_height: 16.0 * 1.2,
+        max_size: (300.0, 100.0),
+        alignment: Alignment::Start,
+        tab_size: 4,
+        kerning: true,
+        direction: TextDirection::LeftToRight,
+        letter_spacing: 0.0,
+    };
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        for label in labels.iter() {
+            measure(label, properties);
+        }
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let font = make_font();
+
+    let uncached = run_frames(|content, properties| {
+        std::hint::black_box(font.measure_uncached(content, properties));
+    });
+    let cached = run_frames(|content, properties| {
+        std::hint::black_box(font.measure(content, properties));
+    });
+
+    println!(
+        "{} labels over {} frames: uncached {:?}, cached {:?} ({:.1}x faster)",
+        LABELS,
+        FRAMES,
+        uncached,
+        cached,
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -4,25 +4,53 @@ use bevy::prelude::{AssetEvent, Assets, EventReader, Handle, Image, Local, Res,
 use bevy::render::render_resource::{TextureFormat, TextureUsages};
 use bevy::render::texture::{ImageFilterMode, ImageSampler, ImageSamplerDescriptor};
 
+const FONT_TEXTURE_USAGES: TextureUsages = TextureUsages::TEXTURE_BINDING
+    .union(TextureUsages::COPY_DST)
+    .union(TextureUsages::COPY_SRC);
+
 pub fn init_font_texture(
     mut not_processed: Local<Vec<Handle<KayakFont>>>,
     mut font_events: EventReader<AssetEvent<KayakFont>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
     mut images: ResMut<Assets<Image>>,
     fonts: Res<Assets<KayakFont>>,
     asset_server: Res<AssetServer>,
 ) {
     // quick and dirty, run this for all textures anytime a texture is created.
     for event in font_events.read() {
-        if let AssetEvent::Added { id } = event {
+        if let AssetEvent::Added { id } | AssetEvent::Modified { id } = event {
             let handle = asset_server.get_id_handle(*id).unwrap();
             not_processed.push(handle.clone_weak());
         }
     }
 
+    // A reloaded atlas comes back with the default texture settings
+    for event in image_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            for (font_id, font) in fonts.iter() {
+                if font.image.get().map(|image| image.id()) == Some(*id) {
+                    not_processed.push(Handle::Weak(font_id));
+                }
+            }
+        }
+    }
+
     let not_processed_fonts = not_processed.drain(..).collect::<Vec<_>>();
     for font_handle in not_processed_fonts {
         if let Some(font) = fonts.get(&font_handle) {
-            if let Some(texture) = images.get_mut(font.image.get()) {
+            let Some(image) = font.image.get() else {
+                // Generated fonts don't have an image to set up
+                continue;
+            };
+            let is_set_up = images.get(image).is_some_and(|texture| {
+                texture.texture_descriptor.format == TextureFormat::Rgba8Unorm
+                    && texture.texture_descriptor.usage == FONT_TEXTURE_USAGES
+            });
+            if is_set_up {
+                // Setting the texture up again would report it as modified once more
+                continue;
+            }
+            if let Some(texture) = images.get_mut(image) {
                 texture.texture_descriptor.format = TextureFormat::Rgba8Unorm;
                 texture.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
                     label: Some("Present Sampler".into()),
@@ -30,9 +58,7 @@ pub fn init_font_texture(
                     min_filter: ImageFilterMode::Linear,
                     ..Default::default()
                 });
-                texture.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
-                    | TextureUsages::COPY_DST
-                    | TextureUsages::COPY_SRC;
+                texture.texture_descriptor.usage = FONT_TEXTURE_USAGES;
             } else {
                 not_processed.push(font_handle.clone_weak());
             }
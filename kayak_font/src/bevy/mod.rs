@@ -2,6 +2,7 @@
 //!
 //! [Bevy]: https://github.com/bevyengine/bevy
 
+pub use crate::ttf::loader::TTFLoaderSettings;
 pub use loader::KayakFontLoader;
 pub use plugin::KayakFontPlugin;
 pub use renderer::*;
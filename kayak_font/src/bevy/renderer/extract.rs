@@ -20,6 +20,7 @@ pub(crate) fn extract_fonts(
     mut commands: Commands,
     font_assets: Extract<Res<Assets<KayakFont>>>,
     mut events: Extract<EventReader<AssetEvent<KayakFont>>>,
+    mut image_events: Extract<EventReader<AssetEvent<Image>>>,
     textures: Extract<Res<Assets<Image>>>,
     asset_server: Res<AssetServer>,
 ) {
@@ -50,25 +51,39 @@ pub(crate) fn extract_fonts(
         }
     }
 
+    // Fonts are uploaded again when their atlas is reloaded on its own
+    for event in image_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            for (font_id, font) in font_assets.iter() {
+                if font.image.get().map(|image| image.id()) == Some(*id) {
+                    changed_assets.insert(Handle::Weak(font_id));
+                }
+            }
+        }
+    }
+
     for handle in not_processed.drain(..) {
         changed_assets.insert(handle);
     }
 
     for handle in changed_assets {
         let font_asset = font_assets.get(&handle).unwrap();
-        if let Some(image) = textures.get(font_asset.image.get()) {
-            if !image
-                .texture_descriptor
-                .usage
-                .contains(TextureUsages::COPY_SRC)
-                || image.texture_descriptor.format != TextureFormat::Rgba8Unorm
-            {
+        // Generated fonts don't have an image to wait for
+        if let Some(image_handle) = font_asset.image.get() {
+            if let Some(image) = textures.get(image_handle) {
+                if !image
+                    .texture_descriptor
+                    .usage
+                    .contains(TextureUsages::COPY_SRC)
+                    || image.texture_descriptor.format != TextureFormat::Rgba8Unorm
+                {
+                    not_processed.push(handle);
+                    continue;
+                }
+            } else {
                 not_processed.push(handle);
                 continue;
             }
-        } else {
-            not_processed.push(handle);
-            continue;
         }
 
         let font = font_asset.clone();
@@ -1,15 +1,16 @@
-use crate::{ImageType, KayakFont, Sdf};
+use crate::{ttf::loader::GLYPH_SIZE, ImageType, KayakFont, Sdf};
 use bevy::{
     asset::Handle,
+    log::warn,
     math::Vec2,
     prelude::{Res, Resource},
     render::{
         render_asset::RenderAssets,
         render_resource::{
             AddressMode, BindGroupLayout, CommandEncoderDescriptor, Extent3d, FilterMode,
-            ImageCopyTexture, Origin3d, SamplerDescriptor, TextureAspect, TextureDescriptor,
-            TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
-            TextureViewDimension,
+            ImageCopyTexture, ImageDataLayout, Origin3d, SamplerDescriptor, TextureAspect,
+            TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+            TextureViewDescriptor, TextureViewDimension,
         },
         renderer::{RenderDevice, RenderQueue},
         texture::{GpuImage, Image},
@@ -21,11 +22,26 @@ pub trait FontRenderingPipeline {
     fn get_font_image_layout(&self) -> &BindGroupLayout;
 }
 
+/// The number of glyphs stored in each page (texture array) of a font
 pub const MAX_CHARACTERS: u32 = 500;
 
+/// The maximum number of pages created for a single font
+///
+/// Fonts with more than `MAX_CHARACTERS * MAX_FONT_PAGES` glyphs only upload the first glyphs.
+/// The remaining glyphs are drawn as the font's missing glyph.
+pub const MAX_FONT_PAGES: u32 = 8;
+
+/// Holds the GPU textures of every font
+///
+/// The glyphs of atlas fonts are copied into one texture array layer each. Since the number of
+/// layers is limited, fonts with more than [`MAX_CHARACTERS`] glyphs are split into several
+/// pages, each of which needs its own bind group.
+///
+/// Lazily loaded TTF fonts start without any pages. Their glyphs are uploaded one at a time once
+/// they've been generated, creating new pages as needed.
 #[derive(Resource)]
 pub struct FontTextureCache {
-    images: HashMap<Handle<KayakFont>, GpuImage>,
+    images: HashMap<Handle<KayakFont>, Vec<GpuImage>>,
     fonts: HashMap<Handle<KayakFont>, KayakFont>,
     new_fonts: Vec<Handle<KayakFont>>,
     updated_fonts: Vec<Handle<KayakFont>>,
@@ -57,35 +73,100 @@ impl FontTextureCache {
         }
     }
 
+    /// Returns the texture of the given page of a font
     pub fn get_gpu_image<'s>(
         &'s self,
         handle: &Handle<KayakFont>,
+        page: u32,
         render_images: &'s RenderAssets<Image>,
     ) -> Option<&'s GpuImage> {
-        if let Some(gpu_image) = self.images.get(handle) {
-            Some(gpu_image)
+        if let Some(pages) = self.images.get(handle) {
+            pages.get(page as usize)
         } else if let Some(font) = self.fonts.get(handle) {
-            render_images.get(font.image.get())
+            if page == 0 {
+                font.image.get().and_then(|image| render_images.get(image))
+            } else {
+                None
+            }
         } else {
             None
         }
     }
 
+    /// Returns the page and the layer within that page the glyph with the given id is stored in
+    ///
+    /// Glyphs that didn't fit into the font's pages are replaced by the font's missing glyph.
+    /// Returns `None` if there's no glyph to draw at all.
+    pub fn get_glyph_location(
+        &self,
+        handle: &Handle<KayakFont>,
+        char_id: u32,
+    ) -> Option<(u32, u32)> {
+        let max_glyphs = MAX_CHARACTERS * MAX_FONT_PAGES;
+        let font = self.fonts.get(handle);
+        if let Some(font) = font {
+            // Array fonts come with their own texture holding every glyph
+            if matches!(font.image, ImageType::Array(..)) {
+                return Some((0, char_id));
+            }
+
+            // Generated glyphs are stored in the order they were first used
+            if let Some(rasterizer) = font.rasterizer() {
+                let slot = rasterizer.slot(char_id).or_else(|| {
+                    font.missing_glyph
+                        .and_then(|missing| font.get_char_id(missing))
+                        .and_then(|missing_id| rasterizer.slot(missing_id))
+                })?;
+                return Some((slot / MAX_CHARACTERS, slot % MAX_CHARACTERS));
+            }
+        }
+
+        let char_id = if char_id < max_glyphs {
+            char_id
+        } else {
+            let font = font?;
+            font.missing_glyph
+                .and_then(|missing| font.get_char_id(missing))
+                .filter(|missing_id| *missing_id < max_glyphs)?
+        };
+
+        Some((char_id / MAX_CHARACTERS, char_id % MAX_CHARACTERS))
+    }
+
+    /// Queues reloaded fonts to be uploaded from scratch
+    ///
+    /// A reloaded font may come with a different set of glyphs or a differently sized atlas, so its
+    /// old textures are dropped instead of being written to.
+    fn requeue_updated(&mut self) {
+        for handle in self.updated_fonts.drain(..) {
+            self.images.remove(&handle);
+            if !self.new_fonts.contains(&handle) {
+                self.new_fonts.push(handle);
+            }
+        }
+    }
+
     pub fn process_new(
         &mut self,
         device: &RenderDevice,
         queue: &RenderQueue,
         render_images: &Res<RenderAssets<Image>>,
     ) {
+        self.requeue_updated();
+
         let new_fonts: Vec<_> = self.new_fonts.drain(..).collect();
         for kayak_font_handle in new_fonts {
             let mut was_processed = true;
             if let Some(font) = self.fonts.get(&kayak_font_handle) {
-                if matches!(font.image, ImageType::Array(..)) {
-                    if render_images.get(font.image.get()).is_none() {
+                let image = font.image.get().and_then(|image| render_images.get(image));
+                if matches!(font.image, ImageType::Generated) {
+                    // Pages are created once the first glyphs have been generated
+                    self.images.entry(kayak_font_handle.clone()).or_default();
+                } else if matches!(font.image, ImageType::Array(..)) {
+                    if image.is_none() {
                         was_processed = false;
                     }
-                } else if let Some(atlas_texture) = render_images.get(font.image.get()) {
+                } else if let Some(atlas_texture) = image {
                     Self::create_from_atlas(
                         &mut self.images,
                         &font.sdf,
@@ -103,15 +184,61 @@ impl FontTextureCache {
                 self.new_fonts.push(kayak_font_handle.clone());
             }
         }
+
+        self.upload_generated(device, queue);
     }
 
-    fn create_texture(
-        images: &mut HashMap<Handle<KayakFont>, GpuImage>,
-        font_handle: Handle<KayakFont>,
-        size: (u32, u32),
-        device: &RenderDevice,
-        format: TextureFormat,
-    ) {
+    /// Uploads the glyphs lazily loaded fonts generated since the last frame
+    fn upload_generated(&mut self, device: &RenderDevice, queue: &RenderQueue) {
+        let (width, height) = (GLYPH_SIZE.0 as u32, GLYPH_SIZE.1 as u32);
+        for (handle, font) in self.fonts.iter() {
+            let Some(rasterizer) = font.rasterizer() else {
+                continue;
+            };
+            let generated = rasterizer.take_generated();
+            if generated.is_empty() {
+                continue;
+            }
+
+            let pages = self.images.entry(handle.clone()).or_default();
+            for glyph in generated {
+                let page = (glyph.slot / MAX_CHARACTERS) as usize;
+                while pages.len() <= page {
+                    pages.push(Self::create_texture(
+                        (width, height),
+                        device,
+                        TextureFormat::Rgba8Unorm,
+                    ));
+                }
+
+                queue.write_texture(
+                    ImageCopyTexture {
+                        texture: &pages[page].texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: glyph.slot % MAX_CHARACTERS,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    &glyph.pixels,
+                    ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(width * 4),
+                        rows_per_image: Some(height),
+                    },
+                    Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+        }
+    }
+
+    fn create_texture(size: (u32, u32), device: &RenderDevice, format: TextureFormat) -> GpuImage {
         let texture_descriptor = TextureDescriptor {
             label: Some("font_texture_array"),
             size: Extent3d {
@@ -156,7 +283,7 @@ impl FontTextureCache {
             array_layer_count: Some(MAX_CHARACTERS),
         });
 
-        let image = GpuImage {
+        GpuImage {
             texture,
             sampler,
             texture_view,
@@ -166,9 +293,7 @@ impl FontTextureCache {
                 y: size.1 as f32,
             },
             texture_format: format,
-        };
-
-        images.insert(font_handle, image);
+        }
     }
 
     pub fn get_empty(device: &RenderDevice) -> GpuImage {
@@ -214,7 +339,7 @@ impl FontTextureCache {
     }
 
     pub fn create_from_atlas(
-        images: &mut HashMap<Handle<KayakFont>, GpuImage>,
+        images: &mut HashMap<Handle<KayakFont>, Vec<GpuImage>>,
         sdf: &Sdf,
         font_handle: Handle<KayakFont>,
         device: &RenderDevice,
@@ -222,26 +347,41 @@ impl FontTextureCache {
         atlas_texture: &GpuImage,
         size: Vec2,
     ) {
-        Self::create_texture(
-            images,
-            font_handle.clone(),
-            (size.x as u32, size.y as u32),
-            device,
-            TextureFormat::Rgba8Unorm,
-        );
+        let glyph_count = sdf.glyphs.len() as u32;
+        if glyph_count > MAX_CHARACTERS * MAX_FONT_PAGES {
+            warn!(
+                "Font has {} glyphs but only {} fit into its textures, the rest will be drawn as the missing glyph",
+                glyph_count,
+                MAX_CHARACTERS * MAX_FONT_PAGES
+            );
+        }
+        let page_count = glyph_count
+            .div_ceil(MAX_CHARACTERS)
+            .clamp(1, MAX_FONT_PAGES);
+        let pages = (0..page_count)
+            .map(|_| {
+                Self::create_texture(
+                    (size.x as u32, size.y as u32),
+                    device,
+                    TextureFormat::Rgba8Unorm,
+                )
+            })
+            .collect::<Vec<_>>();
 
         let mut command_encoder = device.create_command_encoder(&CommandEncoderDescriptor {
             label: Some("create_sdf_from_atlas_encoder"),
         });
 
-        let gpu_image = images.get(&font_handle).unwrap();
-
         // Now fill the texture data.
 
         let _atlas_width = sdf.atlas.width;
         let atlas_height = sdf.atlas.height;
 
         for (i, glyph) in sdf.glyphs.iter().enumerate() {
+            let i = i as u32;
+            let Some(gpu_image) = pages.get((i / MAX_CHARACTERS) as usize) else {
+                break;
+            };
             if let Some(atlas_bounds) = glyph.atlas_bounds {
                 let glyph_size = atlas_bounds.size();
                 command_encoder.copy_texture_to_texture(
@@ -261,7 +401,7 @@ impl FontTextureCache {
                         origin: Origin3d {
                             x: 0,
                             y: 0,
-                            z: i as u32,
+                            z: i % MAX_CHARACTERS,
                         },
                         aspect: TextureAspect::All,
                     },
@@ -276,5 +416,38 @@ impl FontTextureCache {
 
         let command_buffer = command_encoder.finish();
         queue.submit(vec![command_buffer]);
+
+        images.insert(font_handle, pages);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FontTextureCache;
+    use crate::{ImageType, KayakFont, Sdf};
+    use bevy::asset::Handle;
+
+    fn make_font() -> KayakFont {
+        let bytes = std::fs::read("assets/roboto.kayak_font")
+            .expect("a `roboto.kayak_font` file in the `assets/` directory of this crate");
+        KayakFont::new(Sdf::from_bytes(&bytes), ImageType::Atlas(Handle::default()))
+    }
+
+    #[test]
+    fn should_upload_reloaded_fonts_again() {
+        let handle = Handle::<KayakFont>::default();
+        let mut cache = FontTextureCache::new();
+        cache.add(handle.clone(), make_font());
+        assert_eq!(cache.new_fonts, vec![handle.clone()]);
+
+        // Pretend the font has been uploaded
+        cache.new_fonts.clear();
+        cache.images.insert(handle.clone(), Vec::new());
+
+        cache.add(handle.clone(), make_font());
+        cache.requeue_updated();
+        assert!(!cache.images.contains_key(&handle));
+        assert_eq!(cache.new_fonts, vec![handle]);
+        assert!(cache.updated_fonts.is_empty());
     }
 }
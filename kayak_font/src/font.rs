@@ -1,12 +1,22 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[cfg(feature = "bevy_renderer")]
 use bevy::{prelude::Handle, prelude::*, reflect::TypePath, render::texture::Image};
+#[cfg(feature = "bevy_renderer")]
+use std::sync::Arc;
+use unicode_bidi::{BidiInfo, Level};
 use unicode_segmentation::UnicodeSegmentation;
 
+#[cfg(feature = "shaping")]
+use crate::shaping;
+use crate::shaping::ShapedGrapheme;
+#[cfg(feature = "bevy_renderer")]
+use crate::ttf::rasterizer::GlyphRasterizer;
 use crate::utility::{BreakableWord, MISSING, SPACE};
 use crate::{
-    utility, Alignment, Glyph, GlyphRect, Grapheme, Line, Sdf, TextLayout, TextProperties,
+    utility, FontMetrics, Glyph, GlyphRect, Grapheme, Line, Sdf, TextDirection, TextLayout,
+    TextLayoutCache, TextProperties, TextSpan,
 };
 
 #[cfg(feature = "bevy_renderer")]
@@ -16,7 +26,11 @@ pub struct KayakFont {
     pub image: ImageType,
     pub missing_glyph: Option<char>,
     char_ids: HashMap<char, u32>,
+    kerning: HashMap<(char, char), f32>,
     max_glyph_size: (f32, f32),
+    layout_cache: TextLayoutCache,
+    /// Generates glyphs on first use for lazily loaded TTF fonts, shared between clones
+    rasterizer: Option<Arc<GlyphRasterizer>>,
 }
 
 #[cfg(feature = "bevy_renderer")]
@@ -24,13 +38,17 @@ pub struct KayakFont {
 pub enum ImageType {
     Atlas(Handle<Image>),
     Array(Handle<Image>),
+    /// The glyphs are generated when they're first used, so there's no image to load
+    Generated,
 }
 
+#[cfg(feature = "bevy_renderer")]
 impl ImageType {
-    pub fn get(&self) -> &Handle<Image> {
+    pub fn get(&self) -> Option<&Handle<Image>> {
         match self {
-            Self::Atlas(handle) => handle,
-            Self::Array(handle) => handle,
+            Self::Atlas(handle) => Some(handle),
+            Self::Array(handle) => Some(handle),
+            Self::Generated => None,
         }
     }
 }
@@ -41,7 +59,9 @@ pub struct KayakFont {
     pub sdf: Sdf,
     pub missing_glyph: Option<char>,
     char_ids: HashMap<char, u32>,
+    kerning: HashMap<(char, char), f32>,
     max_glyph_size: (f32, f32),
+    layout_cache: TextLayoutCache,
 }
 
 impl KayakFont {
@@ -67,20 +87,133 @@ impl KayakFont {
             None
         };
 
+        let kerning = Self::kerning_pairs(&sdf);
+
         Self {
             sdf,
             #[cfg(feature = "bevy_renderer")]
             image: image_type,
             missing_glyph,
             char_ids,
+            kerning,
             max_glyph_size,
+            layout_cache: TextLayoutCache::default(),
+            #[cfg(feature = "bevy_renderer")]
+            rasterizer: None,
+        }
+    }
+
+    #[cfg(feature = "bevy_renderer")]
+    pub(crate) fn with_rasterizer(mut self, rasterizer: GlyphRasterizer) -> Self {
+        let rasterizer = Arc::new(rasterizer);
+        // Glyphs that can't be generated are drawn as the missing glyph, so always generate it
+        if let Some(missing) = self.missing_glyph.and_then(|c| self.get_char_id(c)) {
+            rasterizer.request(missing);
         }
+        self.rasterizer = Some(rasterizer);
+        self
+    }
+
+    /// Creates a copy of this font with a variation axis set to the given value, like
+    /// `("wght", 700.0)` for a bold weight
+    ///
+    /// Only lazily loaded TTF fonts of a variable font file can be varied. The copy generates
+    /// its glyphs again at the new value and has its own texture pages, so every value used costs
+    /// a separate atlas and rasterizing each of its glyphs once. Keep the copy around rather than
+    /// creating it again whenever the value changes.
+    ///
+    /// Returns `None` if the font isn't lazily loaded or doesn't have the given axis.
+    #[cfg(feature = "bevy_renderer")]
+    pub fn with_variation(&self, axis: &str, value: f32) -> Option<KayakFont> {
+        let mut font = self.rasterizer.as_ref()?.with_variation(axis, value)?;
+        font.missing_glyph = self.missing_glyph;
+        if let Some(missing) = self.missing_glyph {
+            font.request_glyph(missing);
+        }
+        Some(font)
+    }
+
+    #[cfg(feature = "bevy_renderer")]
+    pub(crate) fn rasterizer(&self) -> Option<&GlyphRasterizer> {
+        self.rasterizer.as_deref()
+    }
+
+    /// Generates the glyph of a lazily loaded font if it hasn't been yet.
+    #[cfg(feature = "bevy_renderer")]
+    fn request_glyph(&self, c: char) {
+        if let Some(rasterizer) = &self.rasterizer {
+            if let Some(char_id) = self.get_char_id(c) {
+                rasterizer.request(char_id);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "bevy_renderer"))]
+    fn request_glyph(&self, _c: char) {}
+
+    /// Shapes a run of text and splits its glyphs by grapheme cluster, if the font can be shaped.
+    ///
+    /// Only lazily loaded TTF fonts keep the font file around, so only those can be shaped.
+    #[cfg(feature = "shaping")]
+    fn shape(&self, text: &str) -> Option<Vec<ShapedGrapheme>> {
+        let glyphs = self.rasterizer.as_ref()?.shape(text)?;
+        Some(shaping::group_by_grapheme(&glyphs, text))
+    }
+
+    #[cfg(not(feature = "shaping"))]
+    fn shape(&self, _text: &str) -> Option<Vec<ShapedGrapheme>> {
+        None
     }
 
     pub fn generate_char_ids(&mut self) {
         for (count, glyph) in self.sdf.glyphs.iter().enumerate() {
             self.char_ids.insert(glyph.unicode, count as u32);
         }
+        self.kerning = Self::kerning_pairs(&self.sdf);
+        self.layout_cache.clear();
+    }
+
+    fn kerning_pairs(sdf: &Sdf) -> HashMap<(char, char), f32> {
+        sdf.kerning
+            .iter()
+            .filter_map(|pair| {
+                let first = char::from_u32(pair.unicode1)?;
+                let second = char::from_u32(pair.unicode2)?;
+                Some(((first, second), pair.advance))
+            })
+            .collect()
+    }
+
+    /// Returns the kerning between two consecutive characters, if the font defines any.
+    ///
+    /// The kerning is relative to the font size and is added to the advance of the first character.
+    pub fn kerning(&self, first: char, second: char) -> Option<f32> {
+        self.kerning.get(&(first, second)).copied()
+    }
+
+    /// Returns the kerning to apply between two characters (in pixels), if kerning is enabled.
+    fn kerning_advance(&self, previous: Option<char>, c: char, properties: TextProperties) -> f32 {
+        if !properties.kerning {
+            return 0.0;
+        }
+
+        previous
+            .and_then(|previous| self.kerning(previous, c))
+            .map(|kerning| kerning * properties.font_size)
+            .unwrap_or_default()
+    }
+
+    /// The cache of layouts returned by [`measure`](Self::measure).
+    ///
+    /// Layouts are cached per font asset, so reloading the font starts with an empty cache. Clear
+    /// it yourself after changing the font's `sdf` in place.
+    pub fn layout_cache(&self) -> &TextLayoutCache {
+        &self.layout_cache
+    }
+
+    /// Removes all layouts cached by [`measure`](Self::measure).
+    pub fn clear_layout_cache(&self) {
+        self.layout_cache.clear();
     }
 
     pub fn get_char_id(&self, c: char) -> Option<u32> {
@@ -88,21 +221,57 @@ impl KayakFont {
     }
 
     pub fn get_word_width(&self, word: &str, properties: TextProperties) -> f32 {
+        self.get_span_word_width(word, 0, properties, &[])
+    }
+
+    /// Returns the width of a word starting at the given byte offset of spanned content.
+    fn get_span_word_width(
+        &self,
+        word: &str,
+        offset: usize,
+        properties: TextProperties,
+        spans: &[TextSpan],
+    ) -> f32 {
         let space_width = self.get_space_width(properties);
         let tab_width = self.get_tab_width(properties);
 
         let mut width = 0.0;
-        for c in word.chars() {
-            if utility::is_space(c) {
-                width += space_width;
-            } else if utility::is_tab(c) {
-                width += tab_width;
-            } else if let Some(glyph) = self.get_glyph(c) {
-                width += glyph.advance * properties.font_size;
+        let mut previous_char = None;
+        let shaped = self.shape(word);
+        for (grapheme_number, (grapheme_offset, grapheme)) in
+            word.grapheme_indices(true).enumerate()
+        {
+            if let Some(shaped_grapheme) = shaped
+                .as_ref()
+                .and_then(|shaped| shaped.get(grapheme_number))
+                .filter(|_| !utility::is_blank(grapheme))
+            {
+                let (scale, _) = TextSpan::resolve(spans, offset + grapheme_offset);
+                width += shaped_grapheme.advance * properties.font_size * scale;
+                continue;
+            }
+
+            for (index, c) in grapheme.char_indices() {
+                let (scale, _) = TextSpan::resolve(spans, offset + grapheme_offset + index);
+                let previous = previous_char.replace(c);
+                if utility::is_space(c) {
+                    width += space_width * scale;
+                } else if utility::is_tab(c) {
+                    width += tab_width * scale;
+                } else if let Some(glyph) = self.get_glyph(c) {
+                    width += (glyph.advance * properties.font_size
+                        + self.kerning_advance(previous, c, properties))
+                        * scale;
+                }
             }
         }
 
-        width
+        // Letter spacing follows every visible grapheme
+        let graphemes = word
+            .graphemes(true)
+            .filter(|grapheme| !grapheme.chars().all(utility::is_newline))
+            .count();
+        width + graphemes as f32 * properties.letter_spacing
     }
 
     /// Splits up the provided &str into grapheme clusters.
@@ -113,22 +282,113 @@ impl KayakFont {
     /// Measures the given text content and calculates an appropriate layout
     /// given a set of properties.
     ///
+    /// Layouts are cached, so measuring the same content with the same properties again is cheap.
+    ///
     /// # Arguments
     ///
     /// * `content`: The textual content to measure.
     /// * `properties`: The text properties to use.
     ///
     pub fn measure(&self, content: &str, properties: TextProperties) -> TextLayout {
+        self.measure_with_spans(content, properties, &[])
+    }
+
+    /// Measures the given text content like [`measure`](Self::measure), styling runs of it with
+    /// the given spans.
+    ///
+    /// Glyphs within a span advance using their scaled size. Lines containing shifted glyphs
+    /// grow to fit them, so raised or lowered glyphs don't overlap neighbouring lines.
+    pub fn measure_with_spans(
+        &self,
+        content: &str,
+        properties: TextProperties,
+        spans: &[TextSpan],
+    ) -> TextLayout {
+        self.layout_cache
+            .get_or_insert_with_spans(content, properties, spans, || {
+                self.layout_text(content, properties, spans)
+            })
+    }
+
+    /// Measures the given text content and returns the bounds of the given byte range within it.
+    ///
+    /// The range is widened to the grapheme clusters it touches. See
+    /// [`TextLayout::bounds_of`] for the returned rects, one per line the range spans.
+    pub fn measure_range(
+        &self,
+        content: &str,
+        range: Range<usize>,
+        properties: TextProperties,
+    ) -> Vec<bevy::math::Rect> {
+        let mut start = 0;
+        let mut end = 0;
+        for (byte_index, grapheme) in content.grapheme_indices(true) {
+            if byte_index + grapheme.len() <= range.start {
+                start += 1;
+            }
+            if byte_index < range.end {
+                end += 1;
+            }
+        }
+
+        self.measure(content, properties).bounds_of(start..end)
+    }
+
+    /// Measures the given text content like [`measure`](Self::measure) without using the cache.
+    pub fn measure_uncached(&self, content: &str, properties: TextProperties) -> TextLayout {
+        self.layout_text(content, properties, &[])
+    }
+
+    fn layout_text(
+        &self,
+        content: &str,
+        properties: TextProperties,
+        spans: &[TextSpan],
+    ) -> TextLayout {
         let space_width = self.get_space_width(properties);
         let tab_width = self.get_tab_width(properties);
 
         let mut size: (f32, f32) = (0.0, 0.0);
         let mut glyph_rects = Vec::new();
         let mut lines = Vec::new();
+        // How far shifted glyphs reach above and below each line (in pixels)
+        let mut line_extents = Vec::new();
+        let mut line_extent: (f32, f32) = (0.0, 0.0);
+        // The previous character on the current line, used for kerning
+        let mut previous_char = None;
+        // The byte offset of each grapheme, used for bidi reordering
+        let mut grapheme_offsets = Vec::new();
 
         // This is the normalized glyph bounds for all glyphs in the atlas.
         // It's needed to ensure all glyphs render proportional to each other.
         let norm_glyph_bounds = self.calc_glyph_size(properties.font_size);
+        let ascent = self.line_ascent(properties.font_size);
+
+        // Places a glyph with the pen at the given position, moved by the given offset (in ems,
+        // with y pointing up). Also returns how far the glyph reaches above and below where it
+        // would without its span.
+        let place_glyph =
+            |glyph: &Glyph, pen: (f32, f32), offset: (f32, f32), scale: f32, shift: f32| {
+                let (left, top, bottom) = match glyph.plane_bounds.as_ref() {
+                    Some(rect) => (rect.left, rect.top, rect.bottom),
+                    None => (0.0, 0.0, 0.0),
+                };
+
+                // Calculate position relative to line and normalized glyph bounds
+                let pos_x = pen.0 + (left + offset.0) * properties.font_size * scale;
+                let pos_y =
+                    (pen.1 + ascent) - ((top + offset.1) * scale + shift) * properties.font_size;
+                let rect = GlyphRect {
+                    position: (pos_x, pos_y),
+                    size: (norm_glyph_bounds.0 * scale, norm_glyph_bounds.1 * scale),
+                    content: glyph.unicode,
+                };
+
+                // Track how far the glyph reaches past where it would without its span
+                let rise = (top * scale + shift - top) * properties.font_size;
+                let drop = (bottom - (bottom * scale + shift)) * properties.font_size;
+                (rect, (rise, drop))
+            };
 
         // The current line being calculated
         let mut line = Line::new(0);
@@ -152,6 +412,9 @@ impl KayakFont {
         //        rather than each character individually,— however, this might take some
         //        careful thought and consideration, so it should probably be addressed later.
         //        Once resolved, this comment should be updated accordingly.
+        //
+        // With the `shaping` feature, fonts that can be shaped shape each word as a whole
+        // instead, and the shaped glyphs are placed per grapheme cluster in #2.
 
         let words = utility::split_breakable_words(content).collect::<Vec<_>>();
         for (index, word) in words.iter().enumerate() {
@@ -164,7 +427,10 @@ impl KayakFont {
                 if idx == index {
                     let next_line = Line::new_after(&line);
                     lines.push(line);
+                    line_extents.push(line_extent);
                     line = next_line;
+                    line_extent = (0.0, 0.0);
+                    previous_char = None;
                     break_index = None;
                 }
             }
@@ -175,8 +441,14 @@ impl KayakFont {
                         // Skip finding a line break since we're guaranteed not to find one until `idx`
                     }
                     _ => {
-                        let (next_break, next_skip) =
-                            self.find_next_break(index, line.width(), properties, &words);
+                        let (next_break, next_skip) = self.find_next_break(
+                            index,
+                            line.width(),
+                            properties,
+                            &words,
+                            content,
+                            spans,
+                        );
                         break_index = next_break;
                         skip_until_index = next_skip;
                         will_break |= break_index.map(|idx| index + 1 == idx).unwrap_or_default();
@@ -185,15 +457,27 @@ impl KayakFont {
             }
 
             // === Iterate Grapheme Clusters === //
-            for grapheme_content in word.content.graphemes(true) {
+            let word_offset = byte_offset(content, word.content);
+            // Shaped words take their glyphs and advances from the shaper instead
+            let shaped = self.shape(word.content);
+            for (grapheme_number, (grapheme_offset, grapheme_content)) in
+                word.content.grapheme_indices(true).enumerate()
+            {
+                grapheme_offsets.push(word_offset + grapheme_offset);
                 let mut grapheme = Grapheme {
                     position: (line.width(), properties.line_height * lines.len() as f32),
                     glyph_index,
                     char_index,
                     ..Default::default()
                 };
-
-                for c in grapheme_content.chars() {
+                let shaped_grapheme = shaped
+                    .as_ref()
+                    .and_then(|shaped| shaped.get(grapheme_number))
+                    .filter(|_| !utility::is_blank(grapheme_content));
+
+                for (offset, c) in grapheme_content.char_indices() {
+                    let (scale, shift) =
+                        TextSpan::resolve(spans, word_offset + grapheme_offset + offset);
                     char_index += 1;
                     grapheme.char_total += 1;
 
@@ -202,14 +486,16 @@ impl KayakFont {
                         continue;
                     }
 
+                    let previous = previous_char.replace(c);
+
                     if utility::is_space(c) {
                         if !will_break {
                             // Don't add the space if we're about to break the line
-                            grapheme.size.0 += space_width;
+                            grapheme.size.0 += space_width * scale;
                         }
                     } else if utility::is_tab(c) {
-                        grapheme.size.0 += tab_width;
-                    } else {
+                        grapheme.size.0 += tab_width * scale;
+                    } else if shaped_grapheme.is_none() {
                         let glyph = self.get_glyph(c).or_else(|| {
                             if let Some(missing) = self.missing_glyph {
                                 self.get_glyph(missing)
@@ -219,35 +505,63 @@ impl KayakFont {
                         });
 
                         if let Some(glyph) = glyph {
-                            // Character is valid glyph -> calculate its size and position
-                            let plane_bounds = glyph.plane_bounds.as_ref();
-                            let (left, top, _width, _height) = match plane_bounds {
-                                Some(rect) => (
-                                    rect.left,
-                                    rect.top,
-                                    rect.width() * properties.font_size,
-                                    rect.height() * properties.font_size,
+                            self.request_glyph(glyph.unicode);
+                            grapheme.size.0 +=
+                                self.kerning_advance(previous, c, properties) * scale;
+
+                            let (rect, (rise, drop)) = place_glyph(
+                                glyph,
+                                (
+                                    grapheme.position.0 + grapheme.size.0,
+                                    grapheme.position.1 + grapheme.size.1,
                                 ),
-                                None => (0.0, 0.0, 0.0, 0.0),
-                            };
+                                (0.0, 0.0),
+                                scale,
+                                shift,
+                            );
+                            glyph_rects.push(rect);
+                            line_extent.0 = line_extent.0.max(rise);
+                            line_extent.1 = line_extent.1.max(drop);
 
-                            // Calculate position relative to line and normalized glyph bounds
-                            let pos_x = (grapheme.position.0 + grapheme.size.0)
-                                + left * properties.font_size;
-                            let pos_y = (grapheme.position.1 + grapheme.size.1)
-                                - top * properties.font_size;
+                            glyph_index += 1;
+                            grapheme.glyph_total += 1;
+                            grapheme.size.0 += glyph.advance * properties.font_size * scale;
+                        }
+                    }
+                }
+
+                if let Some(shaped_grapheme) = shaped_grapheme {
+                    let (scale, shift) = TextSpan::resolve(spans, word_offset + grapheme_offset);
+                    let mut pen = grapheme.position.0 + grapheme.size.0;
+                    for shaped_glyph in shaped_grapheme.glyphs.iter() {
+                        let glyph = self
+                            .get_glyph(shaped_glyph.unicode)
+                            .or_else(|| self.missing_glyph.and_then(|c| self.get_glyph(c)));
 
-                            glyph_rects.push(GlyphRect {
-                                position: (pos_x, pos_y),
-                                size: norm_glyph_bounds,
-                                content: glyph.unicode,
-                            });
+                        if let Some(glyph) = glyph {
+                            self.request_glyph(glyph.unicode);
+                            let (rect, (rise, drop)) = place_glyph(
+                                glyph,
+                                (pen, grapheme.position.1),
+                                shaped_glyph.offset,
+                                scale,
+                                shift,
+                            );
+                            glyph_rects.push(rect);
+                            line_extent.0 = line_extent.0.max(rise);
+                            line_extent.1 = line_extent.1.max(drop);
 
                             glyph_index += 1;
                             grapheme.glyph_total += 1;
-                            grapheme.size.0 += glyph.advance * properties.font_size;
                         }
+                        pen += shaped_glyph.advance * properties.font_size * scale;
                     }
+                    grapheme.size.0 += shaped_grapheme.advance * properties.font_size * scale;
+                }
+
+                // Letter spacing follows every grapheme taking up space on the line
+                if grapheme.size.0 > 0.0 {
+                    grapheme.size.0 += properties.letter_spacing;
                 }
 
                 line.add_grapheme(grapheme);
@@ -257,15 +571,29 @@ impl KayakFont {
 
         // Push the final line
         lines.push(line);
+        line_extents.push(line_extent);
         size.1 = properties.line_height * lines.len() as f32;
 
+        // === Reorder Lines === //
+        Self::reorder_lines(
+            content,
+            properties,
+            &mut lines,
+            &mut glyph_rects,
+            &grapheme_offsets,
+        );
+
         // === Shift Lines & Glyphs === //
-        for line in lines.iter() {
-            let shift_x = match properties.alignment {
-                Alignment::Start => 0.0,
-                Alignment::Middle => (properties.max_size.0 - line.width()) / 2.0,
-                Alignment::End => properties.max_size.0 - line.width(),
-            };
+        let mut shift_y = 0.0;
+        for (row, (line, (rise, drop))) in lines.iter_mut().zip(line_extents).enumerate() {
+            // Make room for glyphs raised above this line
+            shift_y += rise;
+            if shift_y > 0.0 {
+                line.offset_y(shift_y);
+            }
+            line.set_baseline(properties.line_height * row as f32 + shift_y + ascent);
+
+            let shift_x = properties.line_offset(line);
 
             let start = line.glyph_index();
             let end = line.glyph_index() + line.total_glyphs();
@@ -274,11 +602,86 @@ impl KayakFont {
             for index in start..end {
                 let rect = &mut glyph_rects[index];
                 rect.position.0 += shift_x;
+                rect.position.1 += shift_y;
             }
+
+            // Make room for glyphs lowered below this line
+            shift_y += drop;
         }
+        size.1 += shift_y;
+
         TextLayout::new(glyph_rects, lines, size, properties)
     }
 
+    /// Reorders the graphemes of each line from logical to visual order using the Unicode
+    /// bidirectional algorithm, moving their glyphs along with them.
+    ///
+    /// Lines are reordered after line breaking, so right-to-left runs wrap in reading order.
+    fn reorder_lines(
+        content: &str,
+        properties: TextProperties,
+        lines: &mut [Line],
+        glyph_rects: &mut [GlyphRect],
+        grapheme_offsets: &[usize],
+    ) {
+        let base_level = match properties.direction {
+            TextDirection::LeftToRight => Some(Level::ltr()),
+            TextDirection::RightToLeft => Some(Level::rtl()),
+            TextDirection::Auto => None,
+        };
+        let bidi = BidiInfo::new(content, base_level);
+        if properties.direction == TextDirection::LeftToRight && !bidi.has_rtl() {
+            // Nothing to reorder
+            return;
+        }
+
+        for line in lines.iter_mut() {
+            let first_index = line.grapheme_index();
+            let Some(line_start) = grapheme_offsets.get(first_index).copied() else {
+                continue;
+            };
+            let line_end = grapheme_offsets
+                .get(first_index + line.total_graphemes())
+                .copied()
+                .unwrap_or(content.len());
+            let Some(paragraph) = bidi
+                .paragraphs
+                .iter()
+                .find(|paragraph| paragraph.range.contains(&line_start))
+            else {
+                continue;
+            };
+
+            let (levels, runs) = bidi.visual_runs(paragraph, line_start..line_end);
+            let mut visual_order = Vec::with_capacity(line.total_graphemes());
+            for run in runs {
+                let cols = (0..line.total_graphemes())
+                    .filter(|col| run.contains(&grapheme_offsets[first_index + col]));
+                if levels[run.start].is_rtl() {
+                    visual_order.extend(cols.rev());
+                } else {
+                    visual_order.extend(cols);
+                }
+            }
+
+            // Place the graphemes from left to right in their visual order
+            let mut position_x = 0.0;
+            let graphemes = line.graphemes_mut();
+            for col in visual_order.iter() {
+                let grapheme = &mut graphemes[*col];
+                grapheme.right_to_left = levels[grapheme_offsets[first_index + col]].is_rtl();
+                let glyphs = grapheme.glyph_index..grapheme.glyph_index + grapheme.glyph_total;
+                for rect in glyph_rects[glyphs].iter_mut() {
+                    rect.position.0 += position_x - grapheme.position.0;
+                }
+                grapheme.position.0 = position_x;
+                position_x += grapheme.size.0;
+            }
+
+            line.set_visual_order(visual_order, paragraph.level.is_rtl());
+        }
+    }
+
     /// Attempts to find the next line break for a given set of [breakable words](BreakableWord).
     ///
     /// Each line break returned is guaranteed to be a _future_ index. That is, a line break will
@@ -301,6 +704,8 @@ impl KayakFont {
     /// * `line_width`: The current line's current width
     /// * `properties`: The associated text properties
     /// * `words`: The list of breakable words
+    /// * `content`: The text content the words were split from
+    /// * `spans`: The spans styling the text content
     ///
     fn find_next_break(
         &self,
@@ -308,7 +713,13 @@ impl KayakFont {
         line_width: f32,
         properties: TextProperties,
         words: &[BreakableWord],
+        content: &str,
+        spans: &[TextSpan],
     ) -> (Option<usize>, Option<usize>) {
+        let word_width = |word: &str| {
+            self.get_span_word_width(word, byte_offset(content, word), properties, spans)
+        };
+
         // Line Break Rules:
         //
         // Break before Next if...
@@ -355,14 +766,14 @@ impl KayakFont {
             return (None, None);
         };
 
-        let next_trimmed_width = self.get_word_width(next.content.trim_end(), properties);
+        let next_trimmed_width = word_width(next.content.trim_end());
 
         // 2.
         if next_trimmed_width > properties.max_size.0 {
             return (Some(next_index), None);
         }
 
-        let curr_width = self.get_word_width(curr.content, properties);
+        let curr_width = word_width(curr.content);
 
         // 3.
         if next_trimmed_width + curr_width > properties.max_size.0 {
@@ -389,7 +800,7 @@ impl KayakFont {
         let mut best_break_index = next_index;
 
         while let Some(peek) = words.get(peek_index) {
-            chain_width += self.get_word_width(peek.content, properties);
+            chain_width += word_width(peek.content);
 
             if peek.content.ends_with(char::is_whitespace) {
                 // End of joined chain
@@ -447,6 +858,44 @@ impl KayakFont {
             .and_then(|index| self.sdf.glyphs.get(*index as usize))
     }
 
+    /// Returns the vertical metrics of this font, as read from the font file.
+    ///
+    /// Atlases generated by `msdf-atlas-gen` don't store a cap height or x-height, so for those
+    /// fonts they're measured from the "H" and "x" glyphs instead.
+    pub fn metrics(&self) -> FontMetrics {
+        let metrics = &self.sdf.metrics;
+        // Both glyphs sit on the baseline, so the padding below them matches the padding above
+        let glyph_height = |c| {
+            self.get_glyph(c)
+                .and_then(|glyph| glyph.plane_bounds)
+                .map(|bounds| bounds.top + bounds.bottom)
+                .unwrap_or_default()
+        };
+
+        FontMetrics {
+            em_size: metrics.em_size,
+            ascent: metrics.ascender,
+            descent: metrics.descender,
+            line_gap: metrics.line_height - (metrics.ascender - metrics.descender),
+            cap_height: metrics.cap_height.unwrap_or_else(|| glyph_height('H')),
+            x_height: metrics.x_height.unwrap_or_else(|| glyph_height('x')),
+            underline_position: metrics.underline_y,
+            underline_thickness: metrics.underline_thickness,
+        }
+    }
+
+    /// The distance from the top of a line to its baseline (in pixels).
+    ///
+    /// Fonts without vertical metrics fall back to the font size.
+    fn line_ascent(&self, font_size: f32) -> f32 {
+        let metrics = self.metrics();
+        if metrics.em_size > 0.0 {
+            metrics.scaled(font_size).ascent
+        } else {
+            font_size
+        }
+    }
+
     /// Calculates the appropriate glyph size for a desired font size.
     ///
     /// This glyph size can then be used to provide a normalized size across all glyphs
@@ -459,3 +908,8 @@ impl KayakFont {
         )
     }
 }
+
+/// Returns the byte offset of a substring within the string it was sliced from.
+fn byte_offset(content: &str, substring: &str) -> usize {
+    substring.as_ptr() as usize - content.as_ptr() as usize
+}
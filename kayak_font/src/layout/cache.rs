@@ -0,0 +1,194 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use crate::{TextLayout, TextProperties, TextSpan};
+
+/// The number of layouts a font keeps by default.
+pub const DEFAULT_LAYOUT_CACHE_CAPACITY: usize = 1024;
+
+struct CachedLayout {
+    content: String,
+    properties: TextProperties,
+    spans: Vec<TextSpan>,
+    layout: TextLayout,
+    last_used: u64,
+}
+
+struct LayoutCacheInner {
+    entries: HashMap<u64, CachedLayout>,
+    capacity: usize,
+    tick: u64,
+}
+
+/// A least recently used cache of measured [`TextLayout`]s, keyed by content, [`TextProperties`]
+/// and [`TextSpan`]s.
+///
+/// Clones share the same cache. Two caches always compare as equal so that the cache doesn't
+/// affect the equality of the font that owns it.
+#[derive(Clone)]
+pub struct TextLayoutCache {
+    inner: Arc<Mutex<LayoutCacheInner>>,
+}
+
+impl Default for TextLayoutCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_LAYOUT_CACHE_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for TextLayoutCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextLayoutCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl PartialEq for TextLayoutCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl TextLayoutCache {
+    /// Creates a cache holding at most `capacity` layouts. A capacity of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LayoutCacheInner {
+                entries: HashMap::new(),
+                capacity,
+                tick: 0,
+            })),
+        }
+    }
+
+    /// Returns the cached layout for the content and properties or calculates and stores it.
+    pub fn get_or_insert_with(
+        &self,
+        content: &str,
+        properties: TextProperties,
+        measure: impl FnOnce() -> TextLayout,
+    ) -> TextLayout {
+        self.get_or_insert_with_spans(content, properties, &[], measure)
+    }
+
+    /// Like [`get_or_insert_with`](Self::get_or_insert_with) for content styled with spans.
+    pub fn get_or_insert_with_spans(
+        &self,
+        content: &str,
+        properties: TextProperties,
+        spans: &[TextSpan],
+        measure: impl FnOnce() -> TextLayout,
+    ) -> TextLayout {
+        let key = Self::key(content, properties, spans);
+
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.tick += 1;
+            let tick = inner.tick;
+            if let Some(cached) = inner.entries.get_mut(&key) {
+                // The key is only a hash, so make sure this really is the same text
+                if cached.content == content
+                    && cached.properties == properties
+                    && cached.spans == spans
+                {
+                    cached.last_used = tick;
+                    return cached.layout.clone();
+                }
+            }
+        }
+
+        let layout = measure();
+
+        if let Ok(mut inner) = self.inner.lock() {
+            if inner.capacity == 0 {
+                return layout;
+            }
+
+            if inner.entries.len() >= inner.capacity && !inner.entries.contains_key(&key) {
+                // Evict the least recently used layout
+                let oldest = inner
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.last_used)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    inner.entries.remove(&oldest);
+                }
+            }
+
+            let last_used = inner.tick;
+            inner.entries.insert(
+                key,
+                CachedLayout {
+                    content: content.to_string(),
+                    properties,
+                    spans: spans.to_vec(),
+                    layout: layout.clone(),
+                    last_used,
+                },
+            );
+        }
+
+        layout
+    }
+
+    /// Removes all cached layouts.
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.entries.clear();
+        }
+    }
+
+    /// Sets the maximum number of cached layouts, evicting the least recently used ones if needed.
+    pub fn set_capacity(&self, capacity: usize) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.capacity = capacity;
+            if inner.entries.len() > capacity {
+                let mut by_age = inner
+                    .entries
+                    .iter()
+                    .map(|(key, cached)| (cached.last_used, *key))
+                    .collect::<Vec<_>>();
+                by_age.sort_unstable();
+                let excess = by_age.len() - capacity;
+                for (_, key) in by_age.into_iter().take(excess) {
+                    inner.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// The number of cached layouts.
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .map(|inner| inner.entries.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn key(content: &str, properties: TextProperties, spans: &[TextSpan]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        properties.font_size.to_bits().hash(&mut hasher);
+        properties.line_height.to_bits().hash(&mut hasher);
+        properties.max_size.0.to_bits().hash(&mut hasher);
+        properties.max_size.1.to_bits().hash(&mut hasher);
+        std::mem::discriminant(&properties.alignment).hash(&mut hasher);
+        properties.tab_size.hash(&mut hasher);
+        properties.kerning.hash(&mut hasher);
+        properties.direction.hash(&mut hasher);
+        properties.letter_spacing.to_bits().hash(&mut hasher);
+        for span in spans {
+            span.range.hash(&mut hasher);
+            span.size_scale.to_bits().hash(&mut hasher);
+            span.baseline_shift.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
@@ -19,6 +19,8 @@ pub struct Grapheme {
     pub position: (f32, f32),
     /// The size of this grapheme.
     pub size: (f32, f32),
+    /// Whether this grapheme is part of a right-to-left run of text.
+    pub right_to_left: bool,
 }
 
 impl PartialOrd for Grapheme {
@@ -11,6 +11,10 @@ pub struct Line {
     grapheme_index: usize,
     graphemes: Vec<Grapheme>,
     width: f32,
+    baseline: f32,
+    /// The line-local grapheme indices in visual order, empty if it matches the logical order
+    visual_order: Vec<usize>,
+    right_to_left: bool,
 }
 
 /// A reference to the grapheme at a specific row and column of a given line of text.
@@ -33,6 +37,9 @@ impl Line {
             grapheme_index,
             graphemes: Vec::new(),
             width: 0.0,
+            baseline: 0.0,
+            visual_order: Vec::new(),
+            right_to_left: false,
         }
     }
 
@@ -50,6 +57,21 @@ impl Line {
         self.width
     }
 
+    /// The y position of this line's baseline (in pixels), relative to the top of the text.
+    ///
+    /// This is where glyphs without a [baseline shift] sit, found by adding the font's ascent to
+    /// the top of the line. Descenders, like the tail of a "g", reach below it.
+    ///
+    /// [baseline shift]: crate::TextSpan::baseline_shift
+    pub fn baseline(&self) -> f32 {
+        self.baseline
+    }
+
+    /// Sets the y position of this line's baseline (in pixels).
+    pub(crate) fn set_baseline(&mut self, baseline: f32) {
+        self.baseline = baseline;
+    }
+
     /// Returns the grapheme at the given index within this line, if any.
     ///
     /// If the grapheme does
@@ -71,6 +93,36 @@ impl Line {
         &self.graphemes
     }
 
+    /// The line-local indices of this line's graphemes, ordered from left to right as rendered.
+    ///
+    /// Graphemes are stored in logical (reading) order. Right-to-left runs of text are rendered
+    /// in reverse, so this differs from the logical order for lines containing them.
+    pub fn visual_order(&self) -> Vec<usize> {
+        if self.visual_order.is_empty() {
+            (0..self.graphemes.len()).collect()
+        } else {
+            self.visual_order.clone()
+        }
+    }
+
+    /// Whether this line belongs to a right-to-left paragraph.
+    ///
+    /// This is the base direction the line's alignment is relative to.
+    pub fn is_right_to_left(&self) -> bool {
+        self.right_to_left
+    }
+
+    /// Sets the visual order and base direction of this line.
+    pub(crate) fn set_visual_order(&mut self, visual_order: Vec<usize>, right_to_left: bool) {
+        self.visual_order = visual_order;
+        self.right_to_left = right_to_left;
+    }
+
+    /// The list of grapheme clusters in this line, mutably.
+    pub(crate) fn graphemes_mut(&mut self) -> &mut [Grapheme] {
+        &mut self.graphemes
+    }
+
     /// The index of the starting grapheme cluster within this line, relative to the entire text content.
     pub fn grapheme_index(&self) -> usize {
         self.grapheme_index
@@ -123,6 +175,13 @@ impl Line {
         }
     }
 
+    /// Moves the graphemes of this line down by the given amount (in pixels).
+    pub(crate) fn offset_y(&mut self, offset: f32) {
+        for grapheme in self.graphemes.iter_mut() {
+            grapheme.position.1 += offset;
+        }
+    }
+
     /// Add a new grapheme to this line.
     pub fn add_grapheme(&mut self, grapheme: Grapheme) {
         self.width += grapheme.size.0;
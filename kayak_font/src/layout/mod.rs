@@ -1,11 +1,15 @@
 //! Contains useful types for text layout.
 
+mod cache;
 mod glyph;
 mod grapheme;
 mod line;
+mod span;
 mod text;
 
+pub use cache::*;
 pub use glyph::*;
 pub use grapheme::*;
 pub use line::*;
+pub use span::*;
 pub use text::*;
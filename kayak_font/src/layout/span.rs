@@ -0,0 +1,72 @@
+use bevy::reflect::Reflect;
+use std::ops::Range;
+
+/// Styling applied to a run of text, such as a superscript or subscript.
+///
+/// Glyphs within the span are scaled by [`size_scale`](Self::size_scale) and moved off the
+/// baseline by [`baseline_shift`](Self::baseline_shift). The rest of the text keeps using the
+/// main baseline.
+#[derive(Clone, Reflect, Debug, PartialEq)]
+pub struct TextSpan {
+    /// The byte range of the text content this span applies to.
+    pub range: Range<usize>,
+    /// The size of the span's glyphs relative to the font size.
+    pub size_scale: f32,
+    /// How far the span's glyphs are raised above the baseline, relative to the font size.
+    ///
+    /// Negative values lower the glyphs.
+    pub baseline_shift: f32,
+}
+
+impl TextSpan {
+    /// Creates a span that leaves its text unchanged.
+    pub fn new(range: Range<usize>) -> Self {
+        Self {
+            range,
+            size_scale: 1.0,
+            baseline_shift: 0.0,
+        }
+    }
+
+    /// Creates a span that renders its text smaller and raised, like the "2" in "x²".
+    pub fn superscript(range: Range<usize>) -> Self {
+        Self {
+            range,
+            size_scale: 0.6,
+            baseline_shift: 0.4,
+        }
+    }
+
+    /// Creates a span that renders its text smaller and lowered, like the "2" in "H₂O".
+    pub fn subscript(range: Range<usize>) -> Self {
+        Self {
+            range,
+            size_scale: 0.6,
+            baseline_shift: -0.2,
+        }
+    }
+
+    /// Sets the size of the span's glyphs relative to the font size.
+    pub fn with_size_scale(mut self, size_scale: f32) -> Self {
+        self.size_scale = size_scale;
+        self
+    }
+
+    /// Sets how far the span's glyphs are raised above the baseline, relative to the font size.
+    pub fn with_baseline_shift(mut self, baseline_shift: f32) -> Self {
+        self.baseline_shift = baseline_shift;
+        self
+    }
+
+    /// Returns the size scale and baseline shift of the span containing the given byte index.
+    ///
+    /// Later spans take precedence over earlier ones. Text outside every span is unscaled.
+    pub fn resolve(spans: &[TextSpan], byte_index: usize) -> (f32, f32) {
+        spans
+            .iter()
+            .rev()
+            .find(|span| span.range.contains(&byte_index))
+            .map(|span| (span.size_scale, span.baseline_shift))
+            .unwrap_or((1.0, 0.0))
+    }
+}
@@ -2,6 +2,7 @@ use bevy::reflect::Reflect;
 
 use crate::{GlyphRect, Line, RowCol};
 use std::cmp::Ordering;
+use std::ops::Range;
 
 /// The text alignment.
 #[derive(Copy, Clone, Reflect, Debug, PartialEq, Eq)]
@@ -11,6 +12,25 @@ pub enum Alignment {
     End,
 }
 
+/// The base direction text is laid out in.
+///
+/// Lines are reordered with the Unicode bidirectional algorithm, so runs of the opposite
+/// direction (like English words or numbers in Arabic text) are still rendered correctly.
+/// [`Alignment::Start`] and [`Alignment::End`] are relative to the base direction, so `Start`
+/// aligns right-to-left paragraphs to the right.
+#[derive(Copy, Clone, Reflect, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Lines start at the left edge and advance rightward.
+    #[default]
+    LeftToRight,
+    /// Lines start at the right edge and advance leftward, as in Arabic or Hebrew.
+    RightToLeft,
+    /// Each paragraph takes the direction of its first strongly directional character.
+    ///
+    /// Paragraphs without any are laid out left-to-right.
+    Auto,
+}
+
 /// Properties to control text layout.
 #[derive(Copy, Clone, Reflect, Debug, PartialEq)]
 pub struct TextProperties {
@@ -24,6 +44,12 @@ pub struct TextProperties {
     pub alignment: Alignment,
     /// The size of a tab (`'\t'`) character in equivalent spaces.
     pub tab_size: u8,
+    /// Whether to apply the font's kerning between pairs of characters.
+    pub kerning: bool,
+    /// The direction lines are laid out in.
+    pub direction: TextDirection,
+    /// Extra space added after every grapheme (in pixels). Negative values tighten the text.
+    pub letter_spacing: f32,
 }
 
 impl Default for TextProperties {
@@ -34,6 +60,26 @@ impl Default for TextProperties {
             max_size: (f32::MAX, f32::MAX),
             tab_size: 4,
             alignment: Alignment::Start,
+            kerning: true,
+            direction: TextDirection::LeftToRight,
+            letter_spacing: 0.0,
+        }
+    }
+}
+
+impl TextProperties {
+    /// The horizontal offset the alignment applies to the given line (in pixels).
+    pub fn line_offset(&self, line: &Line) -> f32 {
+        let alignment = match (line.is_right_to_left(), self.alignment) {
+            (true, Alignment::Start) => Alignment::End,
+            (true, Alignment::End) => Alignment::Start,
+            (_, alignment) => alignment,
+        };
+
+        match alignment {
+            Alignment::Start => 0.0,
+            Alignment::Middle => (self.max_size.0 - line.width()) / 2.0,
+            Alignment::End => self.max_size.0 - line.width(),
         }
     }
 }
@@ -117,6 +163,83 @@ impl TextLayout {
             .unwrap_or_default()
     }
 
+    /// Returns the caret index (a grapheme boundary) closest to the given position.
+    ///
+    /// The position is relative to the top-left of the text content. Positions outside the
+    /// content are clamped to the nearest line and to the start or end of that line.
+    pub fn grapheme_at_position(&self, position: (f32, f32)) -> usize {
+        if self.lines.is_empty() {
+            return 0;
+        }
+
+        let line_height = self.properties.line_height.max(f32::EPSILON);
+        let row = (position.1 / line_height).max(0.0) as usize;
+        let row = row.min(self.lines.len() - 1);
+        let line = &self.lines[row];
+
+        // Walk the graphemes from left to right. Right-to-left graphemes advance leftward, so
+        // their logical start is on their right side.
+        let visual_order = line.visual_order();
+        let caret = visual_order
+            .iter()
+            .map(|col| (*col, line[*col]))
+            .find(|(_, grapheme)| position.0 < grapheme.position.0 + grapheme.size.0 / 2.0)
+            .map(|(col, grapheme)| if grapheme.right_to_left { col + 1 } else { col })
+            .or_else(|| {
+                visual_order.last().map(|col| {
+                    if line[*col].right_to_left {
+                        *col
+                    } else {
+                        col + 1
+                    }
+                })
+            })
+            .unwrap_or_default();
+
+        if caret >= line.total_graphemes() && row + 1 < self.lines.len() && caret > 0 {
+            // Stay on this line rather than jumping past its final (breaking) grapheme
+            line.grapheme_index() + line.total_graphemes() - 1
+        } else {
+            line.grapheme_index() + caret
+        }
+    }
+
+    /// Returns the bounds of the graphemes in the given grapheme range.
+    ///
+    /// There's one rect per line the range spans, each covering the range's graphemes on that
+    /// line and the full line height. Within mixed-direction lines the graphemes of a range may
+    /// not be next to each other, in which case the rect covers all of them. Rects are relative to the top-left of the text content and
+    /// include the line's alignment, so they line up with the rendered glyphs.
+    pub fn bounds_of(&self, range: Range<usize>) -> Vec<bevy::math::Rect> {
+        let mut bounds = Vec::new();
+        for line in self.lines.iter() {
+            let start = range.start.max(line.grapheme_index());
+            let end = range
+                .end
+                .min(line.grapheme_index() + line.total_graphemes());
+            if start >= end {
+                continue;
+            }
+
+            let graphemes = &line[start - line.grapheme_index()..end - line.grapheme_index()];
+            let (left, right) = graphemes
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(left, right), g| {
+                    (left.min(g.position.0), right.max(g.position.0 + g.size.0))
+                });
+            let offset = self.properties.line_offset(line);
+            let top = graphemes[0].position.1;
+            bounds.push(bevy::math::Rect::new(
+                left + offset,
+                top,
+                right + offset,
+                top + self.properties.line_height,
+            ));
+        }
+
+        bounds
+    }
+
     /// Performs a binary search to find the grapheme at the given index.
     ///
     /// If the grapheme could not be found, `None` is returned.
@@ -5,6 +5,7 @@ mod layout;
 mod metrics;
 mod msdf;
 mod sdf;
+mod shaping;
 mod ttf;
 mod utility;
 
@@ -20,7 +21,10 @@ pub mod bevy;
 
 #[cfg(test)]
 mod tests {
-    use crate::{Alignment, ImageType, KayakFont, Sdf, TextProperties};
+    use crate::{
+        Alignment, ImageType, KayakFont, KerningData, Sdf, TextDirection, TextLayout,
+        TextProperties, TextSpan,
+    };
 
     fn make_font() -> KayakFont {
         let bytes = std::fs::read("assets/roboto.kayak_font")
@@ -43,6 +47,9 @@ mod tests {
             alignment: Alignment::Start,
             max_size: (200.0, 300.0),
             tab_size: 4,
+            kerning: true,
+            direction: TextDirection::LeftToRight,
+            letter_spacing: 0.0,
         }
     }
 
@@ -120,6 +127,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_find_grapheme_at_position() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let layout = font.measure(content, properties);
+
+        assert_eq!(0, layout.grapheme_at_position((-10.0, -10.0)));
+        assert_eq!(
+            content.find("How").unwrap(),
+            layout.grapheme_at_position((0.0, properties.line_height * 1.5))
+        );
+        assert_eq!(
+            content.len(),
+            layout.grapheme_at_position((10000.0, 10000.0))
+        );
+
+        let last_line = layout.lines().last().unwrap();
+        let grapheme = last_line.grapheme(1);
+        let position = (
+            grapheme.position.0 + grapheme.size.0 * 0.25,
+            grapheme.position.1,
+        );
+        assert_eq!(
+            last_line.grapheme_index() + 1,
+            layout.grapheme_at_position(position)
+        );
+    }
+
     #[test]
     fn grapheme_should_be_correct_position() {
         let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
@@ -138,4 +174,298 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn should_treat_multi_codepoint_clusters_as_one_grapheme() {
+        // "e" + combining acute accent, a regional indicator flag, and a ZWJ emoji sequence
+        let content = "ae\u{301}b\u{1F1EF}\u{1F1F5}c\u{1F469}\u{200D}\u{1F4BB}d";
+        let font = make_font();
+        let properties = make_properties();
+        let layout = font.measure(content, properties);
+
+        let graphemes = font.get_graphemes(content);
+        assert_eq!(
+            vec![
+                "a",
+                "e\u{301}",
+                "b",
+                "\u{1F1EF}\u{1F1F5}",
+                "c",
+                "\u{1F469}\u{200D}\u{1F4BB}",
+                "d"
+            ],
+            graphemes
+        );
+        assert_eq!(graphemes.len(), layout.total_graphemes());
+        assert_eq!(1, layout.total_lines());
+
+        let line = &layout.lines()[0];
+        assert_eq!(2, line.grapheme(1).char_total);
+        assert_eq!(2, line.grapheme(3).char_total);
+        assert_eq!(3, line.grapheme(5).char_total);
+        assert!(layout.find_grapheme(graphemes.len() - 1).is_some());
+        assert!(layout.find_grapheme(graphemes.len()).is_none());
+
+        // Stepping the cursor right lands on each cluster boundary in turn
+        for index in 0..graphemes.len() {
+            let grapheme = line.grapheme(index);
+            let position = (
+                grapheme.position.0 + grapheme.size.0 * 0.25,
+                grapheme.position.1,
+            );
+            assert_eq!(index, layout.grapheme_at_position(position));
+        }
+    }
+
+    #[test]
+    fn should_read_font_metrics() {
+        let font = make_font();
+        let metrics = font.metrics();
+
+        assert_eq!(1.0, metrics.em_size);
+        // Roboto has 2048 units per em, an ascender of 1900 and a descender of -500
+        assert_eq!(1900.0 / 2048.0, metrics.ascent);
+        assert_eq!(-500.0 / 2048.0, metrics.descent);
+        assert_eq!(0.0, metrics.line_gap);
+        // Roboto's OS/2 table has a cap height of 1456 and an x-height of 1082 units per 2048
+        assert!((metrics.cap_height - 1456.0 / 2048.0).abs() < 0.001);
+        assert!((metrics.x_height - 1082.0 / 2048.0).abs() < 0.001);
+
+        let scaled = metrics.scaled(14.0);
+        assert_eq!(14.0, scaled.em_size);
+        assert_eq!(1900.0 / 2048.0 * 14.0, scaled.ascent);
+        assert!((metrics.line_height() * 14.0 - scaled.line_height()).abs() < 0.001);
+    }
+
+    #[test]
+    fn should_place_baseline_below_line_top() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let layout = font.measure(content, properties);
+
+        let ascent = font.metrics().scaled(properties.font_size).ascent;
+        for (row, line) in layout.lines().iter().enumerate() {
+            let expected = properties.line_height * row as f32 + ascent;
+            assert!((expected - line.baseline()).abs() < 0.001);
+        }
+
+        // Glyphs hang from their top, which sits above the baseline by the glyph's height
+        let top = font.get_glyph('H').unwrap().plane_bounds.unwrap().top;
+        let baseline = layout.lines()[0].baseline();
+        let expected = baseline - top * properties.font_size;
+        assert!((expected - layout.glyphs()[0].position.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn should_return_bounds_per_line() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let layout = font.measure(content, properties);
+
+        let world = content.find("world").unwrap();
+        let bounds = font.measure_range(content, world..world + 5, properties);
+        assert_eq!(1, bounds.len());
+        assert_eq!(layout.bounds_of(world..world + 5), bounds);
+        assert_eq!(0.0, bounds[0].min.y);
+        assert_eq!(properties.line_height, bounds[0].height());
+
+        let how = content.find("How").unwrap();
+        let bounds = layout.bounds_of(how..content.len());
+        assert_eq!(3, bounds.len());
+        for (index, rect) in bounds.iter().enumerate() {
+            assert_eq!(properties.line_height * (index + 1) as f32, rect.min.y);
+        }
+        assert_eq!(0.0, bounds[0].min.x);
+        assert!(layout.bounds_of(how..how).is_empty());
+    }
+
+    #[test]
+    fn should_scale_and_shift_spans() {
+        let content = "x2 + y2";
+        let font = make_font();
+        let properties = make_properties();
+        let plain = font.measure(content, properties);
+        let spans = [TextSpan::superscript(1..2), TextSpan::subscript(6..7)];
+        let layout = font.measure_with_spans(content, properties, &spans);
+
+        let plain_glyphs = plain.glyphs();
+        let glyphs = layout.glyphs();
+        assert_eq!(plain_glyphs.len(), glyphs.len());
+
+        // Superscript is smaller and raised relative to the surrounding text
+        assert!(glyphs[1].size.0 < plain_glyphs[1].size.0);
+        let superscript_offset = glyphs[1].position.1 - glyphs[0].position.1;
+        let plain_offset = plain_glyphs[1].position.1 - plain_glyphs[0].position.1;
+        assert!(superscript_offset < plain_offset);
+
+        // Glyphs after a span keep the main baseline but advance less
+        let offset = glyphs[2].position.1 - glyphs[0].position.1;
+        let plain_offset = plain_glyphs[2].position.1 - plain_glyphs[0].position.1;
+        assert!((offset - plain_offset).abs() < 0.001);
+        assert!(glyphs[2].position.0 < plain_glyphs[2].position.0);
+
+        // The line grows to fit the raised and lowered glyphs
+        assert!(layout.size().1 > plain.size().1);
+        assert!(glyphs[0].position.1 > plain_glyphs[0].position.1);
+    }
+
+    #[test]
+    fn should_apply_kerning_when_enabled() {
+        let content = "AVA";
+        let plain_font = make_font();
+        let properties = make_properties();
+        let plain = plain_font.measure(content, properties);
+
+        let mut font = make_font();
+        font.sdf.kerning.push(KerningData {
+            unicode1: 'A' as u32,
+            unicode2: 'V' as u32,
+            advance: -0.1,
+        });
+        font.generate_char_ids();
+        assert_eq!(Some(-0.1), font.kerning('A', 'V'));
+        assert_eq!(None, font.kerning('V', 'A'));
+
+        let kerned = font.measure(content, properties);
+        let shift = kerned.glyphs()[1].position.0 - plain.glyphs()[1].position.0;
+        assert!((shift + 0.1 * properties.font_size).abs() < 0.001);
+        let next_shift = kerned.glyphs()[2].position.0 - plain.glyphs()[2].position.0;
+        assert!((next_shift - shift).abs() < 0.001);
+
+        let unkerned = TextProperties {
+            kerning: false,
+            ..properties
+        };
+        assert_eq!(plain.glyphs(), font.measure(content, unkerned).glyphs());
+    }
+
+    #[test]
+    fn should_lay_out_right_to_left() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let rtl = TextProperties {
+            direction: TextDirection::RightToLeft,
+            ..properties
+        };
+        let ltr_layout = font.measure(content, properties);
+        let layout = font.measure(content, rtl);
+
+        // Wrapping doesn't depend on the direction
+        assert_eq!(ltr_layout.total_lines(), layout.total_lines());
+        assert_eq!(ltr_layout.size(), layout.size());
+
+        // Start alignment means the right edge
+        for (line, ltr_line) in layout.lines().iter().zip(ltr_layout.lines()) {
+            assert_eq!(ltr_line.width(), line.width());
+            assert!(line.is_right_to_left());
+            let range = line.grapheme_index()..line.grapheme_index() + line.total_graphemes();
+            let bounds = layout.bounds_of(range);
+            assert!((bounds[0].max.x - properties.max_size.0).abs() < 0.001);
+        }
+
+        // Right-to-left text starts at the right edge and advances leftward
+        let content = "שלום עולם";
+        let layout = font.measure(content, rtl);
+        let line = &layout.lines()[0];
+        for pair in line.graphemes().windows(2) {
+            assert!(pair[1].position.0 < pair[0].position.0);
+            assert!(pair[0].right_to_left);
+        }
+        let first = line.graphemes()[0];
+        assert!((first.position.0 + first.size.0 - line.width()).abs() < 0.001);
+
+        let end = TextProperties {
+            alignment: Alignment::End,
+            ..rtl
+        };
+        let bounds = font.measure(content, end).bounds_of(0..1);
+        assert!((bounds[0].max.x - line.width()).abs() < 0.001);
+    }
+
+    #[test]
+    fn should_reorder_mixed_direction_text() {
+        let font = make_font();
+        let properties = make_properties();
+        let x = |layout: &TextLayout, index: usize| {
+            layout.find_grapheme(index).unwrap().grapheme.position.0
+        };
+
+        // Visually: "abc 123 גבא"
+        let layout = font.measure("abc אבג 123", properties);
+        assert!(!layout.lines()[0].is_right_to_left());
+        assert!(x(&layout, 0) < x(&layout, 1));
+        assert!(x(&layout, 5) < x(&layout, 4));
+        assert!(x(&layout, 6) < x(&layout, 5));
+        // Numbers stay left-to-right and end up before the hebrew run
+        assert!(x(&layout, 8) < x(&layout, 9));
+        assert!(x(&layout, 10) < x(&layout, 6));
+        assert!(x(&layout, 2) < x(&layout, 8));
+
+        // Visually: "123 גבא", with the base direction taken from the hebrew text
+        let auto = TextProperties {
+            direction: TextDirection::Auto,
+            ..properties
+        };
+        let layout = font.measure("אבג 123", auto);
+        assert!(layout.lines()[0].is_right_to_left());
+        assert!(x(&layout, 4) < x(&layout, 5));
+        assert!(x(&layout, 6) < x(&layout, 2));
+        assert!(x(&layout, 1) < x(&layout, 0));
+
+        // Hit testing follows the logical order
+        assert_eq!(0, layout.grapheme_at_position((10000.0, 0.0)));
+        let first = layout.find_grapheme(0).unwrap().grapheme;
+        let position = (first.position.0 + first.size.0 * 0.25, 0.0);
+        assert_eq!(1, layout.grapheme_at_position(position));
+        assert_eq!(vec![4, 5, 6, 3, 2, 1, 0], layout.lines()[0].visual_order());
+    }
+
+    #[test]
+    fn should_cache_layouts() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+
+        let layout = font.measure(content, properties);
+        assert_eq!(1, font.layout_cache().len());
+        assert_eq!(layout, font.measure(content, properties));
+        assert_eq!(layout, font.measure_uncached(content, properties));
+        assert_eq!(1, font.layout_cache().len());
+
+        let wider = TextProperties {
+            max_size: (400.0, 300.0),
+            ..properties
+        };
+        assert_eq!(
+            font.measure_uncached(content, wider),
+            font.measure(content, wider)
+        );
+        assert_eq!(2, font.layout_cache().len());
+
+        font.layout_cache().set_capacity(1);
+        assert_eq!(1, font.layout_cache().len());
+        font.measure(content, properties);
+        assert_eq!(layout, font.measure(content, properties));
+
+        font.clear_layout_cache();
+        assert!(font.layout_cache().is_empty());
+    }
+
+    #[test]
+    fn should_add_letter_spacing_after_each_grapheme() {
+        let content = "Hello";
+        let font = make_font();
+        let properties = make_properties();
+        let spaced = TextProperties {
+            letter_spacing: 2.0,
+            ..properties
+        };
+
+        let width = font.measure(content, properties).lines()[0].width();
+        let spaced_width = font.measure(content, spaced).lines()[0].width();
+        assert!((spaced_width - width - 2.0 * content.len() as f32).abs() < 0.001);
+    }
 }
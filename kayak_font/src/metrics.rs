@@ -1,15 +1,83 @@
+// The `DeJson` derive expands optional fields into blocks that clippy would rewrite with `?`
+#![allow(clippy::question_mark)]
+
 use nanoserde::DeJson;
 
 #[derive(DeJson, Default, Debug, Copy, Clone, PartialEq)]
 pub struct Metrics {
     #[nserde(rename = "emSize")]
-    em_size: f32,
+    pub(crate) em_size: f32,
     #[nserde(rename = "lineHeight")]
-    line_height: f32,
-    ascender: f32,
-    descender: f32,
+    pub(crate) line_height: f32,
+    pub(crate) ascender: f32,
+    pub(crate) descender: f32,
     #[nserde(rename = "underlineY")]
-    underline_y: f32,
+    pub(crate) underline_y: f32,
     #[nserde(rename = "underlineThickness")]
-    underline_thickness: f32,
+    pub(crate) underline_thickness: f32,
+    #[nserde(rename = "capHeight")]
+    pub(crate) cap_height: Option<f32>,
+    #[nserde(rename = "xHeight")]
+    pub(crate) x_height: Option<f32>,
+}
+
+/// The vertical metrics of a font.
+///
+/// Values are in font units, where [`em_size`](Self::em_size) units make up one em. For fonts
+/// generated by `msdf-atlas-gen` and for TTF fonts, the em size is `1.0`, so values are fractions
+/// of the font size. Use [`scaled`](Self::scaled) to convert them to pixels.
+///
+/// Like the font itself, the y-axis points up: positive values are above the baseline and
+/// negative values are below it.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct FontMetrics {
+    /// The number of font units in one em.
+    pub em_size: f32,
+    /// The distance from the baseline to the top of the tallest glyphs.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the lowest glyphs.
+    ///
+    /// This is usually negative.
+    pub descent: f32,
+    /// The extra space the font recommends between the descent of one line and the ascent of the
+    /// next.
+    pub line_gap: f32,
+    /// The height of capital letters above the baseline.
+    pub cap_height: f32,
+    /// The height of lowercase letters, such as "x", above the baseline.
+    pub x_height: f32,
+    /// The position of an underline relative to the baseline.
+    ///
+    /// This is usually negative.
+    pub underline_position: f32,
+    /// The thickness of an underline.
+    pub underline_thickness: f32,
+}
+
+impl FontMetrics {
+    /// Returns these metrics converted to pixels for the given font size.
+    pub fn scaled(&self, font_size: f32) -> Self {
+        let scale = if self.em_size > 0.0 {
+            font_size / self.em_size
+        } else {
+            0.0
+        };
+
+        Self {
+            em_size: font_size,
+            ascent: self.ascent * scale,
+            descent: self.descent * scale,
+            line_gap: self.line_gap * scale,
+            cap_height: self.cap_height * scale,
+            x_height: self.x_height * scale,
+            underline_position: self.underline_position * scale,
+            underline_thickness: self.underline_thickness * scale,
+        }
+    }
+
+    /// The recommended distance between the baselines of two lines, in the same units as the
+    /// other metrics.
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
 }
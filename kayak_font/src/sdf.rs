@@ -4,9 +4,9 @@ use nanoserde::DeJson;
 #[derive(DeJson, Default, Debug, Clone, PartialEq)]
 pub struct Sdf {
     pub atlas: Atlas,
-    metrics: Metrics,
+    pub(crate) metrics: Metrics,
     pub glyphs: Vec<Glyph>,
-    kerning: Vec<KerningData>,
+    pub kerning: Vec<KerningData>,
 }
 
 #[derive(DeJson, Default, Debug, Clone, Copy, PartialEq)]
@@ -0,0 +1,175 @@
+// Without the `shaping` feature fonts are never shaped, so most of this goes unused
+#![cfg_attr(not(feature = "shaping"), allow(dead_code))]
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The first character glyphs without a character of their own are stored under, in the
+/// Supplementary Private Use Area-B
+const UNMAPPED_GLYPHS_START: u32 = 0x10_0000;
+
+/// A glyph placed by the text shaper
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    /// The character the glyph is stored under in the font
+    pub unicode: char,
+    /// The byte offset of the cluster the glyph belongs to, relative to the shaped text
+    pub cluster: usize,
+    /// How far the glyph moves the pen (in ems)
+    pub advance: f32,
+    /// How far the glyph is moved away from the pen (in ems, with y pointing up)
+    pub offset: (f32, f32),
+}
+
+/// The shaped glyphs drawn for a grapheme cluster
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct ShapedGrapheme {
+    /// The glyphs drawn at the start of the grapheme
+    pub glyphs: Vec<ShapedGlyph>,
+    /// The width of the grapheme (in ems)
+    pub advance: f32,
+}
+
+/// The character a glyph that isn't mapped to any character (like a ligature) is stored under
+#[cfg(feature = "shaping")]
+pub(crate) fn unmapped_glyph_char(glyph_id: u16) -> char {
+    char::from_u32(UNMAPPED_GLYPHS_START + glyph_id as u32).unwrap()
+}
+
+/// Whether a character is one a glyph without a character of its own is stored under
+pub(crate) fn is_unmapped_glyph_char(c: char) -> bool {
+    c as u32 >= UNMAPPED_GLYPHS_START
+}
+
+/// Splits the shaped glyphs of some text by the grapheme clusters of that text
+///
+/// A ligature can be made from several graphemes, such as the "fi" in "find". Its glyphs are drawn
+/// with the first of these graphemes, while its width is shared by all of them, so each grapheme
+/// can still be selected and hit on its own.
+pub(crate) fn group_by_grapheme(glyphs: &[ShapedGlyph], text: &str) -> Vec<ShapedGrapheme> {
+    let offsets = text
+        .grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .collect::<Vec<_>>();
+    let mut graphemes = vec![ShapedGrapheme::default(); offsets.len()];
+
+    let mut clusters = glyphs.iter().map(|glyph| glyph.cluster).collect::<Vec<_>>();
+    clusters.sort_unstable();
+    clusters.dedup();
+
+    for (index, start) in clusters.iter().enumerate() {
+        let end = clusters.get(index + 1).copied().unwrap_or(text.len());
+        let members = offsets
+            .iter()
+            .enumerate()
+            .filter(|(_, offset)| (*start..end).contains(*offset))
+            .map(|(grapheme, _)| grapheme)
+            .collect::<Vec<_>>();
+        let Some(first) = members.first().copied() else {
+            continue;
+        };
+
+        let cluster_glyphs = glyphs
+            .iter()
+            .filter(|glyph| glyph.cluster == *start)
+            .copied()
+            .collect::<Vec<_>>();
+        let advance = cluster_glyphs
+            .iter()
+            .map(|glyph| glyph.advance)
+            .sum::<f32>();
+        for grapheme in members.iter() {
+            graphemes[*grapheme].advance = advance / members.len() as f32;
+        }
+        graphemes[first].glyphs = cluster_glyphs;
+    }
+
+    graphemes
+}
+
+/// Shapes the given text with a face, in logical order
+///
+/// Right-to-left runs are reordered later on along with the rest of the line, so the text is
+/// always shaped left-to-right here. Glyphs are looked up in `glyph_chars` to find the character
+/// they're stored under.
+#[cfg(feature = "shaping")]
+pub(crate) fn shape(
+    face: ttf_parser::Face,
+    text: &str,
+    glyph_chars: &bevy::utils::HashMap<ttf_parser::GlyphId, char>,
+) -> Option<Vec<ShapedGlyph>> {
+    let units_per_em = face.units_per_em() as f32;
+    let face = rustybuzz::Face::from_face(face)?;
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.guess_segment_properties();
+    buffer.set_direction(rustybuzz::Direction::LeftToRight);
+    let output = rustybuzz::shape(&face, &[], buffer);
+
+    let glyphs = output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| {
+            let glyph_id = ttf_parser::GlyphId(info.glyph_id as u16);
+            ShapedGlyph {
+                unicode: glyph_chars
+                    .get(&glyph_id)
+                    .copied()
+                    .unwrap_or_else(|| unmapped_glyph_char(glyph_id.0)),
+                cluster: info.cluster as usize,
+                advance: position.x_advance as f32 / units_per_em,
+                offset: (
+                    position.x_offset as f32 / units_per_em,
+                    position.y_offset as f32 / units_per_em,
+                ),
+            }
+        })
+        .collect();
+
+    Some(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_by_grapheme, ShapedGlyph};
+
+    fn glyph(unicode: char, cluster: usize, advance: f32) -> ShapedGlyph {
+        ShapedGlyph {
+            unicode,
+            cluster,
+            advance,
+            offset: (0.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn should_share_ligature_width_between_its_graphemes() {
+        let ligature = '\u{10_0078}';
+        let glyphs = [
+            glyph(ligature, 0, 0.6),
+            glyph('n', 2, 0.5),
+            glyph('d', 3, 0.5),
+        ];
+
+        let graphemes = group_by_grapheme(&glyphs, "find");
+
+        assert_eq!(4, graphemes.len());
+        assert_eq!(vec![glyphs[0]], graphemes[0].glyphs);
+        assert!(graphemes[1].glyphs.is_empty());
+        assert_eq!(0.3, graphemes[0].advance);
+        assert_eq!(0.3, graphemes[1].advance);
+        assert_eq!(vec![glyphs[2]], graphemes[3].glyphs);
+    }
+
+    #[test]
+    fn should_keep_marks_with_their_base() {
+        let glyphs = [glyph('a', 0, 0.5), glyph('\u{310}', 0, 0.0)];
+
+        let graphemes = group_by_grapheme(&glyphs, "a\u{310}");
+
+        assert_eq!(1, graphemes.len());
+        assert_eq!(2, graphemes[0].glyphs.len());
+        assert_eq!(0.5, graphemes[0].advance);
+    }
+}
@@ -11,15 +11,22 @@ use bevy::{
     utils::{BoxedFuture, HashMap},
 };
 
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use thiserror::Error;
 
 use image::{EncodableLayout, RgbaImage};
 use nanoserde::DeJson;
 
+#[cfg(feature = "shaping")]
+use crate::shaping::unmapped_glyph_char;
 use crate::{
     msdf::{self, bitmap::FloatRGBBmp, shape::Shape, ttf_parser::ShapeBuilder, vector::Vector2},
-    Glyph, ImageType, KayakFont, Rect, Sdf,
+    shaping::is_unmapped_glyph_char,
+    Glyph, ImageType, KayakFont, Metrics, Rect, Sdf,
 };
+
+use super::rasterizer::GlyphRasterizer;
 #[derive(Default)]
 pub struct TTFLoader;
 
@@ -30,6 +37,60 @@ pub enum TTFLoaderError {
     /// An [IO](std::io) Error
     #[error("Could not load asset: {0}")]
     Io(#[from] std::io::Error),
+    /// The font file doesn't contain the requested face
+    #[error("Could not parse face {0} of the font: {1}")]
+    Face(u32, ttf_parser::FaceParsingError),
+    /// The font has no named instance with the requested name
+    #[error("The font has no named instance called {0:?}")]
+    UnknownInstance(String),
+    /// The font has no variation axis with the requested tag
+    #[error("The font has no variation axis {0:?}")]
+    UnknownAxis(String),
+}
+
+/// Settings for the [`TTFLoader`], used to pick a single face out of a font file
+///
+/// Font collections (`.ttc`) hold several faces, and variable fonts can be set to one of their
+/// named instances or to any value along their variation axes. The defaults load the first face
+/// as it is, so these only need to be set for such fonts.
+///
+/// # Example
+///
+/// ```ignore
+/// let bold: Handle<KayakFont> = asset_server.load_with_settings(
+///     "inter.kttf",
+///     |settings: &mut TTFLoaderSettings| {
+///         settings.named_instance = Some("Bold".into());
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct TTFLoaderSettings {
+    /// The index of the face to load from a font collection
+    pub face_index: u32,
+    /// The named instance of a variable font to load, like `"Bold"`
+    pub named_instance: Option<String>,
+    /// Values for the variation axes of a variable font, keyed by their tag, like `("wght", 600.0)`
+    ///
+    /// These are applied after the named instance, so they override its values.
+    pub variations: Vec<(String, f32)>,
+}
+
+impl TTFLoaderSettings {
+    /// A suffix for the cached glyph image, so every face of a font gets its own cache
+    fn cache_suffix(&self) -> String {
+        let mut suffix = String::new();
+        if self.face_index != 0 {
+            suffix.push_str(&format!("-face{}", self.face_index));
+        }
+        if let Some(name) = &self.named_instance {
+            suffix.push_str(&format!("-{}", name.replace(' ', "_")));
+        }
+        for (tag, value) in &self.variations {
+            suffix.push_str(&format!("-{}{}", tag, value));
+        }
+        suffix
+    }
 }
 
 #[derive(DeJson, Default, Debug, Clone)]
@@ -39,19 +100,21 @@ pub struct Kttf {
     char_range_end: String,
     offset_x: Option<f32>,
     offset_y: Option<f32>,
+    /// Generate glyphs the first time they're used instead of all of them while loading
+    lazy: Option<bool>,
 }
 
 impl AssetLoader for TTFLoader {
     type Asset = KayakFont;
 
-    type Settings = ();
+    type Settings = TTFLoaderSettings;
 
     type Error = TTFLoaderError;
 
     fn load<'a>(
         &'a self,
         reader: &'a mut Reader,
-        _settings: &'a (),
+        settings: &'a TTFLoaderSettings,
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
@@ -80,19 +143,18 @@ impl AssetLoader for TTFLoader {
                 .to_str()
                 .unwrap()
                 .to_string();
-            cache_path.set_file_name(format!("{}-cached.png", file_name));
+            cache_path.set_file_name(format!(
+                "{}{}-cached.png",
+                file_name,
+                settings.cache_suffix()
+            ));
             let cache_image = load_context.read_asset_bytes(cache_path.clone()).await;
 
             let font_range = char_range_start..char_range_end;
             let char_count = font_range.len() as u32;
 
-            let size_x = 64usize;
-            let size_y = 128usize;
-            let face = ttf_parser::Face::parse(&font_bytes, 0).unwrap();
-            let image_height = size_y as u32 * char_count;
-            let mut image_builder: RgbaImage = image::ImageBuffer::new(size_x as u32, image_height);
-            let mut yy = 0u32;
-            let mut glyphs = vec![];
+            let (size_x, size_y) = GLYPH_SIZE;
+            let face = parse_face(&font_bytes, settings)?;
 
             // Build char to glyph mapping..
             let mut glyph_to_char: HashMap<ttf_parser::GlyphId, char> =
@@ -112,6 +174,32 @@ impl AssetLoader for TTFLoader {
 
             let loaded_file = &kttf;
 
+            if loaded_file.lazy.unwrap_or_default() {
+                // Only calculate the metrics needed for layout now, glyphs are generated on first use
+                let mut glyph_ids = vec![];
+                for char_u in font_range {
+                    let Some(glyph_id) = char::from_u32(char_u)
+                        .and_then(|c| char_to_glyph.get(&c))
+                        .copied()
+                    else {
+                        continue;
+                    };
+                    glyph_ids.push((glyph_id, *glyph_to_char.get(&glyph_id).unwrap()));
+                }
+
+                return lazy_font(
+                    Arc::new(font_bytes),
+                    kttf.clone(),
+                    settings.clone(),
+                    glyph_ids,
+                );
+            }
+
+            let image_height = size_y as u32 * char_count;
+            let mut image_builder: RgbaImage = image::ImageBuffer::new(size_x as u32, image_height);
+            let mut yy = 0u32;
+            let mut glyphs = vec![];
+
             for char_u in font_range {
                 let c = char::from_u32(char_u).unwrap();
                 let glyph_id = char_to_glyph.get(&c);
@@ -119,100 +207,17 @@ impl AssetLoader for TTFLoader {
                     continue;
                 }
                 let glyph_id = *glyph_id.unwrap();
-                let mut output = FloatRGBBmp::new(size_x, size_y);
-                let mut builder = ShapeBuilder::default();
-                let pixel_scale = size_x as f64 / face.units_per_em() as f64;
-                builder.pixel_scale = pixel_scale;
-                let _result = face.outline_glyph(glyph_id, &mut builder);
-
-                let char_bounds = face
-                    .glyph_bounding_box(glyph_id)
-                    .unwrap_or(ttf_parser::Rect {
-                        x_min: 0,
-                        x_max: size_x as i16,
-                        y_min: 0,
-                        y_max: size_y as i16,
-                    });
-
-                let mut shape = builder.build();
-                shape.inverse_y_axis = true;
-                // let (left, bottom, right, top) = shape.get_bounds();
-
-                let scale = Vector2::new(1.0, 1.0);
-                let px_range = 8.0;
-                let range = px_range / scale.x.min(scale.y);
-
-                let (translation, plane) = calculate_plane(
-                    loaded_file,
-                    &mut shape,
-                    pixel_scale as f32,
-                    1.0,
-                    px_range as f32,
-                    1.0,
-                );
-                let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 / size_x as f32;
                 let c = *glyph_to_char.get(&glyph_id).unwrap();
-                glyphs.push(Glyph {
-                    unicode: c,
-                    advance: advance * pixel_scale as f32,
-                    atlas_bounds: Some(Rect {
-                        left: 0.0,
-                        bottom: 0.0,
-                        right: size_x as f32,
-                        top: size_y as f32,
-                    }),
-                    plane_bounds: Some(plane),
-                });
-
-                // let frame = Vector2::new(size_x as f64, size_y as f64);
-
-                // dbg!((left, right, top, bottom));
-
-                // left = (left - (size_x as f64 / 8.0)).max(0.0);
-                // right = (right + (size_x as f64 / 8.0)).min(size_x as f64);
-                // top = (top + (size_y as f64 / 8.0)).min(size_y as f64);
-                // bottom = (bottom - (size_y as f64 / 8.0)).max(0.0);
-
-                // dbg!((left, right, top, bottom));
+                let outlined = outline_glyph(&face, glyph_id, c, loaded_file);
+                glyphs.push(outlined.glyph);
 
-                // let dims = Vector2::new(right - left, top - bottom);
-
-                // let translate = Vector2::new(-left + (frame.x - dims.x), (frame.y - (bottom + dims.y)) - 1.0);
                 if cache_image.is_err() {
-                    msdf::edge_coloring::simple(&mut shape, 3.0, 0);
-                    msdf::gen::generate_msdf(
-                        &mut output,
-                        &shape,
-                        range,
-                        scale,
-                        translation + Vector2::new(0.0, size_x as f64 * 1.25),
-                        1.111_111_111_111_111_2,
+                    image::imageops::replace(
+                        &mut image_builder,
+                        &outlined.rasterize(),
+                        0,
+                        yy as i64,
                     );
-
-                    // let left = (translation.x - char_bounds.x_min as f64 * pixel_scale).max(0.0).floor() as u32;
-                    let right =
-                        (translation.x + char_bounds.x_max as f64 * pixel_scale).floor() as u32;
-                    // let top = (translation.y - char_bounds.y_min as f64 * pixel_scale).max(0.0).floor() as u32;
-                    let bottom =
-                        (translation.y + char_bounds.y_max as f64 * pixel_scale).floor() as u32;
-
-                    for x in 0..(right + 2).min(64) {
-                        for y in 0..bottom + 48 {
-                            // for x in 0..size_x as u32 {
-                            //     for y  in 0..size_y as u32 {
-                            let pixel = output.get_pixel(x as usize, y as usize);
-                            image_builder.put_pixel(
-                                x,
-                                yy + y,
-                                image::Rgba([
-                                    (pixel.r * 255.0) as u8,
-                                    (pixel.g * 255.0) as u8,
-                                    (pixel.b * 255.0) as u8,
-                                    255,
-                                ]),
-                            );
-                        }
-                    }
                 }
                 // if c == '\"' {
                 //     image_builder.save("test.png").unwrap();
@@ -255,8 +260,11 @@ impl AssetLoader for TTFLoader {
                 }
             };
 
-            let mut sdf = Sdf::default();
-            sdf.glyphs = glyphs;
+            let mut sdf = Sdf {
+                glyphs,
+                metrics: face_metrics(&face),
+                ..Default::default()
+            };
             sdf.atlas.font_size = size_x as f32;
 
             let mut image = bevy::prelude::Image::new(
@@ -287,6 +295,308 @@ impl AssetLoader for TTFLoader {
     }
 }
 
+/// The size of every glyph generated from a TTF file
+pub(crate) const GLYPH_SIZE: (usize, usize) = (64, 128);
+
+/// Builds a font whose glyphs are generated the first time they're used
+///
+/// Only the metrics needed for layout are calculated here.
+pub(crate) fn lazy_font(
+    font_bytes: Arc<Vec<u8>>,
+    kttf: Kttf,
+    settings: TTFLoaderSettings,
+    glyph_ids: Vec<(ttf_parser::GlyphId, char)>,
+) -> Result<KayakFont, TTFLoaderError> {
+    let face = parse_face(&font_bytes, &settings)?;
+    #[cfg(feature = "shaping")]
+    let glyph_ids = with_unmapped_glyphs(&face, glyph_ids);
+
+    let mut sdf = Sdf {
+        glyphs: glyph_ids
+            .iter()
+            .map(|(glyph_id, c)| {
+                if is_unmapped_glyph_char(*c) {
+                    glyph_metrics(&face, *glyph_id, *c, &kttf)
+                } else {
+                    outline_glyph(&face, *glyph_id, *c, &kttf).glyph
+                }
+            })
+            .collect(),
+        metrics: face_metrics(&face),
+        ..Default::default()
+    };
+    sdf.atlas.font_size = GLYPH_SIZE.0 as f32;
+
+    let rasterizer = GlyphRasterizer::new(font_bytes.clone(), kttf, settings, glyph_ids);
+    Ok(KayakFont::new(sdf, ImageType::Generated).with_rasterizer(rasterizer))
+}
+
+/// Adds the glyphs that no character maps to, like ligatures, which only shaping produces
+#[cfg(feature = "shaping")]
+fn with_unmapped_glyphs(
+    face: &ttf_parser::Face,
+    mut glyph_ids: Vec<(ttf_parser::GlyphId, char)>,
+) -> Vec<(ttf_parser::GlyphId, char)> {
+    let mapped = glyph_ids
+        .iter()
+        .map(|(glyph_id, _)| *glyph_id)
+        .collect::<bevy::utils::HashSet<_>>();
+    glyph_ids.extend(
+        (0..face.number_of_glyphs())
+            .map(ttf_parser::GlyphId)
+            .filter(|glyph_id| !mapped.contains(glyph_id))
+            .map(|glyph_id| (glyph_id, unmapped_glyph_char(glyph_id.0))),
+    );
+    glyph_ids
+}
+
+/// Parses the face picked by the loader settings and applies its variations
+pub(crate) fn parse_face<'a>(
+    font_bytes: &'a [u8],
+    settings: &TTFLoaderSettings,
+) -> Result<ttf_parser::Face<'a>, TTFLoaderError> {
+    let mut face = ttf_parser::Face::parse(font_bytes, settings.face_index)
+        .map_err(|error| TTFLoaderError::Face(settings.face_index, error))?;
+
+    if let Some(name) = &settings.named_instance {
+        let coordinates = named_instance(&face, name)
+            .ok_or_else(|| TTFLoaderError::UnknownInstance(name.clone()))?;
+        for (axis, value) in coordinates {
+            face.set_variation(axis, value);
+        }
+    }
+
+    for (tag, value) in &settings.variations {
+        <[u8; 4]>::try_from(tag.as_bytes())
+            .ok()
+            .and_then(|axis| face.set_variation(ttf_parser::Tag::from_bytes(&axis), *value))
+            .ok_or_else(|| TTFLoaderError::UnknownAxis(tag.clone()))?;
+    }
+
+    Ok(face)
+}
+
+/// Looks up the axis values of a named instance in the `fvar` table of a variable font
+fn named_instance(face: &ttf_parser::Face, name: &str) -> Option<Vec<(ttf_parser::Tag, f32)>> {
+    let data = face
+        .raw_face()
+        .table(ttf_parser::Tag::from_bytes(b"fvar"))?;
+    let read_u16 = |offset: usize| {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let axes_offset = read_u16(4)?;
+    let axis_count = read_u16(8)?;
+    let axis_size = read_u16(10)?;
+    let instance_count = read_u16(12)?;
+    let instance_size = read_u16(14)?;
+    let instances_offset = axes_offset + axis_count * axis_size;
+    let axes = face.variation_axes();
+
+    (0..instance_count).find_map(|index| {
+        let offset = instances_offset + index * instance_size;
+        let name_id = read_u16(offset)? as u16;
+        let has_name = face
+            .names()
+            .into_iter()
+            .any(|record| record.name_id == name_id && record.to_string().as_deref() == Some(name));
+        if !has_name {
+            return None;
+        }
+
+        // Each instance starts with its name id and flags, followed by a 16.16 value per axis
+        (0..axis_count)
+            .map(|axis| {
+                let start = offset + 4 + axis * 4;
+                let value = i32::from_be_bytes(data.get(start..start + 4)?.try_into().ok()?);
+                Some((axes.get(axis as u16)?.tag, value as f32 / 65536.0))
+            })
+            .collect()
+    })
+}
+
+/// Reads the vertical metrics of a font, scaled so that one em is `1.0` like glyph advances
+fn face_metrics(face: &ttf_parser::Face) -> Metrics {
+    let units_per_em = face.units_per_em() as f32;
+    let ascender = face.ascender() as f32 / units_per_em;
+    let descender = face.descender() as f32 / units_per_em;
+    let line_gap = face.line_gap() as f32 / units_per_em;
+    let underline = face.underline_metrics();
+
+    Metrics {
+        em_size: 1.0,
+        line_height: ascender - descender + line_gap,
+        ascender,
+        descender,
+        underline_y: underline
+            .map(|underline| underline.position as f32 / units_per_em)
+            .unwrap_or_default(),
+        underline_thickness: underline
+            .map(|underline| underline.thickness as f32 / units_per_em)
+            .unwrap_or_default(),
+        cap_height: face
+            .capital_height()
+            .map(|height| height as f32 / units_per_em),
+        x_height: face.x_height().map(|height| height as f32 / units_per_em),
+    }
+}
+
+/// Calculates the metrics of a glyph like [`outline_glyph`] does, without outlining it
+///
+/// There can be thousands of glyphs no character maps to, so these are only outlined once
+/// they're drawn.
+fn glyph_metrics(
+    face: &ttf_parser::Face,
+    glyph_id: ttf_parser::GlyphId,
+    unicode: char,
+    loaded_file: &Kttf,
+) -> Glyph {
+    let (size_x, size_y) = GLYPH_SIZE;
+    let pixel_scale = size_x as f32 / face.units_per_em() as f32;
+    let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 / size_x as f32;
+
+    Glyph {
+        unicode,
+        advance: advance * pixel_scale,
+        atlas_bounds: Some(Rect {
+            left: 0.0,
+            bottom: 0.0,
+            right: size_x as f32,
+            top: size_y as f32,
+        }),
+        plane_bounds: Some(Rect {
+            left: loaded_file.offset_x.unwrap_or_default() * pixel_scale,
+            bottom: 0.0,
+            right: 0.0,
+            top: loaded_file.offset_y.unwrap_or_default() * pixel_scale,
+        }),
+    }
+}
+
+/// A glyph outline along with everything needed to generate its MSDF image
+pub(crate) struct OutlinedGlyph {
+    pub glyph: Glyph,
+    shape: Shape,
+    translation: Vector2,
+    pixel_scale: f64,
+    char_bounds: ttf_parser::Rect,
+}
+
+/// Outlines a glyph and calculates its metrics
+pub(crate) fn outline_glyph(
+    face: &ttf_parser::Face,
+    glyph_id: ttf_parser::GlyphId,
+    unicode: char,
+    loaded_file: &Kttf,
+) -> OutlinedGlyph {
+    let (size_x, size_y) = GLYPH_SIZE;
+    let mut builder = ShapeBuilder::default();
+    let pixel_scale = size_x as f64 / face.units_per_em() as f64;
+    builder.pixel_scale = pixel_scale;
+    let _result = face.outline_glyph(glyph_id, &mut builder);
+
+    let char_bounds = face
+        .glyph_bounding_box(glyph_id)
+        .unwrap_or(ttf_parser::Rect {
+            x_min: 0,
+            x_max: size_x as i16,
+            y_min: 0,
+            y_max: size_y as i16,
+        });
+
+    let mut shape = builder.build();
+    shape.inverse_y_axis = true;
+    // let (left, bottom, right, top) = shape.get_bounds();
+
+    let px_range = 8.0;
+    let (translation, plane) = calculate_plane(
+        loaded_file,
+        &mut shape,
+        pixel_scale as f32,
+        1.0,
+        px_range as f32,
+        1.0,
+    );
+    let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 / size_x as f32;
+
+    OutlinedGlyph {
+        glyph: Glyph {
+            unicode,
+            advance: advance * pixel_scale as f32,
+            atlas_bounds: Some(Rect {
+                left: 0.0,
+                bottom: 0.0,
+                right: size_x as f32,
+                top: size_y as f32,
+            }),
+            plane_bounds: Some(plane),
+        },
+        shape,
+        translation,
+        pixel_scale,
+        char_bounds,
+    }
+}
+
+impl OutlinedGlyph {
+    /// Generates the MSDF image of the glyph
+    pub(crate) fn rasterize(mut self) -> RgbaImage {
+        let (size_x, size_y) = GLYPH_SIZE;
+        let mut output = FloatRGBBmp::new(size_x, size_y);
+        let scale = Vector2::new(1.0, 1.0);
+        let px_range = 8.0;
+        let range = px_range / scale.x.min(scale.y);
+
+        // let frame = Vector2::new(size_x as f64, size_y as f64);
+
+        // left = (left - (size_x as f64 / 8.0)).max(0.0);
+        // right = (right + (size_x as f64 / 8.0)).min(size_x as f64);
+        // top = (top + (size_y as f64 / 8.0)).min(size_y as f64);
+        // bottom = (bottom - (size_y as f64 / 8.0)).max(0.0);
+
+        // let dims = Vector2::new(right - left, top - bottom);
+
+        // let translate = Vector2::new(-left + (frame.x - dims.x), (frame.y - (bottom + dims.y)) - 1.0);
+        msdf::edge_coloring::simple(&mut self.shape, 3.0, 0);
+        msdf::gen::generate_msdf(
+            &mut output,
+            &self.shape,
+            range,
+            scale,
+            self.translation + Vector2::new(0.0, size_x as f64 * 1.25),
+            1.111_111_111_111_111_2,
+        );
+
+        // let left = (translation.x - char_bounds.x_min as f64 * pixel_scale).max(0.0).floor() as u32;
+        let right =
+            (self.translation.x + self.char_bounds.x_max as f64 * self.pixel_scale).floor() as u32;
+        // let top = (translation.y - char_bounds.y_min as f64 * pixel_scale).max(0.0).floor() as u32;
+        let bottom =
+            (self.translation.y + self.char_bounds.y_max as f64 * self.pixel_scale).floor() as u32;
+
+        let mut image: RgbaImage = image::ImageBuffer::new(size_x as u32, size_y as u32);
+        for x in 0..(right + 2).min(size_x as u32) {
+            for y in 0..(bottom + 48).min(size_y as u32) {
+                // for x in 0..size_x as u32 {
+                //     for y  in 0..size_y as u32 {
+                let pixel = output.get_pixel(x as usize, y as usize);
+                image.put_pixel(
+                    x,
+                    y,
+                    image::Rgba([
+                        (pixel.r * 255.0) as u8,
+                        (pixel.g * 255.0) as u8,
+                        (pixel.b * 255.0) as u8,
+                        255,
+                    ]),
+                );
+            }
+        }
+
+        image
+    }
+}
+
 fn calculate_plane(
     loaded_file: &Kttf,
     shape: &mut Shape,
@@ -367,3 +677,63 @@ fn calculate_plane(
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{lazy_font, parse_face, Kttf, TTFLoaderError, TTFLoaderSettings};
+
+    fn font_bytes() -> Vec<u8> {
+        std::fs::read("assets/roboto.ttf")
+            .expect("a `roboto.ttf` file in the `assets/` directory of this crate")
+    }
+
+    #[test]
+    fn should_load_first_face_by_default() {
+        let bytes = font_bytes();
+        let face = parse_face(&bytes, &TTFLoaderSettings::default());
+        assert!(face.is_ok());
+    }
+
+    #[test]
+    fn should_reject_missing_faces_and_axes() {
+        let bytes = font_bytes();
+        let missing_face = TTFLoaderSettings {
+            face_index: 3,
+            ..Default::default()
+        };
+        assert!(matches!(
+            parse_face(&bytes, &missing_face),
+            Err(TTFLoaderError::Face(3, _))
+        ));
+
+        let missing_axis = TTFLoaderSettings {
+            variations: vec![("wght".into(), 700.0)],
+            ..Default::default()
+        };
+        assert!(matches!(
+            parse_face(&bytes, &missing_axis),
+            Err(TTFLoaderError::UnknownAxis(_))
+        ));
+    }
+
+    #[test]
+    fn should_only_vary_fonts_along_their_axes() {
+        let bytes = font_bytes();
+        let glyph_id = parse_face(&bytes, &TTFLoaderSettings::default())
+            .unwrap()
+            .glyph_index('a')
+            .unwrap();
+        let font = lazy_font(
+            Arc::new(bytes),
+            Kttf::default(),
+            TTFLoaderSettings::default(),
+            vec![(glyph_id, 'a')],
+        )
+        .unwrap();
+
+        assert!(font.get_glyph('a').is_some());
+        assert!(font.with_variation("wght", 700.0).is_none());
+    }
+}
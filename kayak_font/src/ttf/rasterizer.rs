@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::utils::HashMap;
+
+use crate::bevy::{MAX_CHARACTERS, MAX_FONT_PAGES};
+
+use crate::KayakFont;
+
+use super::loader::{lazy_font, outline_glyph, parse_face, Kttf, TTFLoaderSettings};
+
+/// The MSDF image of a glyph waiting to be uploaded to the GPU
+pub(crate) struct GeneratedGlyph {
+    /// The index of the glyph in the font's texture pages
+    pub slot: u32,
+    /// The RGBA pixels of the glyph, [`GLYPH_SIZE`](super::loader::GLYPH_SIZE) in size
+    pub pixels: Vec<u8>,
+}
+
+#[derive(Default)]
+struct RasterizerState {
+    slots: HashMap<u32, u32>,
+    pending: Vec<GeneratedGlyph>,
+}
+
+/// Generates the glyphs of a lazily loaded TTF font the first time they're used
+///
+/// Every generated glyph is assigned the next free slot in the font's texture pages and kept
+/// until the font is unloaded, so a glyph is only ever generated once.
+pub(crate) struct GlyphRasterizer {
+    /// The font file, shared with the variations of the font
+    font_bytes: Arc<Vec<u8>>,
+    kttf: Kttf,
+    settings: TTFLoaderSettings,
+    /// The TTF glyph and character of each glyph in the font's [`Sdf`](crate::Sdf)
+    glyph_ids: Vec<(ttf_parser::GlyphId, char)>,
+    /// The character each TTF glyph is stored under, used to look up shaped glyphs
+    #[cfg(feature = "shaping")]
+    glyph_chars: HashMap<ttf_parser::GlyphId, char>,
+    state: Mutex<RasterizerState>,
+}
+
+impl std::fmt::Debug for GlyphRasterizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GlyphRasterizer")
+            .field("kttf", &self.kttf)
+            .field("settings", &self.settings)
+            .field("glyphs", &self.glyph_ids.len())
+            .finish()
+    }
+}
+
+impl PartialEq for GlyphRasterizer {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl GlyphRasterizer {
+    pub fn new(
+        font_bytes: Arc<Vec<u8>>,
+        kttf: Kttf,
+        settings: TTFLoaderSettings,
+        glyph_ids: Vec<(ttf_parser::GlyphId, char)>,
+    ) -> Self {
+        Self {
+            font_bytes,
+            kttf,
+            settings,
+            #[cfg(feature = "shaping")]
+            glyph_chars: glyph_ids.iter().copied().collect(),
+            glyph_ids,
+            state: Mutex::new(RasterizerState::default()),
+        }
+    }
+
+    /// Returns the slot of the glyph with the given id, generating it if it hasn't been yet
+    ///
+    /// Returns `None` if the glyph doesn't exist or the font's texture pages are full.
+    pub fn request(&self, char_id: u32) -> Option<u32> {
+        let mut state = self.state.lock().ok()?;
+        if let Some(slot) = state.slots.get(&char_id) {
+            return Some(*slot);
+        }
+
+        let slot = state.slots.len() as u32;
+        if slot >= MAX_CHARACTERS * MAX_FONT_PAGES {
+            return None;
+        }
+
+        let (glyph_id, unicode) = *self.glyph_ids.get(char_id as usize)?;
+        let face = parse_face(&self.font_bytes, &self.settings).ok()?;
+        let image = outline_glyph(&face, glyph_id, unicode, &self.kttf).rasterize();
+
+        state.slots.insert(char_id, slot);
+        state.pending.push(GeneratedGlyph {
+            slot,
+            pixels: image.into_raw(),
+        });
+
+        Some(slot)
+    }
+
+    /// Shapes the given text with the font's shaping tables
+    #[cfg(feature = "shaping")]
+    pub fn shape(&self, text: &str) -> Option<Vec<crate::shaping::ShapedGlyph>> {
+        let face = parse_face(&self.font_bytes, &self.settings).ok()?;
+        crate::shaping::shape(face, text, &self.glyph_chars)
+    }
+
+    /// Creates a font with a variation axis set to the given value
+    ///
+    /// The new font has its own glyphs and texture pages, which start out empty. Returns `None`
+    /// if the font doesn't have the given axis.
+    pub fn with_variation(&self, axis: &str, value: f32) -> Option<KayakFont> {
+        let mut settings = self.settings.clone();
+        settings.variations.retain(|(tag, _)| tag != axis);
+        settings.variations.push((axis.to_string(), value));
+        lazy_font(
+            self.font_bytes.clone(),
+            self.kttf.clone(),
+            settings,
+            self.glyph_ids.clone(),
+        )
+        .ok()
+    }
+
+    /// Returns the slot of the glyph with the given id if it has been generated
+    pub fn slot(&self, char_id: u32) -> Option<u32> {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.slots.get(&char_id).copied())
+    }
+
+    /// Takes the glyphs generated since the last call
+    pub fn take_generated(&self) -> Vec<GeneratedGlyph> {
+        self.state
+            .lock()
+            .map(|mut state| std::mem::take(&mut state.pending))
+            .unwrap_or_default()
+    }
+}
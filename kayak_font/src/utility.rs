@@ -1,3 +1,4 @@
+use unicode_segmentation::GraphemeCursor;
 use xi_unicode::LineBreakIterator;
 
 pub const NEWLINE: char = '\n';
@@ -24,11 +25,20 @@ pub fn is_tab(c: char) -> bool {
     c == TAB
 }
 
+/// Returns true if the given text is made up of only spaces, tabs and newlines.
+pub fn is_blank(text: &str) -> bool {
+    text.chars()
+        .all(|c| is_space(c) || is_tab(c) || is_newline(c))
+}
+
 /// Split a string into a collection of "words" that may be followed by a line break,
 /// according to [UAX #14](https://www.unicode.org/reports/tr14/).
 ///
 /// For example, `"Hello, world!"` would be broken into `["Hello, ", "world!"]`. And
 /// `"A-rather-long-word"` would be broken into `["A-", "rather-", "long-", "word"]`.
+///
+/// Words are never split within a grapheme cluster (such as a flag or an emoji joined with a
+/// zero width joiner), even where UAX #14 alone would allow a break.
 pub fn split_breakable_words(text: &str) -> BreakableWordIter {
     BreakableWordIter::new(text)
 }
@@ -67,7 +77,12 @@ impl<'a> Iterator for BreakableWordIter<'a> {
     type Item = BreakableWord<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (next_idx, is_hard) = self.iter.next()?;
+        let (next_idx, is_hard) = loop {
+            let (next_idx, is_hard) = self.iter.next()?;
+            if is_grapheme_boundary(self.text, next_idx) {
+                break (next_idx, is_hard);
+            }
+        };
         let word = self.text.get(self.index..next_idx)?;
         self.index = next_idx;
 
@@ -82,3 +97,10 @@ impl<'a> Iterator for BreakableWordIter<'a> {
         self.iter.size_hint()
     }
 }
+
+/// Returns true if the given byte index of the text is between two grapheme clusters
+fn is_grapheme_boundary(text: &str, index: usize) -> bool {
+    GraphemeCursor::new(index, text.len(), true)
+        .is_boundary(text, 0)
+        .unwrap_or(true)
+}
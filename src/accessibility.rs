@@ -0,0 +1,165 @@
+use bevy::{
+    a11y::{
+        accesskit::{self, Action, Checked, NodeBuilder},
+        AccessibilityNode, Focus,
+    },
+    prelude::*,
+};
+use morphorm::Hierarchy;
+
+pub use bevy::a11y::accesskit::Role;
+
+use crate::{context::KayakRootContext, layout::Rect, Focusable};
+
+/// Describes how a widget is presented to assistive technologies such as screen readers
+///
+/// The built-in widgets add this component while rendering. Custom widgets can insert it on
+/// their own entity to expose a role and label; widgets without it are exposed as generic
+/// containers so the hierarchy stays intact.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct AccessibilityInfo {
+    /// What kind of element the widget is
+    pub role: Role,
+    /// The text read out for the widget
+    pub label: Option<String>,
+    /// The current value of an input widget
+    pub value: Option<String>,
+    /// Whether a checkable widget (like a radio) is checked
+    pub checked: Option<bool>,
+    /// Whether the widget is disabled
+    pub disabled: bool,
+}
+
+impl AccessibilityInfo {
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            label: None,
+            value: None,
+            checked: None,
+            disabled: false,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Everything the [`AccessibilityNode`] of a widget was last built from
+///
+/// Used to only rebuild nodes that actually changed, since every rebuilt node is sent to the
+/// screen reader again.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub(crate) struct AccessibilitySnapshot {
+    info: Option<AccessibilityInfo>,
+    layout: Rect,
+    focusable: bool,
+}
+
+impl AccessibilitySnapshot {
+    fn build(&self) -> NodeBuilder {
+        let mut node = NodeBuilder::new(
+            self.info
+                .as_ref()
+                .map(|info| info.role)
+                .unwrap_or(Role::GenericContainer),
+        );
+
+        if let Some(info) = &self.info {
+            if let Some(label) = &info.label {
+                node.set_name(label.clone());
+            }
+            if let Some(value) = &info.value {
+                node.set_value(value.clone());
+            }
+            if let Some(checked) = info.checked {
+                node.set_checked(if checked {
+                    Checked::True
+                } else {
+                    Checked::False
+                });
+            }
+            if info.disabled {
+                node.set_disabled();
+            }
+        }
+
+        if self.focusable {
+            node.add_action(Action::Focus);
+        }
+
+        node.set_bounds(accesskit::Rect {
+            x0: self.layout.posx as f64,
+            y0: self.layout.posy as f64,
+            x1: (self.layout.posx + self.layout.width) as f64,
+            y1: (self.layout.posy + self.layout.height) as f64,
+        });
+
+        node
+    }
+}
+
+/// Keeps the [`AccessibilityNode`] of every widget in sync with its [`AccessibilityInfo`],
+/// layout and focus
+pub(crate) fn update_accessibility_nodes(
+    mut commands: Commands,
+    contexts: Query<&KayakRootContext>,
+    widgets: Query<(
+        Option<&AccessibilityInfo>,
+        Option<&AccessibilitySnapshot>,
+        Has<Focusable>,
+    )>,
+    focus: Option<ResMut<Focus>>,
+    mut last_focus: Local<Option<Entity>>,
+) {
+    let mut current_focus = None;
+    for context in contexts.iter() {
+        if let (Ok(tree), Ok(layout_cache)) =
+            (context.tree.try_read(), context.layout_cache.try_read())
+        {
+            for node in tree.down_iter() {
+                if let Ok((info, snapshot, focusable)) = widgets.get(node.0) {
+                    let new_snapshot = AccessibilitySnapshot {
+                        info: info.cloned(),
+                        layout: layout_cache.rect.get(&node).copied().unwrap_or_default(),
+                        focusable,
+                    };
+                    if snapshot != Some(&new_snapshot) {
+                        commands
+                            .entity(node.0)
+                            .insert((AccessibilityNode(new_snapshot.build()), new_snapshot));
+                    }
+                }
+            }
+        }
+
+        if current_focus.is_none() {
+            current_focus = context.focus_tree.current();
+        }
+    }
+
+    // Only push Kayak's focus when it changes so focus set elsewhere (e.g. by bevy_ui) is kept
+    if *last_focus != current_focus {
+        *last_focus = current_focus;
+        if let Some(mut focus) = focus {
+            focus.0 = current_focus;
+        }
+    }
+}
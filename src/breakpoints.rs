@@ -0,0 +1,66 @@
+use bevy::prelude::Resource;
+
+/// A range of window widths, from narrowest to widest
+///
+/// See [`KayakWidgetContext::use_breakpoint`](crate::prelude::KayakWidgetContext::use_breakpoint).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Narrower than [`Breakpoints::md`], such as a phone
+    #[default]
+    Sm,
+    /// At least [`Breakpoints::md`] wide, such as a tablet
+    Md,
+    /// At least [`Breakpoints::lg`] wide, such as a desktop
+    Lg,
+}
+
+/// The window widths (in UI units) at which the active [`Breakpoint`] changes
+///
+/// Insert this resource to use custom thresholds.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Breakpoints {
+    /// The narrowest width of [`Breakpoint::Md`]
+    pub md: f32,
+    /// The narrowest width of [`Breakpoint::Lg`]
+    pub lg: f32,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            md: 768.0,
+            lg: 1024.0,
+        }
+    }
+}
+
+impl Breakpoints {
+    /// Returns the breakpoint a window of the given width falls into
+    pub fn get(&self, width: f32) -> Breakpoint {
+        if width >= self.lg {
+            Breakpoint::Lg
+        } else if width >= self.md {
+            Breakpoint::Md
+        } else {
+            Breakpoint::Sm
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Breakpoint, Breakpoints};
+
+    #[test]
+    fn breakpoints_should_start_at_their_threshold() {
+        let breakpoints = Breakpoints {
+            md: 600.0,
+            lg: 900.0,
+        };
+
+        assert_eq!(Breakpoint::Sm, breakpoints.get(599.0));
+        assert_eq!(Breakpoint::Md, breakpoints.get(600.0));
+        assert_eq!(Breakpoint::Md, breakpoints.get(899.0));
+        assert_eq!(Breakpoint::Lg, breakpoints.get(900.0));
+    }
+}
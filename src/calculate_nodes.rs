@@ -1,16 +1,19 @@
 use bevy::{
     prelude::{Assets, Commands, Entity, In, Query, Res, With},
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
 use kayak_font::{KayakFont, TextProperties};
-use morphorm::Hierarchy;
+use morphorm::{Hierarchy, Node as _, PositionType};
 
 use crate::{
-    layout::{DataCache, Rect},
-    node::{DirtyNode, Node, NodeBuilder, WrappedIndex},
+    layout::{DataCache, GeometryChanged, LayoutCache, Rect},
+    node::{DirtyNode, LayoutStore, Node, NodeBuilder, WrappedIndex},
     prelude::{KStyle, KayakRootContext, Tree},
     render::font::FontMapping,
-    styles::{ComputedStyles, RenderCommand, StyleProp, Units},
+    styles::{
+        AlignItems, ComputedStyles, LayoutDirection, LayoutType, RenderCommand, StyleProp, Units,
+    },
+    theme::Theme,
 };
 
 pub fn calculate_nodes(
@@ -20,7 +23,9 @@ pub fn calculate_nodes(
     font_mapping: Res<FontMapping>,
     query: Query<Entity, With<DirtyNode>>,
     all_styles_query: Query<&ComputedStyles>,
+    inline_styles_query: Query<&KStyle>,
     node_query: Query<(Entity, &Node)>,
+    theme: Res<Theme>,
     // widget_names: Query<&WidgetName>,
 ) -> KayakRootContext {
     let mut new_nodes = HashMap::<Entity, (Node, bool)>::default();
@@ -38,6 +43,15 @@ pub fn calculate_nodes(
             return context;
         }
 
+        // Applies any theme style to the computed styles of a widget
+        let resolve_styles = |entity: WrappedIndex| {
+            all_styles_query.get(entity.0).ok().map(|styles| {
+                theme
+                    .resolve(inline_styles_query.get(entity.0).ok(), &styles.0)
+                    .unwrap_or_else(|| styles.0.clone())
+            })
+        };
+
         let mut dirty_entities = query.iter().collect::<Vec<_>>();
         dirty_entities.sort_unstable_by(|a, b| a.index().partial_cmp(&b.index()).unwrap());
 
@@ -47,9 +61,7 @@ pub fn calculate_nodes(
                 continue;
             }
 
-            let styles = all_styles_query.get(dirty_entity.0).map(|cs| &cs.0);
-
-            let styles = styles.unwrap_or(&default_styles);
+            let styles = resolve_styles(dirty_entity).unwrap_or_else(|| default_styles.clone());
 
             // Get the parent styles. Will be one of the following:
             // 1. Already-resolved node styles (best)
@@ -92,6 +104,34 @@ pub fn calculate_nodes(
             // Fill in all `inherited` values for any `inherit` property
             styles.inherit(&parent_styles);
 
+            if matches!(
+                parent_styles.layout_type,
+                StyleProp::Value(LayoutType::Grid)
+            ) {
+                if is_grid_auto_placed(&styles) {
+                    apply_grid_auto_placement(
+                        &tree,
+                        &resolve_styles,
+                        &parent_styles,
+                        dirty_entity,
+                        &mut styles,
+                    );
+                } else {
+                    // Auto-placed siblings flow around the cells this item takes
+                    let previous_placement = node_query
+                        .get(dirty_entity.0)
+                        .ok()
+                        .map(|(_, node)| grid_placement(&node.resolved_styles));
+                    if previous_placement != Some(grid_placement(&styles)) {
+                        for sibling in
+                            grid_auto_placed_siblings(&tree, &resolve_styles, dirty_entity)
+                        {
+                            commands.entity(sibling.0).insert(DirtyNode);
+                        }
+                    }
+                }
+            }
+
             // Lock opacity so the max opacity for a child is the opacity of the parent.
             // if let StyleProp::Value(opacity) = &mut styles.opacity {
             //     if let StyleProp::Value(parent_opacity) = &parent_styles.opacity {
@@ -167,6 +207,10 @@ pub fn calculate_nodes(
             //     commands.entity(child.0).insert(DirtyNode);
             // }
 
+            let needs_layout =
+                apply_aspect_ratio(&mut commands, &context, dirty_entity, &mut styles)
+                    || needs_layout;
+
             let width = styles.width.resolve().value_or(0.0, 0.0);
             let height = styles.height.resolve().value_or(0.0, 0.0);
 
@@ -215,6 +259,9 @@ pub fn calculate_nodes(
     context
 }
 
+/// The most layout passes made to enforce the min/max constraints of stretched nodes
+const MAX_CONSTRAINT_PASSES: usize = 4;
+
 pub fn calculate_layout(
     In(context): In<KayakRootContext>,
     mut commands: Commands,
@@ -224,13 +271,55 @@ pub fn calculate_layout(
         // tree.dump();
         let node_tree = &*tree;
         if let Ok(mut cache) = context.layout_cache.try_write() {
-            let mut data_cache = DataCache {
-                cache: &mut cache,
-                query: &nodes_no_entity_query,
-            };
-            morphorm::layout(&mut data_cache, node_tree, &nodes_no_entity_query);
+            let previous_rects = cache.rect.clone();
+            let mut store = LayoutStore::new(&nodes_no_entity_query);
+            let mut constrained = false;
+            {
+                let mut data_cache = DataCache {
+                    cache: &mut cache,
+                    query: &nodes_no_entity_query,
+                };
+                morphorm::layout(&mut data_cache, node_tree, &store);
+
+                // Stretched nodes that end up outside of their min/max sizes are pinned to the
+                // sizes they should have had and the tree is laid out again
+                for _ in 0..MAX_CONSTRAINT_PASSES {
+                    let overrides = constrain_stretched_sizes(node_tree, &store, data_cache.cache);
+                    if overrides.is_empty() {
+                        break;
+                    }
+                    for (node, (width, height)) in overrides {
+                        let size = store.size_overrides.entry(node).or_default();
+                        size.0 = width.or(size.0);
+                        size.1 = height.or(size.1);
+                    }
+                    morphorm::layout(&mut data_cache, node_tree, &store);
+                    constrained = true;
+                }
+            }
+
+            let mirrored = mirror_rtl_layouts(node_tree, &store, &mut cache);
+            let aligned = align_baselines(node_tree, &store, &mut cache);
+
+            if constrained || mirrored || aligned {
+                // Later passes and moved nodes are only compared against the first pass
+                refresh_geometry_changed(&mut cache, &previous_rects);
+            }
 
             for (entity, change) in cache.geometry_changed.iter() {
+                // Widgets with an aspect ratio need to derive their size again when resized
+                if change
+                    .intersects(GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED)
+                {
+                    if let Ok(node) = nodes_no_entity_query.get(entity.0) {
+                        if matches!(node.resolved_styles.aspect_ratio, StyleProp::Value(..)) {
+                            if let Some(mut entity_commands) = commands.get_entity(entity.0) {
+                                entity_commands.insert(DirtyNode);
+                            }
+                        }
+                    }
+                }
+
                 if !change.is_empty() {
                     for child in tree.child_iter(*entity) {
                         // log::info!("Layout changed for: {:?}", child.0.id());
@@ -263,49 +352,54 @@ fn create_primitive(
         match render_command {
             RenderCommand::Text {
                 alignment,
+                direction,
                 content,
                 word_wrap,
                 text_layout,
                 properties,
+                spans,
                 ..
             } => {
                 let font = styles
                     .font
                     .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
                 // --- Bind to Font Asset --- //
-                let font_handle = font_mapping.get_handle(font).unwrap();
+                let font_handle = font_mapping
+                    .get_weighted_handle(font, styles.font_weight.resolve_as_option())
+                    .unwrap();
                 if let Some(font) = fonts.get(&font_handle) {
                     if let Ok(node_tree) = context.tree.try_read() {
                         if let Some(parent_id) =
                             find_not_empty_parent(&node_tree, all_styles_query, &id)
                         {
                             if let Some(parent_layout) = context.get_layout(&parent_id) {
-                                let border_x = if let Ok(style) = all_styles_query.get(parent_id.0)
-                                {
-                                    let border = style.0.border.resolve();
-                                    border.left + border.right
-                                } else {
-                                    0.0
-                                };
-                                let border_y = if let Ok(style) = all_styles_query.get(parent_id.0)
-                                {
-                                    let border = style.0.border.resolve();
-                                    border.top + border.bottom
-                                } else {
-                                    0.0
-                                };
-
                                 let font_size = styles.font_size.resolve_or(14.0);
                                 *properties = TextProperties {
                                     font_size,
                                     line_height: styles.line_height.resolve_or(font_size * 1.2),
                                     alignment: *alignment,
+                                    direction: *direction,
+                                    letter_spacing: styles.letter_spacing.resolve_or(0.0),
                                     ..*properties
                                 };
 
                                 properties.max_size = (
-                                    parent_layout.width - border_x,
-                                    parent_layout.height - border_y,
+                                    available_space(
+                                        context,
+                                        &node_tree,
+                                        all_styles_query,
+                                        parent_id,
+                                        true,
+                                    )
+                                    .unwrap_or(parent_layout.width),
+                                    available_space(
+                                        context,
+                                        &node_tree,
+                                        all_styles_query,
+                                        parent_id,
+                                        false,
+                                    )
+                                    .unwrap_or(parent_layout.height),
                                 );
 
                                 // TODO: Fix this hack.
@@ -328,7 +422,7 @@ fn create_primitive(
                                 }
 
                                 // --- Calculate Text Layout --- //
-                                *text_layout = font.measure(content, *properties);
+                                *text_layout = font.measure_with_spans(content, *properties, spans);
                                 let measurement = text_layout.size();
 
                                 log::trace!(
@@ -340,10 +434,18 @@ fn create_primitive(
 
                                 // --- Apply Layout --- //
                                 if matches!(styles.width, StyleProp::Default) {
-                                    styles.width = StyleProp::Value(Units::Pixels(measurement.0));
+                                    styles.width = StyleProp::Value(Units::Pixels(clamp_pixels(
+                                        measurement.0,
+                                        &styles.min_width,
+                                        &styles.max_width,
+                                    )));
                                 }
                                 if matches!(styles.height, StyleProp::Default) {
-                                    styles.height = StyleProp::Value(Units::Pixels(measurement.1));
+                                    styles.height = StyleProp::Value(Units::Pixels(clamp_pixels(
+                                        measurement.1,
+                                        &styles.min_height,
+                                        &styles.max_height,
+                                    )));
                                 }
                             } else {
                                 log::trace!("no layout for: {:?}", parent_id.0.index());
@@ -374,6 +476,677 @@ fn create_primitive(
     needs_layout
 }
 
+/// Returns the space inside of a node's border along an axis
+///
+/// Nodes sized with [`Units::Auto`] take the size of their content, so the space of the nearest
+/// ancestor that isn't is used instead. This lets text measure itself before its container is
+/// wrapped around it.
+fn available_space(
+    context: &KayakRootContext,
+    tree: &Tree,
+    all_styles_query: &Query<&ComputedStyles>,
+    node: WrappedIndex,
+    horizontal: bool,
+) -> Option<f32> {
+    let styles = all_styles_query.get(node.0).ok().map(|styles| &styles.0);
+    if let Some(styles) = styles {
+        let size = if horizontal {
+            &styles.width
+        } else {
+            &styles.height
+        };
+        if matches!(size, StyleProp::Value(Units::Auto)) {
+            if let Some(parent) = tree.parent(node) {
+                return available_space(context, tree, all_styles_query, parent, horizontal);
+            }
+        }
+    }
+
+    let layout = context.get_layout(&node)?;
+    let border = styles
+        .map(|styles| styles.border.resolve())
+        .unwrap_or_default();
+    Some(if horizontal {
+        layout.width - border.left - border.right
+    } else {
+        layout.height - border.top - border.bottom
+    })
+}
+
+/// Derives a dimension of the given node from its `aspect_ratio`, if it has one
+///
+/// Returns true if the node needs to be laid out before this can be done.
+fn apply_aspect_ratio(
+    commands: &mut Commands,
+    context: &KayakRootContext,
+    id: WrappedIndex,
+    styles: &mut KStyle,
+) -> bool {
+    let needs_layout = derive_aspect_ratio_size(styles, context.get_layout(&id));
+    if needs_layout {
+        commands.entity(id.0).insert(DirtyNode);
+    }
+    needs_layout
+}
+
+/// Sets the missing width or height of a style from its `aspect_ratio`
+///
+/// A dimension is only derived when exactly one of the width and height is set. With neither
+/// set, the size would be derived from the node's own layout and feed back into itself.
+/// Relative sizes (such as [`Units::Stretch`]) are resolved using the node's last layout, so
+/// the derived dimension may lag behind by a layout pass.
+///
+/// Returns true if `layout` is needed but missing.
+fn derive_aspect_ratio_size(styles: &mut KStyle, layout: Option<Rect>) -> bool {
+    let aspect_ratio = match styles.aspect_ratio {
+        StyleProp::Value(aspect_ratio) if aspect_ratio > 0.0 => aspect_ratio,
+        _ => return false,
+    };
+
+    let is_set = |units: &StyleProp<Units>| matches!(units, StyleProp::Value(units) if *units != Units::Auto);
+    let width_set = is_set(&styles.width);
+    let height_set = is_set(&styles.height);
+    if width_set == height_set {
+        return false;
+    }
+
+    if height_set {
+        let height = match styles.height {
+            StyleProp::Value(Units::Pixels(height)) => {
+                Some(clamp_pixels(height, &styles.min_height, &styles.max_height))
+            }
+            _ => layout.map(|layout| layout.height),
+        };
+        if let Some(height) = height {
+            let width = clamp_pixels(height * aspect_ratio, &styles.min_width, &styles.max_width);
+            styles.width = StyleProp::Value(Units::Pixels(width));
+            return false;
+        }
+    } else {
+        let width = match styles.width {
+            StyleProp::Value(Units::Pixels(width)) => {
+                Some(clamp_pixels(width, &styles.min_width, &styles.max_width))
+            }
+            _ => layout.map(|layout| layout.width),
+        };
+        if let Some(width) = width {
+            let height = clamp_pixels(width / aspect_ratio, &styles.min_height, &styles.max_height);
+            styles.height = StyleProp::Value(Units::Pixels(height));
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns true if a grid item has neither a `row_index` nor a `col_index`
+fn is_grid_auto_placed(styles: &KStyle) -> bool {
+    !matches!(styles.row_index, StyleProp::Value(..))
+        && !matches!(styles.col_index, StyleProp::Value(..))
+}
+
+/// The cells a grid item was placed in
+fn grid_placement(styles: &KStyle) -> [StyleProp<usize>; 4] {
+    [
+        styles.row_index.clone(),
+        styles.col_index.clone(),
+        styles.row_span.clone(),
+        styles.col_span.clone(),
+    ]
+}
+
+/// Finds the siblings of a grid item that are placed automatically
+fn grid_auto_placed_siblings(
+    tree: &Tree,
+    resolve_styles: &impl Fn(WrappedIndex) -> Option<KStyle>,
+    id: WrappedIndex,
+) -> Vec<WrappedIndex> {
+    let Some(parent) = tree.parents.get(&id) else {
+        return Vec::default();
+    };
+    tree.children
+        .get(parent)
+        .map(|siblings| {
+            siblings
+                .iter()
+                .filter(|sibling| {
+                    **sibling != id
+                        && resolve_styles(**sibling)
+                            .is_some_and(|styles| is_grid_auto_placed(&styles))
+                })
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Places a grid item that has neither a `row_index` nor a `col_index` in the next free cell
+///
+/// Cells are filled row by row, skipping any taken by items that were placed explicitly.
+/// Siblings are resolved with `resolve_styles`, so placements set by a theme style count.
+fn apply_grid_auto_placement(
+    tree: &Tree,
+    resolve_styles: &impl Fn(WrappedIndex) -> Option<KStyle>,
+    parent_styles: &KStyle,
+    id: WrappedIndex,
+    styles: &mut KStyle,
+) {
+    if !is_grid_auto_placed(styles) {
+        return;
+    }
+
+    let siblings = if let Some(parent) = tree.parents.get(&id) {
+        tree.children.get(parent).cloned().unwrap_or_default()
+    } else {
+        return;
+    };
+    let columns = match &parent_styles.grid_cols {
+        StyleProp::Value(grid_cols) => grid_cols.len().max(1),
+        _ => 1,
+    };
+
+    let spans = |styles: &KStyle| {
+        (
+            styles.row_span.resolve_or(1).max(1),
+            styles.col_span.resolve_or(1).clamp(1, columns),
+        )
+    };
+
+    // Mark the cells taken by explicitly placed items
+    let mut occupied = HashSet::<(usize, usize)>::default();
+    let occupy = |occupied: &mut HashSet<(usize, usize)>,
+                  row: usize,
+                  col: usize,
+                  (row_span, col_span): (usize, usize)| {
+        for r in row..row + row_span {
+            for c in col..col + col_span {
+                occupied.insert((r, c));
+            }
+        }
+    };
+    for sibling in siblings.iter() {
+        if let Some(sibling_styles) = resolve_styles(*sibling) {
+            if !is_grid_auto_placed(&sibling_styles) {
+                occupy(
+                    &mut occupied,
+                    sibling_styles.row_index.resolve_or(0),
+                    sibling_styles.col_index.resolve_or(0),
+                    spans(&sibling_styles),
+                );
+            }
+        }
+    }
+
+    // Walk through the auto-placed items in order until we reach this one
+    let mut cursor = (0, 0);
+    for sibling in siblings.iter() {
+        let sibling_styles = if *sibling == id {
+            styles.clone()
+        } else if let Some(sibling_styles) = resolve_styles(*sibling) {
+            if !is_grid_auto_placed(&sibling_styles) {
+                continue;
+            }
+            sibling_styles
+        } else {
+            continue;
+        };
+
+        let (row_span, col_span) = spans(&sibling_styles);
+        let (mut row, mut col) = cursor;
+        loop {
+            if col + col_span > columns {
+                row += 1;
+                col = 0;
+                continue;
+            }
+            let is_free = (row..row + row_span)
+                .all(|r| (col..col + col_span).all(|c| !occupied.contains(&(r, c))));
+            if is_free {
+                break;
+            }
+            col += 1;
+        }
+
+        if *sibling == id {
+            styles.row_index = StyleProp::Value(row);
+            styles.col_index = StyleProp::Value(col);
+            return;
+        }
+
+        occupy(&mut occupied, row, col, (row_span, col_span));
+        cursor = (row, col + col_span);
+    }
+}
+
+/// Limits a value to any pixel min/max constraints
+fn clamp_pixels(value: f32, min: &StyleProp<Units>, max: &StyleProp<Units>) -> f32 {
+    let mut value = value;
+    if let StyleProp::Value(Units::Pixels(max)) = max {
+        value = value.min(*max);
+    }
+    if let StyleProp::Value(Units::Pixels(min)) = min {
+        value = value.max(*min);
+    }
+    value
+}
+
+type SizeOverrides = HashMap<WrappedIndex, (Option<f32>, Option<f32>)>;
+
+/// Finds the stretched nodes whose size breaks their min/max constraints
+///
+/// Returns the sizes these nodes (and their stretched siblings) should be laid out with instead.
+/// Nodes below a resized node are only checked once it has been laid out again.
+fn constrain_stretched_sizes(
+    tree: &Tree,
+    store: &LayoutStore,
+    cache: &LayoutCache,
+) -> SizeOverrides {
+    let mut overrides = SizeOverrides::default();
+    if let Some(root) = tree.root_node {
+        constrain_children(tree, store, cache, root, &mut overrides);
+    }
+    overrides
+}
+
+fn constrain_children(
+    tree: &Tree,
+    store: &LayoutStore,
+    cache: &LayoutCache,
+    parent: WrappedIndex,
+    overrides: &mut SizeOverrides,
+) {
+    if let Some(rect) = cache.rect.get(&parent) {
+        // The children were laid out within the computed size, which the root's rect lacks
+        let parent_rect = &match cache.computed_size(&parent) {
+            Some(size) => Rect {
+                width: size.width,
+                height: size.height,
+                ..*rect
+            },
+            None => *rect,
+        };
+        let horizontal = match parent.layout_type(store) {
+            Some(morphorm::LayoutType::Row) => Some(true),
+            Some(morphorm::LayoutType::Grid) => None,
+            _ => Some(false),
+        };
+        if let Some(horizontal) = horizontal {
+            let children = tree
+                .child_iter(parent)
+                .filter(|child| {
+                    cache.rect.contains_key(child)
+                        && cache.visible.get(child) != Some(&false)
+                        && child.position_type(store) != Some(PositionType::SelfDirected)
+                })
+                .collect::<Vec<_>>();
+            constrain_main_axis(
+                store,
+                cache,
+                parent,
+                parent_rect,
+                &children,
+                horizontal,
+                overrides,
+            );
+            constrain_cross_axis(store, cache, parent_rect, &children, !horizontal, overrides);
+        }
+    }
+
+    for child in tree.child_iter(parent) {
+        if !overrides.contains_key(&child) {
+            constrain_children(tree, store, cache, child, overrides);
+        }
+    }
+}
+
+/// The size, min size and max size of a node along an axis
+fn axis_size(
+    node: &WrappedIndex,
+    store: &LayoutStore,
+    horizontal: bool,
+) -> [Option<morphorm::Units>; 3] {
+    if horizontal {
+        [
+            node.width(store),
+            node.min_width(store),
+            node.max_width(store),
+        ]
+    } else {
+        [
+            node.height(store),
+            node.min_height(store),
+            node.max_height(store),
+        ]
+    }
+}
+
+/// The space before and after a node along an axis, followed by the space it puts before, after
+/// and between its children
+fn axis_space(
+    node: &WrappedIndex,
+    store: &LayoutStore,
+    horizontal: bool,
+) -> [Option<morphorm::Units>; 5] {
+    if horizontal {
+        [
+            node.left(store),
+            node.right(store),
+            node.child_left(store),
+            node.child_right(store),
+            node.col_between(store),
+        ]
+    } else {
+        [
+            node.top(store),
+            node.bottom(store),
+            node.child_top(store),
+            node.child_bottom(store),
+            node.row_between(store),
+        ]
+    }
+}
+
+/// Returns the start and length of a rect along an axis
+fn axis_rect(rect: &Rect, horizontal: bool) -> (f32, f32) {
+    if horizontal {
+        (rect.posx, rect.width)
+    } else {
+        (rect.posy, rect.height)
+    }
+}
+
+fn set_axis_override(
+    overrides: &mut SizeOverrides,
+    node: WrappedIndex,
+    horizontal: bool,
+    size: f32,
+) {
+    let entry = overrides.entry(node).or_default();
+    if horizontal {
+        entry.0 = Some(size);
+    } else {
+        entry.1 = Some(size);
+    }
+}
+
+/// Shares the space of the children stretched along the layout direction again, if any of them
+/// breaks its constraints
+fn constrain_main_axis(
+    store: &LayoutStore,
+    cache: &LayoutCache,
+    parent: WrappedIndex,
+    parent_rect: &Rect,
+    children: &[WrappedIndex],
+    horizontal: bool,
+    overrides: &mut SizeOverrides,
+) {
+    let is_stretch =
+        |units: &Option<morphorm::Units>| matches!(units, Some(morphorm::Units::Stretch(..)));
+
+    // Stretched space takes up any free space itself, so there is nothing to share again
+    let [_, _, _, parent_after, _] = axis_space(&parent, store, horizontal);
+    if axis_space(&parent, store, horizontal)[2..]
+        .iter()
+        .any(is_stretch)
+        || children.iter().any(|child| {
+            axis_space(child, store, horizontal)[..2]
+                .iter()
+                .any(is_stretch)
+        })
+    {
+        return;
+    }
+    let Some((last, last_rect)) = children
+        .last()
+        .and_then(|last| Some((last, cache.rect.get(last)?)))
+    else {
+        return;
+    };
+    let (parent_start, parent_size) = axis_rect(parent_rect, horizontal);
+    let trailing = match axis_space(last, store, horizontal)[1] {
+        Some(morphorm::Units::Auto) | None => parent_after,
+        units => units,
+    };
+    let (last_start, last_size) = axis_rect(last_rect, horizontal);
+    let mut free = parent_start + parent_size
+        - resolve_units(trailing, parent_size, 0.0)
+        - (last_start + last_size);
+
+    let mut stretched = Vec::new();
+    let mut items = Vec::new();
+    for child in children {
+        if let ([Some(morphorm::Units::Stretch(factor)), min, max], Some(rect)) =
+            (axis_size(child, store, horizontal), cache.rect.get(child))
+        {
+            let (_, size) = axis_rect(rect, horizontal);
+            stretched.push((*child, size));
+            items.push((
+                factor,
+                resolve_units(min, parent_size, 0.0),
+                resolve_units(max, parent_size, f32::INFINITY),
+            ));
+            free += size;
+        }
+    }
+
+    let sizes = distribute_stretch(free.max(0.0), &items);
+    if stretched
+        .iter()
+        .zip(sizes.iter())
+        .any(|((_, current), size)| (current - size).abs() > 0.5)
+    {
+        for ((child, _), size) in stretched.into_iter().zip(sizes) {
+            set_axis_override(overrides, child, horizontal, size);
+        }
+    }
+}
+
+/// Clamps the size of children stretched across the layout direction
+fn constrain_cross_axis(
+    store: &LayoutStore,
+    cache: &LayoutCache,
+    parent_rect: &Rect,
+    children: &[WrappedIndex],
+    horizontal: bool,
+    overrides: &mut SizeOverrides,
+) {
+    let (_, parent_size) = axis_rect(parent_rect, horizontal);
+    for child in children {
+        if let ([Some(morphorm::Units::Stretch(..)), min, max], Some(rect)) =
+            (axis_size(child, store, horizontal), cache.rect.get(child))
+        {
+            let (_, size) = axis_rect(rect, horizontal);
+            let clamped = size
+                .min(resolve_units(max, parent_size, f32::INFINITY))
+                .max(resolve_units(min, parent_size, 0.0));
+            if (clamped - size).abs() > 0.5 {
+                set_axis_override(overrides, *child, horizontal, clamped);
+            }
+        }
+    }
+}
+
+/// Resolves pixel and percentage units, using the fallback for anything else
+fn resolve_units(units: Option<morphorm::Units>, parent_size: f32, fallback: f32) -> f32 {
+    match units {
+        Some(morphorm::Units::Pixels(pixels)) => pixels,
+        Some(morphorm::Units::Percentage(percentage)) => percentage / 100.0 * parent_size,
+        _ => fallback,
+    }
+}
+
+/// Shares free space between stretched items in proportion to their factors
+///
+/// Items are given as `(factor, min, max)`. Like in flexbox, items whose share breaks one of their
+/// constraints are frozen at it and what's left is shared again between the others.
+fn distribute_stretch(free: f32, items: &[(f32, f32, f32)]) -> Vec<f32> {
+    let mut sizes = vec![0.0; items.len()];
+    let mut frozen = vec![false; items.len()];
+    loop {
+        let mut space = free;
+        let mut factors = 0.0;
+        for (i, (factor, ..)) in items.iter().enumerate() {
+            if frozen[i] {
+                space -= sizes[i];
+            } else {
+                factors += factor;
+            }
+        }
+
+        let share = |factor: f32| {
+            if factors > 0.0 {
+                space.max(0.0) * factor / factors
+            } else {
+                0.0
+            }
+        };
+        let mut violation = 0.0;
+        for (i, (factor, min, max)) in items.iter().enumerate() {
+            if !frozen[i] {
+                sizes[i] = share(*factor).min(*max).max(*min);
+                violation += sizes[i] - share(*factor);
+            }
+        }
+
+        if violation.abs() < 0.01 {
+            break;
+        }
+        for (i, (factor, ..)) in items.iter().enumerate() {
+            if !frozen[i] {
+                let share = share(*factor);
+                frozen[i] = if violation > 0.0 {
+                    sizes[i] > share
+                } else {
+                    sizes[i] < share
+                };
+            }
+        }
+    }
+    sizes
+}
+
+/// Mirrors the children of nodes laid out with [`LayoutDirection::Rtl`] horizontally
+///
+/// Each child is moved (along with its descendants) to the opposite side of its parent, which
+/// nested right-to-left nodes then mirror their own children within. Returns true if any node was
+/// right-to-left.
+fn mirror_rtl_layouts(tree: &Tree, store: &LayoutStore, cache: &mut LayoutCache) -> bool {
+    let mut mirrored = false;
+    for (parent, children) in tree.children.iter() {
+        let Ok(node) = store.get(parent.0) else {
+            continue;
+        };
+        if node.resolved_styles.layout_direction.resolve() != LayoutDirection::Rtl {
+            continue;
+        }
+        let Some(parent_rect) = cache.rect.get(parent).copied() else {
+            continue;
+        };
+        mirrored = true;
+
+        for child in children {
+            if let Some(rect) = cache.rect.get(child) {
+                let offset = 2.0 * (parent_rect.posx - rect.posx) + parent_rect.width - rect.width;
+                if offset != 0.0 {
+                    offset_subtree(tree, cache, *child, offset, 0.0);
+                }
+            }
+        }
+    }
+    mirrored
+}
+
+/// Moves the children of rows aligned with [`AlignItems::Baseline`] so their baselines line up
+///
+/// The children keep the topmost position among them as the top of the line. Returns true if
+/// there were any such rows.
+fn align_baselines(tree: &Tree, store: &LayoutStore, cache: &mut LayoutCache) -> bool {
+    let mut aligned = false;
+    for (parent, children) in tree.children.iter() {
+        let Ok(node) = store.get(parent.0) else {
+            continue;
+        };
+        if node.resolved_styles.layout_type.resolve() != LayoutType::Row
+            || node.resolved_styles.align_items.resolve() != AlignItems::Baseline
+        {
+            continue;
+        }
+        aligned = true;
+
+        let baselines = children
+            .iter()
+            .filter(|child| {
+                cache.visible.get(*child) != Some(&false)
+                    && child.position_type(store) != Some(PositionType::SelfDirected)
+            })
+            .filter_map(|child| {
+                let rect = cache.rect.get(child)?;
+                let baseline = text_baseline(store, *child).unwrap_or(rect.height);
+                Some((*child, rect.posy, baseline))
+            })
+            .collect::<Vec<_>>();
+        let top = baselines
+            .iter()
+            .map(|(_, posy, _)| *posy)
+            .fold(f32::INFINITY, f32::min);
+        let line_baseline = baselines
+            .iter()
+            .map(|(_, _, baseline)| *baseline)
+            .fold(0.0, f32::max);
+        for (child, posy, baseline) in baselines {
+            let offset = top + line_baseline - baseline - posy;
+            if offset != 0.0 {
+                offset_subtree(tree, cache, child, 0.0, offset);
+            }
+        }
+    }
+    aligned
+}
+
+/// Returns the baseline of the first line of a text node, relative to its top
+fn text_baseline(store: &LayoutStore, node: WrappedIndex) -> Option<f32> {
+    let node = store.get(node.0).ok()?;
+    if let StyleProp::Value(RenderCommand::Text { text_layout, .. }) =
+        &node.resolved_styles.render_command
+    {
+        return text_layout.lines().first().map(|line| line.baseline());
+    }
+    None
+}
+
+/// Moves a node and all of its descendants by the given offsets
+fn offset_subtree(
+    tree: &Tree,
+    cache: &mut LayoutCache,
+    node: WrappedIndex,
+    offset_x: f32,
+    offset_y: f32,
+) {
+    if let Some(rect) = cache.rect.get_mut(&node) {
+        rect.posx += offset_x;
+        rect.posy += offset_y;
+    }
+    if let Some(children) = tree.children.get(&node) {
+        for child in children {
+            offset_subtree(tree, cache, *child, offset_x, offset_y);
+        }
+    }
+}
+
+/// Compares the layout of every node against its layout before this update
+fn refresh_geometry_changed(
+    cache: &mut LayoutCache,
+    previous_rects: &std::collections::HashMap<WrappedIndex, Rect>,
+) {
+    cache.geometry_changed.clear();
+    for (node, rect) in cache.rect.iter() {
+        let change = rect.changes_from(&previous_rects.get(node).copied().unwrap_or_default());
+        if !change.is_empty() {
+            cache.geometry_changed.insert(*node, change);
+        }
+    }
+}
+
 pub fn find_not_empty_parent(
     tree: &Tree,
     all_styles_query: &Query<&ComputedStyles>,
@@ -481,3 +1254,311 @@ pub fn find_not_empty_parent(
 
 //     None
 // }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::system::SystemState,
+        prelude::{Entity, IntoSystem, Query, System, World},
+        utils::HashMap,
+    };
+
+    use super::{
+        apply_grid_auto_placement, calculate_layout, constrain_stretched_sizes,
+        derive_aspect_ratio_size, distribute_stretch, grid_auto_placed_siblings,
+    };
+    use crate::{
+        context::KayakRootContext,
+        layout::Rect,
+        node::{LayoutStore, Node, NodeBuilder, WrappedIndex},
+        styles::{KStyle, LayoutType, StyleProp, Units},
+        theme::Theme,
+        tree::Tree,
+    };
+
+    #[test]
+    fn should_share_space_between_constrained_stretched_items() {
+        let sizes = distribute_stretch(
+            300.0,
+            &[
+                (1.0, 0.0, 50.0),
+                (1.0, 150.0, f32::INFINITY),
+                (1.0, 0.0, f32::INFINITY),
+            ],
+        );
+        assert_eq!(sizes, vec![50.0, 150.0, 100.0]);
+        assert_eq!(sizes.iter().sum::<f32>(), 300.0);
+
+        let sizes = distribute_stretch(
+            300.0,
+            &[
+                (1.0, 0.0, 50.0),
+                (1.0, 0.0, f32::INFINITY),
+                (1.0, 0.0, f32::INFINITY),
+            ],
+        );
+        assert_eq!(sizes, vec![50.0, 125.0, 125.0]);
+    }
+
+    fn aspect_ratio_style(width: Units, height: Units) -> KStyle {
+        KStyle {
+            width: StyleProp::Value(width),
+            height: StyleProp::Value(height),
+            aspect_ratio: StyleProp::Value(2.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn aspect_ratio_should_only_derive_from_a_single_set_dimension() {
+        let layout = Some(Rect {
+            posx: 0.0,
+            posy: 0.0,
+            width: 100.0,
+            height: 100.0,
+            z_index: Some(0.0),
+        });
+
+        // Deriving from the node's own layout would feed back into its size
+        let mut styles = aspect_ratio_style(Units::Auto, Units::Auto);
+        assert!(!derive_aspect_ratio_size(&mut styles, layout));
+        assert_eq!(StyleProp::Value(Units::Auto), styles.width);
+        assert_eq!(StyleProp::Value(Units::Auto), styles.height);
+
+        let mut styles = aspect_ratio_style(Units::Pixels(30.0), Units::Pixels(40.0));
+        assert!(!derive_aspect_ratio_size(&mut styles, layout));
+        assert_eq!(StyleProp::Value(Units::Pixels(40.0)), styles.height);
+
+        let mut styles = aspect_ratio_style(Units::Pixels(30.0), Units::Auto);
+        assert!(!derive_aspect_ratio_size(&mut styles, None));
+        assert_eq!(StyleProp::Value(Units::Pixels(15.0)), styles.height);
+
+        let mut styles = aspect_ratio_style(Units::Auto, Units::Pixels(30.0));
+        assert!(!derive_aspect_ratio_size(&mut styles, None));
+        assert_eq!(StyleProp::Value(Units::Pixels(60.0)), styles.width);
+
+        // Relative sizes need a layout to be resolved first
+        let mut styles = aspect_ratio_style(Units::Stretch(1.0), Units::Auto);
+        assert!(derive_aspect_ratio_size(&mut styles, None));
+        assert!(!derive_aspect_ratio_size(&mut styles, layout));
+        assert_eq!(StyleProp::Value(Units::Pixels(50.0)), styles.height);
+    }
+
+    #[test]
+    fn aspect_ratio_should_respect_min_and_max_sizes() {
+        // The set dimension is clamped before the other is derived from it
+        let mut styles = KStyle {
+            min_width: StyleProp::Value(Units::Pixels(50.0)),
+            ..aspect_ratio_style(Units::Pixels(20.0), Units::Auto)
+        };
+        derive_aspect_ratio_size(&mut styles, None);
+        assert_eq!(StyleProp::Value(Units::Pixels(25.0)), styles.height);
+
+        // The derived dimension is clamped as well, breaking the ratio
+        let mut styles = KStyle {
+            max_height: StyleProp::Value(Units::Pixels(10.0)),
+            ..aspect_ratio_style(Units::Pixels(100.0), Units::Auto)
+        };
+        derive_aspect_ratio_size(&mut styles, None);
+        assert_eq!(StyleProp::Value(Units::Pixels(10.0)), styles.height);
+
+        let mut styles = KStyle {
+            max_width: StyleProp::Value(Units::Pixels(30.0)),
+            ..aspect_ratio_style(Units::Auto, Units::Pixels(100.0))
+        };
+        derive_aspect_ratio_size(&mut styles, None);
+        assert_eq!(StyleProp::Value(Units::Pixels(30.0)), styles.width);
+    }
+
+    #[test]
+    fn grid_items_should_be_auto_placed_row_by_row() {
+        let theme = Theme::new().with_style(
+            "pinned",
+            KStyle {
+                row_index: StyleProp::Value(0),
+                col_index: StyleProp::Value(1),
+                ..Default::default()
+            },
+        );
+        let parent_styles = KStyle {
+            grid_cols: StyleProp::Value(vec![Units::Stretch(1.0); 3]),
+            ..Default::default()
+        };
+
+        let parent = WrappedIndex(Entity::from_raw(0));
+        let children = [1, 2, 3, 4, 5, 6].map(|index| WrappedIndex(Entity::from_raw(index)));
+        let [wide, pinned, first, tall, placed, last] = children;
+        let mut tree = Tree::default();
+        tree.add(parent, None);
+        for child in children {
+            tree.add(child, Some(parent));
+        }
+
+        let computed = HashMap::from_iter([
+            (
+                wide,
+                KStyle {
+                    col_span: StyleProp::Value(2),
+                    ..Default::default()
+                },
+            ),
+            // Only placed by its theme style
+            (
+                pinned,
+                KStyle {
+                    theme: StyleProp::Value("pinned".into()),
+                    ..Default::default()
+                },
+            ),
+            (first, KStyle::default()),
+            (
+                tall,
+                KStyle {
+                    row_span: StyleProp::Value(2),
+                    ..Default::default()
+                },
+            ),
+            (
+                placed,
+                KStyle {
+                    row_index: StyleProp::Value(1),
+                    col_index: StyleProp::Value(2),
+                    ..Default::default()
+                },
+            ),
+            (last, KStyle::default()),
+        ]);
+        let resolve_styles = |id: WrappedIndex| {
+            computed.get(&id).map(|styles| {
+                theme
+                    .resolve(None, styles)
+                    .unwrap_or_else(|| styles.clone())
+            })
+        };
+
+        let place = |id: WrappedIndex| {
+            let mut styles = resolve_styles(id).unwrap();
+            apply_grid_auto_placement(&tree, &resolve_styles, &parent_styles, id, &mut styles);
+            (styles.row_index.resolve(), styles.col_index.resolve())
+        };
+        // The wide item doesn't fit next to the pinned one, so it starts the second row
+        assert_eq!((1, 0), place(wide));
+        assert_eq!((0, 1), place(pinned));
+        // Cells taken by explicitly placed items are skipped
+        assert_eq!((2, 0), place(first));
+        assert_eq!((2, 1), place(tall));
+        assert_eq!((1, 2), place(placed));
+        assert_eq!((2, 2), place(last));
+
+        // Moving an explicitly placed item can move any auto-placed one
+        assert_eq!(
+            vec![wide, first, tall, last],
+            grid_auto_placed_siblings(&tree, &resolve_styles, placed)
+        );
+    }
+
+    /// Lays out a 300x100 row with the given children, returning the ids of the children
+    fn layout_row(
+        world: &mut World,
+        row_styles: KStyle,
+        child_styles: Vec<KStyle>,
+    ) -> (KayakRootContext, Vec<WrappedIndex>) {
+        let mut context = KayakRootContext::new(world.spawn_empty().id());
+        let mut spawn_node = |parent: Option<WrappedIndex>, styles: KStyle| {
+            let id = WrappedIndex(world.spawn_empty().id());
+            world
+                .entity_mut(id.0)
+                .insert(NodeBuilder::new(id, styles).build());
+            context.add_widget(parent.map(|parent| parent.0), id.0);
+            id
+        };
+
+        let row = spawn_node(
+            None,
+            KStyle {
+                layout_type: StyleProp::Value(LayoutType::Row),
+                width: StyleProp::Value(Units::Pixels(300.0)),
+                height: StyleProp::Value(Units::Pixels(100.0)),
+                ..row_styles
+            },
+        );
+        let children = child_styles
+            .into_iter()
+            .map(|styles| spawn_node(Some(row), styles))
+            .collect::<Vec<_>>();
+
+        let mut system = IntoSystem::into_system(calculate_layout);
+        system.initialize(world);
+        let context = system.run(context, world);
+        system.apply_deferred(world);
+        (context, children)
+    }
+
+    #[test]
+    fn stretched_children_should_be_laid_out_within_their_constraints() {
+        let mut world = World::new();
+        let (context, children) = layout_row(
+            &mut world,
+            KStyle::default(),
+            vec![
+                KStyle {
+                    max_width: StyleProp::Value(Units::Pixels(50.0)),
+                    max_height: StyleProp::Value(Units::Pixels(40.0)),
+                    ..Default::default()
+                },
+                KStyle {
+                    min_width: StyleProp::Value(Units::Pixels(150.0)),
+                    ..Default::default()
+                },
+                KStyle::default(),
+            ],
+        );
+
+        let rects = children
+            .iter()
+            .map(|child| {
+                let rect = context.get_layout(child).unwrap();
+                (rect.posx, rect.posy, rect.width, rect.height)
+            })
+            .collect::<Vec<_>>();
+        // The space the constrained children give up or take is shared with the others, and
+        // the cross axis is clamped to the max height
+        assert_eq!(
+            vec![
+                (0.0, 0.0, 50.0, 40.0),
+                (50.0, 0.0, 150.0, 100.0),
+                (200.0, 0.0, 100.0, 100.0),
+            ],
+            rects
+        );
+    }
+
+    #[test]
+    fn stretched_spacing_should_not_be_shared_again() {
+        let mut world = World::new();
+        let (context, _) = layout_row(
+            &mut world,
+            KStyle {
+                gap: StyleProp::Value(Units::Stretch(1.0)),
+                ..Default::default()
+            },
+            vec![
+                KStyle {
+                    max_width: StyleProp::Value(Units::Pixels(50.0)),
+                    ..Default::default()
+                },
+                KStyle::default(),
+            ],
+        );
+
+        let mut nodes = SystemState::<Query<&Node>>::new(&mut world);
+        let nodes = nodes.get(&world);
+        let store = LayoutStore::new(&nodes);
+        let overrides = constrain_stretched_sizes(
+            &context.tree.read().unwrap(),
+            &store,
+            &context.layout_cache.read().unwrap(),
+        );
+        assert!(overrides.values().all(|(width, _)| width.is_none()));
+    }
+}
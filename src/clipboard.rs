@@ -0,0 +1,107 @@
+use std::sync::RwLock;
+
+use bevy::prelude::Resource;
+
+/// A source and destination for copied text
+///
+/// Implement this to route clipboard operations somewhere other than the defaults
+/// (for example, a platform-specific clipboard or a stub in tests).
+pub trait ClipboardProvider: Send + Sync {
+    /// Returns the current contents of the clipboard, if any
+    fn get_text(&self) -> Option<String>;
+    /// Replaces the contents of the clipboard
+    fn set_text(&self, text: String);
+}
+
+/// A clipboard that only lives within the running application
+#[derive(Default)]
+pub struct LocalClipboard {
+    contents: RwLock<Option<String>>,
+}
+
+impl ClipboardProvider for LocalClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.contents
+            .read()
+            .ok()
+            .and_then(|contents| contents.clone())
+    }
+
+    fn set_text(&self, text: String) {
+        if let Ok(mut contents) = self.contents.write() {
+            *contents = Some(text);
+        }
+    }
+}
+
+/// A clipboard backed by the operating system's clipboard
+#[cfg(feature = "clipboard")]
+pub struct SystemClipboard {
+    clipboard: std::sync::Mutex<arboard::Clipboard>,
+}
+
+#[cfg(feature = "clipboard")]
+impl SystemClipboard {
+    /// Connects to the system clipboard, returning `None` if it's unavailable
+    pub fn new() -> Option<Self> {
+        arboard::Clipboard::new().ok().map(|clipboard| Self {
+            clipboard: std::sync::Mutex::new(clipboard),
+        })
+    }
+}
+
+#[cfg(feature = "clipboard")]
+impl ClipboardProvider for SystemClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.clipboard
+            .lock()
+            .ok()
+            .and_then(|mut clipboard| clipboard.get_text().ok())
+    }
+
+    fn set_text(&self, text: String) {
+        if let Ok(mut clipboard) = self.clipboard.lock() {
+            if let Err(err) = clipboard.set_text(text) {
+                log::warn!("Failed to write to the system clipboard: {}", err);
+            }
+        }
+    }
+}
+
+/// The clipboard used by widgets for copying and pasting text
+///
+/// By default this uses the system clipboard when the `clipboard` feature is enabled and
+/// falls back to a [`LocalClipboard`] otherwise.
+#[derive(Resource)]
+pub struct Clipboard {
+    provider: Box<dyn ClipboardProvider>,
+}
+
+impl Clipboard {
+    pub fn new(provider: impl ClipboardProvider + 'static) -> Self {
+        Self {
+            provider: Box::new(provider),
+        }
+    }
+
+    /// Returns the current contents of the clipboard, if any
+    pub fn get_text(&self) -> Option<String> {
+        self.provider.get_text()
+    }
+
+    /// Replaces the contents of the clipboard
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.provider.set_text(text.into());
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        #[cfg(feature = "clipboard")]
+        if let Some(clipboard) = SystemClipboard::new() {
+            return Self::new(clipboard);
+        }
+
+        Self::new(LocalClipboard::default())
+    }
+}
@@ -2,6 +2,11 @@ use bevy::{ecs::system::CommandQueue, prelude::*};
 
 use crate::widget_state::WidgetState;
 
+/// Marks an entity holding a copy of a widget's props, state and styles from its last render
+///
+/// Kayak compares a widget against this copy to decide whether to re-render it. Use
+/// [`KayakWidgetContext::previous_props`](crate::prelude::KayakWidgetContext::previous_props)
+/// to read it from your own systems.
 #[derive(Component, Default)]
 pub struct PreviousWidget;
 
@@ -6,7 +6,7 @@ use bevy::{
     utils::{HashMap, HashSet},
     window::PrimaryWindow,
 };
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use kayak_font::KayakFont;
 use morphorm::Hierarchy;
 
@@ -16,22 +16,30 @@ use crate::{
     clone_component::{clone_state, clone_system, EntityCloneSystems, PreviousWidget},
     context_entities::ContextEntities,
     cursor::PointerEvents,
+    disabled::Disabled,
+    event::{EventType, KEvent},
     event_dispatcher::EventDispatcher,
-    focus_tree::FocusTree,
+    focus_tree::{AutoFocus, FocusTrap, FocusTree},
+    handler::Handler,
     input::query_world,
     layout::{LayoutCache, Rect},
     layout_dispatcher::LayoutEventDispatcher,
     node::{DirtyNode, WrappedIndex},
+    on_mount::OnMount,
+    on_unmount::OnUnmount,
     prelude::KayakWidgetContext,
     render::{
+        extract_debug_overlay,
         font::FontMapping,
         unified::pipeline::{ExtractedQuad, ExtractedQuads, QuadOrMaterial, UIQuadType},
-        MAX_OPACITY_LAYERS,
+        LayoutDebugOverlay,
     },
     render_primitive::RenderPrimitive,
+    shortcut::{KeyCombination, Shortcut, Shortcuts},
+    snapshot::WidgetSnapshot,
     styles::{
-        ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, LayoutType,
-        RenderCommand, StyleProp, Units,
+        ComputedStyles, Corner, CursorImage, Edge, KCursorIcon, KPositionType, KStyle, KVisibility,
+        LayoutType, RenderCommand, StyleProp, Units,
     },
     tree::{Change, Tree},
     widget_state::WidgetState,
@@ -43,6 +51,20 @@ use crate::{
 #[reflect(Component)]
 pub struct Mounted;
 
+/// A tag component that memoizes a widget's subtree.
+///
+/// Widgets are only re-rendered when their update system reports a change (props, state, styles,
+/// children or context), but after every update Kayak walks the widget's children again to check
+/// them too. For a memoized widget that walk is skipped whenever the widget itself wasn't
+/// re-rendered, so its existing subtree is reused as is. This saves re-checking large subtrees
+/// once for every ancestor of the memoized widget.
+///
+/// Descendants are still checked once per update, so state or context changes inside a memoized
+/// subtree re-render the affected widgets as usual.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct Memo;
+
 const UPDATE_DEPTH: u32 = 0;
 
 type WidgetSystems = HashMap<
@@ -94,6 +116,10 @@ pub struct KayakRootContext {
     pub(crate) current_z: f32,
     pub(crate) context_entities: ContextEntities,
     pub(crate) current_cursor: CursorIcon,
+    /// The custom cursor image to draw and the pointer position to draw it at.
+    pub(crate) current_cursor_image: Option<(CursorImage, Vec2)>,
+    /// Whether the system cursor was hidden to show a custom cursor image.
+    pub(crate) cursor_hidden: bool,
     pub(crate) clone_systems: Arc<RwLock<EntityCloneSystems>>,
     pub(crate) cloned_widget_entities: Arc<DashMap<Entity, Entity>>,
     pub(crate) widget_state: WidgetState,
@@ -105,7 +131,12 @@ pub struct KayakRootContext {
     pub(crate) unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
     /// Maps keyed entities to spawn parents. We can't use the tree in this case.
     pub(crate) unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+    /// Widgets that have been mounted and not yet unmounted.
+    pub(crate) mounted_widgets: Arc<DashSet<Entity>>,
     pub(crate) uninitilized_systems: HashSet<String>,
+    pub(crate) shortcuts: Shortcuts,
+    /// Whether the render primitives need to be built again this frame.
+    pub(crate) needs_render: bool,
     pub camera_entity: Entity,
 }
 
@@ -126,6 +157,8 @@ impl KayakRootContext {
             current_z: 0.0,
             context_entities: ContextEntities::new(),
             current_cursor: CursorIcon::Default,
+            current_cursor_image: None,
+            cursor_hidden: false,
             clone_systems: Default::default(),
             cloned_widget_entities: Default::default(),
             widget_state: Default::default(),
@@ -133,7 +166,10 @@ impl KayakRootContext {
             order_tree: Default::default(),
             unique_ids: Default::default(),
             unique_ids_parents: Default::default(),
+            mounted_widgets: Default::default(),
             uninitilized_systems: Default::default(),
+            shortcuts: Default::default(),
+            needs_render: true,
             camera_entity,
         }
     }
@@ -143,6 +179,52 @@ impl KayakRootContext {
         plugin.build(self)
     }
 
+    /// Sets whether Tab/Shift+Tab navigation wraps around once it reaches the last or first
+    /// focusable widget (enabled by default).
+    pub fn set_focus_wrapping(&mut self, wrap: bool) {
+        self.focus_tree.set_wrapping(wrap);
+    }
+
+    /// Registers an app-wide keyboard shortcut
+    ///
+    /// The handler is called (with the camera entity of this context) whenever the combination
+    /// is pressed, regardless of which widget is focused. The key press is then consumed, so the
+    /// focused widget doesn't receive a `KeyDown` event for it. If several registered
+    /// combinations match the pressed keys, the one requiring the most modifiers wins.
+    ///
+    /// Registering the same combination again replaces the previous shortcut.
+    pub fn register_shortcut(&mut self, combo: KeyCombination, handler: Handler) {
+        self.shortcuts.register(Shortcut {
+            combo,
+            handler,
+            consume: true,
+        });
+    }
+
+    /// Registers an app-wide keyboard shortcut that still sends the key press to the focused widget
+    ///
+    /// See [`register_shortcut`](Self::register_shortcut) for details.
+    pub fn register_shortcut_passthrough(&mut self, combo: KeyCombination, handler: Handler) {
+        self.shortcuts.register(Shortcut {
+            combo,
+            handler,
+            consume: false,
+        });
+    }
+
+    /// Removes the shortcut registered for the given combination (if any)
+    pub fn unregister_shortcut(&mut self, combo: KeyCombination) {
+        self.shortcuts.unregister(combo);
+    }
+
+    /// Rebuilds all render primitives of this context on the next extraction
+    ///
+    /// Kayak only rebuilds the quads it draws when widgets, layout, assets or the camera changed.
+    /// Call this if something else the UI is drawn from changed.
+    pub fn force_full_render(&mut self) {
+        self.needs_render = true;
+    }
+
     /// Get's the layout for th given widget index.
     pub(crate) fn get_layout(&self, id: &WrappedIndex) -> Option<Rect> {
         if let Ok(cache) = self.layout_cache.try_read() {
@@ -152,6 +234,83 @@ impl KayakRootContext {
         }
     }
 
+    /// Creates a serializable copy of the widget tree, starting from the root widget
+    ///
+    /// Returns `None` if no widgets have been added yet.
+    pub fn snapshot(&self, world: &World) -> Option<WidgetSnapshot> {
+        if let Ok(tree) = self.tree.try_read() {
+            if let Some(root) = tree.root_node {
+                return Some(WidgetSnapshot::new(
+                    world,
+                    &tree,
+                    &|id| self.get_layout(id),
+                    root,
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Dumps the widget tree to a pretty-printed RON string
+    ///
+    /// This includes the name, layout and resolved styles of every widget. The output only
+    /// changes when the UI does, which makes it useful for snapshot (golden) tests.
+    /// Use [`KayakRootContext::snapshot`] to serialize the tree to another format.
+    ///
+    /// Returns `None` if no widgets have been added yet or the tree couldn't be serialized.
+    pub fn dump_tree(&self, world: &World) -> Option<String> {
+        let snapshot = self.snapshot(world)?;
+        ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default()).ok()
+    }
+
+    /// Gets the layout of the given widget, or `None` if it hasn't been laid out yet
+    ///
+    /// The position is relative to the top-left corner of the camera's render target, in logical
+    /// pixels. This can be used to anchor things outside of the UI to a widget.
+    ///
+    /// Layouts are calculated towards the end of [`PostUpdate`], after widgets are rendered. Systems
+    /// running in [`Last`] see the layout of the current frame, while systems in [`Update`] see the
+    /// layout of the previous one.
+    pub fn layout(&self, widget: Entity) -> Option<Rect> {
+        self.get_layout(&WrappedIndex(widget))
+    }
+
+    /// Finds the topmost widget at the given point, or `None` if there is no widget there
+    ///
+    /// The point is in the same coordinates as the position of a
+    /// [`CursorEvent`](crate::prelude::CursorEvent). This uses the hit-test of the event
+    /// dispatcher, so it respects the `pointer_events` style, z-index and clipping of widgets, and
+    /// skips hidden and disabled ones.
+    pub fn widget_at(&self, world: &World, point: Vec2) -> Option<Entity> {
+        EventDispatcher::widget_at(self, world, (point.x, point.y)).map(|index| index.0)
+    }
+
+    /// Finds the widget with the given bevy [`Name`]
+    ///
+    /// Names aren't required to be unique: if several widgets share a name, the first one in tree
+    /// order (depth-first, parents before their children) is returned. Give widgets that tests or
+    /// scripts need to find a unique name, for example by inserting it on the entity bound with
+    /// `id` in `rsx!`:
+    ///
+    /// ```ignore
+    /// rsx! {
+    ///     <KButtonBundle id={"save_button"} />
+    /// };
+    /// commands.entity(save_button).insert(Name::new("save_button"));
+    /// ```
+    pub fn find_by_name(&self, world: &World, name: &str) -> Option<Entity> {
+        let tree = self.tree.try_read().ok()?;
+        let root = tree.root_node?;
+        tree.down_iter_at(root, true)
+            .find(|index| {
+                world
+                    .get::<Name>(index.0)
+                    .is_some_and(|widget_name| widget_name.as_str() == name)
+            })
+            .map(|index| index.0)
+    }
+
     pub(crate) fn get_geometry_changed(&self, id: &WrappedIndex) -> bool {
         if let Ok(cache) = self.layout_cache.try_read() {
             if let Some(geometry_changed) = cache.geometry_changed.get(id) {
@@ -164,6 +323,17 @@ impl KayakRootContext {
         }
     }
 
+    /// Re-renders the widgets of every context whose props, state or globals changed
+    ///
+    /// Widgets are normally updated once per frame in `PostUpdate`, so any number of changes
+    /// made during the frame only re-render each widget once, with the final values. Exclusive
+    /// systems that need their changes rendered straight away (e.g. to read the new children
+    /// of a widget) can flush them with this instead. Flushed widgets are only re-rendered
+    /// again for changes made afterwards. Layout is still calculated later in the frame.
+    pub fn flush(world: &mut World) {
+        update_widgets_sys(world);
+    }
+
     /// Adds a new set of systems for a widget type.
     /// Update systems are ran every frame and return true or false depending on if the widget has "changed".
     /// Render systems are ran only if the widget has changed and are meant to re-render children and handle
@@ -230,6 +400,9 @@ impl KayakRootContext {
     /// Because a re-render can potentially spawn new entities it's advised to use this
     /// to avoid creating a new entity.
     ///
+    /// See [`KayakWidgetContext::spawn_widget`] for how keyed and unkeyed children are matched
+    /// to the entities of the previous render.
+    ///
     /// Usage:
     /// ```rust
     /// fn setup() {
@@ -366,6 +539,8 @@ impl KayakRootContext {
         fonts: &Assets<KayakFont>,
         font_mapping: &FontMapping,
         images: &Assets<Image>,
+        debug_overlay: &LayoutDebugOverlay,
+        opacity_layers: u32,
         extracted_quads: &mut ExtractedQuads,
     ) {
         let node_tree = self.tree.try_read();
@@ -393,18 +568,73 @@ impl KayakRootContext {
                 fonts,
                 font_mapping,
                 images,
+                opacity_layers,
                 extracted_quads,
                 node_tree.root_node.unwrap(),
                 &mut prev_clip,
                 0,
                 0,
             );
+
+            if debug_overlay.enabled {
+                extracted_quads.new_layer(Some(DEBUG_OVERLAY_Z));
+                extracted_quads.extend(
+                    extract_debug_overlay(
+                        debug_overlay,
+                        camera_entity,
+                        dpi,
+                        &node_tree,
+                        &layout_cache,
+                        nodes,
+                        widget_names,
+                        fonts,
+                        font_mapping,
+                    )
+                    .into_iter()
+                    .map(QuadOrMaterial::Quad)
+                    .collect(),
+                );
+                extracted_quads.pop_stack();
+            }
+
+            // The cursor is drawn on top of everything else
+            if let Some((cursor_image, position)) = &self.current_cursor_image {
+                if let Some(root_layout) = layout_cache.rect.get(&node_tree.root_node.unwrap()) {
+                    let clip = bevy::prelude::Rect {
+                        min: Vec2::new(root_layout.posx, root_layout.posy) * dpi,
+                        max: Vec2::new(
+                            root_layout.posx + root_layout.width,
+                            root_layout.posy + root_layout.height,
+                        ) * dpi,
+                    };
+                    extracted_quads.new_layer(Some(CURSOR_Z));
+                    extracted_quads.extend(
+                        crate::render::image::extract_cursor_image(
+                            camera_entity,
+                            cursor_image,
+                            *position,
+                            clip,
+                            images,
+                        )
+                        .into_iter()
+                        .map(QuadOrMaterial::Quad)
+                        .collect(),
+                    );
+                    extracted_quads.pop_stack();
+                }
+            }
         }
     }
 }
 
 pub const UI_Z_STEP: f32 = 0.001;
 
+/// The z layer custom cursor images are drawn at
+const CURSOR_Z: f32 = 1_000_000.0;
+
+/// The z layer the [`LayoutDebugOverlay`] is drawn at, between the UI and the cursor
+const DEBUG_OVERLAY_Z: f32 = CURSOR_Z - 1.0;
+
 fn recurse_node_tree_to_build_primitives2(
     commands: &mut Commands,
     camera_entity: Entity,
@@ -416,6 +646,7 @@ fn recurse_node_tree_to_build_primitives2(
     fonts: &Assets<KayakFont>,
     font_mapping: &FontMapping,
     images: &Assets<Image>,
+    opacity_layers: u32,
     extracted_quads: &mut ExtractedQuads,
     current_node: WrappedIndex,
     prev_clip: &mut Option<ExtractedQuad>,
@@ -430,6 +661,11 @@ fn recurse_node_tree_to_build_primitives2(
             return total_opacity_layers;
         }
 
+        // Hidden nodes keep their place in the layout but nothing in their subtree is drawn.
+        if node.resolved_styles.visibility.resolve() == KVisibility::Hidden {
+            return total_opacity_layers;
+        }
+
         let Some(layout) = layout_cache.rect.get_mut(&current_node) else {
             log::warn!(
                 "No layout for node: {}-{}",
@@ -456,7 +692,11 @@ fn recurse_node_tree_to_build_primitives2(
         // Only spawn an opacity layer if we have an opacity greater than zero or less than one.
         if node.opacity < 1.0 {
             // If we've hit max opacity layer capacity skip rendering.
-            if total_opacity_layers + 1 >= MAX_OPACITY_LAYERS {
+            if total_opacity_layers >= opacity_layers {
+                bevy::log::warn_once!(
+                    "Skipping translucent widgets past the limit of {} opacity layers, see KUiOpacityLayers",
+                    opacity_layers
+                );
                 return total_opacity_layers;
             }
 
@@ -498,6 +738,7 @@ fn recurse_node_tree_to_build_primitives2(
                     fonts,
                     font_mapping,
                     images,
+                    opacity_layers,
                     extracted_quads,
                     *child,
                     prev_clip,
@@ -782,6 +1023,10 @@ fn recurse_node_tree_to_build_primitives2(
 // }
 
 /// Updates the widgets
+///
+/// This runs once per frame in `PostUpdate`, which batches all changes made to props, state and
+/// globals during the frame into (at most) a single re-render of each widget. Use
+/// [`KayakRootContext::flush`] to apply the changes sooner.
 pub fn update_widgets_sys(world: &mut World) {
     let mut context_data = Vec::new();
 
@@ -840,6 +1085,7 @@ pub fn update_widgets_sys(world: &mut World) {
             &context.index,
             &context.unique_ids,
             &context.unique_ids_parents,
+            &context.mounted_widgets,
         );
 
         // if let Some(old_focus) = old_focus {
@@ -848,6 +1094,36 @@ pub fn update_widgets_sys(world: &mut World) {
         //     }
         // }
 
+        // Release any focus traps whose widget no longer has one
+        for trap in context.focus_tree.traps() {
+            let is_trap = world
+                .get_entity(trap)
+                .map(|entity_ref| entity_ref.contains::<FocusTrap>())
+                .unwrap_or(false);
+            if !is_trap {
+                context.focus_tree.release_trap(WrappedIndex(trap));
+            }
+        }
+
+        // Focus the first `AutoFocus` widget that was mounted, after any trap took focus
+        if let Some(auto_focus) = context.focus_tree.take_auto_focus() {
+            let previous_focus = context.focus_tree.current();
+            if previous_focus != Some(auto_focus) {
+                context.focus_tree.focus(auto_focus);
+                let mut events = vec![KEvent::new(auto_focus, EventType::Focus)];
+                if let Some(previous_focus) = previous_focus {
+                    events.push(KEvent::new(previous_focus, EventType::Blur));
+                }
+                let event_dispatcher = world
+                    .get_mut::<EventDispatcher>(entity)
+                    .map(|mut event_dispatcher| std::mem::take(&mut *event_dispatcher));
+                if let Some(mut event_dispatcher) = event_dispatcher {
+                    event_dispatcher.dispatch_events(events, &mut context, world);
+                    world.entity_mut(entity).insert(event_dispatcher);
+                }
+            }
+        }
+
         let tick = world.read_change_tick();
 
         for (key, system) in context.systems.iter_mut() {
@@ -884,6 +1160,7 @@ fn update_widgets(
     index: &Arc<DashMap<Entity, usize>>,
     unique_ids: &Arc<DashMap<Entity, DashMap<String, Entity>>>,
     unique_ids_parents: &Arc<DashMap<Entity, Entity>>,
+    mounted_widgets: &Arc<DashSet<Entity>>,
 ) {
     for entity in widgets.iter() {
         // if let (Some(entity_ref), Some(_)) = (
@@ -904,9 +1181,11 @@ fn update_widgets(
                     Some(camera_entity),
                     unique_ids.clone(),
                     unique_ids_parents.clone(),
+                    cloned_widget_entities.clone(),
                 );
                 widget_context.copy_from_point(tree, *entity);
                 let children_before = widget_context.get_children(entity.0);
+                let is_memo = entity_ref.contains::<Memo>();
                 // let widget_name = widget_type.0.clone();
                 let (widget_context, should_update_children) = update_widget(
                     systems,
@@ -923,6 +1202,17 @@ fn update_widgets(
                     new_ticks,
                 );
 
+                if mounted_widgets.insert(entity.0) {
+                    if let Some(on_mount) = world.get::<OnMount>(entity.0).cloned() {
+                        on_mount.0.try_call(entity.0, (), world);
+                    }
+                    if world.get::<AutoFocus>(entity.0).is_some()
+                        && world.get::<Disabled>(entity.0).is_none()
+                    {
+                        focus_tree.request_auto_focus(*entity);
+                    }
+                }
+
                 if should_update_children {
                     if let Ok(mut tree) = tree.write() {
                         let diff = tree.diff_children(&widget_context, *entity, 0);
@@ -947,6 +1237,8 @@ fn update_widgets(
 
                         // Children of this node need to be despawned.
                         let mut despawn_list = Vec::default();
+                        // Widgets that need to be unmounted, children before parents.
+                        let mut unmount_list = Vec::default();
                         'outer: for (_index, changed_entity, parent, changes) in diff.changes.iter()
                         {
                             // If a tree node goes from A to B we need to know and delete the descendants.
@@ -965,6 +1257,11 @@ fn update_widgets(
                                         if widget_name != prev_widget_name {
                                             // It doesn't matter we always need to remove state
                                             remove_state.push(changed_entity.0);
+                                            // The entity now holds the new widget, so the old
+                                            // handler has to come from its previous widget clone
+                                            let on_unmount =
+                                                prev_entity_ref.get::<OnUnmount>().cloned();
+                                            let mut removed_children = Vec::default();
                                             if tree.parent(*changed_entity).is_some() {
                                                 for child in
                                                     tree.down_iter_at(*changed_entity, false)
@@ -1022,6 +1319,7 @@ fn update_widgets(
                                                     }
                                                     if should_delete {
                                                         despawn_list.push((parent.0, child.0));
+                                                        removed_children.push(child);
                                                         if let Ok(mut order_tree) =
                                                             order_tree.try_write()
                                                         {
@@ -1030,6 +1328,21 @@ fn update_widgets(
                                                     }
                                                 }
                                             }
+                                            unmount_list.extend(
+                                                removed_children.into_iter().rev().map(|child| {
+                                                    (
+                                                        child,
+                                                        unmount_handler(
+                                                            world,
+                                                            cloned_widget_entities,
+                                                            child.0,
+                                                        ),
+                                                    )
+                                                }),
+                                            );
+                                            // Unmounting the old widget also lets the new one
+                                            // mount when it's first updated
+                                            unmount_list.push((*changed_entity, on_unmount));
                                         }
                                     }
                                 }
@@ -1107,6 +1420,12 @@ fn update_widgets(
                                 if let Some(parent) = tree.parent(*changed_entity) {
                                     despawn_list.push((parent.0, changed_entity.0));
                                 }
+                                unmount_list.extend(collect_unmounts(
+                                    world,
+                                    &tree,
+                                    cloned_widget_entities,
+                                    *changed_entity,
+                                ));
 
                                 if let Ok(mut order_tree) = order_tree.try_write() {
                                     order_tree.remove(*changed_entity);
@@ -1121,6 +1440,8 @@ fn update_widgets(
                         //     tree.dump_all_at(Some(world), entity.0);
                         // }
 
+                        unmount_widgets(world, mounted_widgets, unmount_list);
+
                         for (parent, entity) in despawn_list.drain(..) {
                             // Clear out keyed entity.
                             if let Some(parent) =
@@ -1200,32 +1521,34 @@ fn update_widgets(
                     }
                 }
 
-                // if should_update_children {
-                let children = if let Ok(tree) = tree.read() {
-                    tree.child_iter(*entity).collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
-                update_widgets(
-                    camera_entity,
-                    world,
-                    tree,
-                    layout_cache,
-                    systems,
-                    children,
-                    context_entities,
-                    focus_tree,
-                    clone_systems,
-                    cloned_widget_entities,
-                    widget_state,
-                    new_ticks,
-                    order_tree,
-                    index,
-                    unique_ids,
-                    unique_ids_parents,
-                );
-                // }
+                // Memoized widgets reuse their subtree unless they were re-rendered.
+                if !is_memo || should_update_children {
+                    let children = if let Ok(tree) = tree.read() {
+                        tree.child_iter(*entity).collect::<Vec<_>>()
+                    } else {
+                        vec![]
+                    };
+
+                    update_widgets(
+                        camera_entity,
+                        world,
+                        tree,
+                        layout_cache,
+                        systems,
+                        children,
+                        context_entities,
+                        focus_tree,
+                        clone_systems,
+                        cloned_widget_entities,
+                        widget_state,
+                        new_ticks,
+                        order_tree,
+                        index,
+                        unique_ids,
+                        unique_ids_parents,
+                        mounted_widgets,
+                    );
+                }
             }
         } else {
             // In this case the entity we are trying to process no longer exists.
@@ -1256,11 +1579,14 @@ fn update_widgets(
             }
             if !contained_in_children {
                 if let Ok(mut tree) = tree.write() {
+                    let unmount_list =
+                        collect_unmounts(world, &tree, cloned_widget_entities, *entity);
                     if let Ok(mut order_tree) = order_tree.try_write() {
                         log::trace!("Removing dangling entity! {:?}", entity.0.index());
                         order_tree.remove(*entity);
                     }
                     tree.remove(*entity);
+                    unmount_widgets(world, mounted_widgets, unmount_list);
                     if let Some(mut entity_mut) = world.get_entity_mut(entity.0) {
                         entity_mut.remove_parent();
                         entity_mut.remove::<bevy::prelude::Children>();
@@ -1278,11 +1604,74 @@ fn update_widgets(
         }
 
         if let Some(entity_ref) = world.get_entity(entity.0) {
-            if entity_ref.contains::<Focusable>() {
+            let is_trap = entity_ref.contains::<FocusTrap>();
+            if entity_ref.contains::<Disabled>() {
+                // Disabled widgets can't be focused
+                if focus_tree.contains(entity.0) {
+                    focus_tree.remove(*entity);
+                }
+            } else if entity_ref.contains::<Focusable>() || is_trap {
                 if let Ok(tree) = tree.try_read() {
                     focus_tree.add(*entity, &tree);
                 }
             }
+            if is_trap {
+                focus_tree.push_trap(*entity);
+            }
+        }
+    }
+}
+
+/// Finds the [`OnUnmount`] handler of a widget
+///
+/// Widgets that were already despawned fall back to the handler copied onto their previous
+/// widget entity.
+fn unmount_handler(
+    world: &World,
+    cloned_widget_entities: &DashMap<Entity, Entity>,
+    entity: Entity,
+) -> Option<OnUnmount> {
+    world.get::<OnUnmount>(entity).cloned().or_else(|| {
+        cloned_widget_entities
+            .get(&entity)
+            .and_then(|previous| world.get::<OnUnmount>(*previous).cloned())
+    })
+}
+
+/// Collects a widget and its descendants with their [`OnUnmount`] handlers, children first
+///
+/// Handlers are captured up front since the entities may be despawned or reused by the time
+/// the widgets are unmounted.
+fn collect_unmounts(
+    world: &World,
+    tree: &Tree,
+    cloned_widget_entities: &DashMap<Entity, Entity>,
+    root: WrappedIndex,
+) -> Vec<(WrappedIndex, Option<OnUnmount>)> {
+    let mut widgets = tree
+        .down_iter_at(root, true)
+        .map(|widget| {
+            (
+                widget,
+                unmount_handler(world, cloned_widget_entities, widget.0),
+            )
+        })
+        .collect::<Vec<_>>();
+    widgets.reverse();
+    widgets
+}
+
+/// Calls the captured [`OnUnmount`] handler of each widget that is still mounted, in order
+fn unmount_widgets(
+    world: &mut World,
+    mounted_widgets: &Arc<DashSet<Entity>>,
+    widgets: Vec<(WrappedIndex, Option<OnUnmount>)>,
+) {
+    for (widget, on_unmount) in widgets {
+        if mounted_widgets.remove(&widget.0).is_some() {
+            if let Some(on_unmount) = on_unmount {
+                on_unmount.0.try_call(widget.0, (), world);
+            }
         }
     }
 }
@@ -1353,7 +1742,8 @@ fn update_widget(
 
         // Insert context as a bevy resource.
         world.insert_resource(widget_context);
-        let should_rerender = widget_update_system.run((entity.0, old_props_entity), world);
+        let should_rerender = widget_update_system.run((entity.0, old_props_entity), world)
+            || widget_state.has_global_changed(entity.0, world, old_tick);
         let new_tick = widget_update_system.get_last_run();
         new_ticks.insert(widget_type.clone(), new_tick);
         widget_update_system.set_last_run(old_tick);
@@ -1393,6 +1783,15 @@ fn update_widget(
                                 entity.insert(widget_name);
                             }
                         }
+
+                        // Kept so the widget can still be unmounted once its entity is reused
+                        let on_unmount = world.entity(entity.0).get::<OnUnmount>().cloned();
+                        if let Some(mut entity) = world.get_entity_mut(target_entity) {
+                            match on_unmount {
+                                Some(on_unmount) => entity.insert(on_unmount),
+                                None => entity.remove::<OnUnmount>(),
+                            };
+                        }
                     }
                 }
             }
@@ -1436,6 +1835,7 @@ fn update_widget(
         world.insert_resource(widget_context.clone());
         world.insert_resource(focus_tree.clone());
         should_update_children = widget_render_system.run(entity.0, world);
+        widget_state.set_rendered(entity.0, world.read_change_tick());
         let new_tick = widget_render_system.get_last_run();
         new_ticks.insert(widget_type.clone(), new_tick);
         widget_render_system.set_last_run(old_tick);
@@ -1470,6 +1870,14 @@ pub struct CustomEventReader<T: bevy::ecs::event::Event>(pub ManualEventReader<T
 impl Plugin for KayakContextPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(WindowSize::default())
+            .init_resource::<crate::KUiScale>()
+            .init_resource::<crate::clipboard::Clipboard>()
+            .init_resource::<crate::gamepad::GamepadNavigation>()
+            .init_resource::<crate::theme::Theme>()
+            .init_resource::<crate::breakpoints::Breakpoints>()
+            .init_resource::<crate::localization::Localization>()
+            .init_asset::<crate::style_sheet::StyleSheet>()
+            .init_asset_loader::<crate::style_sheet::StyleSheetLoader>()
             .insert_resource(CustomEventReader(ManualEventReader::<
                 bevy::window::CursorMoved,
             >::default()))
@@ -1485,6 +1893,9 @@ impl Plugin for KayakContextPlugin {
             .insert_resource(CustomEventReader(ManualEventReader::<
                 bevy::input::keyboard::KeyboardInput,
             >::default()))
+            .insert_resource(CustomEventReader(
+                ManualEventReader::<bevy::window::Ime>::default(),
+            ))
             .add_plugins((
                 crate::camera::KayakUICameraPlugin,
                 crate::render::BevyKayakUIRenderPlugin,
@@ -1496,7 +1907,26 @@ impl Plugin for KayakContextPlugin {
                     crate::window_size::update_window_size,
                 ),
             )
-            .add_systems(PostUpdate, (update_widgets_sys, calculate_ui).chain());
+            .add_systems(
+                PostUpdate,
+                (
+                    crate::on_resize::call_on_resize,
+                    update_widgets_sys,
+                    crate::style_sheet::update_theme_from_style_sheets,
+                    crate::theme::restyle_on_theme_change,
+                    crate::localization::relocalize_on_change,
+                    calculate_ui,
+                    track_render_changes,
+                )
+                    .chain(),
+            )
+            .add_systems(First, reset_render_changes);
+
+        #[cfg(feature = "accessibility")]
+        app.add_systems(
+            PostUpdate,
+            crate::accessibility::update_accessibility_nodes.after(calculate_ui),
+        );
 
         // Register reflection types.
         // A bit annoying..
@@ -1507,11 +1937,15 @@ impl Plugin for KayakContextPlugin {
             .register_type::<crate::layout::Rect>()
             .register_type::<crate::node::Node>()
             .register_type::<WidgetName>()
+            .register_type::<FocusTrap>()
+            .register_type::<AutoFocus>()
+            .register_type::<Disabled>()
             .register_type::<StyleProp<Color>>()
             .register_type::<StyleProp<Corner<f32>>>()
             .register_type::<StyleProp<Edge<f32>>>()
             .register_type::<StyleProp<Units>>()
             .register_type::<StyleProp<KCursorIcon>>()
+            .register_type::<StyleProp<CursorImage>>()
             .register_type::<StyleProp<String>>()
             .register_type::<StyleProp<f32>>()
             .register_type::<StyleProp<LayoutType>>()
@@ -1539,42 +1973,139 @@ fn calculate_ui(world: &mut World) {
         world,
     );
 
+    let mut cursor = CursorIcon::Default;
     for (entity, event_dispatcher, mut context) in context_data.drain(..) {
         let mut node_system = IntoSystem::into_system(calculate_nodes);
         node_system.initialize(world);
         let mut layout_system = IntoSystem::into_system(calculate_layout);
         layout_system.initialize(world);
 
+        let previous_rects = context
+            .layout_cache
+            .try_read()
+            .map(|cache| cache.rect.clone())
+            .unwrap_or_default();
+
         for _ in 0..2 {
             context = node_system.run(context, world);
             node_system.apply_deferred(world);
 
             context = layout_system.run(context, world);
             layout_system.apply_deferred(world);
-            LayoutEventDispatcher::dispatch(&mut context, world);
         }
 
-        if event_dispatcher.hovered.is_none() {
-            context.current_cursor = CursorIcon::Default;
-        } else {
-            let hovered = event_dispatcher.hovered.unwrap();
-            if let Some(entity) = world.get_entity(hovered.0) {
-                if let Some(node) = entity.get::<crate::node::Node>() {
-                    let icon = node.resolved_styles.cursor.resolve();
-                    context.current_cursor = icon.0;
-                }
-            }
+        // Layout events are only sent once both passes are done, so handlers see the final layout
+        LayoutEventDispatcher::dispatch(&mut context, world, &previous_rects);
 
-            if let Ok(mut window) = world
-                .query_filtered::<&mut Window, With<PrimaryWindow>>()
-                .get_single_mut(world)
-            {
-                window.cursor.icon = context.current_cursor;
-            }
+        if let Some(icon) = update_cursor(world, &event_dispatcher, &mut context) {
+            cursor = icon;
         }
 
         world.entity_mut(entity).insert((event_dispatcher, context));
     }
+
+    // The default cursor is shown while the cursor isn't over any widget
+    if let Ok(mut window) = world
+        .query_filtered::<&mut Window, With<PrimaryWindow>>()
+        .get_single_mut(world)
+    {
+        if window.cursor.icon != cursor {
+            window.cursor.icon = cursor;
+        }
+    }
+}
+
+/// Picks the cursor of the frontmost widget of a context under the cursor
+///
+/// Widgets without a `cursor` style of their own inherit the one of their parent. Returns `None`
+/// if the cursor isn't over any widget of the context.
+fn update_cursor(
+    world: &mut World,
+    event_dispatcher: &EventDispatcher,
+    context: &mut KayakRootContext,
+) -> Option<CursorIcon> {
+    let mut window_query = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+    let cursor_position = window_query
+        .get_single(world)
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let styles = cursor_position
+        .and(event_dispatcher.cursor_target(context, world))
+        .and_then(|target| world.get::<crate::node::Node>(target.0))
+        .map(|node| {
+            (
+                node.resolved_styles
+                    .cursor
+                    .resolve_as_option()
+                    .map(|icon| icon.0)
+                    .unwrap_or_default(),
+                node.resolved_styles.cursor_image.resolve_as_option(),
+            )
+        });
+    let (cursor, cursor_image) = match styles {
+        Some((cursor, cursor_image)) => (Some(cursor), cursor_image),
+        None => (None, None),
+    };
+    context.current_cursor = cursor.unwrap_or_default();
+
+    if let Ok(mut window) = window_query.get_single_mut(world) {
+        // Swap the system cursor for the custom cursor image (if any) while it's in the window
+        let current_cursor_image = cursor_image.zip(cursor_position);
+        if current_cursor_image != context.current_cursor_image {
+            context.needs_render = true;
+        }
+        context.current_cursor_image = current_cursor_image;
+        let hide_cursor = context.current_cursor_image.is_some();
+        if hide_cursor != context.cursor_hidden {
+            window.cursor.visible = !hide_cursor;
+            context.cursor_hidden = hide_cursor;
+        }
+    }
+
+    cursor
+}
+
+fn reset_render_changes(mut contexts: Query<&mut KayakRootContext>) {
+    for mut context in contexts.iter_mut() {
+        context.needs_render = false;
+    }
+}
+
+/// Flags contexts whose render primitives are out of date so extraction can skip the others
+fn track_render_changes(
+    mut contexts: Query<&mut KayakRootContext>,
+    changed_nodes: Query<(), Changed<crate::node::Node>>,
+    mut removed_nodes: RemovedComponents<crate::node::Node>,
+    changed_cameras: Query<(), Changed<Camera>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut font_events: EventReader<AssetEvent<KayakFont>>,
+) {
+    // Node and asset changes aren't tracked per context, so they rebuild every context
+    let nodes_changed = !changed_nodes.is_empty() || removed_nodes.read().count() > 0;
+    let assets_changed = image_events.read().count() > 0 || font_events.read().count() > 0;
+
+    for mut context in contexts.iter_mut() {
+        let layout_changed = context
+            .layout_cache
+            .try_read()
+            .map(|cache| {
+                cache
+                    .geometry_changed
+                    .values()
+                    .any(|change| !change.is_empty())
+            })
+            .unwrap_or(true);
+
+        if context.is_added()
+            || nodes_changed
+            || assets_changed
+            || layout_changed
+            || changed_cameras.contains(context.camera_entity)
+        {
+            context.needs_render = true;
+        }
+    }
 }
 
 /// A simple component that stores the type name of a widget
@@ -1601,3 +2132,153 @@ impl From<WidgetName> for String {
         val.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::prelude::{Commands, Component, Entity, In, Query, Res, ResMut, Resource, World};
+    use dashmap::{DashMap, DashSet};
+
+    use super::{collect_unmounts, unmount_widgets, KayakRootContext, Mounted};
+    use crate::{
+        node::WrappedIndex,
+        on_unmount::OnUnmount,
+        tree::Tree,
+        widget::{widget_update, Widget},
+        widget_context::KayakWidgetContext,
+    };
+
+    #[derive(Resource, Default)]
+    struct Unmounted(Vec<Entity>);
+
+    fn spawn_widget(world: &mut World) -> WrappedIndex {
+        let on_unmount = OnUnmount::new(
+            |In((entity, _)): In<(Entity, ())>, mut unmounted: ResMut<Unmounted>| {
+                unmounted.0.push(entity);
+            },
+        );
+        WrappedIndex(world.spawn(on_unmount).id())
+    }
+
+    #[test]
+    fn widgets_should_unmount_once_children_first() {
+        let mut world = World::new();
+        world.init_resource::<Unmounted>();
+        let parent = spawn_widget(&mut world);
+        let child = spawn_widget(&mut world);
+        let grandchild = spawn_widget(&mut world);
+        let sibling = spawn_widget(&mut world);
+
+        let mut tree = Tree::default();
+        tree.add(parent, None);
+        tree.add(child, Some(parent));
+        tree.add(grandchild, Some(child));
+        tree.add(sibling, Some(parent));
+
+        let mounted_widgets = Arc::new(DashSet::new());
+        for widget in [parent, child, grandchild, sibling] {
+            mounted_widgets.insert(widget.0);
+        }
+
+        let widgets = collect_unmounts(&world, &tree, &DashMap::new(), parent);
+        unmount_widgets(&mut world, &mounted_widgets, widgets.clone());
+        // Widgets that were already unmounted are skipped
+        unmount_widgets(&mut world, &mounted_widgets, widgets);
+
+        let unmounted = &world.resource::<Unmounted>().0;
+        assert_eq!(4, unmounted.len());
+        let position = |widget: WrappedIndex| unmounted.iter().position(|e| *e == widget.0);
+        assert!(position(grandchild) < position(child));
+        assert!(position(child) < position(parent));
+        assert!(position(sibling) < position(parent));
+        assert!(mounted_widgets.is_empty());
+    }
+
+    #[test]
+    fn despawned_widgets_should_unmount_with_their_previous_handler() {
+        let mut world = World::new();
+        world.init_resource::<Unmounted>();
+        let previous_widget = spawn_widget(&mut world);
+        let widget = WrappedIndex(world.spawn_empty().id());
+        world.despawn(widget.0);
+
+        let mut tree = Tree::default();
+        tree.add(widget, None);
+        let cloned_widget_entities = DashMap::new();
+        cloned_widget_entities.insert(widget.0, previous_widget.0);
+        let mounted_widgets = Arc::new(DashSet::new());
+        mounted_widgets.insert(widget.0);
+
+        let widgets = collect_unmounts(&world, &tree, &cloned_widget_entities, widget);
+        unmount_widgets(&mut world, &mounted_widgets, widgets);
+
+        assert_eq!(vec![widget.0], world.resource::<Unmounted>().0);
+    }
+
+    #[derive(Component, Default, Clone, PartialEq)]
+    struct CounterProps;
+
+    impl Widget for CounterProps {}
+
+    #[derive(Component, Default, Clone, PartialEq)]
+    struct CounterState(u32);
+
+    /// The state value of each render of a counter
+    #[derive(Resource, Default)]
+    struct Renders(Vec<u32>);
+
+    fn counter_render(
+        In(entity): In<Entity>,
+        widget_context: Res<KayakWidgetContext>,
+        mut commands: Commands,
+        state_query: Query<&CounterState>,
+        mut renders: ResMut<Renders>,
+    ) -> bool {
+        let state_entity = widget_context.use_state(&mut commands, entity, CounterState::default());
+        let count = state_query
+            .get(state_entity)
+            .map(|state| state.0)
+            .unwrap_or_default();
+        renders.0.push(count);
+        true
+    }
+
+    #[test]
+    fn flush_should_render_batched_state_changes_once() {
+        let mut world = World::new();
+        world.init_resource::<Renders>();
+        let camera = world.spawn_empty().id();
+        let widget = world
+            .spawn((CounterProps, CounterProps.get_name(), Mounted))
+            .id();
+
+        let mut context = KayakRootContext::new(camera);
+        context.add_widget_data::<CounterProps, CounterState>();
+        context.add_widget_system(
+            CounterProps.get_name(),
+            widget_update::<CounterProps, CounterState>,
+            counter_render,
+        );
+        context.add_widget(None, widget);
+        world.entity_mut(camera).insert(context);
+
+        KayakRootContext::flush(&mut world);
+        assert_eq!(vec![0], world.resource::<Renders>().0);
+
+        let state_entity = world
+            .get::<KayakRootContext>(camera)
+            .and_then(|context| context.widget_state.get(widget))
+            .unwrap();
+        for count in 1..=3 {
+            world.get_mut::<CounterState>(state_entity).unwrap().0 = count;
+        }
+
+        KayakRootContext::flush(&mut world);
+        assert_eq!(vec![0, 3], world.resource::<Renders>().0);
+
+        // Nothing changed since the last flush
+        KayakRootContext::flush(&mut world);
+        assert_eq!(vec![0, 3], world.resource::<Renders>().0);
+    }
+}
@@ -1,7 +1,12 @@
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
 /// Controls how the cursor interacts on a given node
-#[derive(Debug, Reflect, Copy, Clone, PartialEq, Eq)]
+///
+/// Widgets that don't receive pointer events are skipped when hit testing, so the events fall
+/// through to whatever is beneath them. A transparent overlay set to [`PointerEvents::None`]
+/// therefore doesn't block the UI below it.
+#[derive(Debug, Reflect, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
 pub enum PointerEvents {
     /// Allow all pointer events on this node and its children
     All,
@@ -11,6 +16,13 @@ pub enum PointerEvents {
     ChildrenOnly,
     /// Disallow all pointer events on this node and its children
     None,
+    /// Allow all pointer events on this node and its children, without this node blocking them
+    /// from reaching the widgets beneath it
+    ///
+    /// The node receives its events alongside the topmost widget under the cursor rather than in
+    /// place of it. It never takes focus or starts a drag, which makes this useful for overlays
+    /// that only want to track the cursor, like drag ghosts.
+    PassThrough,
 }
 
 impl Default for PointerEvents {
@@ -19,12 +31,26 @@ impl Default for PointerEvents {
     }
 }
 
+impl PointerEvents {
+    /// Whether the node itself receives pointer events
+    pub fn includes_self(&self) -> bool {
+        matches!(self, Self::All | Self::SelfOnly | Self::PassThrough)
+    }
+
+    /// Whether the children of the node receive pointer events
+    pub fn includes_children(&self) -> bool {
+        matches!(self, Self::All | Self::ChildrenOnly | Self::PassThrough)
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct CursorEvent {
     pub pressed: bool,
     pub just_pressed: bool,
     pub just_released: bool,
     pub position: (f32, f32),
+    /// The number of successive clicks on the same widget (only set for click events)
+    pub click_count: u32,
 }
 
 /// An event created on scroll
@@ -0,0 +1,37 @@
+use bevy::prelude::{Commands, Component, Entity, Reflect, ReflectComponent};
+
+use crate::styles::KStyle;
+
+/// The opacity disabled widgets are drawn with unless their styles set one
+pub const DISABLED_OPACITY: f32 = 0.5;
+
+/// Marks a widget as disabled
+///
+/// Disabled widgets are still rendered, but they and their children are treated as if their
+/// [`pointer_events`](crate::styles::KStyle::pointer_events) style were
+/// [`PointerEvents::None`](crate::prelude::PointerEvents::None), whatever it's actually set to.
+/// They're also left out of the focus tree (losing focus if they had it), so they can't be tabbed
+/// to and don't receive keyboard events.
+///
+/// Widgets with a `disabled` prop, such as [`KButton`](crate::widgets::KButton), add and remove
+/// this component themselves.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Disabled;
+
+/// Adds or removes the [`Disabled`] component of a widget
+///
+/// Returns the styles used to dim the widget while it's disabled, which should be applied after
+/// the widget's own styles so they can override them.
+pub(crate) fn set_disabled(commands: &mut Commands, entity: Entity, disabled: bool) -> KStyle {
+    if disabled {
+        commands.entity(entity).insert(Disabled);
+        KStyle {
+            opacity: DISABLED_OPACITY.into(),
+            ..Default::default()
+        }
+    } else {
+        commands.entity(entity).remove::<Disabled>();
+        KStyle::default()
+    }
+}
@@ -0,0 +1,64 @@
+use std::{any::Any, fmt::Debug, sync::Arc};
+
+use bevy::{
+    prelude::{Component, Entity, ReflectComponent},
+    reflect::Reflect,
+};
+
+/// Marks a widget as one that can be dragged
+///
+/// Pressing the left mouse button on the widget (or any of its descendants) and moving the
+/// cursor past the [drag threshold](crate::prelude::EventDispatcher::set_drag_threshold) starts
+/// a drag. The widget then receives [`DragStart`](crate::prelude::EventType::DragStart),
+/// [`Drag`](crate::prelude::EventType::Drag) and [`DragEnd`](crate::prelude::EventType::DragEnd)
+/// events. A payload can be attached to the drag while handling `DragStart` by calling
+/// [`EventDispatcherContext::set_drag_payload`](crate::prelude::EventDispatcherContext::set_drag_payload).
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Draggable;
+
+/// Marks a widget as one that dragged widgets can be dropped onto
+///
+/// While a drag is over the widget, it receives [`DragEnter`](crate::prelude::EventType::DragEnter),
+/// [`DragOver`](crate::prelude::EventType::DragOver) and [`DragLeave`](crate::prelude::EventType::DragLeave)
+/// events. Releasing the drag over it sends a [`Drop`](crate::prelude::EventType::Drop) event.
+///
+/// Like other cursor events, the widget needs an `OnEvent` handler to be considered a target.
+/// If several drop targets are under the cursor, the innermost one is used.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct DropTarget;
+
+/// User-defined data carried by a drag
+#[derive(Clone)]
+pub struct DragPayload(Arc<dyn Any + Send + Sync>);
+
+impl DragPayload {
+    pub fn new(value: impl Any + Send + Sync) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns the payload if it's of type `T`
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+}
+
+impl Debug for DragPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragPayload").finish_non_exhaustive()
+    }
+}
+
+/// An event created while dragging a [`Draggable`] widget
+#[derive(Debug, Clone)]
+pub struct DragEvent {
+    /// The widget being dragged
+    pub source: Entity,
+    /// The current position of the cursor
+    pub position: (f32, f32),
+    /// How far the cursor moved since the last drag event
+    pub delta: (f32, f32),
+    /// The payload attached when the drag started (if any)
+    pub payload: Option<DragPayload>,
+}
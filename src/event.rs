@@ -2,11 +2,30 @@ use bevy::prelude::{Entity, Resource, World};
 
 use crate::{
     cursor::{CursorEvent, ScrollEvent},
+    drag::DragEvent,
     keyboard_event::KeyboardEvent,
     prelude::{KayakWidgetContext, OnChange},
 };
 
 /// An event type sent to widgets
+///
+/// Events are first sent to their [`target`](Self::target) and then bubble up through its
+/// ancestors, one at a time, until they reach the root widget. Some event types don't bubble at
+/// all (see [`EventType::propagates`]). Any `OnEvent` handler along the way can stop the event
+/// from going any further with [`stop_propagation`](Self::stop_propagation).
+///
+/// Handlers can also cancel the built-in behavior tied to an event with
+/// [`prevent_default`](Self::prevent_default). Since events bubble upwards, this lets a widget
+/// keep its ancestors from reacting to it. The behaviors that can be cancelled are:
+///
+/// * `KeyDown` of Tab (and Shift+Tab): moving focus to the next (or previous) widget
+/// * `Scroll`: scrolling the [`ScrollBox`](crate::widgets::ScrollBoxBundle) around the target
+/// * `KeyDown` of PageUp, PageDown, Home, End and the arrow keys: scrolling the
+///   [`ScrollBox`](crate::widgets::ScrollBoxBundle) around the target
+/// * `Click`: toggling the [`AccordionSummary`](crate::widgets::AccordionSummary) around the target
+/// * `RightClick`: opening the [`ContextMenu`](crate::widgets::ContextMenu) around the target
+/// * `KeyDown` of Escape: closing the [`Modal`](crate::widgets::Modal) or
+///   [`ContextMenu`](crate::widgets::ContextMenu) around the target
 #[derive(Resource, Clone, Debug)]
 pub struct KEvent {
     /// The node targeted by this event
@@ -68,18 +87,12 @@ impl KEvent {
     }
 
     /// If called, prevents this event from propagating up the hierarchy
+    ///
+    /// The handler of the current widget still finishes running, and the default action of
+    /// the event (if any) is still executed unless [`prevent_default`](Self::prevent_default)
+    /// is called as well.
     pub fn stop_propagation(&mut self) {
-        if matches!(
-            self.event_type,
-            EventType::Click(..)
-                | EventType::MouseIn(..)
-                | EventType::MouseDown(..)
-                | EventType::Scroll(..)
-                | EventType::Focus
-                | EventType::Hover(..)
-        ) {
-            self.should_propagate = false;
-        }
+        self.should_propagate = false;
     }
 
     /// Returns whether this event's default action has been prevented or not
@@ -88,6 +101,9 @@ impl KEvent {
     }
 
     /// Prevents this event's default action (if any) from being executed
+    ///
+    /// This also tells the widgets further up the hierarchy that the event was already handled
+    /// (see [`KEvent`] for the built-in behaviors that respect this).
     pub fn prevent_default(&mut self) {
         self.default_prevented = true;
     }
@@ -112,24 +128,68 @@ impl KEvent {
 pub enum EventType {
     /// An event that occurs when the user clicks a widget
     Click(CursorEvent),
+    /// An event that occurs when the user clicks the same widget twice in quick succession
+    DoubleClick(CursorEvent),
+    /// An event that occurs when the user right-clicks a widget
+    RightClick(CursorEvent),
+    /// An event that occurs when the user middle-clicks a widget
+    MiddleClick(CursorEvent),
     /// An event that occurs when the user hovers the cursor over a widget
     Hover(CursorEvent),
     /// An event that occurs when the user moves the cursor into a widget
     MouseIn(CursorEvent),
     /// An event that occurs when the user moves the cursor out of a widget
     MouseOut(CursorEvent),
+    /// An event that occurs once when the cursor enters a widget or one of its descendants
+    ///
+    /// Unlike [`MouseIn`](Self::MouseIn), this fires for every widget under the cursor (outermost first),
+    /// not just the topmost one.
+    MouseEnter(CursorEvent),
+    /// An event that occurs once when the cursor leaves a widget and all of its descendants
+    ///
+    /// Fires for every widget no longer under the cursor (innermost first).
+    MouseLeave(CursorEvent),
     /// An event that occurs when the user presses down on the cursor over a widget
     MouseDown(CursorEvent),
     /// An event that occurs when the user releases the cursor over a widget
     MouseUp(CursorEvent),
     /// An event that occurs when the user scrolls over a widget
     Scroll(ScrollEvent),
+    /// An event that occurs when the user starts dragging a [`Draggable`](crate::prelude::Draggable) widget
+    DragStart(DragEvent),
+    /// An event that occurs when the cursor moves while dragging a widget
+    Drag(DragEvent),
+    /// An event that occurs when the user stops dragging a widget (whether or not it was dropped)
+    DragEnd(DragEvent),
+    /// An event that occurs when a drag moves onto a [`DropTarget`](crate::prelude::DropTarget)
+    DragEnter(DragEvent),
+    /// An event that occurs when the cursor moves while dragging over a drop target
+    DragOver(DragEvent),
+    /// An event that occurs when a drag moves off of a drop target (or is cancelled over it)
+    DragLeave(DragEvent),
+    /// An event that occurs when a drag is released over a drop target
+    Drop(DragEvent),
     /// An event that occurs when a widget receives focus
     Focus,
     /// An event that occurs when a widget loses focus
     Blur,
     /// An event that occurs when the user types in a character within a _focused_ widget
     CharInput { c: smol_str::SmolStr },
+    /// An event that occurs when an IME updates the text being composed within a _focused_ widget
+    ///
+    /// The text isn't final and should be displayed without being added to the widget's value.
+    /// The `cursor` is the byte range of the IME cursor within `text`, if it should be shown.
+    /// An empty `text` means the composition was cancelled.
+    ImePreedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// An event that occurs when an IME commits composed text within a _focused_ widget
+    ImeCommit { text: String },
+    /// An event that occurs when the user asks to go back or cancel within a _focused_ widget
+    ///
+    /// This is currently sent when the East (B) button of a gamepad is pressed.
+    Cancel,
     /// An event that occurs when the user releases a key within a _focused_ widget
     KeyUp(KeyboardEvent),
     /// An event that occurs when the user presses a key down within a _focused_ widget
@@ -173,15 +233,30 @@ impl EventType {
             // Propagates
             Self::Hover(..) => true,
             Self::Click(..) => true,
+            Self::DoubleClick(..) => true,
+            Self::RightClick(..) => true,
+            Self::MiddleClick(..) => true,
             Self::MouseDown(..) => true,
             Self::MouseUp(..) => true,
             Self::Scroll(..) => true,
+            Self::DragStart(..) => true,
+            Self::Drag(..) => true,
+            Self::DragEnd(..) => true,
+            Self::DragOver(..) => true,
+            Self::Drop(..) => true,
             Self::CharInput { .. } => true,
+            Self::ImePreedit { .. } => true,
+            Self::ImeCommit { .. } => true,
+            Self::Cancel => true,
             Self::KeyUp(..) => true,
             Self::KeyDown(..) => true,
             // Doesn't Propagate
             Self::MouseIn(..) => false,
             Self::MouseOut(..) => false,
+            Self::MouseEnter(..) => false,
+            Self::MouseLeave(..) => false,
+            Self::DragEnter(..) => false,
+            Self::DragLeave(..) => false,
             Self::Focus => false,
             Self::Blur => false,
         }
@@ -193,13 +268,28 @@ impl EventType {
             // Mouse
             Self::Hover(..) => EventCategory::Mouse,
             Self::Click(..) => EventCategory::Mouse,
+            Self::DoubleClick(..) => EventCategory::Mouse,
+            Self::RightClick(..) => EventCategory::Mouse,
+            Self::MiddleClick(..) => EventCategory::Mouse,
             Self::MouseDown(..) => EventCategory::Mouse,
             Self::MouseUp(..) => EventCategory::Mouse,
             Self::MouseIn(..) => EventCategory::Mouse,
             Self::MouseOut(..) => EventCategory::Mouse,
+            Self::MouseEnter(..) => EventCategory::Mouse,
+            Self::MouseLeave(..) => EventCategory::Mouse,
             Self::Scroll(..) => EventCategory::Mouse,
+            Self::DragStart(..) => EventCategory::Mouse,
+            Self::Drag(..) => EventCategory::Mouse,
+            Self::DragEnd(..) => EventCategory::Mouse,
+            Self::DragEnter(..) => EventCategory::Mouse,
+            Self::DragOver(..) => EventCategory::Mouse,
+            Self::DragLeave(..) => EventCategory::Mouse,
+            Self::Drop(..) => EventCategory::Mouse,
             // Keyboard
             Self::CharInput { .. } => EventCategory::Keyboard,
+            Self::ImePreedit { .. } => EventCategory::Keyboard,
+            Self::ImeCommit { .. } => EventCategory::Keyboard,
+            Self::Cancel => EventCategory::Keyboard,
             Self::KeyUp(..) => EventCategory::Keyboard,
             Self::KeyDown(..) => EventCategory::Keyboard,
             // Focus
@@ -207,4 +297,31 @@ impl EventType {
             Self::Blur => EventCategory::Focus,
         }
     }
+
+    /// Returns the drag data of this event, if it's a drag event
+    pub fn drag_event(&self) -> Option<&DragEvent> {
+        match self {
+            Self::DragStart(event)
+            | Self::Drag(event)
+            | Self::DragEnd(event)
+            | Self::DragEnter(event)
+            | Self::DragOver(event)
+            | Self::DragLeave(event)
+            | Self::Drop(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn drag_event_mut(&mut self) -> Option<&mut DragEvent> {
+        match self {
+            Self::DragStart(event)
+            | Self::Drag(event)
+            | Self::DragEnd(event)
+            | Self::DragEnter(event)
+            | Self::DragOver(event)
+            | Self::DragLeave(event)
+            | Self::Drop(event) => Some(event),
+            _ => None,
+        }
+    }
 }
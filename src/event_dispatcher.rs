@@ -1,11 +1,16 @@
+use std::{cmp::Reverse, time::Duration};
+
 use bevy::{
     prelude::{Component, Entity, KeyCode, Resource, World},
     utils::{HashMap, HashSet},
 };
+use instant::Instant;
 
 use crate::{
     context::KayakRootContext,
     cursor::{CursorEvent, PointerEvents, ScrollEvent, ScrollUnit},
+    disabled::Disabled,
+    drag::{DragEvent, DragPayload, Draggable, DropTarget},
     event::{EventType, KEvent},
     focus_tree::FocusTree,
     input_event::{InputEvent, InputEventCategory},
@@ -14,7 +19,8 @@ use crate::{
     node::{Node, WrappedIndex},
     on_event::OnEvent,
     prelude::KayakWidgetContext,
-    styles::{ComputedStyles, KStyle, RenderCommand},
+    styles::{ComputedStyles, KStyle, KVisibility, RenderCommand},
+    tree::Tree,
     Focusable,
 };
 
@@ -43,7 +49,23 @@ impl Default for EventState {
     }
 }
 
-#[derive(Component, Debug, Clone, Default)]
+/// The default maximum time between two clicks for them to count as a double-click
+const DEFAULT_DOUBLE_CLICK_THRESHOLD: Duration = Duration::from_millis(500);
+/// The default maximum distance (in pixels) between two clicks for them to count as a double-click
+const DEFAULT_DOUBLE_CLICK_TOLERANCE: f32 = 4.0;
+/// The default distance (in pixels) the cursor must move while pressed before a drag starts
+const DEFAULT_DRAG_THRESHOLD: f32 = 4.0;
+
+/// The last click registered by the dispatcher (used to count successive clicks)
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    node: WrappedIndex,
+    time: Instant,
+    position: (f32, f32),
+    count: u32,
+}
+
+#[derive(Component, Debug, Clone)]
 pub struct EventDispatcher {
     is_mouse_pressed: bool,
     next_mouse_pressed: bool,
@@ -57,6 +79,33 @@ pub struct EventDispatcher {
     has_cursor: Option<WrappedIndex>,
     pub(crate) cursor_capture: Option<WrappedIndex>,
     pub(crate) hovered: Option<WrappedIndex>,
+    /// Every node under the cursor (along with its depth) as of the last mouse move
+    hovered_nodes: Vec<TreeNode>,
+    last_click: Option<LastClick>,
+    /// Where the right mouse button was pressed (if it's currently held)
+    right_press_position: Option<(f32, f32)>,
+    /// Where the middle mouse button was pressed (if it's currently held)
+    middle_press_position: Option<(f32, f32)>,
+    double_click_threshold: Duration,
+    double_click_tolerance: f32,
+    double_click_replaces_click: bool,
+    /// The draggable widget the left mouse button was pressed on (and where it was pressed)
+    drag_press: Option<(WrappedIndex, (f32, f32))>,
+    /// The widget currently being dragged
+    drag_source: Option<WrappedIndex>,
+    /// The drop target currently under the drag
+    drop_target: Option<WrappedIndex>,
+    /// The payload attached to the current (or most recent) drag
+    drag_payload: Option<DragPayload>,
+    drag_threshold: f32,
+    /// The widget that was focused when the current IME composition started
+    ime_target: Option<WrappedIndex>,
+}
+
+impl Default for EventDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventDispatcher {
@@ -74,9 +123,50 @@ impl EventDispatcher {
             has_cursor: None,
             cursor_capture: None,
             hovered: None,
+            hovered_nodes: Vec::new(),
+            last_click: None,
+            right_press_position: None,
+            middle_press_position: None,
+            double_click_threshold: DEFAULT_DOUBLE_CLICK_THRESHOLD,
+            double_click_tolerance: DEFAULT_DOUBLE_CLICK_TOLERANCE,
+            double_click_replaces_click: false,
+            drag_press: None,
+            drag_source: None,
+            drop_target: None,
+            drag_payload: None,
+            drag_threshold: DEFAULT_DRAG_THRESHOLD,
+            ime_target: None,
         }
     }
 
+    /// Sets the maximum time allowed between two clicks for them to count as a double-click
+    pub fn set_double_click_threshold(&mut self, threshold: Duration) {
+        self.double_click_threshold = threshold;
+    }
+
+    /// Sets the maximum distance (in pixels) allowed between two clicks for them to count as a double-click
+    pub fn set_double_click_tolerance(&mut self, tolerance: f32) {
+        self.double_click_tolerance = tolerance;
+    }
+
+    /// If true, a [`DoubleClick`](EventType::DoubleClick) event is sent _instead of_ the second
+    /// [`Click`](EventType::Click) event rather than in addition to it
+    pub fn set_double_click_replaces_click(&mut self, replaces_click: bool) {
+        self.double_click_replaces_click = replaces_click;
+    }
+
+    /// Sets the distance (in pixels) the cursor must move while pressed on a
+    /// [`Draggable`] widget before a drag starts
+    pub fn set_drag_threshold(&mut self, threshold: f32) {
+        self.drag_threshold = threshold;
+    }
+
+    /// The widget currently being dragged
+    #[allow(dead_code)]
+    pub fn drag_source(&self) -> Option<WrappedIndex> {
+        self.drag_source
+    }
+
     /// Returns whether the mouse is currently pressed or not
     #[allow(dead_code)]
     pub fn is_mouse_pressed(&self) -> bool {
@@ -160,7 +250,7 @@ impl EventDispatcher {
         self.hovered
     }
 
-    /// Process and dispatch an [InputEvent](crate::InputEvent)
+    // /// Process and dispatch an [InputEvent](crate::InputEvent)
     // #[allow(dead_code)]
     // pub fn process_event(
     //     &mut self,
@@ -206,6 +296,13 @@ impl EventDispatcher {
         // === Dispatch Events === //
         let mut next_events = HashMap::default();
         for mut event in events {
+            // The payload is attached while handling `DragStart`, after the drag events were created
+            if let Some(drag_event) = event.event_type.drag_event_mut() {
+                if drag_event.payload.is_none() {
+                    drag_event.payload = self.drag_payload.clone();
+                }
+            }
+
             let mut current_target: Option<WrappedIndex> = Some(WrappedIndex(event.target));
             while let Some(index) = current_target {
                 // Create a copy of the event, specific for this node
@@ -224,6 +321,7 @@ impl EventDispatcher {
                     if let Some(mut on_event) = entity.take::<OnEvent>() {
                         let mut event_dispatcher_context = EventDispatcherContext {
                             cursor_capture: self.cursor_capture,
+                            drag_payload: None,
                         };
                         (event_dispatcher_context, node_event) = on_event.try_call(
                             event_dispatcher_context,
@@ -248,6 +346,7 @@ impl EventDispatcher {
                             None,
                             context.unique_ids.clone(),
                             context.unique_ids_parents.clone(),
+                            context.cloned_widget_entities.clone(),
                         );
                         node_event.run_on_change(world, widget_context);
                     }
@@ -342,6 +441,17 @@ impl EventDispatcher {
                     self.next_mouse_position = *point;
                 }
 
+                // Use the same cursor position the left button resolves clicks against
+                match input_event {
+                    InputEvent::MouseRightPress => {
+                        self.right_press_position = Some(self.current_mouse_position)
+                    }
+                    InputEvent::MouseMiddlePress => {
+                        self.middle_press_position = Some(self.current_mouse_position)
+                    }
+                    _ => {}
+                }
+
                 if matches!(input_event, InputEvent::MouseLeftPress) {
                     // Reset next global mouse pressed
                     self.next_mouse_pressed = true;
@@ -356,100 +466,142 @@ impl EventDispatcher {
             }
 
             // === Mouse Events === //
+            // Disabled widgets can't hold on to the cursor
+            if self
+                .cursor_capture
+                .is_some_and(|captor| world.get::<Disabled>(captor.0).is_some())
+            {
+                self.cursor_capture = None;
+            }
             if let Some(captor) = self.cursor_capture {
                 // A widget has been set to capture pointer events -> it should be the only one receiving events
                 for input_event in input_events {
                     // --- Process Event --- //
                     if matches!(input_event.category(), InputEventCategory::Mouse) {
                         // A widget's PointerEvents style will determine how it and its children are processed
-                        let pointer_events = Self::resolve_pointer_events(captor, world);
-
-                        match pointer_events {
-                            PointerEvents::All | PointerEvents::SelfOnly => {
-                                let events = self.process_pointer_events(
-                                    input_event,
-                                    (captor, 0),
-                                    &mut states,
-                                    world,
-                                    context,
-                                    true,
-                                );
-                                event_stream.extend(events);
-                            }
-                            _ => {}
+                        if Self::resolve_pointer_events(captor, world).includes_self() {
+                            let events = self.process_pointer_events(
+                                input_event,
+                                (captor, 0),
+                                &mut states,
+                                world,
+                                context,
+                                true,
+                            );
+                            event_stream.extend(events);
                         }
                     }
                 }
             } else {
                 // No capturing widget -> process cursor events as normal
-                let mut stack: Vec<TreeNode> = vec![(root, 0)];
-                while let Some((current, depth)) = stack.pop() {
-                    let mut enter_children = true;
-
-                    if let Some(entity_ref) = world.get_entity(current.0) {
-                        if entity_ref.contains::<OnEvent>() {
-                            for input_event in input_events {
-                                // --- Process Event --- //
-                                if matches!(input_event.category(), InputEventCategory::Mouse) {
-                                    // A widget's PointerEvents style will determine how it and its children are processed
-                                    let pointer_events =
-                                        Self::resolve_pointer_events(current, world);
-
-                                    match pointer_events {
-                                        PointerEvents::All | PointerEvents::SelfOnly => {
-                                            let events = self.process_pointer_events(
-                                                input_event,
-                                                (current, depth),
-                                                &mut states,
-                                                world,
-                                                context,
-                                                false,
-                                            );
-                                            event_stream.extend(events);
-
-                                            if matches!(pointer_events, PointerEvents::SelfOnly) {
-                                                enter_children = false;
-                                            }
-                                        }
-                                        PointerEvents::None => enter_children = false,
-                                        PointerEvents::ChildrenOnly => {}
-                                    }
-                                }
+                let mouse_moved = input_events
+                    .iter()
+                    .any(|input_event| matches!(input_event, InputEvent::MouseMoved(..)));
+                let mut hovered_nodes = Vec::new();
+
+                for ((current, depth), pointer_events) in
+                    Self::pointer_targets(&node_tree, root, world, true)
+                {
+                    if mouse_moved {
+                        if let Some(layout) = context.get_layout(&current) {
+                            if layout.contains(&self.next_mouse_position) {
+                                hovered_nodes.push((current, depth));
                             }
                         }
                     }
-                    // --- Push Children to Stack --- //
-                    if enter_children {
-                        if let Some(children) = node_tree.children.get(&current) {
-                            let mut stack_children = Vec::new();
-                            for child in children {
-                                let child_z = world
-                                    .get_entity(child.0)
-                                    .map(|e| e.get::<Node>().map(|node| node.z).unwrap_or(0.0))
-                                    .unwrap_or(0.0);
-                                stack_children.push((child_z, (*child, depth + 1)));
-                            }
-                            stack_children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-                            stack.extend(stack_children.iter().map(|c| c.1));
+
+                    // Pass-through widgets get events of their own rather than competing with
+                    // (and blocking) the widgets beneath them
+                    let pass_through = pointer_events == PointerEvents::PassThrough;
+                    let mut own_states = HashMap::new();
+                    for input_event in input_events {
+                        // --- Process Event --- //
+                        if matches!(input_event.category(), InputEventCategory::Mouse) {
+                            let events = self.process_pointer_events(
+                                input_event,
+                                (current, depth),
+                                if pass_through {
+                                    &mut own_states
+                                } else {
+                                    &mut states
+                                },
+                                world,
+                                context,
+                                false,
+                            );
+                            event_stream.extend(events);
                         }
                     }
+                    event_stream.extend(
+                        own_states
+                            .into_keys()
+                            .filter(|event_type| !matches!(event_type, EventType::Focus))
+                            .map(|event_type| KEvent::new(current.0, event_type)),
+                    );
+                }
+
+                if mouse_moved {
+                    event_stream.extend(self.diff_hovered_nodes(hovered_nodes));
                 }
             }
 
             // === Keyboard Events === //
             for input_event in input_events {
+                if let InputEvent::Keyboard { key, is_pressed } = input_event {
+                    // Modifiers are tracked even when nothing is focused so shortcuts always see them
+                    self.update_keyboard_modifiers(*key, *is_pressed);
+
+                    // Shortcuts take precedence over the focused widget
+                    if *is_pressed {
+                        if let Some(shortcut) =
+                            context.shortcuts.find(*key, self.keyboard_modifiers)
+                        {
+                            let (handler, consume) = (shortcut.handler.clone(), shortcut.consume);
+                            handler.try_call(context.camera_entity, (), world);
+                            if consume {
+                                continue;
+                            }
+                        }
+                    }
+                }
+
                 // Keyboard events only care about the currently focused widget so we don't need to run this over every node in the tree
                 let events =
                     self.process_keyboard_events(input_event, &mut states, &context.focus_tree);
                 event_stream.extend(events);
             }
 
+            // === Gamepad Events === //
+            for input_event in input_events {
+                if matches!(input_event.category(), InputEventCategory::Gamepad) {
+                    event_stream.extend(self.process_gamepad_events(input_event, context));
+                }
+            }
+
             // === Additional Events === //
             let mut had_focus_event = false;
+            let mut pressed_node = None;
 
             // These events are ones that require a specific target and need the tree to be evaluated before selecting the best match
             for (event_type, state) in states {
                 if let Some(node) = state.best_match {
+                    if let EventType::Click(mut cursor_event) = event_type {
+                        if self.drag_source.is_some() {
+                            // Releasing a drag isn't a click
+                            continue;
+                        }
+                        cursor_event.click_count = self.register_click(node, cursor_event.position);
+                        let is_double_click = cursor_event.click_count == 2;
+                        if is_double_click {
+                            event_stream
+                                .push(KEvent::new(node.0, EventType::DoubleClick(cursor_event)));
+                        }
+                        if !is_double_click || !self.double_click_replaces_click {
+                            event_stream.push(KEvent::new(node.0, EventType::Click(cursor_event)));
+                        }
+                        continue;
+                    }
+
                     event_stream.push(KEvent::new(node.0, event_type.clone()));
 
                     match event_type {
@@ -468,6 +620,9 @@ impl EventDispatcher {
                         EventType::Hover(..) => {
                             self.hovered = Some(node);
                         }
+                        EventType::MouseDown(..) => {
+                            pressed_node = Some(node);
+                        }
                         _ => {}
                     }
                 }
@@ -482,7 +637,18 @@ impl EventDispatcher {
                 }
             }
 
+            // === Drag Events === //
+            let events = self.process_drag_events(input_events, pressed_node, &node_tree, world);
+            event_stream.extend(events);
+
             // === Process Cursor States === //
+            for input_event in input_events {
+                match input_event {
+                    InputEvent::MouseRightRelease => self.right_press_position = None,
+                    InputEvent::MouseMiddleRelease => self.middle_press_position = None,
+                    _ => {}
+                }
+            }
             self.current_mouse_position = self.next_mouse_position;
             self.is_mouse_pressed = self.next_mouse_pressed;
 
@@ -639,7 +805,35 @@ impl EventDispatcher {
                     }
                 }
             }
-            InputEvent::Scroll { dx, dy, is_line } => {
+            InputEvent::MouseRightRelease | InputEvent::MouseMiddleRelease => {
+                let press_position = if matches!(input_event, InputEvent::MouseRightRelease) {
+                    self.right_press_position
+                } else {
+                    self.middle_press_position
+                };
+                if let (Some(layout), Some(press_position)) =
+                    (context.get_layout(&node), press_position)
+                {
+                    // Like a left click, the press and release must both happen over the widget
+                    if ignore_layout
+                        || (layout.contains(&self.current_mouse_position)
+                            && layout.contains(&press_position))
+                    {
+                        let cursor_event = self.get_cursor_event(self.current_mouse_position);
+                        let event_type = if matches!(input_event, InputEvent::MouseRightRelease) {
+                            EventType::RightClick(cursor_event)
+                        } else {
+                            EventType::MiddleClick(cursor_event)
+                        };
+                        Self::update_state(states, (node, depth), &layout, event_type);
+                    }
+                }
+            }
+            InputEvent::Scroll {
+                delta_x,
+                delta_y,
+                is_line_based,
+            } => {
                 if let Some(layout) = context.get_layout(&node) {
                     // Check for scroll eligibility
                     if ignore_layout || layout.contains(&self.current_mouse_position) {
@@ -648,10 +842,16 @@ impl EventDispatcher {
                             (node, depth),
                             &layout,
                             EventType::Scroll(ScrollEvent {
-                                delta: if *is_line {
-                                    ScrollUnit::Line { x: *dx, y: *dy }
+                                delta: if *is_line_based {
+                                    ScrollUnit::Line {
+                                        x: *delta_x,
+                                        y: *delta_y,
+                                    }
                                 } else {
-                                    ScrollUnit::Pixel { x: *dx, y: *dy }
+                                    ScrollUnit::Pixel {
+                                        x: *delta_x,
+                                        y: *delta_y,
+                                    }
                                 },
                             }),
                         );
@@ -664,7 +864,288 @@ impl EventDispatcher {
         event_stream
     }
 
-    fn resolve_pointer_events(index: WrappedIndex, world: &mut World) -> PointerEvents {
+    /// Generates the drag lifecycle events for a set of input events
+    ///
+    /// # Arguments
+    ///
+    /// * `input_events`: The input events
+    /// * `pressed_node`: The node the left mouse button was pressed on (if it was pressed)
+    /// * `node_tree`: The widget tree
+    /// * `world`: The world
+    ///
+    /// returns: Vec<KEvent>
+    fn process_drag_events(
+        &mut self,
+        input_events: &[InputEvent],
+        pressed_node: Option<WrappedIndex>,
+        node_tree: &Tree,
+        world: &World,
+    ) -> Vec<KEvent> {
+        let mut event_stream = Vec::<KEvent>::new();
+
+        if input_events.contains(&InputEvent::MouseLeftPress) {
+            // A press on a draggable widget (or one of its descendants) may become a drag
+            self.drag_press = None;
+            let mut current = pressed_node;
+            while let Some(index) = current {
+                if world.get::<Draggable>(index.0).is_some() {
+                    self.drag_press = Some((index, self.current_mouse_position));
+                    break;
+                }
+                current = node_tree.get_parent(index);
+            }
+        }
+
+        let mouse_moved = input_events
+            .iter()
+            .any(|input_event| matches!(input_event, InputEvent::MouseMoved(..)));
+        if mouse_moved && self.next_mouse_pressed {
+            let position = self.next_mouse_position;
+            if let Some(source) = self.drag_source {
+                let delta = (
+                    position.0 - self.current_mouse_position.0,
+                    position.1 - self.current_mouse_position.1,
+                );
+                event_stream.push(KEvent::new(
+                    source.0,
+                    EventType::Drag(self.get_drag_event(source, delta)),
+                ));
+            } else if let Some((source, press_position)) = self.drag_press {
+                let delta = (position.0 - press_position.0, position.1 - press_position.1);
+                if delta.0.hypot(delta.1) > self.drag_threshold {
+                    self.drag_source = Some(source);
+                    self.drag_payload = None;
+                    event_stream.push(KEvent::new(
+                        source.0,
+                        EventType::DragStart(self.get_drag_event(source, delta)),
+                    ));
+                }
+            }
+
+            if let Some(source) = self.drag_source {
+                let drop_target = self.find_drop_target(source, world);
+                if drop_target != self.drop_target {
+                    if let Some(old_target) = self.drop_target {
+                        event_stream.push(KEvent::new(
+                            old_target.0,
+                            EventType::DragLeave(self.get_drag_event(source, (0.0, 0.0))),
+                        ));
+                    }
+                    if let Some(new_target) = drop_target {
+                        event_stream.push(KEvent::new(
+                            new_target.0,
+                            EventType::DragEnter(self.get_drag_event(source, (0.0, 0.0))),
+                        ));
+                    }
+                    self.drop_target = drop_target;
+                }
+
+                if let Some(target) = drop_target {
+                    event_stream.push(KEvent::new(
+                        target.0,
+                        EventType::DragOver(self.get_drag_event(source, (0.0, 0.0))),
+                    ));
+                }
+            }
+        }
+
+        if input_events.contains(&InputEvent::MouseLeftRelease) {
+            self.drag_press = None;
+            if let Some(source) = self.drag_source.take() {
+                if let Some(target) = self.drop_target.take() {
+                    event_stream.push(KEvent::new(
+                        target.0,
+                        EventType::Drop(self.get_drag_event(source, (0.0, 0.0))),
+                    ));
+                }
+                event_stream.push(KEvent::new(
+                    source.0,
+                    EventType::DragEnd(self.get_drag_event(source, (0.0, 0.0))),
+                ));
+            }
+        }
+
+        event_stream
+    }
+
+    /// Finds the innermost drop target under the cursor, ignoring the dragged widget itself
+    fn find_drop_target(&self, source: WrappedIndex, world: &World) -> Option<WrappedIndex> {
+        self.hovered_nodes
+            .iter()
+            .filter(|(node, _)| *node != source && world.get::<DropTarget>(node.0).is_some())
+            .max_by_key(|(_, depth)| *depth)
+            .map(|(node, _)| *node)
+    }
+
+    fn get_drag_event(&self, source: WrappedIndex, delta: (f32, f32)) -> DragEvent {
+        DragEvent {
+            source: source.0,
+            position: self.next_mouse_position,
+            delta,
+            payload: self.drag_payload.clone(),
+        }
+    }
+
+    /// Replaces the set of hovered nodes, generating enter/leave events for any that changed
+    ///
+    /// Leave events are ordered innermost first, followed by enter events ordered outermost first.
+    fn diff_hovered_nodes(&mut self, hovered_nodes: Vec<TreeNode>) -> Vec<KEvent> {
+        let cursor_event = self.get_cursor_event(self.next_mouse_position);
+
+        let mut left = self
+            .hovered_nodes
+            .iter()
+            .filter(|(node, _)| !hovered_nodes.iter().any(|(other, _)| other == node))
+            .copied()
+            .collect::<Vec<_>>();
+        left.sort_by_key(|(_, depth)| Reverse(*depth));
+
+        let mut entered = hovered_nodes
+            .iter()
+            .filter(|(node, _)| !self.hovered_nodes.iter().any(|(other, _)| other == node))
+            .copied()
+            .collect::<Vec<_>>();
+        entered.sort_by_key(|(_, depth)| *depth);
+
+        self.hovered_nodes = hovered_nodes;
+
+        left.into_iter()
+            .map(|(node, _)| KEvent::new(node.0, EventType::MouseLeave(cursor_event)))
+            .chain(
+                entered
+                    .into_iter()
+                    .map(|(node, _)| KEvent::new(node.0, EventType::MouseEnter(cursor_event))),
+            )
+            .collect()
+    }
+
+    /// Finds the frontmost widget under the cursor, whose `cursor` style should be shown
+    ///
+    /// Unlike the [hovered](Self::hovered) widget, this doesn't need to handle any events. Widgets
+    /// that don't receive pointer events are skipped. While a widget has captured the cursor, the
+    /// cursor of that widget is shown wherever the cursor is.
+    pub(crate) fn cursor_target(
+        &self,
+        context: &KayakRootContext,
+        world: &World,
+    ) -> Option<WrappedIndex> {
+        if let Some(captor) = self.cursor_capture {
+            return Some(captor);
+        }
+
+        Self::widget_at(context, world, self.current_mouse_position)
+    }
+
+    /// Finds the topmost widget at the given point that receives pointer events
+    ///
+    /// Widgets are hit-tested like they are when dispatching events, except that widgets without
+    /// an [`OnEvent`] are included too. Parts of widgets cut off by a clipping ancestor can't be
+    /// hit.
+    pub(crate) fn widget_at(
+        context: &KayakRootContext,
+        world: &World,
+        point: (f32, f32),
+    ) -> Option<WrappedIndex> {
+        let node_tree = context.tree.try_read().ok()?;
+        let root = node_tree.root_node?;
+        let mut states = HashMap::new();
+        for (node, _) in Self::pointer_targets(&node_tree, root, world, false) {
+            if let Some(layout) = context.get_layout(&node.0) {
+                if layout.contains(&point)
+                    && !Self::is_clipped(&node_tree, node.0, point, context, world)
+                {
+                    Self::update_state(
+                        &mut states,
+                        node,
+                        &layout,
+                        EventType::Hover(Default::default()),
+                    );
+                }
+            }
+        }
+
+        states
+            .remove(&EventType::Hover(Default::default()))
+            .and_then(|state| state.best_match)
+    }
+
+    /// Walks the widget tree from the given root, returning the widgets that receive pointer
+    /// events along with their [`PointerEvents`]
+    ///
+    /// Widgets are returned topmost first. If `handlers_only` is set, only widgets with an
+    /// [`OnEvent`] are returned, but the `pointer_events` style of every widget still applies to
+    /// its children. Hidden and disabled widgets are skipped along with their children, so events
+    /// fall through to whatever is beneath them.
+    fn pointer_targets(
+        node_tree: &Tree,
+        root: WrappedIndex,
+        world: &World,
+        handlers_only: bool,
+    ) -> Vec<(TreeNode, PointerEvents)> {
+        let mut targets = Vec::new();
+        let mut stack: Vec<TreeNode> = vec![(root, 0)];
+        while let Some((current, depth)) = stack.pop() {
+            // Hidden widgets (and their children) can't be interacted with
+            let pointer_events = if Self::resolve_visibility(current, world) == KVisibility::Hidden
+            {
+                PointerEvents::None
+            } else {
+                Self::resolve_pointer_events(current, world)
+            };
+
+            if pointer_events.includes_self()
+                && (!handlers_only || world.get::<OnEvent>(current.0).is_some())
+            {
+                targets.push(((current, depth), pointer_events));
+            }
+
+            // --- Push Children to Stack --- //
+            if pointer_events.includes_children() {
+                if let Some(children) = node_tree.children.get(&current) {
+                    let mut stack_children = Vec::new();
+                    for child in children {
+                        let child_z = world.get::<Node>(child.0).map(|node| node.z).unwrap_or(0.0);
+                        stack_children.push((child_z, (*child, depth + 1)));
+                    }
+                    stack_children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                    stack.extend(stack_children.iter().map(|c| c.1));
+                }
+            }
+        }
+
+        targets
+    }
+
+    /// Checks if the point lies outside the clip of any of the widget's ancestors
+    fn is_clipped(
+        node_tree: &Tree,
+        index: WrappedIndex,
+        point: (f32, f32),
+        context: &KayakRootContext,
+        world: &World,
+    ) -> bool {
+        let mut current = node_tree.get_parent(index);
+        while let Some(parent) = current {
+            let is_clip = world
+                .get::<ComputedStyles>(parent.0)
+                .is_some_and(|styles| styles.0.render_command.resolve() == RenderCommand::Clip);
+            if is_clip
+                && context
+                    .get_layout(&parent)
+                    .is_some_and(|layout| !layout.contains(&point))
+            {
+                return true;
+            }
+            current = node_tree.get_parent(parent);
+        }
+
+        false
+    }
+
+    fn resolve_pointer_events(index: WrappedIndex, world: &World) -> PointerEvents {
+        if world.get::<Disabled>(index.0).is_some() {
+            return PointerEvents::None;
+        }
         let mut pointer_events = PointerEvents::default();
         if let Some(styles) = world.get::<ComputedStyles>(index.0) {
             pointer_events = styles.0.pointer_events.resolve();
@@ -672,6 +1153,37 @@ impl EventDispatcher {
         pointer_events
     }
 
+    fn resolve_visibility(index: WrappedIndex, world: &World) -> KVisibility {
+        let mut visibility = KVisibility::default();
+        if let Some(styles) = world.get::<ComputedStyles>(index.0) {
+            visibility = styles.0.visibility.resolve();
+        }
+        visibility
+    }
+
+    /// Registers a click on the given node, returning the number of successive clicks on it
+    fn register_click(&mut self, node: WrappedIndex, position: (f32, f32)) -> u32 {
+        let now = Instant::now();
+        let count = match self.last_click {
+            Some(last)
+                if last.node == node
+                    && now.duration_since(last.time) <= self.double_click_threshold
+                    && (last.position.0 - position.0).hypot(last.position.1 - position.1)
+                        <= self.double_click_tolerance =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some(LastClick {
+            node,
+            time: now,
+            position,
+            count,
+        });
+        count
+    }
+
     fn get_cursor_event(&self, position: (f32, f32)) -> CursorEvent {
         let change = self.next_mouse_pressed != self.is_mouse_pressed;
         let pressed = self.next_mouse_pressed;
@@ -680,6 +1192,26 @@ impl EventDispatcher {
             pressed,
             just_pressed: change && pressed,
             just_released: change && !pressed,
+            click_count: 0,
+        }
+    }
+
+    /// Keeps track of the held modifiers as modifier keys are pressed and released
+    fn update_keyboard_modifiers(&mut self, key: KeyCode, is_pressed: bool) {
+        match key {
+            KeyCode::ControlLeft | KeyCode::ControlRight => {
+                self.keyboard_modifiers.is_ctrl_pressed = is_pressed
+            }
+            KeyCode::ShiftLeft | KeyCode::ShiftRight => {
+                self.keyboard_modifiers.is_shift_pressed = is_pressed
+            }
+            KeyCode::AltLeft | KeyCode::AltRight => {
+                self.keyboard_modifiers.is_alt_pressed = is_pressed
+            }
+            KeyCode::SuperLeft | KeyCode::SuperRight => {
+                self.keyboard_modifiers.is_meta_pressed = is_pressed
+            }
+            _ => {}
         }
     }
 
@@ -690,6 +1222,20 @@ impl EventDispatcher {
         focus_tree: &FocusTree,
     ) -> Vec<KEvent> {
         let mut event_stream = Vec::new();
+        if focus_tree.current().is_none() {
+            // Nothing is focused yet -> Tab focuses the first focusable
+            if let InputEvent::Keyboard {
+                key: KeyCode::Tab,
+                is_pressed: true,
+            } = input_event
+            {
+                if let Some(index) = focus_tree.next() {
+                    event_stream.push(KEvent::new(index, EventType::Focus));
+                }
+            }
+            return event_stream;
+        }
+
         if let Some(current_focus) = focus_tree.current() {
             match input_event {
                 InputEvent::CharEvent { c } => event_stream.push(KEvent::new(
@@ -697,24 +1243,6 @@ impl EventDispatcher {
                     EventType::CharInput { c: c.clone() },
                 )),
                 InputEvent::Keyboard { key, is_pressed } => {
-                    // === Modifers === //
-                    match key {
-                        KeyCode::ControlLeft | KeyCode::ControlRight => {
-                            self.keyboard_modifiers.is_ctrl_pressed = *is_pressed
-                        }
-                        KeyCode::ShiftLeft | KeyCode::ShiftRight => {
-                            self.keyboard_modifiers.is_shift_pressed = *is_pressed
-                        }
-                        KeyCode::AltLeft | KeyCode::AltRight => {
-                            self.keyboard_modifiers.is_alt_pressed = *is_pressed
-                        }
-                        KeyCode::SuperLeft | KeyCode::SuperRight => {
-                            self.keyboard_modifiers.is_meta_pressed = *is_pressed
-                        }
-                        _ => {}
-                    }
-
-                    // === Event === //
                     if *is_pressed {
                         event_stream.push(KEvent::new(
                             current_focus,
@@ -727,6 +1255,37 @@ impl EventDispatcher {
                         ))
                     }
                 }
+                InputEvent::ImePreedit { text, cursor } => {
+                    // A composition started in a widget that has since lost focus is cancelled:
+                    // its updates are ignored until it ends rather than shown in the focused widget
+                    let focus = WrappedIndex(current_focus);
+                    let is_cancelled = self.ime_target.is_some_and(|target| target != focus);
+                    if text.is_empty() {
+                        self.ime_target = None;
+                    } else if !is_cancelled {
+                        self.ime_target = Some(focus);
+                    }
+
+                    if text.is_empty() || !is_cancelled {
+                        event_stream.push(KEvent::new(
+                            current_focus,
+                            EventType::ImePreedit {
+                                text: text.clone(),
+                                cursor: *cursor,
+                            },
+                        ))
+                    }
+                }
+                InputEvent::ImeCommit(text) => {
+                    // The same goes for committing a cancelled composition
+                    let target = self.ime_target.take();
+                    if target.is_none() || target == Some(WrappedIndex(current_focus)) {
+                        event_stream.push(KEvent::new(
+                            current_focus,
+                            EventType::ImeCommit { text: text.clone() },
+                        ))
+                    }
+                }
                 _ => {}
             }
         }
@@ -734,6 +1293,58 @@ impl EventDispatcher {
         event_stream
     }
 
+    fn process_gamepad_events(
+        &self,
+        input_event: &InputEvent,
+        context: &KayakRootContext,
+    ) -> Vec<KEvent> {
+        let mut event_stream = Vec::new();
+        let focus_tree = &context.focus_tree;
+        let current_focus = if let Some(current_focus) = focus_tree.current() {
+            current_focus
+        } else {
+            return event_stream;
+        };
+
+        match input_event {
+            InputEvent::GamepadNavigate(direction) => {
+                let next = focus_tree.find_in_direction(*direction, |index| {
+                    context.get_layout(&WrappedIndex(index))
+                });
+                if let Some(next) = next {
+                    event_stream.push(KEvent::new(next, EventType::Focus));
+                    event_stream.push(KEvent::new(current_focus, EventType::Blur));
+                    focus_tree.focus(next);
+                }
+            }
+            InputEvent::GamepadActivate => {
+                // Click the center of the focused widget
+                let layout = context
+                    .get_layout(&WrappedIndex(current_focus))
+                    .unwrap_or_default();
+                event_stream.push(KEvent::new(
+                    current_focus,
+                    EventType::Click(CursorEvent {
+                        pressed: false,
+                        just_pressed: false,
+                        just_released: true,
+                        position: (
+                            layout.posx + layout.width / 2.0,
+                            layout.posy + layout.height / 2.0,
+                        ),
+                        click_count: 1,
+                    }),
+                ));
+            }
+            InputEvent::GamepadCancel => {
+                event_stream.push(KEvent::new(current_focus, EventType::Cancel));
+            }
+            _ => {}
+        }
+
+        event_stream
+    }
+
     /// Updates the state data for the given event
     fn update_state(
         states: &mut HashMap<EventType, EventState>,
@@ -811,12 +1422,10 @@ impl EventDispatcher {
                     (index, current_focus)
                 };
 
-                if let Some(index) = index {
+                if let Some(index) = index.filter(|index| Some(*index) != current_focus) {
                     let mut events = vec![KEvent::new(index, EventType::Focus)];
                     if let Some(current_focus) = current_focus {
-                        if current_focus != index {
-                            events.push(KEvent::new(current_focus, EventType::Blur));
-                        }
+                        events.push(KEvent::new(current_focus, EventType::Blur));
                     }
                     context.focus_tree.focus(index);
                     self.dispatch_events(events, context, world);
@@ -852,6 +1461,15 @@ impl EventDispatcher {
         self.wants_cursor = from.wants_cursor;
         self.has_cursor = from.has_cursor;
         self.hovered = from.hovered;
+        self.hovered_nodes = from.hovered_nodes;
+        self.last_click = from.last_click;
+        self.right_press_position = from.right_press_position;
+        self.middle_press_position = from.middle_press_position;
+        self.drag_press = from.drag_press;
+        self.drag_source = from.drag_source;
+        self.drop_target = from.drop_target;
+        self.drag_payload = from.drag_payload;
+        self.ime_target = from.ime_target;
 
         // Do not include:
         // self.cursor_capture = from.cursor_capture;
@@ -861,6 +1479,7 @@ impl EventDispatcher {
 #[derive(Resource, Default)]
 pub struct EventDispatcherContext {
     cursor_capture: Option<WrappedIndex>,
+    drag_payload: Option<DragPayload>,
 }
 
 impl EventDispatcherContext {
@@ -901,7 +1520,275 @@ impl EventDispatcherContext {
         old
     }
 
+    /// Attaches a payload to the current drag
+    ///
+    /// This should be called while handling [`DragStart`](EventType::DragStart). The payload is
+    /// included in all following events of the drag, including [`Drop`](EventType::Drop).
+    pub fn set_drag_payload(&mut self, payload: impl std::any::Any + Send + Sync) {
+        self.drag_payload = Some(DragPayload::new(payload));
+    }
+
     pub(crate) fn merge(self, event_dispatcher: &mut EventDispatcher) {
         event_dispatcher.cursor_capture = self.cursor_capture;
+        if let Some(payload) = self.drag_payload {
+            event_dispatcher.drag_payload = Some(payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Entity, In, Res, ResMut, Resource, World};
+
+    use super::{EventDispatcher, EventDispatcherContext};
+    use crate::{
+        context::KayakRootContext,
+        cursor::PointerEvents,
+        disabled::Disabled,
+        drag::{Draggable, DropTarget},
+        event::{EventType, KEvent},
+        input_event::InputEvent,
+        layout::Rect,
+        node::WrappedIndex,
+        on_event::OnEvent,
+        styles::{ComputedStyles, KStyle},
+        tree::Tree,
+    };
+
+    /// Builds a button with a full-screen overlay on top of it, which has a child of its own
+    fn overlay_tree(pointer_events: PointerEvents) -> (World, Tree, [WrappedIndex; 4]) {
+        let mut world = World::new();
+        let root = WrappedIndex(world.spawn_empty().id());
+        let button = WrappedIndex(world.spawn(OnEvent::default()).id());
+        let overlay = WrappedIndex(
+            world
+                .spawn((
+                    OnEvent::default(),
+                    ComputedStyles(KStyle {
+                        pointer_events: pointer_events.into(),
+                        ..Default::default()
+                    }),
+                ))
+                .id(),
+        );
+        let overlay_child = WrappedIndex(world.spawn(OnEvent::default()).id());
+
+        let mut tree = Tree::default();
+        tree.add(root, None);
+        tree.add(button, Some(root));
+        tree.add(overlay, Some(root));
+        tree.add(overlay_child, Some(overlay));
+
+        (world, tree, [root, button, overlay, overlay_child])
+    }
+
+    fn targets(world: &World, tree: &Tree, root: WrappedIndex) -> Vec<WrappedIndex> {
+        EventDispatcher::pointer_targets(tree, root, world, true)
+            .into_iter()
+            .map(|((node, _), _)| node)
+            .collect()
+    }
+
+    #[test]
+    fn overlay_without_pointer_events_should_not_block_widgets_below() {
+        let (world, tree, [root, button, ..]) = overlay_tree(PointerEvents::None);
+        assert_eq!(vec![button], targets(&world, &tree, root));
+    }
+
+    #[test]
+    fn overlay_should_only_receive_the_events_it_allows() {
+        let (world, tree, [root, button, overlay, overlay_child]) =
+            overlay_tree(PointerEvents::All);
+        assert_eq!(
+            vec![overlay, overlay_child, button],
+            targets(&world, &tree, root)
+        );
+
+        let (world, tree, [root, button, overlay, _]) = overlay_tree(PointerEvents::SelfOnly);
+        assert_eq!(vec![overlay, button], targets(&world, &tree, root));
+
+        let (world, tree, [root, button, _, overlay_child]) =
+            overlay_tree(PointerEvents::ChildrenOnly);
+        assert_eq!(vec![overlay_child, button], targets(&world, &tree, root));
+    }
+
+    #[test]
+    fn pass_through_overlay_should_receive_events_without_blocking() {
+        let (world, tree, [root, button, overlay, overlay_child]) =
+            overlay_tree(PointerEvents::PassThrough);
+        let targets = EventDispatcher::pointer_targets(&tree, root, &world, true)
+            .into_iter()
+            .map(|((node, _), pointer_events)| (node, pointer_events))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (overlay, PointerEvents::PassThrough),
+                (overlay_child, PointerEvents::All),
+                (button, PointerEvents::All),
+            ],
+            targets
+        );
+    }
+
+    #[test]
+    fn disabled_overlay_should_not_block_widgets_below() {
+        let (mut world, tree, [root, button, overlay, _]) = overlay_tree(PointerEvents::All);
+        world.entity_mut(overlay.0).insert(Disabled);
+        assert_eq!(vec![button], targets(&world, &tree, root));
+    }
+
+    /// The drag events (and clicks) received by each widget, along with the dropped payload
+    #[derive(Resource, Default)]
+    struct DragLog {
+        events: Vec<(Entity, &'static str)>,
+        payload: Option<u32>,
+    }
+
+    fn log_drag_events() -> OnEvent {
+        OnEvent::new(
+            |In(entity): In<Entity>,
+             event: Res<KEvent>,
+             mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+             mut log: ResMut<DragLog>| {
+                let name = match &event.event_type {
+                    EventType::DragStart(..) => {
+                        event_dispatcher_context.set_drag_payload(7u32);
+                        "DragStart"
+                    }
+                    EventType::Drag(..) => "Drag",
+                    EventType::DragEnd(..) => "DragEnd",
+                    EventType::DragEnter(..) => "DragEnter",
+                    EventType::DragOver(..) => "DragOver",
+                    EventType::DragLeave(..) => "DragLeave",
+                    EventType::Drop(drag_event) => {
+                        log.payload = drag_event
+                            .payload
+                            .as_ref()
+                            .and_then(|payload| payload.get::<u32>())
+                            .copied();
+                        "Drop"
+                    }
+                    EventType::Click(..) => "Click",
+                    _ => return,
+                };
+                log.events.push((entity, name));
+            },
+        )
+    }
+
+    /// Builds a draggable widget at (0, 0) and a drop target at (200, 0), both 100x100
+    fn drag_tree() -> (World, KayakRootContext, [Entity; 2]) {
+        let mut world = World::new();
+        world.init_resource::<DragLog>();
+        let mut context = KayakRootContext::new(world.spawn_empty().id());
+        let root = world.spawn_empty().id();
+        let source = world.spawn((log_drag_events(), Draggable)).id();
+        let target = world.spawn((log_drag_events(), DropTarget)).id();
+        context.add_widget(None, root);
+        context.add_widget(Some(root), source);
+        context.add_widget(Some(root), target);
+
+        let mut cache = context.layout_cache.write().unwrap();
+        for (widget, posx) in [(source, 0.0), (target, 200.0)] {
+            let rect = Rect {
+                posx,
+                posy: 0.0,
+                width: 100.0,
+                height: 100.0,
+                z_index: None,
+            };
+            cache.rect.insert(WrappedIndex(widget), rect);
+        }
+        drop(cache);
+
+        (world, context, [source, target])
+    }
+
+    /// Processes a frame of input, returning the logged events
+    fn send(
+        event_dispatcher: &mut EventDispatcher,
+        context: &mut KayakRootContext,
+        world: &mut World,
+        input_event: InputEvent,
+    ) -> Vec<(Entity, &'static str)> {
+        event_dispatcher.process_events(&[input_event], context, world);
+        std::mem::take(&mut world.resource_mut::<DragLog>().events)
+    }
+
+    #[test]
+    fn drags_should_only_start_past_the_threshold() {
+        let (mut world, mut context, [source, _]) = drag_tree();
+        let mut dispatcher = EventDispatcher::new();
+        let mut send = |input_event| send(&mut dispatcher, &mut context, &mut world, input_event);
+
+        send(InputEvent::MouseMoved((10.0, 10.0)));
+        assert!(send(InputEvent::MouseLeftPress).is_empty());
+        assert!(send(InputEvent::MouseMoved((12.0, 10.0))).is_empty());
+        assert_eq!(vec![(source, "Click")], send(InputEvent::MouseLeftRelease));
+
+        send(InputEvent::MouseLeftPress);
+        assert_eq!(
+            vec![(source, "DragStart")],
+            send(InputEvent::MouseMoved((17.0, 10.0)))
+        );
+    }
+
+    #[test]
+    fn drags_should_visit_drop_targets_and_drop_their_payload() {
+        let (mut world, mut context, [source, target]) = drag_tree();
+        let mut dispatcher = EventDispatcher::new();
+        let mut send = |input_event| send(&mut dispatcher, &mut context, &mut world, input_event);
+
+        send(InputEvent::MouseMoved((10.0, 10.0)));
+        send(InputEvent::MouseLeftPress);
+        assert_eq!(
+            vec![(source, "DragStart")],
+            send(InputEvent::MouseMoved((30.0, 10.0)))
+        );
+        assert_eq!(
+            vec![
+                (source, "Drag"),
+                (target, "DragEnter"),
+                (target, "DragOver")
+            ],
+            send(InputEvent::MouseMoved((250.0, 50.0)))
+        );
+        assert_eq!(
+            vec![(source, "Drag"), (target, "DragOver")],
+            send(InputEvent::MouseMoved((260.0, 50.0)))
+        );
+        assert_eq!(
+            vec![(source, "Drag"), (target, "DragLeave")],
+            send(InputEvent::MouseMoved((150.0, 50.0)))
+        );
+        assert_eq!(
+            vec![
+                (source, "Drag"),
+                (target, "DragEnter"),
+                (target, "DragOver")
+            ],
+            send(InputEvent::MouseMoved((250.0, 50.0)))
+        );
+        assert_eq!(
+            vec![(target, "Drop"), (source, "DragEnd")],
+            send(InputEvent::MouseLeftRelease)
+        );
+        assert_eq!(Some(7), world.resource::<DragLog>().payload);
+    }
+
+    #[test]
+    fn releasing_a_drag_should_not_click() {
+        let (mut world, mut context, [source, _]) = drag_tree();
+        let mut dispatcher = EventDispatcher::new();
+        let mut send = |input_event| send(&mut dispatcher, &mut context, &mut world, input_event);
+
+        send(InputEvent::MouseMoved((10.0, 10.0)));
+        send(InputEvent::MouseLeftPress);
+        send(InputEvent::MouseMoved((30.0, 10.0)));
+        // The cursor is still over the widget it was pressed on
+        assert_eq!(
+            vec![(source, "DragEnd")],
+            send(InputEvent::MouseLeftRelease)
+        );
     }
 }
@@ -1,20 +1,72 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, RwLock,
+};
 
 use bevy::{
     prelude::{Component, Entity, Reflect, ReflectComponent, Resource},
     utils::HashMap,
 };
 
-use crate::{node::WrappedIndex, prelude::Tree};
+use crate::{layout::Rect, node::WrappedIndex, prelude::Tree};
 
 #[derive(Component, Reflect, Default, Clone, Copy)]
 #[reflect(Component)]
 pub struct Focusable;
 
-#[derive(Resource, Debug, Clone, Default)]
+/// Keeps tab navigation within the subtree of the widget it's placed on
+///
+/// While a trap is active, moving focus past the last focusable in the subtree wraps back to
+/// the first (and vice versa). A widget with this component is also treated as focusable and
+/// receives focus when the trap activates. Removing the component (or the widget) releases the
+/// trap and restores focus to whatever was focused before it activated.
+///
+/// Traps can be nested, in which case the innermost (most recently activated) trap wins. Once it
+/// is released, the trap around it becomes active again.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct FocusTrap;
+
+/// Focuses the widget when it's first mounted
+///
+/// If several widgets with this component are mounted during the same update, the first one in
+/// tree order is focused. Re-rendering the widget doesn't move focus back to it, only mounting it
+/// again (after it was unmounted) does. Disabled widgets are never focused.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct AutoFocus;
+
+/// A direction in which focus can be moved based on where widgets are on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Resource, Debug, Clone)]
 pub struct FocusTree {
     tree: Arc<RwLock<Tree>>,
     current_focus: Arc<RwLock<Option<WrappedIndex>>>,
+    /// The stack of active traps along with the focus to restore when they're released
+    traps: Arc<RwLock<Vec<(WrappedIndex, Option<WrappedIndex>)>>>,
+    /// Whether navigation wraps around after reaching either end of the tree
+    wrap: Arc<AtomicBool>,
+    /// The first [`AutoFocus`] widget mounted since the widgets were last updated
+    auto_focus: Arc<RwLock<Option<WrappedIndex>>>,
+}
+
+impl Default for FocusTree {
+    fn default() -> Self {
+        Self {
+            tree: Default::default(),
+            current_focus: Default::default(),
+            traps: Default::default(),
+            wrap: Arc::new(AtomicBool::new(true)),
+            auto_focus: Default::default(),
+        }
+    }
 }
 
 /// A struct used to track and calculate widget focusability, based on the following rule:
@@ -87,6 +139,9 @@ impl FocusTree {
             *tree = Tree::default();
             self.blur();
         }
+        if let Ok(mut traps) = self.traps.try_write() {
+            traps.clear();
+        }
     }
 
     /// Set the current focus
@@ -96,6 +151,22 @@ impl FocusTree {
         }
     }
 
+    /// Request focus for a mounted [`AutoFocus`] widget, unless another one already did
+    pub(crate) fn request_auto_focus(&self, index: WrappedIndex) {
+        if let Ok(mut auto_focus) = self.auto_focus.try_write() {
+            auto_focus.get_or_insert(index);
+        }
+    }
+
+    /// Take the [`AutoFocus`] widget that should be focused, if any
+    pub(crate) fn take_auto_focus(&self) -> Option<Entity> {
+        self.auto_focus
+            .try_write()
+            .ok()
+            .and_then(|mut auto_focus| auto_focus.take())
+            .map(|index| index.0)
+    }
+
     /// Remove the current focus
     ///
     /// This returns focus to the root node
@@ -119,7 +190,11 @@ impl FocusTree {
     /// Change focus to the next focusable index
     pub fn next(&self) -> Option<Entity> {
         if let Ok(mut current_focus) = self.current_focus.try_write() {
-            *current_focus = self.peek_next(*current_focus);
+            let next = self.peek_next(*current_focus);
+            if !self.is_wrapping() && self.is_wrap(*current_focus, next, true) {
+                return current_focus.map(|i| i.0);
+            }
+            *current_focus = self.constrain_to_trap(next, true);
             current_focus.map(|i| i.0)
         } else {
             None
@@ -129,13 +204,144 @@ impl FocusTree {
     /// Change focus to the previous focusable index
     pub fn prev(&self) -> Option<Entity> {
         if let Ok(mut current_focus) = self.current_focus.try_write() {
-            *current_focus = self.peek_prev(*current_focus);
+            let prev = self.peek_prev(*current_focus);
+            if !self.is_wrapping() && self.is_wrap(*current_focus, prev, false) {
+                return current_focus.map(|i| i.0);
+            }
+            *current_focus = self.constrain_to_trap(prev, false);
             current_focus.map(|i| i.0)
         } else {
             None
         }
     }
 
+    /// Set whether [`next`](Self::next) and [`prev`](Self::prev) wrap around after reaching
+    /// the last or first focusable index
+    ///
+    /// Wrapping is enabled by default. Active [`FocusTrap`]s always wrap.
+    pub fn set_wrapping(&self, wrap: bool) {
+        self.wrap.store(wrap, Ordering::Relaxed);
+    }
+
+    /// Returns whether navigation wraps around after reaching either end
+    pub fn is_wrapping(&self) -> bool {
+        self.wrap.load(Ordering::Relaxed)
+    }
+
+    /// Checks if moving focus between the given indices would wrap around the tree
+    fn is_wrap(&self, from: Option<WrappedIndex>, to: Option<WrappedIndex>, forward: bool) -> bool {
+        if let (Some(from), Some(to), Ok(tree)) = (from, to, self.tree.try_read()) {
+            let root = tree.root_node;
+            if forward {
+                // Moving forward only ever returns to the root when cycling
+                Some(to) == root && from != to
+            } else {
+                // Moving backward from the root goes to the very last index
+                Some(from) == root && from != to
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Activate a [`FocusTrap`] on the given index and move focus to it
+    ///
+    /// Does nothing if the trap is already active.
+    pub(crate) fn push_trap(&self, index: WrappedIndex) {
+        if let Ok(mut traps) = self.traps.try_write() {
+            if traps.iter().any(|(trap, _)| *trap == index) {
+                return;
+            }
+            let previous_focus = self.current().map(WrappedIndex);
+            traps.push((index, previous_focus));
+        }
+        self.focus(index.0);
+    }
+
+    /// Release the [`FocusTrap`] on the given index
+    ///
+    /// If the trap currently holds focus, focus is restored to what was focused before it activated.
+    pub(crate) fn release_trap(&self, index: WrappedIndex) {
+        let released = if let Ok(mut traps) = self.traps.try_write() {
+            traps
+                .iter()
+                .position(|(trap, _)| *trap == index)
+                .map(|position| traps.remove(position))
+        } else {
+            None
+        };
+
+        if let Some((trap, previous_focus)) = released {
+            let current = self.current().map(WrappedIndex);
+            let is_focus_within = current.is_some_and(|current| self.is_within(current, trap));
+            if is_focus_within {
+                match previous_focus {
+                    Some(previous_focus) if self.contains(previous_focus.0) => {
+                        self.focus(previous_focus.0)
+                    }
+                    _ => self.blur(),
+                }
+            }
+        }
+    }
+
+    /// Get the indices of all active traps, from outermost to innermost
+    pub fn traps(&self) -> Vec<Entity> {
+        if let Ok(traps) = self.traps.try_read() {
+            traps.iter().map(|(trap, _)| trap.0).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get the currently active (innermost) trap
+    pub fn active_trap(&self) -> Option<Entity> {
+        if let Ok(traps) = self.traps.try_read() {
+            traps.last().map(|(trap, _)| trap.0)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the given index is the trap itself or one of its descendants
+    fn is_within(&self, index: WrappedIndex, trap: WrappedIndex) -> bool {
+        if let Ok(tree) = self.tree.try_read() {
+            index == trap || tree.is_descendant(index, trap)
+        } else {
+            false
+        }
+    }
+
+    /// Wraps the given index around the active trap if it falls outside of it
+    fn constrain_to_trap(
+        &self,
+        index: Option<WrappedIndex>,
+        forward: bool,
+    ) -> Option<WrappedIndex> {
+        let trap = if let Some(trap) = self.active_trap() {
+            WrappedIndex(trap)
+        } else {
+            return index;
+        };
+
+        if !self.contains(trap.0) || index.is_some_and(|index| self.is_within(index, trap)) {
+            return index;
+        }
+
+        if forward {
+            Some(trap)
+        } else if let Ok(tree) = self.tree.try_read() {
+            // Wrap to the deepest, last focusable within the trap
+            let mut last = trap;
+            while let Some(child) = tree.get_last_child(last) {
+                last = child;
+            }
+            Some(last)
+        } else {
+            index
+        }
+    }
+
     /// Peek the next focusable index without actually changing focus
     pub fn peek_next(&self, current_focus: Option<WrappedIndex>) -> Option<WrappedIndex> {
         if let Ok(tree) = self.tree.try_read() {
@@ -199,6 +405,65 @@ impl FocusTree {
             None
         }
     }
+
+    /// Find the focusable index nearest to the current focus in the given direction
+    ///
+    /// Unlike [`next`](Self::next) and [`prev`](Self::prev), this is based on the layout of the
+    /// widgets rather than their order in the tree. `get_layout` should return the layout of the
+    /// given index; indices without a layout are skipped. While a [`FocusTrap`] is active, only
+    /// indices within it are considered.
+    pub fn find_in_direction(
+        &self,
+        direction: FocusDirection,
+        get_layout: impl Fn(Entity) -> Option<Rect>,
+    ) -> Option<Entity> {
+        let current = self.current()?;
+        let from = get_layout(current)?;
+        let trap = self.active_trap().map(WrappedIndex);
+
+        let candidates = if let Ok(tree) = self.tree.try_read() {
+            tree.flatten()
+        } else {
+            return None;
+        };
+
+        candidates
+            .into_iter()
+            .filter(|index| index.0 != current)
+            .filter(|index| match trap {
+                Some(trap) => self.is_within(*index, trap),
+                None => true,
+            })
+            .filter_map(|index| {
+                let layout = get_layout(index.0)?;
+                spatial_distance(&from, &layout, direction).map(|distance| (index.0, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+}
+
+/// Scores how far `to` is from `from` in the given direction (lower is closer)
+///
+/// Returns `None` if `to` isn't in that direction. The offset across the direction counts double
+/// so that widgets lined up with `from` are preferred over slightly closer ones off to the side.
+fn spatial_distance(from: &Rect, to: &Rect, direction: FocusDirection) -> Option<f32> {
+    let center = |rect: &Rect| (rect.posx + rect.width / 2.0, rect.posy + rect.height / 2.0);
+    let (from_x, from_y) = center(from);
+    let (to_x, to_y) = center(to);
+
+    let (along, across) = match direction {
+        FocusDirection::Up => (from_y - to_y, to_x - from_x),
+        FocusDirection::Down => (to_y - from_y, to_x - from_x),
+        FocusDirection::Left => (from_x - to_x, to_y - from_y),
+        FocusDirection::Right => (to_x - from_x, to_y - from_y),
+    };
+
+    if along <= 0.0 {
+        return None;
+    }
+
+    Some(along + across.abs() * 2.0)
 }
 
 impl FocusTracker {
@@ -256,7 +521,8 @@ impl FocusTracker {
 
 #[cfg(test)]
 mod tests {
-    use crate::focus_tree::FocusTree;
+    use crate::focus_tree::{FocusDirection, FocusTree};
+    use crate::layout::Rect;
     use crate::node::WrappedIndex;
     use crate::tree::Tree;
     use bevy::prelude::Entity;
@@ -346,4 +612,145 @@ mod tests {
 
         // etc.
     }
+
+    #[test]
+    fn next_should_stop_without_wrapping() {
+        let focus_tree = FocusTree::default();
+        let mut tree = Tree::default();
+
+        let a = WrappedIndex(Entity::from_raw(0));
+        tree.add(a, None);
+        let a_a = WrappedIndex(Entity::from_raw(1));
+        tree.add(a_a, Some(a));
+        let a_b = WrappedIndex(Entity::from_raw(2));
+        tree.add(a_b, Some(a));
+
+        focus_tree.add(a, &tree);
+        focus_tree.add(a_a, &tree);
+        focus_tree.add(a_b, &tree);
+        focus_tree.set_wrapping(false);
+
+        assert_eq!(Some(a.0), focus_tree.current());
+        assert_eq!(Some(a.0), focus_tree.prev());
+        assert_eq!(Some(a_a.0), focus_tree.next());
+        assert_eq!(Some(a_b.0), focus_tree.next());
+        assert_eq!(Some(a_b.0), focus_tree.next());
+        assert_eq!(Some(a_a.0), focus_tree.prev());
+    }
+
+    #[test]
+    fn trap_should_wrap_and_restore() {
+        let focus_tree = FocusTree::default();
+        let mut tree = Tree::default();
+
+        let a = WrappedIndex(Entity::from_raw(0));
+        tree.add(a, None);
+        let a_a = WrappedIndex(Entity::from_raw(1));
+        tree.add(a_a, Some(a));
+        let a_b = WrappedIndex(Entity::from_raw(2));
+        tree.add(a_b, Some(a));
+        let a_b_a = WrappedIndex(Entity::from_raw(3));
+        tree.add(a_b_a, Some(a_b));
+        let a_b_b = WrappedIndex(Entity::from_raw(4));
+        tree.add(a_b_b, Some(a_b));
+
+        focus_tree.add(a, &tree);
+        focus_tree.add(a_a, &tree);
+        focus_tree.add(a_b, &tree);
+        focus_tree.add(a_b_a, &tree);
+        focus_tree.add(a_b_b, &tree);
+
+        focus_tree.focus(a_a.0);
+        focus_tree.push_trap(a_b);
+        assert_eq!(Some(a_b.0), focus_tree.current());
+
+        assert_eq!(Some(a_b_a.0), focus_tree.next());
+        assert_eq!(Some(a_b_b.0), focus_tree.next());
+        assert_eq!(Some(a_b.0), focus_tree.next());
+        assert_eq!(Some(a_b_b.0), focus_tree.prev());
+
+        // Nested traps: innermost wins
+        focus_tree.push_trap(a_b_a);
+        assert_eq!(Some(a_b_a.0), focus_tree.next());
+        assert_eq!(Some(a_b_a.0), focus_tree.prev());
+
+        focus_tree.release_trap(a_b_a);
+        assert_eq!(Some(a_b_b.0), focus_tree.current());
+        assert_eq!(Some(a_b.0), focus_tree.next());
+
+        focus_tree.release_trap(a_b);
+        assert_eq!(Some(a_a.0), focus_tree.current());
+    }
+
+    #[test]
+    fn find_in_direction_should_pick_nearest_in_line() {
+        let focus_tree = FocusTree::default();
+        let mut tree = Tree::default();
+
+        // A 2x2 grid of buttons with a wide one below it:
+        //
+        // [a_a] [a_b]
+        // [a_c] [a_d]
+        // [   a_e   ]
+        let a = WrappedIndex(Entity::from_raw(0));
+        tree.add(a, None);
+        let children = (1..=5)
+            .map(|i| WrappedIndex(Entity::from_raw(i)))
+            .collect::<Vec<_>>();
+        for child in &children {
+            tree.add(*child, Some(a));
+        }
+        let (a_a, a_b, a_c, a_d, a_e) = (
+            children[0],
+            children[1],
+            children[2],
+            children[3],
+            children[4],
+        );
+
+        focus_tree.add(a, &tree);
+        for child in &children {
+            focus_tree.add(*child, &tree);
+        }
+
+        let rect = |posx: f32, posy: f32, width: f32| Rect {
+            posx,
+            posy,
+            width,
+            height: 20.0,
+            z_index: None,
+        };
+        let get_layout = |index: Entity| match index.index() {
+            1 => Some(rect(0.0, 0.0, 50.0)),
+            2 => Some(rect(60.0, 0.0, 50.0)),
+            3 => Some(rect(0.0, 30.0, 50.0)),
+            4 => Some(rect(60.0, 30.0, 50.0)),
+            5 => Some(rect(0.0, 60.0, 110.0)),
+            _ => None,
+        };
+
+        focus_tree.focus(a_a.0);
+        assert_eq!(
+            Some(a_b.0),
+            focus_tree.find_in_direction(FocusDirection::Right, get_layout)
+        );
+        assert_eq!(
+            Some(a_c.0),
+            focus_tree.find_in_direction(FocusDirection::Down, get_layout)
+        );
+        assert_eq!(
+            None,
+            focus_tree.find_in_direction(FocusDirection::Up, get_layout)
+        );
+
+        focus_tree.focus(a_d.0);
+        assert_eq!(
+            Some(a_e.0),
+            focus_tree.find_in_direction(FocusDirection::Down, get_layout)
+        );
+        assert_eq!(
+            Some(a_c.0),
+            focus_tree.find_in_direction(FocusDirection::Left, get_layout)
+        );
+    }
 }
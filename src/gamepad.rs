@@ -0,0 +1,122 @@
+use bevy::{
+    input::{
+        gamepad::{
+            Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads,
+        },
+        Axis, ButtonInput,
+    },
+    prelude::Resource,
+};
+
+use crate::{focus_tree::FocusDirection, input_event::InputEvent};
+
+/// Settings for navigating the UI with a gamepad
+///
+/// When enabled, the d-pad and left stick move focus to the nearest [`Focusable`](crate::Focusable)
+/// widget in the pressed direction, the South button (A on Xbox controllers) clicks the focused
+/// widget and the East button (B) sends it a [`Cancel`](crate::prelude::EventType::Cancel) event.
+#[derive(Resource, Debug, Clone)]
+pub struct GamepadNavigation {
+    /// Whether gamepad input is used for navigation (enabled by default)
+    pub enabled: bool,
+    /// How far the left stick must be tilted (0.0 to 1.0) before it moves focus
+    pub dead_zone: f32,
+    /// The direction the left stick was tilted in last frame
+    ///
+    /// Focus only moves when the stick is first tilted in a direction, not every frame it's held.
+    stick_direction: Option<FocusDirection>,
+}
+
+impl Default for GamepadNavigation {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dead_zone: 0.5,
+            stick_direction: None,
+        }
+    }
+}
+
+impl GamepadNavigation {
+    /// Converts this frame's gamepad input into navigation events
+    pub(crate) fn read(
+        &mut self,
+        gamepads: &Gamepads,
+        buttons: &ButtonInput<GamepadButton>,
+        axes: &Axis<GamepadAxis>,
+    ) -> Vec<InputEvent> {
+        let mut input_events = Vec::new();
+        if !self.enabled {
+            self.stick_direction = None;
+            return input_events;
+        }
+
+        let mut stick_direction = None;
+        for gamepad in gamepads.iter() {
+            let just_pressed =
+                |button_type| buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+
+            for (button_type, direction) in [
+                (GamepadButtonType::DPadUp, FocusDirection::Up),
+                (GamepadButtonType::DPadDown, FocusDirection::Down),
+                (GamepadButtonType::DPadLeft, FocusDirection::Left),
+                (GamepadButtonType::DPadRight, FocusDirection::Right),
+            ] {
+                if just_pressed(button_type) {
+                    input_events.push(InputEvent::GamepadNavigate(direction));
+                }
+            }
+
+            if just_pressed(GamepadButtonType::South) {
+                input_events.push(InputEvent::GamepadActivate);
+            }
+            if just_pressed(GamepadButtonType::East) {
+                input_events.push(InputEvent::GamepadCancel);
+            }
+
+            if stick_direction.is_none() {
+                stick_direction = self.stick_direction(gamepad, axes);
+            }
+        }
+
+        if let Some(direction) = stick_direction {
+            if self.stick_direction != Some(direction) {
+                input_events.push(InputEvent::GamepadNavigate(direction));
+            }
+        }
+        self.stick_direction = stick_direction;
+
+        input_events
+    }
+
+    /// The direction the left stick of the given gamepad is tilted in (if it's past the dead zone)
+    fn stick_direction(
+        &self,
+        gamepad: Gamepad,
+        axes: &Axis<GamepadAxis>,
+    ) -> Option<FocusDirection> {
+        let x = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or_default();
+        let y = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or_default();
+
+        if x.abs().max(y.abs()) < self.dead_zone {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x > 0.0 {
+                FocusDirection::Right
+            } else {
+                FocusDirection::Left
+            })
+        } else {
+            // The stick's Y axis points up while the UI's points down
+            Some(if y > 0.0 {
+                FocusDirection::Up
+            } else {
+                FocusDirection::Down
+            })
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::{Commands, Entity, In, IntoSystem, System, World};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+
+/// A container for a callback system
+///
+/// Handlers are used by widgets to notify their users that something happened (e.g. a modal
+/// requesting to be closed). The system receives the entity of the widget that invoked the
+/// handler along with a value of type `T`.
+pub struct Handler<T: Send + Sync + 'static = ()> {
+    has_initialized: Arc<RwLock<bool>>,
+    system: Arc<RwLock<dyn System<In = (Entity, T), Out = ()>>>,
+}
+
+impl<T: Send + Sync + 'static> Default for Handler<T> {
+    fn default() -> Self {
+        Self::new(|In(_): In<(Entity, T)>| {})
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for Handler<T> {
+    fn clone(&self) -> Self {
+        Self {
+            has_initialized: self.has_initialized.clone(),
+            system: self.system.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Handler<T> {
+    /// Create a new handler
+    ///
+    /// The handler should be a system that takes `In<(Entity, T)>` as its first parameter.
+    pub fn new<Params>(system: impl IntoSystem<(Entity, T), (), Params>) -> Self {
+        Self {
+            has_initialized: Arc::new(RwLock::new(false)),
+            system: Arc::new(RwLock::new(IntoSystem::into_system(system))),
+        }
+    }
+
+    /// Call the handler immediately
+    pub fn try_call(&self, entity: Entity, value: T, world: &mut World) {
+        if let Ok(mut init) = self.has_initialized.try_write() {
+            if let Ok(mut system) = self.system.try_write() {
+                if !*init {
+                    system.initialize(world);
+                    *init = true;
+                }
+                system.run((entity, value), world);
+                system.apply_deferred(world);
+            }
+        }
+    }
+
+    /// Queue a call to the handler
+    ///
+    /// This is useful from within other systems (such as an [`OnEvent`](crate::prelude::OnEvent))
+    /// where exclusive world access isn't available. The handler runs when the commands are applied.
+    pub fn call(&self, commands: &mut Commands, entity: Entity, value: T) {
+        let handler = self.clone();
+        commands.add(move |world: &mut World| handler.try_call(entity, value, world));
+    }
+}
+
+impl<T: Send + Sync + 'static> Debug for Handler<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handler").finish()
+    }
+}
+
+impl<T: Send + Sync + 'static> PartialEq for Handler<T> {
+    fn eq(&self, _: &Self) -> bool {
+        // Never prevent "==" for being true because of this struct
+        true
+    }
+}
+
+impl<T: Send + Sync + 'static> Eq for Handler<T> {}
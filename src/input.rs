@@ -10,7 +10,9 @@ use bevy::{
 use crate::{
     context::{CustomEventReader, KayakRootContext},
     event_dispatcher::EventDispatcher,
+    gamepad::GamepadNavigation,
     input_event::InputEvent,
+    KUiScale,
 };
 
 pub(crate) fn process_events(world: &mut World) {
@@ -23,11 +25,13 @@ pub(crate) fn process_events(world: &mut World) {
             Res<Events<MouseWheel>>,
             Res<Events<ReceivedCharacter>>,
             Res<Events<KeyboardInput>>,
+            Res<Events<Ime>>,
             ResMut<CustomEventReader<CursorMoved>>,
             ResMut<CustomEventReader<MouseButtonInput>>,
             ResMut<CustomEventReader<MouseWheel>>,
             ResMut<CustomEventReader<ReceivedCharacter>>,
             ResMut<CustomEventReader<KeyboardInput>>,
+            ResMut<CustomEventReader<Ime>>,
         ),
         _,
         _,
@@ -38,11 +42,13 @@ pub(crate) fn process_events(world: &mut World) {
             mouse_wheel_events,
             char_input_events,
             keyboard_input_events,
+            ime_events,
             mut custom_event_reader_cursor,
             mut custom_event_mouse_button,
             mut custom_event_mouse_wheel,
             mut custom_event_char_input,
             mut custom_event_keyboard,
+            mut custom_event_ime,
         )| {
             if let Some(event) = custom_event_reader_cursor
                 .0
@@ -54,12 +60,21 @@ pub(crate) fn process_events(world: &mut World) {
             }
 
             for event in custom_event_mouse_button.0.read(&mouse_button_input_events) {
-                if let MouseButton::Left = event.button {
-                    if event.state == ButtonState::Pressed {
-                        input_events.push(InputEvent::MouseLeftPress);
-                    } else if event.state == ButtonState::Released {
-                        input_events.push(InputEvent::MouseLeftRelease);
+                let is_pressed = event.state == ButtonState::Pressed;
+                match event.button {
+                    MouseButton::Left if is_pressed => {
+                        input_events.push(InputEvent::MouseLeftPress)
                     }
+                    MouseButton::Left => input_events.push(InputEvent::MouseLeftRelease),
+                    MouseButton::Right if is_pressed => {
+                        input_events.push(InputEvent::MouseRightPress)
+                    }
+                    MouseButton::Right => input_events.push(InputEvent::MouseRightRelease),
+                    MouseButton::Middle if is_pressed => {
+                        input_events.push(InputEvent::MouseMiddlePress)
+                    }
+                    MouseButton::Middle => input_events.push(InputEvent::MouseMiddleRelease),
+                    _ => {}
                 }
             }
 
@@ -71,9 +86,9 @@ pub(crate) fn process_events(world: &mut World) {
             } in custom_event_mouse_wheel.0.read(&mouse_wheel_events)
             {
                 input_events.push(InputEvent::Scroll {
-                    dx: *x,
-                    dy: *y,
-                    is_line: matches!(unit, MouseScrollUnit::Line),
+                    delta_x: *x,
+                    delta_y: *y,
+                    is_line_based: matches!(unit, MouseScrollUnit::Line),
                 })
             }
 
@@ -89,6 +104,47 @@ pub(crate) fn process_events(world: &mut World) {
                     is_pressed: matches!(event.state, ButtonState::Pressed),
                 });
             }
+
+            for event in custom_event_ime.0.read(&ime_events) {
+                match event {
+                    Ime::Preedit { value, cursor, .. } => {
+                        input_events.push(InputEvent::ImePreedit {
+                            text: value.clone(),
+                            cursor: *cursor,
+                        })
+                    }
+                    Ime::Commit { value, .. } => {
+                        input_events.push(InputEvent::ImeCommit(value.clone()))
+                    }
+                    // Anything still being composed is dropped
+                    Ime::Disabled { .. } => input_events.push(InputEvent::ImePreedit {
+                        text: String::new(),
+                        cursor: None,
+                    }),
+                    Ime::Enabled { .. } => {}
+                }
+            }
+        },
+        world,
+    );
+
+    // Gamepad input is only available when bevy's gamepad support is enabled
+    query_world::<
+        (
+            Option<ResMut<GamepadNavigation>>,
+            Option<Res<Gamepads>>,
+            Option<Res<ButtonInput<GamepadButton>>>,
+            Option<Res<Axis<GamepadAxis>>>,
+        ),
+        _,
+        _,
+    >(
+        |(navigation, gamepads, buttons, axes)| {
+            if let (Some(mut navigation), Some(gamepads), Some(buttons), Some(axes)) =
+                (navigation, gamepads, buttons, axes)
+            {
+                input_events.extend(navigation.read(&gamepads, &buttons, &axes));
+            }
         },
         world,
     );
@@ -110,7 +166,28 @@ pub(crate) fn process_events(world: &mut World) {
     );
 
     for (entity, mut event_dispatcher, mut context) in context_data.drain(..) {
-        event_dispatcher.process_events(&input_events, &mut context, world);
+        // Cursor positions are relative to the window while each context lays out its widgets
+        // relative to the viewport of its camera, in UI units
+        let ui_scale = world
+            .get_resource::<KUiScale>()
+            .copied()
+            .unwrap_or_default();
+        let viewport_origin = world
+            .get::<Camera>(context.camera_entity)
+            .and_then(|camera| camera.logical_viewport_rect())
+            .map(|viewport| viewport.min)
+            .unwrap_or(Vec2::ZERO);
+        let context_events = input_events
+            .iter()
+            .map(|input_event| match input_event {
+                InputEvent::MouseMoved((x, y)) => InputEvent::MouseMoved((
+                    (x - viewport_origin.x) / ui_scale.0,
+                    (y - viewport_origin.y) / ui_scale.0,
+                )),
+                input_event => input_event.clone(),
+            })
+            .collect::<Vec<_>>();
+        event_dispatcher.process_events(&context_events, &mut context, world);
 
         world.entity_mut(entity).insert((event_dispatcher, context));
     }
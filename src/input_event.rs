@@ -1,7 +1,9 @@
 use bevy::prelude::KeyCode;
 
+use crate::focus_tree::FocusDirection;
+
 /// Events sent to [`KayakContext`](crate::KayakContext) containing user input data
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum InputEvent {
     /// An event that occurs when the user moves the mouse
     MouseMoved((f32, f32)),
@@ -9,12 +11,42 @@ pub enum InputEvent {
     MouseLeftPress,
     /// An event that occurs when the user releases the left mouse button
     MouseLeftRelease,
+    /// An event that occurs when the user presses the right mouse button
+    MouseRightPress,
+    /// An event that occurs when the user releases the right mouse button
+    MouseRightRelease,
+    /// An event that occurs when the user presses the middle mouse button
+    MouseMiddlePress,
+    /// An event that occurs when the user releases the middle mouse button
+    MouseMiddleRelease,
     /// An event that occurs when the user scrolls
-    Scroll { dx: f32, dy: f32, is_line: bool },
+    ///
+    /// Mouse wheels generally report line-based deltas while trackpads report pixel-based ones.
+    Scroll {
+        delta_x: f32,
+        delta_y: f32,
+        is_line_based: bool,
+    },
     /// An event that occurs when the user types in a character
     CharEvent { c: smol_str::SmolStr },
     /// An event that occurs when the user presses or releases a key
     Keyboard { key: KeyCode, is_pressed: bool },
+    /// An event that occurs when an IME updates the text being composed
+    ///
+    /// The `cursor` is the byte range of the IME cursor within `text`, if it should be shown.
+    /// An empty `text` means the composition ended without committing anything.
+    ImePreedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// An event that occurs when an IME commits composed text
+    ImeCommit(String),
+    /// An event that occurs when the user presses a direction on a gamepad
+    GamepadNavigate(FocusDirection),
+    /// An event that occurs when the user presses the South (A) button on a gamepad
+    GamepadActivate,
+    /// An event that occurs when the user presses the East (B) button on a gamepad
+    GamepadCancel,
 }
 
 /// The various categories an input event can belong to
@@ -23,7 +55,8 @@ pub enum InputEventCategory {
     Mouse,
     /// A category for events related to the keyboard
     Keyboard,
-    // TODO: Gamepad, etc.
+    /// A category for events related to gamepads
+    Gamepad,
 }
 
 impl InputEvent {
@@ -34,10 +67,20 @@ impl InputEvent {
             Self::MouseMoved(..) => InputEventCategory::Mouse,
             Self::MouseLeftPress => InputEventCategory::Mouse,
             Self::MouseLeftRelease => InputEventCategory::Mouse,
+            Self::MouseRightPress => InputEventCategory::Mouse,
+            Self::MouseRightRelease => InputEventCategory::Mouse,
+            Self::MouseMiddlePress => InputEventCategory::Mouse,
+            Self::MouseMiddleRelease => InputEventCategory::Mouse,
             Self::Scroll { .. } => InputEventCategory::Mouse,
             // Keyboard events
             Self::CharEvent { .. } => InputEventCategory::Keyboard,
             Self::Keyboard { .. } => InputEventCategory::Keyboard,
+            Self::ImePreedit { .. } => InputEventCategory::Keyboard,
+            Self::ImeCommit(..) => InputEventCategory::Keyboard,
+            // Gamepad events
+            Self::GamepadNavigate(..) => InputEventCategory::Gamepad,
+            Self::GamepadActivate => InputEventCategory::Gamepad,
+            Self::GamepadCancel => InputEventCategory::Gamepad,
         }
     }
 }
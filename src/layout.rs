@@ -7,10 +7,11 @@ use bevy::{
 };
 use morphorm::Cache;
 pub use morphorm::GeometryChanged;
+use serde::Serialize;
 
 use crate::node::WrappedIndex;
 
-#[derive(Debug, Reflect, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Reflect, Serialize, Default, Clone, Copy, PartialEq)]
 pub struct Rect {
     pub posx: f32,
     pub posy: f32,
@@ -24,6 +25,19 @@ impl Rect {
         (point.0 >= self.posx && point.0 <= self.posx + self.width)
             && (point.1 >= self.posy && point.1 <= self.posy + self.height)
     }
+
+    /// Returns the flags of the position and size values that differ from the given rect
+    pub(crate) fn changes_from(&self, previous: &Rect) -> GeometryChanged {
+        let mut change = GeometryChanged::default();
+        change.set(GeometryChanged::POSX_CHANGED, self.posx != previous.posx);
+        change.set(GeometryChanged::POSY_CHANGED, self.posy != previous.posy);
+        change.set(GeometryChanged::WIDTH_CHANGED, self.width != previous.width);
+        change.set(
+            GeometryChanged::HEIGHT_CHANGED,
+            self.height != previous.height,
+        );
+        change
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -46,7 +60,7 @@ pub(crate) struct LayoutCache {
     pub rect: HashMap<WrappedIndex, Rect>,
 
     // Intermediate Values
-    space: HashMap<WrappedIndex, Space>,
+    pub(crate) space: HashMap<WrappedIndex, Space>,
     size: HashMap<WrappedIndex, Size>,
 
     child_width_max: HashMap<WrappedIndex, f32>,
@@ -111,6 +125,14 @@ impl LayoutCache {
         self.rect.entry(node).or_default();
     }
 
+    /// Returns the size computed for a node in the last layout pass
+    ///
+    /// Unlike its [`rect`](Self::rect), this is also set for the root node, whose rect is never
+    /// sized by morphorm.
+    pub(crate) fn computed_size(&self, node: &WrappedIndex) -> Option<Size> {
+        self.size.get(node).copied()
+    }
+
     /// Returns an iterator over nodes whose layout have been changed since the last update
     pub fn iter_changed(&self) -> Iter<'_, WrappedIndex, GeometryChanged> {
         self.geometry_changed.iter()
@@ -456,6 +478,10 @@ impl From<Rect> for Layout {
 pub struct LayoutEvent {
     /// Layout of target component
     pub layout: Layout,
+    /// Layout of target component before this change
+    ///
+    /// This is all zeros the first time the target is laid out.
+    pub previous_layout: Layout,
     /// Flags denoting the layout change.
     ///
     /// Note: The flags can potentially all be unset in cases where the [target's] layout did
@@ -468,11 +494,29 @@ pub struct LayoutEvent {
 }
 
 impl LayoutEvent {
-    pub(crate) fn new(rect: Rect, geometry_change: GeometryChanged, index: Entity) -> LayoutEvent {
+    pub(crate) fn new(
+        rect: Rect,
+        previous_rect: Rect,
+        geometry_change: GeometryChanged,
+        index: Entity,
+    ) -> LayoutEvent {
         LayoutEvent {
             layout: rect.into(),
+            previous_layout: previous_rect.into(),
             flags: geometry_change,
             target: index,
         }
     }
+
+    /// Returns true if the width or height of the target changed
+    pub fn size_changed(&self) -> bool {
+        self.flags
+            .intersects(GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED)
+    }
+
+    /// Returns true if the target moved
+    pub fn position_changed(&self) -> bool {
+        self.flags
+            .intersects(GeometryChanged::POSX_CHANGED | GeometryChanged::POSY_CHANGED)
+    }
 }
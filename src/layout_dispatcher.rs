@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use bevy::{
     prelude::{Entity, With, World},
     utils::HashSet,
@@ -6,7 +8,7 @@ use indexmap::IndexMap;
 use morphorm::GeometryChanged;
 
 use crate::{
-    layout::{LayoutCache, LayoutEvent},
+    layout::{LayoutCache, LayoutEvent, Rect},
     node::WrappedIndex,
     on_layout::OnLayout,
     prelude::KayakRootContext,
@@ -15,20 +17,33 @@ use crate::{
 pub(crate) struct LayoutEventDispatcher;
 
 impl LayoutEventDispatcher {
-    pub fn dispatch(context: &mut KayakRootContext, world: &mut World) {
+    /// Sends a layout event to every node with an [`OnLayout`] whose layout differs from the
+    /// given previous one
+    ///
+    /// This should only be called once the layout has settled, so several layout passes are seen
+    /// as a single change.
+    pub fn dispatch(
+        context: &mut KayakRootContext,
+        world: &mut World,
+        previous_rects: &HashMap<WrappedIndex, Rect>,
+    ) {
         let on_event_entities = {
             let mut query = world.query_filtered::<Entity, With<OnLayout>>();
             query.iter(world).collect::<HashSet<_>>()
         };
 
         if let Ok(layout_cache) = context.layout_cache.try_read() {
-            let changed = layout_cache.iter_changed();
-            let changed = changed
-                .filter_map(|(index, flags)| {
-                    if on_event_entities.contains(&index.0) {
-                        Some((*index, *flags))
-                    } else {
+            let changed = on_event_entities
+                .iter()
+                .filter_map(|entity| {
+                    let index = WrappedIndex(*entity);
+                    let rect = layout_cache.rect.get(&index)?;
+                    let previous = previous_rects.get(&index).copied().unwrap_or_default();
+                    let flags = rect.changes_from(&previous);
+                    if flags.is_empty() {
                         None
+                    } else {
+                        Some((index, flags))
                     }
                 })
                 .collect::<IndexMap<WrappedIndex, GeometryChanged>>();
@@ -46,14 +61,14 @@ impl LayoutEventDispatcher {
                     }
 
                     // Process and dispatch
-                    Self::process(world, &layout_cache, *node_index, *flags);
+                    Self::process(world, &layout_cache, previous_rects, *node_index, *flags);
                 }
             }
 
             // Finally, process all parents
             for (parent_index, flags) in parents {
                 // Process and dispatch
-                Self::process(world, &layout_cache, parent_index, flags);
+                Self::process(world, &layout_cache, previous_rects, parent_index, flags);
             }
         }
     }
@@ -61,6 +76,7 @@ impl LayoutEventDispatcher {
     fn process(
         world: &mut World,
         layout_cache: &LayoutCache,
+        previous_rects: &HashMap<WrappedIndex, Rect>,
         index: WrappedIndex,
         flags: GeometryChanged,
     ) {
@@ -70,7 +86,8 @@ impl LayoutEventDispatcher {
             if let Some(mut on_layout) = entity.take::<OnLayout>() {
                 if let Some(rect) = layout_cache.rect.get(&index) {
                     // dbg!(format!("Processing event for: {:?}", entity.id()));
-                    let layout_event = LayoutEvent::new(*rect, flags, index.0);
+                    let previous = previous_rects.get(&index).copied().unwrap_or_default();
+                    let layout_event = LayoutEvent::new(*rect, previous, flags, index.0);
                     on_layout.try_call(index.0, layout_event, world);
                     world.entity_mut(index.0).insert(on_layout);
                 }
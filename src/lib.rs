@@ -1,28 +1,45 @@
 #![allow(dead_code)]
 
+#[cfg(feature = "accessibility")]
+mod accessibility;
+mod breakpoints;
 mod calculate_nodes;
 mod camera;
 mod children;
+mod clipboard;
 mod clone_component;
 mod context;
 mod context_entities;
 mod cursor;
+mod disabled;
+mod drag;
 mod event;
 mod event_dispatcher;
 mod focus_tree;
+mod gamepad;
+mod handler;
 mod input;
 mod input_event;
 mod keyboard_event;
 mod layout;
 mod layout_dispatcher;
+mod localization;
 mod node;
 mod on_change;
 mod on_event;
 mod on_layout;
+mod on_mount;
+mod on_resize;
+mod on_unmount;
 pub(crate) mod render;
 mod render_primitive;
+mod shortcut;
+mod snapshot;
+mod style_sheet;
 mod styles;
+mod theme;
 mod tree;
+mod ui_scale;
 mod widget;
 mod widget_context;
 mod widget_state;
@@ -30,6 +47,7 @@ pub mod widgets;
 mod window_size;
 
 use context::KayakRootContext;
+pub use ui_scale::KUiScale;
 pub use window_size::WindowSize;
 
 pub use camera::*;
@@ -38,24 +56,43 @@ pub use camera::*;
 pub const DEFAULT_FONT: &str = "Kayak-Default";
 
 pub mod prelude {
+    #[cfg(feature = "accessibility")]
+    pub use crate::accessibility::{AccessibilityInfo, Role};
+    pub use crate::breakpoints::{Breakpoint, Breakpoints};
     pub use crate::camera::*;
     pub use crate::children::KChildren;
+    pub use crate::clipboard::*;
     pub use crate::clone_component::PreviousWidget;
     pub use crate::context::*;
     pub use crate::cursor::*;
+    pub use crate::disabled::{Disabled, DISABLED_OPACITY};
+    pub use crate::drag::{DragEvent, DragPayload, Draggable, DropTarget};
     pub use crate::event::*;
     pub use crate::event_dispatcher::{EventDispatcher, EventDispatcherContext};
-    pub use crate::focus_tree::{FocusTree, Focusable};
+    pub use crate::focus_tree::{AutoFocus, FocusDirection, FocusTrap, FocusTree, Focusable};
+    pub use crate::gamepad::GamepadNavigation;
+    pub use crate::handler::Handler;
     pub use crate::input_event::*;
     pub use crate::keyboard_event::*;
     pub use crate::layout::*;
+    pub use crate::localization::Localization;
     pub use crate::node::DirtyNode;
-    pub use crate::on_change::OnChange;
+    pub use crate::on_change::{OnChange, ValueChange};
     pub use crate::on_event::OnEvent;
     pub use crate::on_layout::OnLayout;
+    pub use crate::on_mount::OnMount;
+    pub use crate::on_resize::OnResize;
+    pub use crate::on_unmount::OnUnmount;
     pub use crate::render::font::FontMapping;
-    pub use crate::render::{DrawUiGraph, KayakUiPass};
+    pub use crate::render::{
+        DrawUiGraph, KUiMsaa, KUiOpacityLayers, KayakUiPass, KayakUiRenderGraphExt,
+        LayoutDebugOverlay, UiPassEdge, MAX_BACKDROP_BLUR,
+    };
+    pub use crate::shortcut::KeyCombination;
+    pub use crate::snapshot::WidgetSnapshot;
+    pub use crate::style_sheet::{StyleSheet, StyleSheetLoader, StyleSheetLoaderError};
     pub use crate::styles::*;
+    pub use crate::theme::Theme;
     pub use crate::tree::*;
     pub use crate::widget::*;
     pub use crate::widget_context::*;
@@ -67,7 +104,7 @@ pub mod prelude {
     pub use crate::render::material::{
         MaterialHandle, MaterialUI, MaterialUIKey, MaterialUIPlugin,
     };
-    pub use ::kayak_font::Alignment;
+    pub use ::kayak_font::{Alignment, TextDirection, TextSpan};
     pub use kayak_ui_macros::{constructor, rsx};
 }
 
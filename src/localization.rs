@@ -0,0 +1,163 @@
+use bevy::{
+    prelude::{Commands, DetectChanges, Entity, Query, Res, Resource},
+    utils::HashMap,
+};
+
+use crate::{
+    node::DirtyNode,
+    styles::{ComputedStyles, RenderCommand, StyleProp},
+    widgets::TextProps,
+};
+
+/// Translated strings for each language, looked up by key
+///
+/// Text widgets with a [`content_key`](crate::widgets::TextProps::content_key) display the string
+/// of the active language, falling back to their `content` when it has no string for the key.
+/// Strings may contain `{name}` placeholders, which are replaced with the
+/// [`content_args`](crate::widgets::TextProps::content_args) of the same name.
+///
+/// Changing the active language re-resolves and re-measures all localized text.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn setup(mut localization: ResMut<Localization>) {
+///     localization.insert("en", "greeting", "Hello, {name}!");
+///     localization.insert("fr", "greeting", "Bonjour, {name} !");
+///     localization.set_language("fr");
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct Localization {
+    language: String,
+    strings: HashMap<String, HashMap<String, String>>,
+}
+
+impl Localization {
+    pub fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The language strings are currently looked up in
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Switches to another language
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        self.language = language.into();
+    }
+
+    /// Adds the string of a key in the given language, replacing any existing one
+    pub fn insert(
+        &mut self,
+        language: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.strings
+            .entry(language.into())
+            .or_default()
+            .insert(key.into(), value.into());
+    }
+
+    /// Adds the strings of a language and returns the updated localization
+    pub fn with_language<K: Into<String>, V: Into<String>>(
+        mut self,
+        language: impl Into<String>,
+        strings: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        let language = language.into();
+        for (key, value) in strings {
+            self.insert(language.clone(), key, value);
+        }
+        self
+    }
+
+    /// Returns the string of a key in the active language, if any
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings
+            .get(&self.language)
+            .and_then(|strings| strings.get(key))
+            .map(String::as_str)
+    }
+
+    /// Returns the string of a key in the active language with its `{name}` placeholders replaced
+    ///
+    /// Placeholders without a matching argument are kept as they are.
+    pub fn format(&self, key: &str, args: &[(String, String)]) -> Option<String> {
+        self.get(key).map(|value| interpolate(value, args))
+    }
+}
+
+fn interpolate(value: &str, args: &[(String, String)]) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(end) = rest.find('}') {
+            let name = &rest[1..end];
+            if let Some((_, arg)) = args.iter().find(|(arg_name, _)| arg_name == name) {
+                result.push_str(arg);
+            } else {
+                result.push_str(&rest[..=end]);
+            }
+            rest = &rest[end + 1..];
+        } else {
+            break;
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Updates all localized text whenever the [`Localization`] changes
+pub(crate) fn relocalize_on_change(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    mut query: Query<(Entity, &TextProps, &mut ComputedStyles)>,
+) {
+    if !localization.is_changed() || localization.is_added() {
+        return;
+    }
+
+    for (entity, text, mut styles) in query.iter_mut() {
+        if text.content_key.is_none() {
+            continue;
+        }
+        if let StyleProp::Value(RenderCommand::Text { content, .. }) = &mut styles.0.render_command
+        {
+            *content = text.localized_content(&localization);
+            commands.entity(entity).insert(DirtyNode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Localization;
+
+    #[test]
+    fn should_interpolate_arguments() {
+        let localization = Localization::new("en").with_language(
+            "en",
+            [("greeting", "Hello, {name}! You have {count} {unknown}")],
+        );
+        let args = vec![
+            ("name".to_string(), "Ada".to_string()),
+            ("count".to_string(), "3".to_string()),
+        ];
+
+        assert_eq!(
+            localization.format("greeting", &args),
+            Some(String::from("Hello, Ada! You have 3 {unknown}"))
+        );
+        assert_eq!(localization.format("missing", &args), None);
+    }
+}
@@ -1,4 +1,8 @@
-use bevy::prelude::{Component, Entity, Query, Reflect, ReflectComponent};
+use bevy::{
+    ecs::query::QueryEntityError,
+    prelude::{Component, Entity, Query, Reflect, ReflectComponent},
+    utils::HashMap,
+};
 
 use crate::styles::{KStyle, StyleProp};
 
@@ -106,8 +110,28 @@ impl NodeBuilder {
 #[derive(Debug, Reflect, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct WrappedIndex(pub Entity);
 
+/// The nodes laid out by morphorm
+pub struct LayoutStore<'a> {
+    pub nodes: &'a Query<'a, 'a, &'static Node>,
+    /// Fixed sizes (in pixels) used in place of the width and height styles of some nodes
+    pub size_overrides: HashMap<WrappedIndex, (Option<f32>, Option<f32>)>,
+}
+
+impl<'a> LayoutStore<'a> {
+    pub fn new(nodes: &'a Query<'a, 'a, &'static Node>) -> Self {
+        Self {
+            nodes,
+            size_overrides: HashMap::default(),
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> Result<&Node, QueryEntityError> {
+        self.nodes.get(entity)
+    }
+}
+
 impl<'a> morphorm::Node<'a> for WrappedIndex {
-    type Data = Query<'a, 'a, &'static Node>;
+    type Data = LayoutStore<'a>;
 
     fn layout_type(&self, store: &'_ Self::Data) -> Option<morphorm::LayoutType> {
         if let Ok(node) = store.get(self.0) {
@@ -132,6 +156,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
     }
 
     fn width(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
+        if let Some((Some(width), _)) = store.size_overrides.get(self) {
+            return Some(morphorm::Units::Pixels(*width));
+        }
         if let Ok(node) = store.get(self.0) {
             return match node.resolved_styles.width {
                 StyleProp::Default => Some(morphorm::Units::Stretch(1.0)),
@@ -143,6 +170,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
     }
 
     fn height(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
+        if let Some((_, Some(height))) = store.size_overrides.get(self) {
+            return Some(morphorm::Units::Pixels(*height));
+        }
         if let Ok(node) = store.get(self.0) {
             return match node.resolved_styles.height {
                 StyleProp::Default => Some(morphorm::Units::Stretch(1.0)),
@@ -352,7 +382,11 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
     fn row_between(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
             return match node.resolved_styles.row_between {
-                StyleProp::Default => Some(morphorm::Units::Auto),
+                StyleProp::Default => match node.resolved_styles.gap {
+                    StyleProp::Default => Some(morphorm::Units::Auto),
+                    StyleProp::Value(prop) => Some(prop.into()),
+                    _ => Some(morphorm::Units::Auto),
+                },
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
@@ -363,7 +397,11 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
     fn col_between(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
             return match node.resolved_styles.col_between {
-                StyleProp::Default => Some(morphorm::Units::Auto),
+                StyleProp::Default => match node.resolved_styles.gap {
+                    StyleProp::Default => Some(morphorm::Units::Auto),
+                    StyleProp::Value(prop) => Some(prop.into()),
+                    _ => Some(morphorm::Units::Auto),
+                },
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
@@ -7,48 +7,79 @@ use crate::prelude::KayakWidgetContext;
 
 pub trait ChangeValue: Component<Storage = TableStorage> + Default {}
 
+/// The payload passed to an [`OnChange`] handler
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ValueChange<T> {
+    /// The value before the change
+    pub previous: T,
+    /// The new value
+    pub value: T,
+}
+
 /// A container for a function that handles layout
 ///
 /// This differs from a standard [`Handler`](crate::Handler) in that it's sent directly
 /// from the [`KayakContext`](crate::KayakContext) and gives the [`KayakContextRef`]
 /// as a parameter.
-#[derive(Component, Clone)]
-pub struct OnChange {
-    value: Arc<RwLock<String>>,
+///
+/// The changed value is a `String` by default, but widgets may report other types
+/// (such as an `f64` for a number input).
+///
+/// The handler only runs when the new value differs from the previous one (as determined by
+/// `PartialEq`). Types without a meaningful notion of equality can opt out of this by
+/// implementing `PartialEq` to always return `false`, in which case every change is reported.
+#[derive(Component)]
+pub struct OnChange<T: Clone + Default + PartialEq + Send + Sync + 'static = String> {
+    change: Arc<RwLock<ValueChange<T>>>,
     has_initialized: Arc<RwLock<bool>>,
-    system: Arc<RwLock<dyn System<In = (Entity, String), Out = ()>>>,
+    system: Arc<RwLock<dyn System<In = (Entity, ValueChange<T>), Out = ()>>>,
 }
 
-impl Default for OnChange {
+impl<T: Clone + Default + PartialEq + Send + Sync + 'static> Default for OnChange<T> {
     fn default() -> Self {
-        Self::new(|In(_)| {})
+        Self::new(|In(_): In<(Entity, ValueChange<T>)>| {})
     }
 }
 
-impl OnChange {
-    /// Create a new layout handler
+impl<T: Clone + Default + PartialEq + Send + Sync + 'static> Clone for OnChange<T> {
+    fn clone(&self) -> Self {
+        Self {
+            change: self.change.clone(),
+            has_initialized: self.has_initialized.clone(),
+            system: self.system.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Default + PartialEq + Send + Sync + 'static> OnChange<T> {
+    /// Create a new change handler
     ///
-    /// The handler should be a closure that takes the following arguments:
-    /// 1. The LayoutEvent
-    pub fn new<Params>(system: impl IntoSystem<(Entity, String), (), Params>) -> Self {
+    /// The handler should be a system that takes `In<(Entity, ValueChange<T>)>` as its first
+    /// parameter.
+    pub fn new<Params>(system: impl IntoSystem<(Entity, ValueChange<T>), (), Params>) -> Self {
         Self {
-            value: Default::default(),
+            change: Default::default(),
             has_initialized: Arc::new(RwLock::new(false)),
             system: Arc::new(RwLock::new(IntoSystem::into_system(system))),
         }
     }
 
-    pub fn set_value(&self, value: String) {
-        if let Ok(mut value_mut) = self.value.try_write() {
-            *value_mut = value;
+    /// Sets the change the handler will be called with
+    pub fn set_value(&self, previous: T, value: T) {
+        if let Ok(mut change_mut) = self.change.try_write() {
+            *change_mut = ValueChange { previous, value };
         };
     }
 
-    /// Call the layout event handler
+    /// Call the change handler
     ///
-    /// Returns true if the handler was successfully invoked.
+    /// The handler is skipped if the value didn't actually change.
     pub fn try_call(&self, entity: Entity, world: &mut World, widget_context: KayakWidgetContext) {
-        if let Ok(value) = self.value.try_read() {
+        if let Ok(change) = self.change.try_read() {
+            if change.previous == change.value {
+                return;
+            }
+
             if let Ok(mut init) = self.has_initialized.try_write() {
                 if let Ok(mut system) = self.system.try_write() {
                     if !*init {
@@ -56,7 +87,7 @@ impl OnChange {
                         *init = true;
                     }
                     world.insert_resource(widget_context);
-                    system.run((entity, value.clone()), world);
+                    system.run((entity, change.clone()), world);
                     system.apply_deferred(world);
                 }
             }
@@ -64,13 +95,13 @@ impl OnChange {
     }
 }
 
-impl Debug for OnChange {
+impl<T: Clone + Default + PartialEq + Send + Sync + 'static> Debug for OnChange<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OnLayout").finish()
     }
 }
 
-impl PartialEq for OnChange {
+impl<T: Clone + Default + PartialEq + Send + Sync + 'static> PartialEq for OnChange<T> {
     fn eq(&self, _: &Self) -> bool {
         // Never prevent "==" for being true because of this struct
         true
@@ -0,0 +1,22 @@
+use bevy::prelude::{Component, Entity, IntoSystem};
+
+use crate::handler::Handler;
+
+/// A handler called once when a widget is added to the tree
+///
+/// The handler runs right after the widget's first render. Parents are always mounted
+/// before their children.
+///
+/// A widget is only mounted again if it was [unmounted](crate::prelude::OnUnmount) first.
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct OnMount(pub Handler);
+
+impl OnMount {
+    /// Create a new mount handler
+    ///
+    /// The handler should be a system that takes `In<(Entity, ())>` as its first parameter,
+    /// where the entity is the widget being mounted.
+    pub fn new<Params>(system: impl IntoSystem<(Entity, ()), (), Params>) -> Self {
+        Self(Handler::new(system))
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::{Component, Entity, IntoSystem, Local, World};
+
+use crate::{handler::Handler, WindowSize};
+
+/// A handler called whenever the [`WindowSize`] changes
+///
+/// The handler receives the new window size, so a widget can switch between layouts (such as
+/// a mobile and a desktop arrangement) by storing it in its state. It's only called when the
+/// size actually changes, before widgets are updated for the frame. Widgets created afterwards
+/// aren't told about the current size, so they should read the [`WindowSize`] resource when
+/// they're first rendered.
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct OnResize(pub Handler<WindowSize>);
+
+impl OnResize {
+    /// Create a new resize handler
+    ///
+    /// The handler should be a system that takes `In<(Entity, WindowSize)>` as its first
+    /// parameter, where the entity is the widget the handler belongs to.
+    pub fn new<Params>(system: impl IntoSystem<(Entity, WindowSize), (), Params>) -> Self {
+        Self(Handler::new(system))
+    }
+}
+
+/// Calls the [`OnResize`] handler of every widget when the [`WindowSize`] changes
+pub(crate) fn call_on_resize(world: &mut World, mut last_size: Local<Option<WindowSize>>) {
+    let Some(window_size) = world.get_resource::<WindowSize>().copied() else {
+        return;
+    };
+    if last_size.replace(window_size) == Some(window_size) {
+        return;
+    }
+
+    let handlers = world
+        .query::<(Entity, &OnResize)>()
+        .iter(world)
+        .map(|(entity, on_resize)| (entity, on_resize.clone()))
+        .collect::<Vec<_>>();
+    for (entity, on_resize) in handlers {
+        on_resize.0.try_call(entity, window_size, world);
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::{Component, Entity, IntoSystem};
+
+use crate::handler::Handler;
+
+/// A handler called once when a widget is removed from the tree
+///
+/// The handler runs before the widget is despawned, so its components (such as its props)
+/// can still be read. This makes it a good place to clean up external resources like
+/// timers or network handles.
+///
+/// When a widget is removed along with its descendants, children are unmounted before
+/// their parents.
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct OnUnmount(pub Handler);
+
+impl OnUnmount {
+    /// Create a new unmount handler
+    ///
+    /// The handler should be a system that takes `In<(Entity, ())>` as its first parameter,
+    /// where the entity is the widget being unmounted.
+    pub fn new<Params>(system: impl IntoSystem<(Entity, ()), (), Params>) -> Self {
+        Self(Handler::new(system))
+    }
+}
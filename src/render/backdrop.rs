@@ -0,0 +1,311 @@
+use bevy::{
+    asset::{load_internal_asset, Handle},
+    core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prelude::*,
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{
+            BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry, BindingResource, BindingType,
+            BufferBindingType, CachedRenderPipelineId, ColorTargetState, ColorWrites, FilterMode,
+            FragmentState, LoadOp, MultisampleState, Operations, PipelineCache, PrimitiveState,
+            RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Sampler,
+            SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, ShaderType,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, StoreOp, TextureFormat,
+            TextureSampleType, TextureView, TextureViewDimension, UniformBuffer,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        texture::BevyDefault,
+        view::ViewTarget,
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{
+    extract::UIExtractedView,
+    opacity_layer::{OpacityLayerManager, BACKDROP_LAYER, BACKDROP_SCRATCH_LAYER},
+};
+
+pub const BACKDROP_BLUR_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(10941470257264151902);
+
+/// The largest radius (in pixels) of a backdrop blur
+///
+/// Each blur pass samples the backdrop `2 * radius + 1` times per pixel, scaled by the UI scale.
+pub const MAX_BACKDROP_BLUR: f32 = 32.0;
+
+/// Blurs whatever is drawn behind widgets with a [`KStyle::backdrop_blur`](crate::styles::KStyle::backdrop_blur)
+pub struct BackdropBlurPlugin;
+
+impl Plugin for BackdropBlurPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            BACKDROP_BLUR_SHADER_HANDLE,
+            "backdrop.wgsl",
+            Shader::from_wgsl
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .init_resource::<BackdropBlurPipeline>()
+            .init_resource::<SpecializedRenderPipelines<BackdropBlurPipeline>>()
+            .add_systems(
+                Render,
+                prepare_backdrop_blur_pipelines.in_set(RenderSet::Prepare),
+            );
+    }
+}
+
+/// The area and radius of a backdrop blur, attached to the batch drawing the blurred backdrop
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BackdropBlur {
+    pub rect: Rect,
+    pub radius: f32,
+}
+
+#[derive(ShaderType, Debug, Clone, Copy)]
+struct BlurSettings {
+    direction: Vec2,
+    radius: f32,
+    _padding: f32,
+}
+
+#[derive(Resource)]
+pub struct BackdropBlurPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for BackdropBlurPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "backdrop_blur_layout",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(BlurSettings::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("backdrop_blur_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self { layout, sampler }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BackdropBlurPipelineKey {
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for BackdropBlurPipeline {
+    type Key = BackdropBlurPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("backdrop_blur_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: BACKDROP_BLUR_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+        }
+    }
+}
+
+/// The backdrop blur pipeline of a view
+#[derive(Component)]
+pub struct ViewBackdropBlurPipeline(CachedRenderPipelineId);
+
+fn prepare_backdrop_blur_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BackdropBlurPipeline>>,
+    blur_pipeline: Res<BackdropBlurPipeline>,
+    views: Query<(Entity, &UIExtractedView)>,
+) {
+    for (entity, view) in views.iter() {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &blur_pipeline,
+            BackdropBlurPipelineKey { hdr: view.hdr },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewBackdropBlurPipeline(pipeline_id));
+    }
+}
+
+/// Blurs everything drawn to the view so far into the [`BACKDROP_LAYER`], where the batch of the
+/// given entity samples it from.
+///
+/// The blur is separated into a horizontal and a vertical pass, both limited to the blurred area.
+pub(crate) fn blur_backdrop(
+    world: &World,
+    render_context: &mut RenderContext,
+    view_entity: Entity,
+    target: &ViewTarget,
+    batch_entity: Entity,
+) {
+    let (Some(backdrop), Some(view), Some(pipeline_id)) = (
+        world.get::<BackdropBlur>(batch_entity),
+        world.get::<UIExtractedView>(view_entity),
+        world.get::<ViewBackdropBlurPipeline>(view_entity),
+    ) else {
+        return;
+    };
+    let Some(pipeline) = world
+        .resource::<PipelineCache>()
+        .get_render_pipeline(pipeline_id.0)
+    else {
+        return;
+    };
+    let Some(layers) = world
+        .resource::<OpacityLayerManager>()
+        .camera_layers
+        .get(&view_entity)
+    else {
+        return;
+    };
+
+    let gpu_images = world.resource::<RenderAssets<Image>>();
+    let (Some(scratch), Some(blurred)) = (
+        gpu_images.get(layers.get_image_handle(BACKDROP_SCRATCH_LAYER)),
+        gpu_images.get(layers.get_image_handle(BACKDROP_LAYER)),
+    ) else {
+        return;
+    };
+
+    // Blur the area the widget covers, plus the radius it samples from outside of it
+    let radius = backdrop.radius * view.ui_scale;
+    let viewport = UVec2::new(view.viewport.x, view.viewport.y).as_vec2();
+    let min = (backdrop.rect.min * view.ui_scale + viewport - radius).max(Vec2::ZERO);
+    let max = (backdrop.rect.max * view.ui_scale + viewport + radius).min(scratch.size);
+    if min.x >= max.x || min.y >= max.y {
+        return;
+    }
+    let scissor = (min.as_uvec2(), (max - min).as_uvec2());
+
+    let passes = [
+        (target.main_texture_view(), &scratch.texture_view, Vec2::X),
+        (&scratch.texture_view, &blurred.texture_view, Vec2::Y),
+    ];
+    for (source, destination, direction) in passes {
+        blur_pass(
+            world,
+            render_context,
+            pipeline,
+            source,
+            destination,
+            scissor,
+            BlurSettings {
+                direction,
+                radius,
+                _padding: 0.0,
+            },
+        );
+    }
+}
+
+fn blur_pass(
+    world: &World,
+    render_context: &mut RenderContext,
+    pipeline: &bevy::render::render_resource::RenderPipeline,
+    source: &TextureView,
+    destination: &TextureView,
+    (position, size): (UVec2, UVec2),
+    settings: BlurSettings,
+) {
+    let blur_pipeline = world.resource::<BackdropBlurPipeline>();
+    let render_device = render_context.render_device().clone();
+
+    let mut settings_buffer = UniformBuffer::from(settings);
+    settings_buffer.write_buffer(&render_device, world.resource::<RenderQueue>());
+    let Some(settings) = settings_buffer.binding() else {
+        return;
+    };
+
+    let bind_group = render_device.create_bind_group(
+        Some("backdrop_blur_bind_group"),
+        &blur_pipeline.layout,
+        &[
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(source),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::Sampler(&blur_pipeline.sampler),
+            },
+            BindGroupEntry {
+                binding: 2,
+                resource: settings,
+            },
+        ],
+    );
+
+    let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("backdrop_blur_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: destination,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        ..Default::default()
+    });
+    pass.set_scissor_rect(position.x, position.y, size.x, size.y);
+    pass.set_render_pipeline(pipeline);
+    pass.set_bind_group(0, &bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
@@ -0,0 +1,32 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct BlurSettings {
+    direction: vec2<f32>,
+    radius: f32,
+    _padding: f32,
+};
+
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> settings: BlurSettings;
+
+// One pass of a separable gaussian blur along `settings.direction`
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = settings.direction / vec2<f32>(textureDimensions(source_texture));
+    let sigma = max(settings.radius / 3.0, 0.0001);
+    let extent = i32(ceil(settings.radius));
+
+    var color = vec4<f32>(0.0);
+    var total = 0.0;
+    for (var i = -extent; i <= extent; i++) {
+        let offset = f32(i);
+        let weight = exp(-(offset * offset) / (2.0 * sigma * sigma));
+        color += textureSampleLevel(source_texture, source_sampler, in.uv + texel * offset, 0.0) * weight;
+        total += weight;
+    }
+    return color / total;
+}
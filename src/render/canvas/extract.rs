@@ -0,0 +1,242 @@
+use crate::{
+    render::unified::pipeline::{ExtractedQuad, UIQuadType},
+    styles::{CanvasShape, Corner},
+};
+use bevy::{
+    math::Vec2,
+    prelude::{Color, Entity, Rect},
+};
+
+/// The most quads a single diagonal line or triangle is split into
+///
+/// Quads can't be rotated, so these shapes are approximated with many small quads (one per
+/// dot or pixel row). Large shapes spread their quads further apart past this limit, trading
+/// accuracy for a bounded cost.
+const MAX_SHAPE_QUADS: usize = 256;
+
+pub fn extract_canvas(
+    camera_entity: Entity,
+    layout: crate::layout::Rect,
+    shapes: Vec<CanvasShape>,
+    opacity_layer: u32,
+    dpi: f32,
+) -> Vec<ExtractedQuad> {
+    let mut extracted_quads = Vec::new();
+
+    let origin = Vec2::new(layout.posx, layout.posy);
+    let mut push_quad = |min: Vec2, max: Vec2, color: Color, radius: f32| {
+        extracted_quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min: (origin + min) * dpi,
+                max: (origin + max) * dpi,
+            },
+            color,
+            quad_type: UIQuadType::Quad,
+            border_radius: Corner::all(radius * dpi),
+            opacity_layer,
+            ..Default::default()
+        });
+    };
+
+    for shape in shapes {
+        match shape {
+            CanvasShape::Line {
+                start,
+                end,
+                width,
+                color,
+            } => {
+                let half_width = width / 2.0;
+                if start.x == end.x || start.y == end.y {
+                    // Axis-aligned lines can be drawn with a single quad
+                    push_quad(
+                        start.min(end) - Vec2::splat(half_width),
+                        start.max(end) + Vec2::splat(half_width),
+                        color,
+                        0.0,
+                    );
+                } else {
+                    // Quads can't be rotated, so diagonal lines are made up of round dots
+                    let length = start.distance(end);
+                    let spacing = half_width.max(1.0 / dpi);
+                    let steps = ((length / spacing).ceil() as usize).clamp(1, MAX_SHAPE_QUADS - 1);
+                    for step in 0..=steps {
+                        let point = start.lerp(end, step as f32 / steps as f32);
+                        push_quad(
+                            point - Vec2::splat(half_width),
+                            point + Vec2::splat(half_width),
+                            color,
+                            half_width,
+                        );
+                    }
+                }
+            }
+            CanvasShape::Rect {
+                position,
+                size,
+                color,
+                radius,
+            } => {
+                push_quad(position, position + size, color, radius);
+            }
+            CanvasShape::Circle {
+                center,
+                radius,
+                color,
+            } => {
+                push_quad(
+                    center - Vec2::splat(radius),
+                    center + Vec2::splat(radius),
+                    color,
+                    radius,
+                );
+            }
+            CanvasShape::Triangle { a, b, c, color } => {
+                // Fill the triangle one physical pixel row at a time
+                let min_y = a.y.min(b.y).min(c.y);
+                let max_y = a.y.max(b.y).max(c.y);
+                let rows = (((max_y - min_y) * dpi).ceil() as usize).min(MAX_SHAPE_QUADS);
+                let row_height = (max_y - min_y) / rows.max(1) as f32;
+                for row in 0..rows {
+                    let y = min_y + row as f32 * row_height;
+                    let center_y = y + row_height / 2.0;
+                    let mut min_x = f32::MAX;
+                    let mut max_x = f32::MIN;
+                    for (from, to) in [(a, b), (b, c), (c, a)] {
+                        if (from.y <= center_y && to.y > center_y)
+                            || (to.y <= center_y && from.y > center_y)
+                        {
+                            let t = (center_y - from.y) / (to.y - from.y);
+                            let x = from.x + t * (to.x - from.x);
+                            min_x = min_x.min(x);
+                            max_x = max_x.max(x);
+                        }
+                    }
+                    if min_x < max_x {
+                        push_quad(
+                            Vec2::new(min_x, y),
+                            Vec2::new(max_x, y + row_height),
+                            color,
+                            0.0,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    extracted_quads
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Color, Entity, Vec2};
+
+    use super::{extract_canvas, MAX_SHAPE_QUADS};
+    use crate::{layout::Rect, styles::CanvasShape};
+
+    fn extract(
+        shapes: Vec<CanvasShape>,
+        dpi: f32,
+    ) -> Vec<crate::render::unified::pipeline::ExtractedQuad> {
+        let layout = Rect {
+            posx: 10.0,
+            posy: 20.0,
+            width: 100.0,
+            height: 100.0,
+            z_index: Some(0.0),
+        };
+        extract_canvas(Entity::PLACEHOLDER, layout, shapes, 0, dpi)
+    }
+
+    #[test]
+    fn axis_aligned_lines_should_use_a_single_quad() {
+        let quads = extract(
+            vec![CanvasShape::Line {
+                start: Vec2::new(50.0, 5.0),
+                end: Vec2::new(0.0, 5.0),
+                width: 2.0,
+                color: Color::RED,
+            }],
+            2.0,
+        );
+
+        assert_eq!(1, quads.len());
+        assert_eq!(Vec2::new(18.0, 48.0), quads[0].rect.min);
+        assert_eq!(Vec2::new(122.0, 52.0), quads[0].rect.max);
+    }
+
+    #[test]
+    fn diagonal_lines_should_be_capped() {
+        let start = Vec2::ZERO;
+        let end = Vec2::new(3000.0, 4000.0);
+        let quads = extract(
+            vec![CanvasShape::Line {
+                start,
+                end,
+                width: 2.0,
+                color: Color::RED,
+            }],
+            1.0,
+        );
+
+        assert_eq!(MAX_SHAPE_QUADS, quads.len());
+        // The dots still reach both ends of the line
+        let origin = Vec2::new(10.0, 20.0);
+        assert_eq!(origin + start, quads[0].rect.center());
+        assert_eq!(origin + end, quads[quads.len() - 1].rect.center());
+    }
+
+    #[test]
+    fn triangles_should_be_filled_row_by_row_and_capped() {
+        let triangle = |height: f32| CanvasShape::Triangle {
+            a: Vec2::new(0.0, 0.0),
+            b: Vec2::new(40.0, height),
+            c: Vec2::new(0.0, height),
+            color: Color::RED,
+        };
+
+        let quads = extract(vec![triangle(10.0)], 2.0);
+        assert_eq!(20, quads.len());
+        assert_eq!(20.0 * 2.0, quads[0].rect.min.y);
+        assert_eq!(30.0 * 2.0, quads[quads.len() - 1].rect.max.y);
+        // Each row is wider than the one above it
+        assert!(quads
+            .windows(2)
+            .all(|rows| rows[0].rect.max.x < rows[1].rect.max.x));
+
+        let quads = extract(vec![triangle(5000.0)], 1.0);
+        assert_eq!(MAX_SHAPE_QUADS, quads.len());
+    }
+
+    #[test]
+    fn shapes_should_keep_their_order_and_not_be_clipped() {
+        let quads = extract(
+            vec![
+                CanvasShape::Rect {
+                    position: Vec2::new(90.0, 90.0),
+                    size: Vec2::new(50.0, 50.0),
+                    color: Color::RED,
+                    radius: 0.0,
+                },
+                CanvasShape::Circle {
+                    center: Vec2::new(-10.0, 0.0),
+                    radius: 5.0,
+                    color: Color::BLUE,
+                },
+            ],
+            1.0,
+        );
+
+        // Later shapes are drawn on top of earlier ones
+        assert_eq!(
+            vec![Color::RED, Color::BLUE],
+            quads.iter().map(|quad| quad.color).collect::<Vec<_>>()
+        );
+        // Shapes may extend past the canvas, which only a surrounding clip cuts off
+        assert_eq!(Vec2::new(150.0, 160.0), quads[0].rect.max);
+        assert_eq!(Vec2::new(-5.0, 15.0), quads[1].rect.min);
+        assert_eq!(5.0, quads[1].border_radius.top_left);
+    }
+}
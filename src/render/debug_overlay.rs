@@ -0,0 +1,200 @@
+use bevy::{
+    math::Vec2,
+    prelude::{Assets, Color, Entity, Query, Rect, Resource},
+};
+use kayak_font::{KayakFont, TextProperties};
+use morphorm::Hierarchy;
+
+use crate::{
+    context::WidgetName,
+    layout::LayoutCache,
+    node::Node,
+    render::unified::pipeline::{ExtractedQuad, UIQuadType},
+    styles::{Edge, StyleProp, TextDecoration, Units},
+    tree::Tree,
+};
+
+use super::font::{extract_texts, FontMapping};
+
+/// The font size of the labels drawn by the [`LayoutDebugOverlay`]
+const LABEL_FONT_SIZE: f32 = 10.0;
+
+/// Draws the computed layout of every widget on top of the UI
+///
+/// Each widget gets an outline around its layout rect, with its padding and the space around it
+/// (its `left`, `right`, `top` and `bottom` as laid out) filled in. This is meant for finding out
+/// why a widget ends up where it does, and is disabled by default.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn toggle_overlay(keys: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<LayoutDebugOverlay>) {
+///     if keys.just_pressed(KeyCode::F3) {
+///         overlay.enabled = !overlay.enabled;
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct LayoutDebugOverlay {
+    /// Whether the overlay is drawn
+    pub enabled: bool,
+    /// Whether the name and z-index of each widget is drawn in its top-left corner
+    pub show_labels: bool,
+    /// The color of the outline around each layout rect
+    pub outline_color: Color,
+    /// The color the padding of each widget is filled with
+    pub padding_color: Color,
+    /// The color the space around each widget is filled with
+    pub margin_color: Color,
+}
+
+impl Default for LayoutDebugOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            show_labels: false,
+            outline_color: Color::rgba(1.0, 0.0, 1.0, 0.8),
+            padding_color: Color::rgba(0.2, 0.8, 0.3, 0.25),
+            margin_color: Color::rgba(1.0, 0.6, 0.1, 0.25),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn extract_debug_overlay(
+    overlay: &LayoutDebugOverlay,
+    camera_entity: Entity,
+    dpi: f32,
+    node_tree: &Tree,
+    layout_cache: &LayoutCache,
+    nodes: &Query<&Node>,
+    widget_names: &Query<&WidgetName>,
+    fonts: &Assets<KayakFont>,
+    font_mapping: &FontMapping,
+) -> Vec<ExtractedQuad> {
+    let mut extracted_quads = Vec::new();
+    let quad = |min: Vec2, max: Vec2, color: Color| ExtractedQuad {
+        camera_entity,
+        rect: Rect {
+            min: min * dpi,
+            max: max * dpi,
+        },
+        color,
+        quad_type: UIQuadType::Quad,
+        ..Default::default()
+    };
+
+    for index in node_tree.down_iter() {
+        let (Ok(node), Some(layout)) = (nodes.get(index.0), layout_cache.rect.get(&index)) else {
+            continue;
+        };
+        let min = Vec2::new(layout.posx, layout.posy);
+        let max = min + Vec2::new(layout.width, layout.height);
+
+        if let Some(space) = layout_cache.space.get(&index) {
+            let outer_min = min - Vec2::new(space.left, space.top);
+            let outer_max = max + Vec2::new(space.right, space.bottom);
+            for (band_min, band_max) in edge_bands(outer_min, outer_max, min, max) {
+                extracted_quads.push(quad(band_min, band_max, overlay.margin_color));
+            }
+        }
+
+        let styles = &node.resolved_styles;
+        let padding = |side: &StyleProp<Units>, edge: fn(&Edge<Units>) -> Units, size: f32| {
+            let units = match (side, &styles.padding) {
+                (StyleProp::Value(units), _) => *units,
+                (StyleProp::Default, StyleProp::Value(padding)) => edge(padding),
+                _ => Units::Auto,
+            };
+            units.value_or(size, 0.0)
+        };
+        let inner_min = min
+            + Vec2::new(
+                padding(&styles.padding_left, |edge| edge.left, layout.width),
+                padding(&styles.padding_top, |edge| edge.top, layout.height),
+            );
+        let inner_max = max
+            - Vec2::new(
+                padding(&styles.padding_right, |edge| edge.right, layout.width),
+                padding(&styles.padding_bottom, |edge| edge.bottom, layout.height),
+            );
+        for (band_min, band_max) in edge_bands(min, max, inner_min.min(max), inner_max.max(min)) {
+            extracted_quads.push(quad(band_min, band_max, overlay.padding_color));
+        }
+
+        let line = 1.0 / dpi;
+        for (band_min, band_max) in edge_bands(min, max, min + line, (max - line).max(min)) {
+            extracted_quads.push(quad(band_min, band_max, overlay.outline_color));
+        }
+
+        if overlay.show_labels {
+            let name = widget_names
+                .get(index.0)
+                .map(|name| name.0.as_str())
+                .unwrap_or("Unknown");
+            let label = format!("{} ({}) z: {}", name, index.0.index(), node.z);
+            let font = String::from(crate::DEFAULT_FONT);
+            if let Some(text_layout) = font_mapping
+                .get_handle(font.clone())
+                .and_then(|handle| fonts.get(&handle))
+                .map(|font| {
+                    font.measure(
+                        &label,
+                        TextProperties {
+                            font_size: LABEL_FONT_SIZE,
+                            line_height: LABEL_FONT_SIZE * 1.2,
+                            ..Default::default()
+                        },
+                    )
+                })
+            {
+                extracted_quads.extend(extract_texts(
+                    camera_entity,
+                    overlay.outline_color,
+                    (Color::NONE, 0.0),
+                    None,
+                    TextDecoration::None,
+                    text_layout,
+                    *layout,
+                    font,
+                    None,
+                    false,
+                    0,
+                    fonts,
+                    font_mapping,
+                    dpi,
+                ));
+            }
+        }
+    }
+
+    extracted_quads
+}
+
+/// Splits the space between an outer and an inner rect into its left, right, top and bottom bands
+///
+/// Empty bands are left out.
+fn edge_bands(
+    outer_min: Vec2,
+    outer_max: Vec2,
+    inner_min: Vec2,
+    inner_max: Vec2,
+) -> Vec<(Vec2, Vec2)> {
+    [
+        (outer_min, Vec2::new(inner_min.x, outer_max.y)),
+        (Vec2::new(inner_max.x, outer_min.y), outer_max),
+        (
+            Vec2::new(inner_min.x, outer_min.y),
+            Vec2::new(inner_max.x, inner_min.y),
+        ),
+        (
+            Vec2::new(inner_min.x, inner_max.y),
+            Vec2::new(inner_max.x, outer_max.y),
+        ),
+    ]
+    .into_iter()
+    .filter(|(min, max)| max.x > min.x && max.y > min.y)
+    .collect()
+}
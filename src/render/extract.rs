@@ -1,7 +1,7 @@
 use crate::{
     context::{KayakRootContext, WidgetName},
     node::Node,
-    CameraUIKayak,
+    CameraUIKayak, KUiScale,
 };
 use bevy::{
     prelude::*,
@@ -11,12 +11,14 @@ use bevy::{
         view::ColorGrading,
         Extract, ExtractSchedule, Render, RenderApp, RenderSet,
     },
-    window::{PrimaryWindow, Window, WindowRef},
 };
 use kayak_font::KayakFont;
 
 use super::{
+    debug_overlay::LayoutDebugOverlay,
     font::FontMapping,
+    msaa::KUiMsaa,
+    opacity_layer::KUiOpacityLayers,
     ui_pass::{TransparentUI, UIRenderPhase},
     unified::pipeline::ExtractedQuads,
 };
@@ -51,24 +53,37 @@ pub fn extract(
     node_query: Extract<Query<&Node>>,
     widget_names: Extract<Query<&WidgetName>>,
     images: Extract<Res<Assets<Image>>>,
-    primary_window: Extract<Query<&Window, With<PrimaryWindow>>>,
     cameras: Extract<Query<&Camera>>,
+    debug_overlay: Extract<Res<LayoutDebugOverlay>>,
+    opacity_layers: Extract<Res<KUiOpacityLayers>>,
     mut extracted_quads: ResMut<ExtractedQuads>,
+    mut extracted_contexts: Local<Vec<Entity>>,
 ) {
+    // Keep last frame's quads when no context changed since they were built
+    let contexts = context_query
+        .iter()
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    let needs_render = context_query
+        .iter()
+        .any(|(_, context)| context.needs_render);
+    if !needs_render
+        && !debug_overlay.is_changed()
+        && !opacity_layers.is_changed()
+        && *extracted_contexts == contexts
+        && !extracted_quads.has_materials()
+    {
+        return;
+    }
+    *extracted_contexts = contexts;
+
     extracted_quads.clear();
     extracted_quads.new_layer(None);
 
     for (_entity, context) in context_query.iter() {
+        // Images are always rendered at a scale factor of 1.0 while windows use their own DPI.
         let dpi = if let Ok(camera) = cameras.get(context.camera_entity) {
-            if let bevy::render::camera::RenderTarget::Window(WindowRef::Primary) = &camera.target {
-                if let Ok(window) = primary_window.get_single() {
-                    window.scale_factor()
-                } else {
-                    1.0
-                }
-            } else {
-                1.0
-            }
+            camera.target_scaling_factor().unwrap_or(1.0)
         } else {
             1.0
         };
@@ -82,6 +97,8 @@ pub fn extract(
             &fonts,
             &font_mapping,
             &images,
+            &debug_overlay,
+            opacity_layers.count(),
             &mut extracted_quads,
         );
         // Resolve extracted quads
@@ -121,11 +138,18 @@ pub struct UIExtractedView {
     // uvec4(origin.x, origin.y, width, height)
     pub viewport: UVec4,
     pub color_grading: ColorGrading,
+    /// The [`KUiScale`] everything in this view is drawn at
+    pub ui_scale: f32,
+    /// The number of samples per pixel the UI of this view is drawn with, see [`KUiMsaa`]
+    pub msaa_samples: u32,
 }
 
 pub fn extract_default_ui_camera_view<T: Component>(
     mut commands: Commands,
     query: Extract<Query<(Entity, &Camera, &CameraUIKayak), With<T>>>,
+    ui_scale: Extract<Res<KUiScale>>,
+    ui_msaa: Extract<Res<KUiMsaa>>,
+    msaa: Extract<Res<Msaa>>,
 ) {
     for (entity, camera, _camera_ui) in &query {
         if let (Some(logical_size), Some(physical_origin), Some(physical_size)) = (
@@ -134,8 +158,10 @@ pub fn extract_default_ui_camera_view<T: Component>(
             camera.physical_viewport_size(),
         ) {
             // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
+            // The UI is laid out in UI units, so scaling the projection scales everything at once
+            let ui_size = logical_size / ui_scale.0;
             let projection_matrix =
-                Mat4::orthographic_rh(0.0, logical_size.x, logical_size.y, 0.0, 0.0, 1000.0);
+                Mat4::orthographic_rh(0.0, ui_size.x, ui_size.y, 0.0, 0.0, 1000.0);
             commands.get_or_spawn(entity).insert((
                 UIExtractedView {
                     projection: projection_matrix,
@@ -153,6 +179,8 @@ pub fn extract_default_ui_camera_view<T: Component>(
                     ),
                     view_projection: None,
                     color_grading: ColorGrading::default(),
+                    ui_scale: ui_scale.0,
+                    msaa_samples: ui_msaa.samples(&msaa),
                 },
                 UIRenderPhase::<TransparentUI>::default(),
             ));
@@ -2,22 +2,28 @@ use bevy::{
     math::Vec2,
     prelude::{Assets, Color, Entity, Rect},
 };
-use kayak_font::{KayakFont, TextLayout, TextProperties};
+use kayak_font::{KayakFont, TextLayout};
 
 use crate::{
     render::unified::pipeline::{ExtractedQuad, UIQuadType},
-    styles::Corner,
+    styles::{Corner, TextDecoration, TextShadow},
 };
 
 use super::font_mapping::FontMapping;
 
+/// The widest outline (in SDF units) that stays within the distance field of a glyph
+const MAX_OUTLINE_WIDTH: f32 = 0.4;
+
 pub fn extract_texts(
     camera_entity: Entity,
     color: Color,
+    outline: (Color, f32),
+    shadow: Option<TextShadow>,
+    decoration: TextDecoration,
     text_layout: TextLayout,
     layout: crate::layout::Rect,
     font: String,
-    properties: TextProperties,
+    font_weight: Option<f32>,
     subpixel: bool,
     opacity_layer: u32,
     fonts: &Assets<KayakFont>,
@@ -26,7 +32,8 @@ pub fn extract_texts(
 ) -> Vec<ExtractedQuad> {
     let mut extracted_texts = Vec::new();
 
-    let font_handle = font_mapping.get_handle(font).unwrap();
+    let base_handle = font_mapping.get_handle(font.clone()).unwrap();
+    let font_handle = font_mapping.get_weighted_handle(font, font_weight).unwrap();
     let font = match fonts.get(&font_handle) {
         Some(font) => font,
         None => {
@@ -34,9 +41,9 @@ pub fn extract_texts(
         }
     };
 
-    let forced = font_mapping.get_subpixel_forced(&font_handle);
+    let forced = font_mapping.get_subpixel_forced(&base_handle);
 
-    let base_position = Vec2::new(layout.posx, layout.posy + properties.font_size);
+    let base_position = Vec2::new(layout.posx, layout.posy);
 
     for glyph_rect in text_layout.glyphs() {
         let mut position = Vec2::from(glyph_rect.position);
@@ -69,5 +76,67 @@ pub fn extract_texts(
         });
     }
 
+    // Every outline is drawn before the glyphs, so an outline never covers a neighboring glyph
+    let (outline_color, outline_width) = outline;
+    let outline_width = outline_width.clamp(0.0, MAX_OUTLINE_WIDTH);
+    let glyph_count = extracted_texts.len();
+    if outline_width > 0.0 {
+        let outlines = extracted_texts
+            .iter()
+            .map(|quad| ExtractedQuad {
+                color: outline_color,
+                quad_type: UIQuadType::Text,
+                outline_width,
+                ..quad.clone()
+            })
+            .collect::<Vec<_>>();
+        extracted_texts.splice(0..0, outlines);
+    }
+
+    // The shadow is drawn behind everything else, following the outline of the text if any
+    if let Some(shadow) = shadow {
+        let shadows = extracted_texts[extracted_texts.len() - glyph_count..]
+            .iter()
+            .map(|quad| ExtractedQuad {
+                rect: Rect {
+                    min: quad.rect.min + shadow.offset,
+                    max: quad.rect.max + shadow.offset,
+                },
+                color: shadow.color,
+                quad_type: UIQuadType::Text,
+                outline_width,
+                blur: shadow.blur.max(0.0),
+                ..quad.clone()
+            })
+            .collect::<Vec<_>>();
+        extracted_texts.splice(0..0, shadows);
+    }
+
+    if decoration != TextDecoration::None {
+        let properties = text_layout.properties();
+        let metrics = font.metrics().scaled(properties.font_size);
+        let thickness = metrics.underline_thickness.max(1.0);
+        for line in text_layout.lines().iter().filter(|line| line.width() > 0.0) {
+            let y = line.baseline()
+                - match decoration {
+                    TextDecoration::Underline => metrics.underline_position,
+                    TextDecoration::Overline => metrics.ascent,
+                    _ => metrics.x_height / 2.0,
+                };
+            let min = base_position + Vec2::new(properties.line_offset(line), y - thickness / 2.0);
+            extracted_texts.push(ExtractedQuad {
+                camera_entity,
+                rect: Rect {
+                    min,
+                    max: min + Vec2::new(line.width(), thickness),
+                },
+                color,
+                quad_type: UIQuadType::Quad,
+                opacity_layer,
+                ..Default::default()
+            });
+        }
+    }
+
     extracted_texts
 }
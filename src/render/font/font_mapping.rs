@@ -41,6 +41,8 @@ pub struct FontMapping {
     font_handles: HashMap<String, Handle<KayakFont>>,
     new_fonts: Vec<String>,
     subpixel: HashSet<Handle<KayakFont>>,
+    /// The variations of fonts at a weight, keyed by the font name and the bits of the weight
+    weights: HashMap<(String, u32), Handle<KayakFont>>,
 }
 
 impl FontMapping {
@@ -68,6 +70,39 @@ impl FontMapping {
         self.font_handles.get(&id).cloned()
     }
 
+    /// Get the handle for the given font name at the given weight
+    ///
+    /// Falls back to the font itself when no weight is given, or when the font has no variation
+    /// at that weight (yet).
+    pub fn get_weighted_handle(
+        &self,
+        id: String,
+        weight: Option<f32>,
+    ) -> Option<Handle<KayakFont>> {
+        weight
+            .and_then(|weight| self.weights.get(&(id.clone(), weight.to_bits())))
+            .cloned()
+            .or_else(|| self.get_handle(id))
+    }
+
+    /// Whether a variation of the font at the given weight has been added
+    pub(crate) fn has_weight(&self, id: &str, weight: f32) -> bool {
+        self.weights
+            .contains_key(&(id.to_string(), weight.to_bits()))
+    }
+
+    /// Adds the variation of a font at the given weight
+    ///
+    /// Fonts that can't change their weight add themselves, so they aren't tried again.
+    pub(crate) fn add_weight(&mut self, id: String, weight: f32, handle: Handle<KayakFont>) {
+        self.weights.insert((id, weight.to_bits()), handle);
+    }
+
+    /// Removes every variation of the font, so they get created again
+    pub(crate) fn clear_weights(&mut self, id: &str) {
+        self.weights.retain(|(font, _), _| font != id);
+    }
+
     /// Get the font name for the given handle
     pub fn get(&self, font: &Handle<KayakFont>) -> Option<String> {
         self.font_ids.get(font).cloned()
@@ -1,4 +1,8 @@
-use bevy::prelude::{Added, Entity, Plugin, Query, ResMut, Update};
+use bevy::prelude::{
+    Added, AssetEvent, Assets, Changed, Commands, Entity, EventReader, Handle, IntoSystemConfigs,
+    Plugin, Query, ResMut, Update,
+};
+use kayak_font::KayakFont;
 
 mod extract;
 mod font_mapping;
@@ -6,15 +10,24 @@ mod font_mapping;
 pub use extract::extract_texts;
 pub use font_mapping::*;
 
-use crate::context::KayakRootContext;
+use crate::{
+    context::KayakRootContext,
+    node::{DirtyNode, Node},
+    styles::{RenderCommand, StyleProp},
+};
 
 #[derive(Default)]
 pub struct TextRendererPlugin;
 
 impl Plugin for TextRendererPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.init_resource::<FontMapping>()
-            .add_systems(Update, process_loaded_fonts);
+        app.init_resource::<FontMapping>().add_systems(
+            Update,
+            (
+                process_loaded_fonts,
+                (relayout_on_font_change, create_font_weights).chain(),
+            ),
+        );
     }
 }
 
@@ -26,3 +39,102 @@ fn process_loaded_fonts(
         font_mapping.mark_all_as_new();
     }
 }
+
+/// Marks text using a reloaded font as dirty so it gets measured again
+fn relayout_on_font_change(
+    mut commands: Commands,
+    mut font_events: EventReader<AssetEvent<KayakFont>>,
+    mut font_mapping: ResMut<FontMapping>,
+    node_query: Query<(Entity, &Node)>,
+) {
+    let modified_fonts = font_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => font_mapping.get(&Handle::Weak(*id)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if modified_fonts.is_empty() {
+        return;
+    }
+    for font in modified_fonts.iter() {
+        // The weights of a reloaded font are created again from the new font
+        font_mapping.clear_weights(font);
+    }
+
+    for (entity, node) in node_query.iter() {
+        if !matches!(
+            node.resolved_styles.render_command,
+            StyleProp::Value(RenderCommand::Text { .. })
+        ) {
+            continue;
+        }
+        let font = node
+            .resolved_styles
+            .font
+            .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
+        if modified_fonts.contains(&font) {
+            commands.entity(entity).insert(DirtyNode);
+        }
+    }
+}
+
+/// Creates the variations of variable fonts at the weights their text uses
+///
+/// Text is laid out with the font's own weight until its variation exists, then marked as dirty
+/// so it gets measured again.
+fn create_font_weights(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    mut fonts: ResMut<Assets<KayakFont>>,
+    changed_nodes: Query<&Node, Changed<Node>>,
+    node_query: Query<(Entity, &Node)>,
+) {
+    let mut created = Vec::new();
+    for node in changed_nodes.iter() {
+        let Some((font, weight)) = text_weight(node) else {
+            continue;
+        };
+        if font_mapping.has_weight(&font, weight) {
+            continue;
+        }
+        let Some(handle) = font_mapping.get_handle(font.clone()) else {
+            continue;
+        };
+        let Some(variation) = fonts
+            .get(&handle)
+            .map(|base| base.with_variation("wght", weight))
+        else {
+            // Try again once the font has loaded
+            continue;
+        };
+        let variation = variation
+            .map(|variation| fonts.add(variation))
+            .unwrap_or(handle);
+        font_mapping.add_weight(font.clone(), weight, variation);
+        created.push((font, weight));
+    }
+    if created.is_empty() {
+        return;
+    }
+
+    for (entity, node) in node_query.iter() {
+        if text_weight(node).is_some_and(|text_weight| created.contains(&text_weight)) {
+            commands.entity(entity).insert(DirtyNode);
+        }
+    }
+}
+
+/// The font and weight of a text node that sets a weight
+fn text_weight(node: &Node) -> Option<(String, f32)> {
+    let styles = &node.resolved_styles;
+    match (&styles.render_command, &styles.font_weight) {
+        (StyleProp::Value(RenderCommand::Text { .. }), StyleProp::Value(weight)) => Some((
+            styles
+                .font
+                .resolve_or_else(|| String::from(crate::DEFAULT_FONT)),
+            *weight,
+        )),
+        _ => None,
+    }
+}
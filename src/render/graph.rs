@@ -0,0 +1,93 @@
+use bevy::{
+    core_pipeline::{
+        core_2d::graph::{Core2d, Node2d},
+        core_3d::graph::{Core3d, Node3d},
+    },
+    prelude::*,
+    render::render_graph::{RenderGraph, RenderLabel, ViewNode, ViewNodeRunner},
+};
+
+use super::KayakUiPass;
+
+/// Where a custom render node runs relative to the [`KayakUiPass`]
+///
+/// The UI pass is added to both the 2D and 3D core pipelines, after `Tonemapping` and before
+/// `Upscaling` (see [`Node2d`] and [`Node3d`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiPassEdge {
+    /// Run after `Tonemapping` and before the UI is drawn, for effects applied to the scene
+    /// behind the UI
+    BeforeUi,
+    /// Run after the UI is drawn and before `Upscaling`, for effects applied to the UI and the
+    /// scene behind it
+    AfterUi,
+}
+
+/// Adds custom render nodes around the [`KayakUiPass`]
+///
+/// The UI pass is only added to the render graph once the Kayak plugins are finished, so call
+/// this from the `finish` of a plugin added after them:
+///
+/// ```ignore
+/// impl Plugin for UiPostProcessPlugin {
+///     fn build(&self, _app: &mut App) {}
+///
+///     fn finish(&self, app: &mut App) {
+///         app.sub_app_mut(RenderApp).add_kayak_ui_node::<UiPostProcessNode>(
+///             UiPostProcessLabel,
+///             UiPassEdge::AfterUi,
+///         );
+///     }
+/// }
+/// ```
+pub trait KayakUiRenderGraphExt {
+    /// Adds the node to the 2D and 3D core pipelines with an edge to or from the [`KayakUiPass`]
+    fn add_kayak_ui_node<T: ViewNode + FromWorld + Send + Sync + 'static>(
+        &mut self,
+        label: impl RenderLabel,
+        edge: UiPassEdge,
+    ) -> &mut Self;
+}
+
+impl KayakUiRenderGraphExt for App {
+    fn add_kayak_ui_node<T: ViewNode + FromWorld + Send + Sync + 'static>(
+        &mut self,
+        label: impl RenderLabel,
+        edge: UiPassEdge,
+    ) -> &mut Self {
+        let label = label.intern();
+        let node_2d = ViewNodeRunner::<T>::from_world(&mut self.world);
+        let node_3d = ViewNodeRunner::<T>::from_world(&mut self.world);
+        let mut graph = self.world.resource_mut::<RenderGraph>();
+
+        if let Some(graph_2d) = graph.get_sub_graph_mut(Core2d) {
+            graph_2d.add_node(label, node_2d);
+            match edge {
+                UiPassEdge::BeforeUi => {
+                    graph_2d.add_node_edge(Node2d::Tonemapping, label);
+                    graph_2d.add_node_edge(label, KayakUiPass);
+                }
+                UiPassEdge::AfterUi => {
+                    graph_2d.add_node_edge(KayakUiPass, label);
+                    graph_2d.add_node_edge(label, Node2d::Upscaling);
+                }
+            }
+        }
+
+        if let Some(graph_3d) = graph.get_sub_graph_mut(Core3d) {
+            graph_3d.add_node(label, node_3d);
+            match edge {
+                UiPassEdge::BeforeUi => {
+                    graph_3d.add_node_edge(Node3d::Tonemapping, label);
+                    graph_3d.add_node_edge(label, KayakUiPass);
+                }
+                UiPassEdge::AfterUi => {
+                    graph_3d.add_node_edge(KayakUiPass, label);
+                    graph_3d.add_node_edge(label, Node3d::Upscaling);
+                }
+            }
+        }
+
+        self
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     render::unified::pipeline::{ExtractedQuad, UIQuadType},
-    styles::Corner,
+    styles::{BackgroundSize, Corner, CursorImage, Edge},
 };
 use bevy::{math::Vec2, prelude::*, render::color::Color};
 
@@ -9,6 +9,7 @@ pub fn extract_images(
     border_radius: Corner<f32>,
     layout: crate::layout::Rect,
     handle: Handle<Image>,
+    tint: Color,
     opacity_layer: u32,
     _dpi: f32,
 ) -> Vec<ExtractedQuad> {
@@ -18,7 +19,7 @@ pub fn extract_images(
             min: Vec2::new(layout.posx, layout.posy),
             max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height),
         },
-        color: Color::WHITE,
+        color: tint,
         char_id: 0,
         font_handle: None,
         quad_type: UIQuadType::Image,
@@ -31,3 +32,164 @@ pub fn extract_images(
         ..Default::default()
     }]
 }
+
+pub fn extract_background_image(
+    camera_entity: Entity,
+    layout: crate::layout::Rect,
+    border: Edge<f32>,
+    border_radius: Corner<f32>,
+    handle: Handle<Image>,
+    background_size: BackgroundSize,
+    opacity_layer: u32,
+    images: &Assets<Image>,
+) -> Vec<ExtractedQuad> {
+    let image_size = if let Some(image) = images.get(&handle) {
+        Vec2::new(
+            image.texture_descriptor.size.width as f32,
+            image.texture_descriptor.size.height as f32,
+        )
+    } else {
+        return vec![];
+    };
+
+    // The image is drawn within the border
+    let min = Vec2::new(layout.posx + border.left, layout.posy + border.top);
+    let max = Vec2::new(
+        layout.posx + layout.width - border.right,
+        layout.posy + layout.height - border.bottom,
+    );
+    let size = max - min;
+    if size.x <= 0.0 || size.y <= 0.0 || image_size.x <= 0.0 || image_size.y <= 0.0 {
+        return vec![];
+    }
+
+    let template = ExtractedQuad {
+        camera_entity,
+        rect: Rect { min, max },
+        color: Color::WHITE,
+        quad_type: UIQuadType::Image,
+        border_radius,
+        image: Some(handle.clone_weak()),
+        opacity_layer,
+        ..Default::default()
+    };
+
+    match background_size {
+        BackgroundSize::Stretch => vec![template],
+        BackgroundSize::Cover => {
+            // Crop the image equally on both sides of the overflowing axis
+            let scale = (size / image_size).max_element();
+            let visible = (size / (image_size * scale)).min(Vec2::ONE);
+            vec![ExtractedQuad {
+                uv_min: Some((Vec2::ONE - visible) / 2.0),
+                uv_max: Some((Vec2::ONE + visible) / 2.0),
+                ..template
+            }]
+        }
+        BackgroundSize::Contain => {
+            // Center the image, leaving empty space along the other axis
+            let scale = (size / image_size).min_element();
+            let image_size = image_size * scale;
+            let min = min + (size - image_size) / 2.0;
+            vec![ExtractedQuad {
+                rect: Rect {
+                    min,
+                    max: min + image_size,
+                },
+                ..template
+            }]
+        }
+        BackgroundSize::Tile => {
+            let mut extracted_quads = Vec::new();
+            let mut y = min.y;
+            while y < max.y {
+                let height = image_size.y.min(max.y - y);
+                let mut x = min.x;
+                while x < max.x {
+                    let width = image_size.x.min(max.x - x);
+                    // Only the tiles touching a corner of the widget are rounded
+                    let border_radius = Corner {
+                        top_left: if x == min.x && y == min.y {
+                            border_radius.top_left
+                        } else {
+                            0.0
+                        },
+                        top_right: if x + width >= max.x && y == min.y {
+                            border_radius.top_right
+                        } else {
+                            0.0
+                        },
+                        bottom_left: if x == min.x && y + height >= max.y {
+                            border_radius.bottom_left
+                        } else {
+                            0.0
+                        },
+                        bottom_right: if x + width >= max.x && y + height >= max.y {
+                            border_radius.bottom_right
+                        } else {
+                            0.0
+                        },
+                    };
+                    // The top of a tile maps to the top of the image (`uv_max.y`)
+                    extracted_quads.push(ExtractedQuad {
+                        rect: Rect {
+                            min: Vec2::new(x, y),
+                            max: Vec2::new(x + width, y + height),
+                        },
+                        uv_min: Some(Vec2::new(0.0, 1.0 - height / image_size.y)),
+                        uv_max: Some(Vec2::new(width / image_size.x, 1.0)),
+                        border_radius,
+                        ..template.clone()
+                    });
+                    x += image_size.x;
+                }
+                y += image_size.y;
+            }
+            extracted_quads
+        }
+    }
+}
+
+/// Draws a custom cursor image at the given pointer position
+///
+/// `clip` is the area of the screen the cursor may be drawn in (in physical pixels).
+pub fn extract_cursor_image(
+    camera_entity: Entity,
+    cursor_image: &CursorImage,
+    position: Vec2,
+    clip: Rect,
+    images: &Assets<Image>,
+) -> Vec<ExtractedQuad> {
+    let size = if let Some(size) = cursor_image.size {
+        size
+    } else if let Some(image) = images.get(&cursor_image.handle) {
+        Vec2::new(
+            image.texture_descriptor.size.width as f32,
+            image.texture_descriptor.size.height as f32,
+        )
+    } else {
+        return vec![];
+    };
+
+    let min = position - cursor_image.hotspot;
+    vec![
+        // Reset any clip left over from the widgets drawn before the cursor
+        ExtractedQuad {
+            camera_entity,
+            rect: clip,
+            quad_type: UIQuadType::Clip,
+            ..Default::default()
+        },
+        ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min,
+                max: min + size,
+            },
+            color: Color::WHITE,
+            quad_type: UIQuadType::Image,
+            image: Some(cursor_image.handle.clone_weak()),
+            ..Default::default()
+        },
+    ]
+}
@@ -1,2 +1,2 @@
 mod extract;
-pub use extract::extract_images;
+pub use extract::{extract_background_image, extract_cursor_image, extract_images};
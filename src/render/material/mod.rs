@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use bevy::{
     asset::Asset,
-    prelude::{Commands, Entity},
+    prelude::{Commands, Entity, Handle},
     reflect::Reflect,
     render::render_resource::{AsBindGroup, RenderPipelineDescriptor, ShaderRef},
 };
@@ -15,9 +15,26 @@ pub use key::*;
 pub use pipeline::*;
 pub use plugin::*;
 
+/// A custom material used to draw widgets in place of the default quad, image or text rendering
+///
+/// Materials are attached to a widget through the `material` field of its
+/// [`KStyle`](crate::styles::KStyle), usually with [`MaterialHandle::from_handle`], and need their
+/// [`MaterialUIPlugin`] added to the app.
+///
+/// Fragment shaders receive a `kayak_ui::vertex_output::VertexOutput` describing the quad being
+/// drawn. The material's own bindings live in bind group 3, while the view and globals (such as
+/// `globals.time`) can be imported from `kayak_ui::bindings`. Calling
+/// `kayak_ui::sample_quad::sample_quad` returns the color Kayak would have drawn, which is handy
+/// to keep rounded corners and glyph shapes. The rounded corners of the clip the widget is drawn
+/// in aren't part of that color, multiply its alpha by `kayak_ui::sample_quad::clip_alpha` to
+/// respect them.
 pub trait MaterialUI: AsBindGroup + Send + Sync + Clone + Asset + Sized {
     /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default mesh vertex shader
     /// will be used.
+    ///
+    /// Quads are drawn instanced, so a custom vertex shader receives the per-quad instance data
+    /// (see the `vertex` function of Kayak's unified shader) and is run for six vertices
+    /// of every quad.
     fn vertex_shader() -> ShaderRef {
         ShaderRef::Default
     }
@@ -84,6 +101,13 @@ impl MaterialHandle {
         }
     }
 
+    /// Creates a handle that draws widgets with the given material
+    pub fn from_handle<M: MaterialUI>(handle: Handle<M>) -> Self {
+        Self::new(move |commands, entity| {
+            commands.entity(entity).insert(handle.clone_weak());
+        })
+    }
+
     pub fn run(&self, commands: &mut Commands, id: Entity) {
         self.closure.c.as_ref()(commands, id);
     }
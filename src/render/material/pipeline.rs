@@ -354,18 +354,20 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
     let mut current_batch = QuadBatch {
         image_handle_id: None,
         font_handle_id: None,
+        font_page: 0,
         quad_type: UIQuadType::None,
         type_id: quad_type_offsets.quad_type_offset,
         z_index: -999.0,
     };
     let mut current_batch_entity = Entity::PLACEHOLDER;
 
-    // Vertex buffer indices
+    // Instance buffer indices
     let mut index = prev_index.index;
     let mut item_start = prev_index.index;
     let mut item_end = prev_index.index;
     let mut old_item_start = prev_index.index;
     let mut current_clip = prev_index.last_clip;
+    let mut current_clip_radius = prev_index.last_clip_radius;
     let mut last_clip = prev_index.last_clip;
 
     // let mut previous_clip_rect = Rect::default();
@@ -379,7 +381,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
         views.iter_mut()
     {
         let key = UnifiedPipelineKey {
-            msaa: 1,
+            msaa: view.msaa_samples,
             hdr: view.hdr,
         };
 
@@ -397,14 +399,26 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                     continue;
                 }
 
-                pipeline_id = Some(pipelines.specialize(
-                    &pipeline_cache,
-                    &materialui_pipeline,
-                    MaterialUIKey {
-                        unified_key: key,
-                        bind_group_data: materialui.key.clone(),
-                    },
+                // Opacity layers are never multisampled
+                pipeline_id = Some((
+                    pipelines.specialize(
+                        &pipeline_cache,
+                        &materialui_pipeline,
+                        MaterialUIKey {
+                            unified_key: key,
+                            bind_group_data: materialui.key.clone(),
+                        },
+                    ),
+                    pipelines.specialize(
+                        &pipeline_cache,
+                        &materialui_pipeline,
+                        MaterialUIKey {
+                            unified_key: UnifiedPipelineKey { msaa: 1, ..key },
+                            bind_group_data: materialui.key.clone(),
+                        },
+                    ),
                 ));
+                let (pipeline, opacity_pipeline) = pipeline_id.unwrap();
 
                 quad.z_index = material_z.0;
 
@@ -422,7 +436,8 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                     &mut opacity_transparent_phase,
                     draw_opacity_quad,
                     draw_quad,
-                    pipeline_id.unwrap(),
+                    pipeline,
+                    opacity_pipeline,
                     &mut quad_meta,
                     &quad,
                     camera_entity,
@@ -434,6 +449,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                     &mut item_end,
                     &last_quad,
                     &mut current_clip,
+                    &mut current_clip_radius,
                     &mut old_item_start,
                     &mut last_clip,
                 );
@@ -448,7 +464,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
             }
         }
 
-        if let Some(pipeline) = pipeline_id {
+        if let Some((pipeline, opacity_pipeline)) = pipeline_id {
             #[allow(clippy::nonminimal_bool)]
             if last_quad.quad_type != UIQuadType::Clip
                 && last_quad.quad_type != UIQuadType::OpacityLayer
@@ -462,7 +478,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                 if last_quad.opacity_layer > 0 {
                     opacity_transparent_phase.add(TransparentOpacityUI {
                         draw_function: draw_opacity_quad,
-                        pipeline,
+                        pipeline: opacity_pipeline,
                         entity: current_batch_entity,
                         sort_key: FloatOrd(last_quad.z_index),
                         quad_type: last_quad.quad_type,
@@ -490,6 +506,6 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
     }
 
     quad_meta
-        .vertices
+        .instances
         .write_buffer(&render_device, &render_queue);
 }
@@ -1,13 +1,11 @@
 use bevy::{
     prelude::*,
     render::{
-        camera::RenderTarget,
         render_asset::RenderAssets,
         render_graph::{RenderGraph, RenderLabel, RenderSubGraph, RunGraphOnViewNode},
         render_phase::DrawFunctions,
         Extract, ExtractSchedule, Render, RenderApp, RenderSet,
     },
-    window::{PrimaryWindow, Window, WindowRef},
 };
 
 use crate::{
@@ -21,10 +19,15 @@ use self::{
     ui_pass::{sort_ui_phase_system, TransparentOpacityUI, TransparentUI, UIRenderPhase},
 };
 
+pub(crate) mod backdrop;
+pub(crate) mod canvas;
+mod debug_overlay;
 mod extract;
 pub(crate) mod font;
+mod graph;
 pub(crate) mod image;
 pub mod material;
+mod msaa;
 pub(crate) mod nine_patch;
 mod opacity_layer;
 pub(crate) mod quad;
@@ -34,11 +37,19 @@ pub(crate) mod texture_atlas;
 mod ui_pass;
 pub mod unified;
 
-pub use opacity_layer::MAX_OPACITY_LAYERS;
+pub use backdrop::MAX_BACKDROP_BLUR;
+pub(crate) use debug_overlay::extract_debug_overlay;
+pub use debug_overlay::LayoutDebugOverlay;
+pub use graph::{KayakUiRenderGraphExt, UiPassEdge};
+pub use msaa::KUiMsaa;
+pub use opacity_layer::{KUiOpacityLayers, MAX_OPACITY_LAYERS};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
 pub struct DrawUiGraph;
 
+/// The render graph node drawing the UI of a camera
+///
+/// Custom nodes can be added before or after it with [`KayakUiRenderGraphExt`].
 #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
 pub struct KayakUiPass;
 
@@ -50,12 +61,16 @@ pub struct BevyKayakUIRenderPlugin;
 impl Plugin for BevyKayakUIRenderPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<OpacityLayerManager>()
+            .init_resource::<KUiOpacityLayers>()
+            .init_resource::<LayoutDebugOverlay>()
             .add_systems(Update, update_opacity_layer_cameras);
 
         app.add_plugins((
             font::TextRendererPlugin,
             UnifiedRenderPlugin,
             BevyKayakUIExtractPlugin,
+            backdrop::BackdropBlurPlugin,
+            msaa::UiMsaaPlugin,
         ));
     }
 
@@ -131,21 +146,30 @@ fn get_ui_graph(render_app: &mut App) -> RenderGraph {
 }
 
 pub fn update_opacity_layer_cameras(
-    windows: Query<&Window>,
     cameras: Query<(Entity, &Camera), With<CameraUIKayak>>,
-    primary_window: Query<Entity, With<PrimaryWindow>>,
+    max_opacity_layers: Res<KUiOpacityLayers>,
     mut opacity_layers: ResMut<OpacityLayerManager>,
     mut images: ResMut<Assets<Image>>,
 ) {
+    if max_opacity_layers.is_changed() && max_opacity_layers.0 > MAX_OPACITY_LAYERS {
+        log::error!(
+            "KUiOpacityLayers({}) is above the limit of {} opacity layers, only {} will be allocated",
+            max_opacity_layers.0,
+            MAX_OPACITY_LAYERS,
+            max_opacity_layers.count(),
+        );
+    }
+
     for (camera_entity, camera) in cameras.iter() {
-        if let RenderTarget::Window(window_ref) = &camera.target {
-            let window_entity = match window_ref {
-                WindowRef::Entity(entity) => *entity,
-                WindowRef::Primary => primary_window.get_single().unwrap(),
-            };
-            if let Ok(camera_window) = windows.get(window_entity) {
-                opacity_layers.add_or_update(&camera_entity, camera, camera_window, &mut images);
-            }
+        // Works for windows and images alike
+        if let Some(target_size) = camera.physical_target_size() {
+            opacity_layers.add_or_update(
+                &camera_entity,
+                camera,
+                target_size,
+                max_opacity_layers.count(),
+                &mut images,
+            );
         }
     }
 }
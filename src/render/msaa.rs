@@ -0,0 +1,172 @@
+use bevy::{
+    core_pipeline::blit::{BlitPipeline, BlitPipelineKey},
+    prelude::*,
+    render::{
+        render_resource::{
+            BindGroupEntries, CachedRenderPipelineId, LoadOp, Operations, PipelineCache,
+            RenderPassColorAttachment, RenderPassDescriptor, SpecializedRenderPipelines, StoreOp,
+            TextureDescriptor, TextureDimension, TextureUsages,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::{Msaa, ViewTarget},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::extract::UIExtractedView;
+
+/// The multisample anti-aliasing the UI is drawn with
+///
+/// Multisampling only smooths the edges of the triangles the UI is made of, like the ones of SVGs.
+/// Text and rounded corners are anti-aliased by the shader already, so they look the same either
+/// way.
+///
+/// Every UI camera drawing with more than one sample gets its own multisampled texture the size of
+/// its render target. With four samples that's an extra 16 bytes per pixel (about 33MB for a
+/// 1920x1080 window), twice as much for HDR cameras.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub enum KUiMsaa {
+    /// Draw the UI without multisampling
+    #[default]
+    Off,
+    /// Use the sample count of bevy's [`Msaa`] resource, like the cameras the UI is drawn on
+    Camera,
+    /// Use the given sample count
+    Samples(Msaa),
+}
+
+impl KUiMsaa {
+    /// The number of samples per pixel, given the [`Msaa`] setting of the cameras
+    pub fn samples(&self, camera_msaa: &Msaa) -> u32 {
+        match self {
+            KUiMsaa::Off => 1,
+            KUiMsaa::Camera => camera_msaa.samples(),
+            KUiMsaa::Samples(msaa) => msaa.samples(),
+        }
+    }
+}
+
+pub struct UiMsaaPlugin;
+
+impl Plugin for UiMsaaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KUiMsaa>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app.add_systems(
+            Render,
+            prepare_ui_msaa_textures.in_set(RenderSet::PrepareResources),
+        );
+    }
+}
+
+/// The multisampled texture the UI of a view is drawn to, before being resolved to the view target
+#[derive(Component)]
+pub struct ViewUiMsaa {
+    texture: CachedTexture,
+    blit_pipeline: CachedRenderPipelineId,
+}
+
+fn prepare_ui_msaa_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    blit_pipeline: Res<BlitPipeline>,
+    views: Query<(Entity, &UIExtractedView, &ViewTarget)>,
+) {
+    for (entity, view, target) in views.iter() {
+        if view.msaa_samples <= 1 {
+            commands.entity(entity).remove::<ViewUiMsaa>();
+            continue;
+        }
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ui_msaa_texture"),
+                size: target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: view.msaa_samples,
+                dimension: TextureDimension::D2,
+                format: target.main_texture_format(),
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+        );
+        let blit_pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &blit_pipeline,
+            BlitPipelineKey {
+                texture_format: target.main_texture_format(),
+                blend_state: None,
+                samples: view.msaa_samples,
+            },
+        );
+        commands.entity(entity).insert(ViewUiMsaa {
+            texture,
+            blit_pipeline,
+        });
+    }
+}
+
+impl ViewUiMsaa {
+    /// Copies what's been drawn to the view so far into the multisampled texture, so the UI is
+    /// drawn on top of it
+    pub(crate) fn write_back(
+        &self,
+        world: &World,
+        render_context: &mut RenderContext,
+        target: &ViewTarget,
+    ) {
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(self.blit_pipeline)
+        else {
+            return;
+        };
+        let blit_pipeline = world.resource::<BlitPipeline>();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            Some("ui_msaa_write_back_bind_group"),
+            &blit_pipeline.texture_bind_group,
+            &BindGroupEntries::sequential((target.main_texture_view(), &blit_pipeline.sampler)),
+        );
+
+        let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("ui_msaa_write_back_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &self.texture.default_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            ..Default::default()
+        });
+        pass.set_render_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    /// The attachment the UI is drawn to, resolving into the view's main texture
+    pub(crate) fn color_attachment<'a>(
+        &'a self,
+        target: &'a ViewTarget,
+    ) -> RenderPassColorAttachment<'a> {
+        RenderPassColorAttachment {
+            view: &self.texture.default_view,
+            resolve_target: Some(target.main_texture_view()),
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     render::unified::pipeline::{ExtractedQuad, UIQuadType},
-    styles::{Corner, Edge},
+    styles::{Corner, Edge, NinePatchMode},
 };
 use bevy::{
     math::Vec2,
@@ -14,9 +14,11 @@ pub fn extract_nine_patch(
     handle: Handle<Image>,
     border: Edge<f32>,
     scale: f32,
+    mode: NinePatchMode,
+    tint: Color,
     opacity_layer: u32,
     images: &Assets<Image>,
-    dpi: f32,
+    _dpi: f32,
 ) -> Vec<ExtractedQuad> {
     let mut extracted_quads = Vec::new();
 
@@ -29,12 +31,14 @@ pub fn extract_nine_patch(
     let image_size = image
         .map(|i| {
             Vec2::new(
-                i.texture_descriptor.size.width as f32 * scale,
-                i.texture_descriptor.size.height as f32 * scale,
+                i.texture_descriptor.size.width as f32,
+                i.texture_descriptor.size.height as f32,
             )
         })
-        .unwrap()
-        * dpi;
+        .unwrap();
+
+    // Half a texel, used to keep tiled UVs from sampling neighboring pixels
+    let half_texel = Vec2::splat(0.5) / image_size;
 
     let extracted_quad_template = ExtractedQuad {
         camera_entity,
@@ -42,7 +46,7 @@ pub fn extract_nine_patch(
             min: Vec2::ZERO,
             max: Vec2::ZERO,
         },
-        color: Color::WHITE,
+        color: tint,
         char_id: 0,
         font_handle: None,
         quad_type: UIQuadType::Image,
@@ -55,161 +59,203 @@ pub fn extract_nine_patch(
         ..Default::default()
     };
 
-    let top_uv_min_y = (image_size.y - border.top) / image_size.y;
-    let top_uv_max_y = 1.0;
-    let bottom_uv_min_y = 0.0;
-    let bottom_uv_max_y = border.bottom / image_size.y;
-    let middle_uv_min_y = border.bottom / image_size.y;
-    let middle_uv_max_y = (image_size.y - border.top) / image_size.y;
+    let segments = nine_patch_segments(layout, border, image_size, scale);
+    for (index, segment) in segments.into_iter().enumerate() {
+        let quad = ExtractedQuad {
+            rect: segment.rect,
+            uv_min: Some(segment.uv_min),
+            uv_max: Some(segment.uv_max),
+            ..extracted_quad_template.clone()
+        };
 
-    // TOP
-    let top_left_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(layout.posx, layout.posy),
-            max: Vec2::new(layout.posx + border.left, layout.posy + border.top),
-        },
-        uv_min: Some(Vec2::new(0.0, top_uv_min_y)),
-        uv_max: Some(Vec2::new(border.left / image_size.x, top_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(top_left_quad);
+        // Corners (the even indices) are always drawn at their original size
+        if index % 2 == 0 && index != 4 {
+            extracted_quads.push(quad);
+        } else {
+            push_segment(
+                &mut extracted_quads,
+                quad,
+                mode,
+                image_size * scale,
+                half_texel,
+            );
+        }
+    }
 
-    let top_right_pos_x = (layout.posx + layout.width) - border.left;
-    let top_right_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(top_right_pos_x, layout.posy),
-            max: Vec2::new(top_right_pos_x + border.left, layout.posy + border.top),
-        },
-        uv_min: Some(Vec2::new(
-            (image_size.x - border.left) / image_size.x,
-            top_uv_min_y,
-        )),
-        uv_max: Some(Vec2::new(1.0, top_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(top_right_quad);
+    extracted_quads
+}
 
-    let top_middle_pos_x = layout.posx + border.left;
-    let top_middle_size_x = layout.width - (border.left + border.right);
-    let top_middle_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(top_middle_pos_x, layout.posy),
-            max: Vec2::new(
-                top_middle_pos_x + top_middle_size_x,
-                layout.posy + border.top,
-            ),
-        },
-        uv_min: Some(Vec2::new(border.left / image_size.x, top_uv_min_y)),
-        uv_max: Some(Vec2::new(
-            (image_size.x - border.left) / image_size.x,
-            top_uv_max_y,
-        )),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(top_middle_quad);
+/// A single piece of a nine-patch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct NinePatchSegment {
+    /// Where the segment is drawn
+    pub rect: Rect,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+}
 
-    // Bottom
-    let bottom_y_pos = layout.posy + (layout.height - border.bottom);
-    let bottom_left_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(layout.posx, bottom_y_pos),
-            max: Vec2::new(layout.posx + border.left, bottom_y_pos + border.bottom),
-        },
-        uv_min: Some(Vec2::new(0.0, bottom_uv_min_y)),
-        uv_max: Some(Vec2::new(border.left / image_size.x, bottom_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(bottom_left_quad);
+/// Splits a nine-patch into its segments
+///
+/// The border is given in pixels of the source image and is multiplied by `scale` when
+/// drawn. The segments are returned row by row, starting at the top-left corner:
+/// 0----1----2
+/// |         |
+/// 3    4    5
+/// |         |
+/// 6----7----8
+pub(crate) fn nine_patch_segments(
+    layout: crate::layout::Rect,
+    border: Edge<f32>,
+    image_size: Vec2,
+    scale: f32,
+) -> [NinePatchSegment; 9] {
+    // Edges of each column and row in the source image, measured from the top-left corner
+    let source_x = [0.0, border.left, image_size.x - border.right, image_size.x];
+    let source_y = [0.0, border.top, image_size.y - border.bottom, image_size.y];
 
-    let bottom_right_pos_x = (layout.posx + layout.width) - border.left;
-    let bottom_right_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(bottom_right_pos_x, bottom_y_pos),
-            max: Vec2::new(bottom_right_pos_x + border.left, bottom_y_pos + border.top),
-        },
-        uv_min: Some(Vec2::new(
-            (image_size.x - border.left) / image_size.x,
-            bottom_uv_min_y,
-        )),
-        uv_max: Some(Vec2::new(1.0, bottom_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(bottom_right_quad);
+    // Edges of each column and row on screen
+    let dest_x = [
+        layout.posx,
+        layout.posx + border.left * scale,
+        layout.posx + layout.width - border.right * scale,
+        layout.posx + layout.width,
+    ];
+    let dest_y = [
+        layout.posy,
+        layout.posy + border.top * scale,
+        layout.posy + layout.height - border.bottom * scale,
+        layout.posy + layout.height,
+    ];
 
-    let bottom_middle_pos_x = layout.posx + border.left;
-    let bottom_middle_size_x = layout.width - (border.left + border.right);
-    let bottom_middle_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(bottom_middle_pos_x, bottom_y_pos),
-            max: Vec2::new(
-                bottom_middle_pos_x + bottom_middle_size_x,
-                bottom_y_pos + border.top,
+    std::array::from_fn(|index| {
+        let column = index % 3;
+        let row = index / 3;
+        NinePatchSegment {
+            rect: Rect {
+                min: Vec2::new(dest_x[column], dest_y[row]),
+                max: Vec2::new(dest_x[column + 1], dest_y[row + 1]),
+            },
+            // The shader samples textures with the v-axis pointing up, so the top of a
+            // segment in the image is its `uv_max.y`
+            uv_min: Vec2::new(
+                source_x[column] / image_size.x,
+                1.0 - source_y[row + 1] / image_size.y,
             ),
-        },
-        uv_min: Some(Vec2::new(border.left / image_size.x, bottom_uv_min_y)),
-        uv_max: Some(Vec2::new(
-            (image_size.x - border.left) / image_size.x,
-            bottom_uv_max_y,
-        )),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(bottom_middle_quad);
-
-    // Left + Right center
-    let left_middle_pos_y = layout.posy + border.top;
-    let left_middle_size_y = layout.height - (border.top + border.bottom);
-    let left_middle_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(layout.posx, left_middle_pos_y),
-            max: Vec2::new(
-                layout.posx + border.left,
-                left_middle_pos_y + left_middle_size_y,
+            uv_max: Vec2::new(
+                source_x[column + 1] / image_size.x,
+                1.0 - source_y[row] / image_size.y,
             ),
-        },
-        uv_min: Some(Vec2::new(0.0, middle_uv_min_y)),
-        uv_max: Some(Vec2::new(border.left / image_size.x, middle_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(left_middle_quad);
+        }
+    })
+}
 
-    let right_middle_pos_x = layout.posx + (layout.width - border.right);
-    let right_middle_pos_y = layout.posy + border.top;
-    let right_middle_size_y = layout.height - (border.top + border.bottom);
-    let right_middle_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(right_middle_pos_x, right_middle_pos_y),
-            max: Vec2::new(
-                right_middle_pos_x + border.left,
-                right_middle_pos_y + right_middle_size_y,
-            ),
-        },
-        uv_min: Some(Vec2::new(
-            (image_size.x - border.left) / image_size.x,
-            middle_uv_min_y,
-        )),
-        uv_max: Some(Vec2::new(1.0, middle_uv_max_y)),
-        ..extracted_quad_template.clone()
-    };
-    extracted_quads.push(right_middle_quad);
-
-    // Last quad in middle.
-    let middle_pos_x = layout.posx + border.left;
-    let middle_pos_y = layout.posy + border.top;
-    let middle_size_x = layout.width - (border.left + border.right);
-    let middle_size_y = layout.height - (border.top + border.bottom);
-    let middle_quad = ExtractedQuad {
-        rect: Rect {
-            min: Vec2::new(middle_pos_x, middle_pos_y),
-            max: Vec2::new(middle_pos_x + middle_size_x, middle_pos_y + middle_size_y),
-        },
-        uv_min: Some(Vec2::new(border.left / image_size.x, middle_uv_min_y)),
-        uv_max: Some(Vec2::new(
-            (image_size.x - border.right) / image_size.x,
-            middle_uv_max_y,
-        )),
-        ..extracted_quad_template
-    };
-    extracted_quads.push(middle_quad);
+/// Adds an edge or center segment, repeating it to fill its rect when tiling
+fn push_segment(
+    extracted_quads: &mut Vec<ExtractedQuad>,
+    segment: ExtractedQuad,
+    mode: NinePatchMode,
+    image_size: Vec2,
+    half_texel: Vec2,
+) {
+    let uv_min = segment.uv_min.unwrap_or(Vec2::ZERO);
+    let uv_max = segment.uv_max.unwrap_or(Vec2::ONE);
 
-    extracted_quads
+    let tile_size = (uv_max - uv_min) * image_size;
+    if mode == NinePatchMode::Stretch || tile_size.x <= 0.0 || tile_size.y <= 0.0 {
+        extracted_quads.push(segment);
+        return;
+    }
+
+    let uv_min = uv_min + half_texel;
+    let uv_max = uv_max - half_texel;
+    let uv_size = uv_max - uv_min;
+
+    let mut y = segment.rect.min.y;
+    while y < segment.rect.max.y {
+        let height = tile_size.y.min(segment.rect.max.y - y);
+        // The top of a tile maps to the top of the source region (`uv_max.y`)
+        let tile_uv_min_y = uv_max.y - uv_size.y * (height / tile_size.y);
+
+        let mut x = segment.rect.min.x;
+        while x < segment.rect.max.x {
+            let width = tile_size.x.min(segment.rect.max.x - x);
+            let tile_uv_max_x = uv_min.x + uv_size.x * (width / tile_size.x);
+
+            extracted_quads.push(ExtractedQuad {
+                rect: Rect {
+                    min: Vec2::new(x, y),
+                    max: Vec2::new(x + width, y + height),
+                },
+                uv_min: Some(Vec2::new(uv_min.x, tile_uv_min_y)),
+                uv_max: Some(Vec2::new(tile_uv_max_x, uv_max.y)),
+                ..segment.clone()
+            });
+
+            x += tile_size.x;
+        }
+
+        y += tile_size.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::nine_patch_segments;
+    use crate::styles::Edge;
+    use bevy::math::Vec2;
+
+    #[test]
+    fn segments_should_map_to_source_image() {
+        let layout = crate::layout::Rect {
+            posx: 10.0,
+            posy: 20.0,
+            width: 200.0,
+            height: 100.0,
+            z_index: None,
+        };
+        let border = Edge::new(8.0, 8.0, 4.0, 16.0);
+        let segments = nine_patch_segments(layout, border, Vec2::new(64.0, 32.0), 2.0);
+
+        // (rect min, rect max, uv min, uv max)
+        let expected = [
+            // Top row
+            ((10.0, 20.0), (42.0, 36.0), (0.0, 0.75), (0.25, 1.0)),
+            ((42.0, 20.0), (194.0, 36.0), (0.25, 0.75), (0.875, 1.0)),
+            ((194.0, 20.0), (210.0, 36.0), (0.875, 0.75), (1.0, 1.0)),
+            // Middle row
+            ((10.0, 36.0), (42.0, 112.0), (0.0, 0.125), (0.25, 0.75)),
+            ((42.0, 36.0), (194.0, 112.0), (0.25, 0.125), (0.875, 0.75)),
+            ((194.0, 36.0), (210.0, 112.0), (0.875, 0.125), (1.0, 0.75)),
+            // Bottom row
+            ((10.0, 112.0), (42.0, 120.0), (0.0, 0.0), (0.25, 0.125)),
+            ((42.0, 112.0), (194.0, 120.0), (0.25, 0.0), (0.875, 0.125)),
+            ((194.0, 112.0), (210.0, 120.0), (0.875, 0.0), (1.0, 0.125)),
+        ];
+
+        for (segment, (min, max, uv_min, uv_max)) in segments.iter().zip(expected) {
+            assert_eq!(Vec2::from(min), segment.rect.min);
+            assert_eq!(Vec2::from(max), segment.rect.max);
+            assert_eq!(Vec2::from(uv_min), segment.uv_min);
+            assert_eq!(Vec2::from(uv_max), segment.uv_max);
+        }
+    }
+
+    #[test]
+    fn unscaled_border_should_not_stretch() {
+        let layout = crate::layout::Rect {
+            posx: 0.0,
+            posy: 0.0,
+            width: 300.0,
+            height: 300.0,
+            z_index: None,
+        };
+        let segments = nine_patch_segments(layout, Edge::all(30.0), Vec2::new(120.0, 120.0), 1.0);
+
+        let top_left = segments[0];
+        assert_eq!(Vec2::new(30.0, 30.0), top_left.rect.max - top_left.rect.min);
+        assert_eq!(
+            Vec2::new(30.0, 30.0),
+            (top_left.uv_max - top_left.uv_min) * 120.0
+        );
+    }
 }
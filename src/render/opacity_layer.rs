@@ -10,7 +10,6 @@ use bevy::{
         view::ViewTarget,
     },
     utils::HashMap,
-    window::Window,
 };
 
 /// Keeps track of opacity layer textures.
@@ -24,40 +23,90 @@ impl OpacityLayerManager {
         &mut self,
         camera_entity: &Entity,
         camera: &Camera,
-        window: &Window,
+        target_size: UVec2,
+        opacity_layers: u32,
         images: &mut Assets<Image>,
     ) {
-        if let Some(opacity_camera) = self.camera_layers.get_mut(camera_entity) {
-            opacity_camera.update_images(window, camera, images);
-        } else {
-            self.camera_layers
-                .insert(*camera_entity, OpacityCamera::new(window, camera, images));
+        match self.camera_layers.get_mut(camera_entity) {
+            Some(opacity_camera) if opacity_camera.opacity_layers == opacity_layers => {
+                opacity_camera.update_images(target_size, camera, images);
+            }
+            _ => {
+                self.camera_layers.insert(
+                    *camera_entity,
+                    OpacityCamera::new(target_size, camera, opacity_layers, images),
+                );
+            }
         }
     }
 }
 
+/// The number of opacity layers each UI camera can draw with
+///
+/// Every widget with an opacity below `1.0` is drawn to its own layer before being blended into
+/// its parent, so this limits how many translucent widgets can be drawn at once. Widgets past the
+/// limit are skipped with a warning. Insert this resource before adding the Kayak plugins to
+/// change it.
+///
+/// Each layer is a texture the size of the camera's render target, which is 4 bytes per pixel
+/// (about 8MB for a 1920x1080 window) or twice as much for HDR cameras. Values above
+/// [`MAX_OPACITY_LAYERS`] are clamped and logged as an error.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KUiOpacityLayers(pub u32);
+
+impl Default for KUiOpacityLayers {
+    fn default() -> Self {
+        Self(DEFAULT_OPACITY_LAYERS)
+    }
+}
+
+impl KUiOpacityLayers {
+    /// The number of layers allocated, at most [`MAX_OPACITY_LAYERS`]
+    pub fn count(&self) -> u32 {
+        self.0.min(MAX_OPACITY_LAYERS)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OpacityCamera {
     layers: HashMap<u32, (Extent3d, Handle<Image>)>,
     views: HashMap<u32, TextureView>,
+    opacity_layers: u32,
 }
 
-pub const MAX_OPACITY_LAYERS: u32 = 5;
+/// The number of opacity layers available by default, see [`KUiOpacityLayers`]
+pub const DEFAULT_OPACITY_LAYERS: u32 = 4;
+/// The most opacity layers that can be configured with [`KUiOpacityLayers`]
+pub const MAX_OPACITY_LAYERS: u32 = 32;
+
+/// The layer a widget's backdrop is blurred into before the widget is drawn
+pub(crate) const BACKDROP_LAYER: u32 = u32::MAX - 1;
+/// The layer holding the first (horizontal) pass of a backdrop blur
+pub(crate) const BACKDROP_SCRATCH_LAYER: u32 = u32::MAX;
 
 impl OpacityCamera {
     /// Creates as new opacity layer render target manager
-    pub(crate) fn new(window: &Window, camera: &Camera, images: &mut Assets<Image>) -> Self {
+    ///
+    /// The layers are sized to the physical size of the camera's render target, which can be a
+    /// window or an image.
+    pub(crate) fn new(
+        target_size: UVec2,
+        camera: &Camera,
+        opacity_layers: u32,
+        images: &mut Assets<Image>,
+    ) -> Self {
         let main_texture_format = if camera.hdr {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
             TextureFormat::bevy_default()
         };
 
+        // Opacity layers start at 1 since 0 is the view itself
         let mut layers = HashMap::default();
-        for layer in 1..MAX_OPACITY_LAYERS {
+        for layer in (1..=opacity_layers).chain([BACKDROP_LAYER, BACKDROP_SCRATCH_LAYER]) {
             let size = Extent3d {
-                width: window.resolution.physical_width(),
-                height: window.resolution.physical_height(),
+                width: target_size.x,
+                height: target_size.y,
                 ..Default::default()
             };
             // This is the texture that will be rendered to.
@@ -85,12 +134,18 @@ impl OpacityCamera {
         Self {
             layers,
             views: HashMap::default(),
+            opacity_layers,
         }
     }
 
+    /// The number of opacity layers, numbered from 1
+    pub(crate) fn opacity_layers(&self) -> u32 {
+        self.opacity_layers
+    }
+
     pub(crate) fn update_images(
         &mut self,
-        window: &Window,
+        target_size: UVec2,
         camera: &Camera,
         images: &mut Assets<Image>,
     ) {
@@ -101,8 +156,8 @@ impl OpacityCamera {
         };
 
         let new_size = Extent3d {
-            width: window.resolution.physical_width(),
-            height: window.resolution.physical_height(),
+            width: target_size.x,
+            height: target_size.y,
             ..Default::default()
         };
         for (size, layer_handle) in self.layers.values_mut() {
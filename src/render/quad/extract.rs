@@ -1,5 +1,8 @@
 use crate::{
-    render::unified::pipeline::{ExtractedQuad, UIQuadType},
+    render::{
+        unified::pipeline::{ExtractedQuad, UIQuadType},
+        MAX_BACKDROP_BLUR,
+    },
     styles::{BoxShadow, Corner, Edge},
 };
 use bevy::{
@@ -16,6 +19,7 @@ pub fn extract_quads(
     mut border: Edge<f32>,
     opacity_layer: u32,
     box_shadow: Vec<BoxShadow>,
+    backdrop_blur: f32,
     dpi: f32,
 ) -> Vec<ExtractedQuad> {
     border *= dpi;
@@ -53,6 +57,22 @@ pub fn extract_quads(
         });
     }
 
+    // Opacity layers are drawn before the main pass, so there's no backdrop to blur yet
+    if backdrop_blur > 0.0 && opacity_layer == 0 {
+        extracted_quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min: Vec2::new(layout.posx, layout.posy) * dpi,
+                max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height) * dpi,
+            },
+            color: Color::WHITE,
+            quad_type: UIQuadType::Backdrop,
+            border_radius,
+            blur: backdrop_blur.min(MAX_BACKDROP_BLUR),
+            ..Default::default()
+        });
+    }
+
     // Border
     if border.bottom > 0.0 || border.top > 0.0 || border.right > 0.0 || border.left > 0.0 {
         extracted_quads.push(ExtractedQuad {
@@ -69,6 +89,13 @@ pub fn extract_quads(
         });
     }
 
+    // The background sits inside the border, so its corners follow the inner edge of the border
+    let inner_radius = Corner {
+        top_left: (border_radius.top_left - border.left.max(border.top)).max(0.0),
+        top_right: (border_radius.top_right - border.right.max(border.top)).max(0.0),
+        bottom_left: (border_radius.bottom_left - border.left.max(border.bottom)).max(0.0),
+        bottom_right: (border_radius.bottom_right - border.right.max(border.bottom)).max(0.0),
+    };
     extracted_quads.push(ExtractedQuad {
         camera_entity,
         rect: Rect {
@@ -80,7 +107,7 @@ pub fn extract_quads(
         },
         color: background_color,
         quad_type: UIQuadType::Quad,
-        border_radius,
+        border_radius: inner_radius,
         opacity_layer,
         ..Default::default()
     });
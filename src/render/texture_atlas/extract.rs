@@ -14,6 +14,7 @@ pub fn extract_texture_atlas(
     position: Vec2,
     layout: crate::layout::Rect,
     handle: Handle<Image>,
+    tint: Color,
     opacity_layer: u32,
     images: &Assets<Image>,
     _dpi: f32,
@@ -49,7 +50,7 @@ pub fn extract_texture_atlas(
             (position.x + size.x) / image_size.x,
             1.0 - (position.y / image_size.y),
         )),
-        color: Color::WHITE,
+        color: tint,
         char_id: 0,
         font_handle: None,
         quad_type: UIQuadType::Image,
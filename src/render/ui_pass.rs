@@ -8,6 +8,7 @@ use bevy::render::render_phase::{
 };
 use bevy::render::render_resource::{CachedRenderPipelineId, RenderPassColorAttachment};
 use bevy::render::{
+    camera::ExtractedCamera,
     render_graph::{Node, NodeRunError, RenderGraphContext},
     render_resource::{LoadOp, Operations, RenderPassDescriptor},
     renderer::RenderContext,
@@ -18,7 +19,8 @@ use bevy::utils::FloatOrd;
 
 use crate::CameraUIKayak;
 
-use super::opacity_layer::{OpacityLayerManager, MAX_OPACITY_LAYERS};
+use super::msaa::ViewUiMsaa;
+use super::opacity_layer::OpacityLayerManager;
 use super::unified::pipeline::UIQuadType;
 
 pub trait TransparentUIGeneric {
@@ -180,6 +182,7 @@ pub struct MainPassUINode {
             &'static UIRenderPhase<TransparentOpacityUI>,
             &'static ViewTarget,
             &'static CameraUIKayak,
+            Option<&'static ExtractedCamera>,
         ),
         With<ExtractedView>,
     >,
@@ -207,7 +210,7 @@ impl Node for MainPassUINode {
         let view_entity = graph.view_entity();
         // adapted from bevy itself;
         // see: <https://github.com/bevyengine/bevy/commit/09a3d8abe062984479bf0e99fcc1508bb722baf6>
-        let (transparent_phase, transparent_opacity_phase, target, _camera_ui) =
+        let (transparent_phase, transparent_opacity_phase, target, _camera_ui, camera) =
             match self.query.get_manual(world, view_entity) {
                 Ok(it) => it,
                 _ => return Ok(()),
@@ -223,7 +226,7 @@ impl Node for MainPassUINode {
                 let mut draw_functions = draw_functions.write();
                 draw_functions.prepare(world);
 
-                for layer_id in 1..MAX_OPACITY_LAYERS {
+                for layer_id in 1..=opacity_layer_manager.opacity_layers() {
                     // Start new render pass.
                     let gpu_images = world.get_resource::<RenderAssets<Image>>().unwrap();
                     let image_handle = opacity_layer_manager.get_image_handle(layer_id);
@@ -244,6 +247,9 @@ impl Node for MainPassUINode {
 
                     let mut tracked_pass =
                         render_context.begin_tracked_render_pass(pass_descriptor);
+                    if let Some(viewport) = camera.and_then(|camera| camera.viewport.as_ref()) {
+                        tracked_pass.set_camera_viewport(viewport);
+                    }
 
                     for item in transparent_opacity_phase
                         .items
@@ -257,16 +263,56 @@ impl Node for MainPassUINode {
             }
         }
 
-        // Regular pass
+        // Regular pass, split before every backdrop so it can blur what was drawn so far
         {
-            let pass_descriptor = RenderPassDescriptor {
-                label: Some("main_transparent_pass_UI"),
-                color_attachments: &[Some(target.get_unsampled_color_attachment())],
-                depth_stencil_attachment: None,
-                ..Default::default()
-            };
-            let mut tracked_pass = render_context.begin_tracked_render_pass(pass_descriptor);
-            transparent_phase.render(&mut tracked_pass, world, view_entity);
+            let items = &transparent_phase.items;
+            let mut splits = vec![0];
+            splits.extend(
+                items
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter(|(_, item)| item.quad_type == UIQuadType::Backdrop)
+                    .map(|(index, _)| index),
+            );
+            splits.push(items.len());
+
+            // Multisampled UIs are drawn on top of a copy of the view, and resolved back into it
+            let msaa = world.get::<ViewUiMsaa>(view_entity);
+            if let Some(msaa) = msaa {
+                msaa.write_back(world, render_context, target);
+            }
+
+            for range in splits.windows(2) {
+                let (start, end) = (range[0], range[1]);
+                if let Some(item) = items.get(start) {
+                    if item.quad_type == UIQuadType::Backdrop {
+                        super::backdrop::blur_backdrop(
+                            world,
+                            render_context,
+                            view_entity,
+                            target,
+                            item.entity,
+                        );
+                    }
+                }
+
+                let pass_descriptor = RenderPassDescriptor {
+                    label: Some("main_transparent_pass_UI"),
+                    color_attachments: &[Some(match msaa {
+                        Some(msaa) => msaa.color_attachment(target),
+                        None => target.get_unsampled_color_attachment(),
+                    })],
+                    depth_stencil_attachment: None,
+                    ..Default::default()
+                };
+                let mut tracked_pass = render_context.begin_tracked_render_pass(pass_descriptor);
+                // Only draw into the camera's viewport so several UIs can share a window
+                if let Some(viewport) = camera.and_then(|camera| camera.viewport.as_ref()) {
+                    tracked_pass.set_camera_viewport(viewport);
+                }
+                transparent_phase.render_range(&mut tracked_pass, world, view_entity, start..end);
+            }
         }
 
         Ok(())
@@ -137,6 +137,6 @@ pub fn queue_vertices(
     mut quad_meta: ResMut<QuadMeta>,
 ) {
     quad_meta
-        .vertices
+        .instances
         .write_buffer(&render_device, &render_queue);
 }
@@ -1,5 +1,7 @@
 use bevy::ecs::query::ROQueryItem;
 use bevy::ecs::system::{SystemParam, SystemParamItem};
+#[cfg(feature = "svg")]
+use bevy::math::{Mat4, Quat, Vec4};
 use bevy::prelude::{Commands, Rect, Resource, With};
 #[cfg(feature = "svg")]
 use bevy::prelude::{Mesh, Vec3};
@@ -11,13 +13,13 @@ use bevy::render::render_phase::{
 };
 use bevy::render::render_resource::{
     CachedRenderPipelineId, DynamicUniformBuffer, ShaderType, SpecializedRenderPipeline,
-    SpecializedRenderPipelines,
+    SpecializedRenderPipelines, TextureViewId,
 };
 use bevy::render::view::ViewTarget;
 use bevy::utils::FloatOrd;
 use bevy::{
     ecs::system::lifetimeless::{Read, SRes},
-    math::{Mat4, Quat, Vec2, Vec4},
+    math::Vec2,
     prelude::{Component, Entity, FromWorld, Handle, Query, Res, ResMut, World},
     render::{
         color::Color,
@@ -47,8 +49,9 @@ use std::marker::PhantomData;
 use super::UNIFIED_SHADER_HANDLE;
 use crate::layout::LayoutCache;
 use crate::prelude::Corner;
+use crate::render::backdrop::BackdropBlur;
 use crate::render::extract::{UIExtractedView, UIViewUniform, UIViewUniformOffset, UIViewUniforms};
-use crate::render::opacity_layer::OpacityLayerManager;
+use crate::render::opacity_layer::{OpacityLayerManager, BACKDROP_LAYER};
 #[cfg(feature = "svg")]
 use crate::render::svg::RenderSvgs;
 use crate::render::ui_pass::{
@@ -64,23 +67,8 @@ pub struct UnifiedPipeline {
     default_image: (GpuImage, BindGroup),
 }
 
-// const QUAD_VERTEX_POSITIONS: &[Vec3] = &[
-//     Vec3::from_array([0.0, 1.0, 0.0]),
-//     Vec3::from_array([1.0, 0.0, 0.0]),
-//     Vec3::from_array([0.0, 0.0, 0.0]),
-//     Vec3::from_array([0.0, 1.0, 0.0]),
-//     Vec3::from_array([1.0, 1.0, 0.0]),
-//     Vec3::from_array([1.0, 0.0, 0.0]),
-// ];
-
-const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
-
-const QUAD_VERTEX_POSITIONS: [Vec2; 4] = [
-    Vec2::new(0.0, 0.0),
-    Vec2::new(1.0, 0.0),
-    Vec2::new(1.0, 1.0),
-    Vec2::new(0.0, 1.0),
-];
+/// The number of vertices drawn for each [`QuadInstance`]
+const QUAD_INSTANCE_VERTICES: u32 = 6;
 
 #[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct UnifiedPipelineKey {
@@ -252,30 +240,45 @@ impl SpecializedRenderPipeline for UnifiedPipeline {
     type Key = UnifiedPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let vertex_buffer_layout = VertexBufferLayout {
-            array_stride: 60,
-            step_mode: VertexStepMode::Vertex,
+        let instance_buffer_layout = VertexBufferLayout {
+            array_stride: std::mem::size_of::<QuadInstance>() as u64,
+            step_mode: VertexStepMode::Instance,
             attributes: vec![
                 VertexAttribute {
-                    format: VertexFormat::Float32x3,
+                    format: VertexFormat::Float32x4,
                     offset: 0,
                     shader_location: 0,
                 },
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
-                    offset: 12,
+                    offset: 16,
                     shader_location: 1,
                 },
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
-                    offset: 28,
+                    offset: 32,
                     shader_location: 2,
                 },
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
-                    offset: 44,
+                    offset: 48,
                     shader_location: 3,
                 },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 64,
+                    shader_location: 4,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 80,
+                    shader_location: 5,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 96,
+                    shader_location: 6,
+                },
             ],
         };
 
@@ -284,7 +287,7 @@ impl SpecializedRenderPipeline for UnifiedPipeline {
                 shader: UNIFIED_SHADER_HANDLE,
                 entry_point: "vertex".into(),
                 shader_defs: vec![],
-                buffers: vec![vertex_buffer_layout],
+                buffers: vec![instance_buffer_layout],
             },
             fragment: Some(FragmentState {
                 shader: UNIFIED_SHADER_HANDLE,
@@ -348,6 +351,8 @@ pub enum UIQuadType {
     Clip,
     OpacityLayer,
     DrawOpacityLayer,
+    /// The blurred contents behind a widget, see [`KStyle::backdrop_blur`](crate::styles::KStyle::backdrop_blur)
+    Backdrop,
     None,
 }
 
@@ -363,6 +368,7 @@ impl UIQuadType {
             UIQuadType::None => 100001,
             UIQuadType::OpacityLayer => 100002,
             UIQuadType::DrawOpacityLayer => quad_type_offsets.image_type_offset,
+            UIQuadType::Backdrop => quad_type_offsets.backdrop_type_offset,
         }
     }
 }
@@ -386,6 +392,15 @@ pub struct ExtractedQuad {
     pub svg_handle: (Option<Handle<Svg>>, Option<Color>),
     pub opacity_layer: u32,
     pub c: char,
+    /// How far the edge of a glyph is pushed outward, in SDF units
+    ///
+    /// Text outlines are drawn as a copy of the glyphs with a wider edge behind the text.
+    pub outline_width: f32,
+    /// How far the edge of a glyph is softened, in pixels
+    ///
+    /// Text shadows are drawn as a blurred copy of the glyphs behind the text. For
+    /// [`UIQuadType::Backdrop`] quads, this is the radius of the backdrop blur instead.
+    pub blur: f32,
 }
 
 impl Default for ExtractedQuad {
@@ -408,21 +423,41 @@ impl Default for ExtractedQuad {
             svg_handle: Default::default(),
             opacity_layer: 0,
             c: ' ',
+            outline_width: 0.0,
+            blur: 0.0,
         }
     }
 }
 
+/// The per-instance data of a drawn quad
+///
+/// The vertex shader expands every instance into the two triangles of its quad, so each quad
+/// only uploads this once instead of six full vertices.
+///
+/// SVG meshes aren't made of quads, so each of their triangles is drawn as its own instance
+/// with `data[1]` set to `1.0`. Its corners are then stored in `uv` and `border_radius[0..2]`.
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct QuadVertex {
-    pub position: [f32; 3],
+pub struct QuadInstance {
+    /// The position of the top left corner and the size of the quad
+    pub pos_size: [f32; 4],
     pub color: [f32; 4],
+    /// The min and max uv
     pub uv: [f32; 4],
-    pub pos_size: [f32; 4],
+    /// The border radius of the top left, top right, bottom right and bottom left corners
+    pub border_radius: [f32; 4],
+    /// The layer of the glyph within the font texture array, whether this is a triangle and the
+    /// outline width and blur of the glyph
+    pub data: [f32; 4],
+    /// The min and max corners of the clip the quad is drawn in
+    pub clip: [f32; 4],
+    /// The border radius of the top left, top right, bottom right and bottom left corners of the
+    /// clip the quad is drawn in
+    pub clip_radius: [f32; 4],
 }
 
-unsafe impl Zeroable for QuadVertex {}
-unsafe impl Pod for QuadVertex {}
+unsafe impl Zeroable for QuadInstance {}
+unsafe impl Pod for QuadInstance {}
 
 #[repr(C)]
 #[derive(Copy, Clone, ShaderType)]
@@ -435,7 +470,7 @@ struct QuadType {
 
 #[derive(Resource)]
 pub struct QuadMeta {
-    pub vertices: BufferVec<QuadVertex>,
+    pub instances: BufferVec<QuadInstance>,
     types_buffer: DynamicUniformBuffer<QuadType>,
     types_bind_group: Option<BindGroup>,
 }
@@ -443,7 +478,7 @@ pub struct QuadMeta {
 impl Default for QuadMeta {
     fn default() -> Self {
         Self {
-            vertices: BufferVec::new(BufferUsages::VERTEX),
+            instances: BufferVec::new(BufferUsages::VERTEX),
             types_buffer: DynamicUniformBuffer::default(),
             types_bind_group: None,
         }
@@ -489,6 +524,17 @@ impl ExtractedQuads {
         self.parents.clear();
     }
 
+    /// Whether any quad is drawn with a material.
+    /// Material quads live on render world entities, which don't survive to the next frame.
+    pub(crate) fn has_materials(&self) -> bool {
+        self.layers.iter().any(|layer| {
+            layer
+                .quads
+                .iter()
+                .any(|quad| matches!(quad, QuadOrMaterial::Material(..)))
+        })
+    }
+
     pub fn push(&mut self, quad: QuadOrMaterial) {
         let layer = self.layers.get_mut(self.current_layer).unwrap();
         layer.quads.push(quad);
@@ -681,6 +727,8 @@ pub struct ZLayer {
 pub struct QuadBatch {
     pub image_handle_id: Option<Handle<Image>>,
     pub font_handle_id: Option<Handle<KayakFont>>,
+    /// The page of the font texture the glyphs of this batch are stored in
+    pub font_page: u32,
     pub quad_type: UIQuadType,
     pub type_id: u32,
     pub z_index: f32,
@@ -689,7 +737,7 @@ pub struct QuadBatch {
 #[derive(Default, Resource)]
 pub struct ImageBindGroups {
     values: HashMap<Handle<Image>, BindGroup>,
-    font_values: HashMap<Handle<KayakFont>, BindGroup>,
+    font_values: HashMap<(Handle<KayakFont>, u32), (TextureViewId, BindGroup)>,
     previous_sizes: HashMap<Handle<Image>, Vec2>,
 }
 
@@ -739,6 +787,7 @@ pub struct QuadTypeOffsets {
     pub text_type_offset: u32,
     pub image_type_offset: u32,
     pub box_shadow_type_offset: u32,
+    pub backdrop_type_offset: u32,
 }
 
 pub fn queue_quad_types(
@@ -780,12 +829,19 @@ pub fn queue_quad_types(
         _padding_2: 0,
         _padding_3: 0,
     });
+    let backdrop_type_offset = quad_meta.types_buffer.push(&QuadType {
+        t: 5,
+        _padding_1: 0,
+        _padding_2: 0,
+        _padding_3: 0,
+    });
     let quad_type_offsets = QuadTypeOffsets {
         quad_type_offset,
         text_sub_pixel_type_offset,
         text_type_offset,
         image_type_offset,
         box_shadow_type_offset,
+        backdrop_type_offset,
     };
     commands.insert_resource(quad_type_offsets);
 
@@ -814,6 +870,7 @@ pub struct PreviousClip {
 pub struct PreviousIndex {
     pub index: u32,
     pub last_clip: Rect,
+    pub last_clip_radius: Corner<f32>,
 }
 
 #[derive(SystemParam)]
@@ -899,29 +956,30 @@ pub fn queue_quads(queue_quads: QueueQuads) {
         return;
     }
 
-    quad_meta.vertices.clear();
-    quad_meta.vertices.reserve(
-        extracted_sprite_len * QUAD_VERTEX_POSITIONS.len(),
-        &render_device,
-    );
+    quad_meta.instances.clear();
+    quad_meta
+        .instances
+        .reserve(extracted_sprite_len, &render_device);
 
     // Sort sprites by z for correct transparency and then by handle to improve batching
     // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
     let mut current_batch = QuadBatch {
         image_handle_id: None,
         font_handle_id: None,
+        font_page: 0,
         quad_type: UIQuadType::None,
         type_id: quad_type_offsets.quad_type_offset,
         z_index: 0.0,
     };
     let mut current_batch_entity = Entity::PLACEHOLDER;
 
-    // Vertex buffer indices
+    // Instance buffer indices
     let mut index = 0;
     let mut item_start = 0;
     let mut item_end = 0;
     let mut old_item_start = 0;
     let mut current_clip = Rect::default();
+    let mut current_clip_radius = Corner::default();
     let mut last_clip = Rect::default();
 
     let draw_quad = draw_functions.read().get_id::<DrawUI>().unwrap();
@@ -933,10 +991,16 @@ pub fn queue_quads(queue_quads: QueueQuads) {
         views.iter_mut()
     {
         let key = UnifiedPipelineKey {
-            msaa: 1,
+            msaa: view.msaa_samples,
             hdr: view.hdr,
         };
         let spec_pipeline = pipelines.specialize(&pipeline_cache, &quad_pipeline, key);
+        // Opacity layers are never multisampled
+        let opacity_spec_pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &quad_pipeline,
+            UnifiedPipelineKey { msaa: 1, ..key },
+        );
 
         let mut last_quad = ExtractedQuad::default();
 
@@ -960,6 +1024,7 @@ pub fn queue_quads(queue_quads: QueueQuads) {
                 draw_opacity_quad,
                 draw_quad,
                 spec_pipeline,
+                opacity_spec_pipeline,
                 &mut quad_meta,
                 quad,
                 camera_entity,
@@ -971,6 +1036,7 @@ pub fn queue_quads(queue_quads: QueueQuads) {
                 &mut item_end,
                 &last_quad,
                 &mut current_clip,
+                &mut current_clip_radius,
                 &mut old_item_start,
                 &mut last_clip,
             );
@@ -991,7 +1057,7 @@ pub fn queue_quads(queue_quads: QueueQuads) {
             if last_quad.opacity_layer > 0 && last_quad.quad_type != UIQuadType::DrawOpacityLayer {
                 opacity_transparent_phase.add(TransparentOpacityUI {
                     draw_function: draw_opacity_quad,
-                    pipeline: spec_pipeline,
+                    pipeline: opacity_spec_pipeline,
                     entity: current_batch_entity,
                     sort_key: FloatOrd(last_quad.z_index),
                     quad_type: last_quad.quad_type,
@@ -1018,11 +1084,12 @@ pub fn queue_quads(queue_quads: QueueQuads) {
     }
 
     quad_meta
-        .vertices
+        .instances
         .write_buffer(&render_device, &render_queue);
 
     prev_index.index = index;
     prev_index.last_clip = last_clip;
+    prev_index.last_clip_radius = current_clip_radius;
 }
 
 pub fn queue_quads_inner(
@@ -1039,6 +1106,7 @@ pub fn queue_quads_inner(
     draw_opacity_quad: DrawFunctionId,
     draw_quad: DrawFunctionId,
     spec_pipeline: CachedRenderPipelineId,
+    opacity_spec_pipeline: CachedRenderPipelineId,
     quad_meta: &mut QuadMeta,
     quad: &ExtractedQuad,
     camera_entity: Entity,
@@ -1050,6 +1118,7 @@ pub fn queue_quads_inner(
     item_end: &mut u32,
     old_quad: &ExtractedQuad,
     current_clip: &mut Rect,
+    current_clip_radius: &mut Corner<f32>,
     old_item_start: &mut u32,
     last_clip: &mut Rect,
 ) {
@@ -1071,11 +1140,24 @@ pub fn queue_quads_inner(
     if quad.quad_type == UIQuadType::Clip {
         // *last_clip = *current_clip;
         *current_clip = quad.rect;
+        *current_clip_radius = quad.border_radius;
     }
 
+    // Glyphs are stored in one texture array layer each, split into pages of `MAX_CHARACTERS`
+    let (font_page, glyph_layer) = if let Some(font_handle) = quad.font_handle.as_ref() {
+        if let Some(location) = font_texture_cache.get_glyph_location(font_handle, quad.char_id) {
+            location
+        } else {
+            return;
+        }
+    } else {
+        (0, quad.char_id)
+    };
+
     let mut new_batch = QuadBatch {
         image_handle_id: quad.image.clone(),
         font_handle_id: quad.font_handle.clone(),
+        font_page,
         quad_type: quad.quad_type,
         type_id: quad.quad_type.get_type_index(&quad_type_offsets),
         z_index: quad.z_index,
@@ -1086,7 +1168,10 @@ pub fn queue_quads_inner(
     if (new_batch != *current_batch || current_batch.quad_type != quad.quad_type)
         || old_quad.quad_type == UIQuadType::Clip
         || quad.quad_type == UIQuadType::Clip
-        || matches!(new_batch.quad_type, UIQuadType::DrawOpacityLayer)
+        || matches!(
+            new_batch.quad_type,
+            UIQuadType::DrawOpacityLayer | UIQuadType::Backdrop
+        )
     {
         if *current_batch_entity != Entity::PLACEHOLDER
             && old_quad.quad_type != UIQuadType::Clip
@@ -1107,7 +1192,7 @@ pub fn queue_quads_inner(
             if old_quad.opacity_layer > 0 && old_quad.quad_type != UIQuadType::DrawOpacityLayer {
                 opacity_transparent_phase.add(TransparentOpacityUI {
                     draw_function: draw_opacity_quad,
-                    pipeline: spec_pipeline,
+                    pipeline: opacity_spec_pipeline,
                     entity: *current_batch_entity,
                     sort_key: FloatOrd(old_quad.z_index),
                     quad_type: old_quad.quad_type,
@@ -1175,45 +1260,60 @@ pub fn queue_quads_inner(
         }
 
         if let Some(font_handle) = quad.font_handle.as_ref() {
-            if let Some(gpu_image) = font_texture_cache.get_gpu_image(font_handle, gpu_images) {
+            if let Some(gpu_image) =
+                font_texture_cache.get_gpu_image(font_handle, font_page, gpu_images)
+            {
                 new_batch.font_handle_id = Some(font_handle.clone_weak());
-                image_bind_groups
+                let key = (font_handle.clone_weak(), font_page);
+                // A reloaded font is uploaded to a new texture
+                let is_stale = image_bind_groups
                     .font_values
-                    .entry(font_handle.clone_weak())
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(
-                            Some("ui_text_bind_group"),
-                            &unified_pipeline.image_layout,
-                            &[
-                                BindGroupEntry {
-                                    binding: 0,
-                                    resource: BindingResource::TextureView(
-                                        &unified_pipeline.default_image.0.texture_view,
-                                    ),
-                                },
-                                BindGroupEntry {
-                                    binding: 1,
-                                    resource: BindingResource::Sampler(
-                                        &unified_pipeline.default_image.0.sampler,
-                                    ),
-                                },
-                                BindGroupEntry {
-                                    binding: 2,
-                                    resource: BindingResource::TextureView(&gpu_image.texture_view),
-                                },
-                                BindGroupEntry {
-                                    binding: 3,
-                                    resource: BindingResource::Sampler(&gpu_image.sampler),
-                                },
-                            ],
-                        )
-                    });
+                    .get(&key)
+                    .is_some_and(|(view_id, _)| *view_id != gpu_image.texture_view.id());
+                if is_stale {
+                    image_bind_groups.font_values.remove(&key);
+                }
+                image_bind_groups.font_values.entry(key).or_insert_with(|| {
+                    let bind_group = render_device.create_bind_group(
+                        Some("ui_text_bind_group"),
+                        &unified_pipeline.image_layout,
+                        &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(
+                                    &unified_pipeline.default_image.0.texture_view,
+                                ),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(
+                                    &unified_pipeline.default_image.0.sampler,
+                                ),
+                            },
+                            BindGroupEntry {
+                                binding: 2,
+                                resource: BindingResource::TextureView(&gpu_image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 3,
+                                resource: BindingResource::Sampler(&gpu_image.sampler),
+                            },
+                        ],
+                    );
+                    (gpu_image.texture_view.id(), bind_group)
+                });
             }
         }
 
-        if quad.quad_type == UIQuadType::DrawOpacityLayer {
+        // The backdrop is blurred into its own layer while the UI is drawn
+        let layer_id = match quad.quad_type {
+            UIQuadType::DrawOpacityLayer => Some(quad.opacity_layer),
+            UIQuadType::Backdrop => Some(BACKDROP_LAYER),
+            _ => None,
+        };
+        if let Some(layer_id) = layer_id {
             if let Some(layer) = opacity_layers.camera_layers.get(&camera_entity) {
-                let image_handle = layer.get_image_handle(quad.opacity_layer);
+                let image_handle = layer.get_image_handle(layer_id);
                 if let Some(gpu_image) = gpu_images.get(&image_handle) {
                     let new_image = if let Some(prev_size) =
                         image_bind_groups.previous_sizes.get(&image_handle)
@@ -1283,12 +1383,26 @@ pub fn queue_quads_inner(
         {
             *current_batch_entity = commands.spawn(current_batch.clone()).id();
         }
+        if current_batch.quad_type == UIQuadType::Backdrop {
+            commands.entity(*current_batch_entity).insert(BackdropBlur {
+                rect: quad.rect,
+                radius: quad.blur,
+            });
+        }
     }
 
     if matches!(current_batch.quad_type, UIQuadType::Clip) {
         return;
     }
 
+    let clip = [
+        current_clip.min.x,
+        current_clip.min.y,
+        current_clip.max.x,
+        current_clip.max.y,
+    ];
+    let clip_radius = current_clip_radius.into_clockwise_array();
+
     #[cfg(feature = "svg")]
     if let (Some(svg_handle), color) = (quad.svg_handle.0.as_ref(), quad.svg_handle.1.as_ref()) {
         if let Some((svg, mesh)) = render_svgs.get(&svg_handle.id()) {
@@ -1305,42 +1419,53 @@ pub fn queue_quads_inner(
                 _ => None,
             }
             .unwrap();
-            let indices = mesh.indices().unwrap();
+            let indices = mesh.indices().unwrap().iter().collect::<Vec<_>>();
+            let world = Mat4::from_scale_rotation_translation(
+                Vec3::new(svg_scale_x, svg_scale_y, 1.0), //sprite_rect.size().extend(1.0),
+                Quat::default(),
+                sprite_rect.min.extend(0.0),
+            );
 
-            for index in indices.iter() {
-                let position = positions[index];
+            let mut triangle_count = 0;
+            for triangle in indices.chunks_exact(3) {
+                let corners = triangle
+                    .iter()
+                    .map(|index| {
+                        let position = positions[*index];
+                        (world
+                            * Vec4::new(
+                                position[0],  // - 34.5,
+                                -position[1], // - 95.0,
+                                position[2],
+                                1.0,
+                            ))
+                        .truncate()
+                    })
+                    .collect::<Vec<_>>();
+                // Each path of the mesh has a single color, so the first vertex speaks for the triangle
                 let color = if let Some(color) = color {
                     [color.r(), color.g(), color.b(), color.a()]
                 } else {
-                    colors[index]
+                    colors[triangle[0]]
                 };
-                let world = Mat4::from_scale_rotation_translation(
-                    Vec3::new(svg_scale_x, svg_scale_y, 1.0), //sprite_rect.size().extend(1.0),
-                    Quat::default(),
-                    sprite_rect.min.extend(0.0),
-                );
-                let final_position = (world
-                    * Vec4::new(
-                        position[0],  // - 34.5,
-                        -position[1], // - 95.0,
-                        position[2],
-                        1.0,
-                    ))
-                .truncate();
-
-                quad_meta.vertices.push(QuadVertex {
-                    position: final_position.into(),
-                    color,
-                    uv: [0.0; 4],
+
+                quad_meta.instances.push(QuadInstance {
                     pos_size: [
                         sprite_rect.min.x,
                         sprite_rect.min.y,
                         sprite_rect.size().x,
                         new_height,
                     ],
+                    color,
+                    uv: [corners[0].x, corners[0].y, corners[1].x, corners[1].y],
+                    border_radius: [corners[2].x, corners[2].y, 0.0, 0.0],
+                    data: [0.0, 1.0, 0.0, 0.0],
+                    clip,
+                    clip_radius,
                 });
+                triangle_count += 1;
             }
-            *index += indices.len() as u32;
+            *index += triangle_count;
             *item_end = *index;
         }
         return;
@@ -1351,71 +1476,22 @@ pub fn queue_quads_inner(
     let uv_min = quad.uv_min.unwrap_or(Vec2::ZERO);
     let uv_max = quad.uv_max.unwrap_or(Vec2::ONE);
 
-    let bottom_left = Vec4::new(
-        uv_min.x,
-        uv_min.y,
-        quad.char_id as f32,
-        quad.border_radius.bottom_left,
-    );
-    let top_left = Vec4::new(
-        uv_min.x,
-        uv_max.y,
-        quad.char_id as f32,
-        quad.border_radius.top_left,
-    );
-    let top_right = Vec4::new(
-        uv_max.x,
-        uv_max.y,
-        quad.char_id as f32,
-        quad.border_radius.top_right,
-    );
-    let bottom_right = Vec4::new(
-        uv_max.x,
-        uv_min.y,
-        quad.char_id as f32,
-        quad.border_radius.bottom_right,
-    );
-
-    let uvs: [[f32; 4]; 6] = [
-        top_left.into(),
-        bottom_right.into(),
-        bottom_left.into(),
-        top_left.into(),
-        top_right.into(),
-        bottom_right.into(),
-    ];
-
-    const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
-
-    const QUAD_VERTEX_POSITIONS: [Vec2; 4] = [
-        Vec2::new(0.0, 0.0),
-        Vec2::new(1.0, 0.0),
-        Vec2::new(1.0, 1.0),
-        Vec2::new(0.0, 1.0),
-    ];
-
-    for (index, vertex_index) in QUAD_INDICES.iter().enumerate() {
-        let vertex_position = QUAD_VERTEX_POSITIONS[*vertex_index];
-        let world = Mat4::from_scale_rotation_translation(
-            sprite_rect.size().extend(1.0),
-            Quat::default(),
-            sprite_rect.min.extend(0.0),
-        );
-        let final_position = (world * vertex_position.extend(0.0).extend(1.0)).truncate();
-        quad_meta.vertices.push(QuadVertex {
-            position: final_position.into(),
-            color,
-            uv: uvs[index],
-            pos_size: [
-                sprite_rect.min.x,
-                sprite_rect.min.y,
-                sprite_rect.size().x,
-                sprite_rect.size().y,
-            ],
-        });
-    }
+    quad_meta.instances.push(QuadInstance {
+        pos_size: [
+            sprite_rect.min.x,
+            sprite_rect.min.y,
+            sprite_rect.size().x,
+            sprite_rect.size().y,
+        ],
+        color,
+        uv: [uv_min.x, uv_min.y, uv_max.x, uv_max.y],
+        border_radius: quad.border_radius.into_clockwise_array(),
+        data: [glyph_layer as f32, 0.0, quad.outline_width, quad.blur],
+        clip,
+        clip_radius,
+    });
 
-    *index += QUAD_INDICES.len() as u32;
+    *index += 1;
     *item_end = *index;
 }
 
@@ -1476,8 +1552,13 @@ impl<T: PhaseItem + TransparentUIGeneric> RenderCommand<T> for DrawUIDraw<T> {
         let (quad_meta, unified_pipeline, image_bind_groups) = param;
 
         let quad_meta = quad_meta.into_inner();
+        // The rect is relative to the camera's viewport, scissor rects to the whole render target
         let window_size = (view.viewport.z as f32, view.viewport.w as f32);
-        let rect = item.get_rect();
+        let item_rect = item.get_rect();
+        let rect = Rect {
+            min: item_rect.min * view.ui_scale,
+            max: item_rect.max * view.ui_scale,
+        };
         let x = rect.min.x as u32;
         let y = rect.min.y as u32;
         let mut width = rect.width() as u32;
@@ -1492,11 +1573,18 @@ impl<T: PhaseItem + TransparentUIGeneric> RenderCommand<T> for DrawUIDraw<T> {
             if y + height >= window_size.1 as u32 {
                 height = window_size.1 as u32 - y;
             }
-            pass.set_scissor_rect(x, y, width, height);
+            pass.set_scissor_rect(view.viewport.x + x, view.viewport.y + y, width, height);
         }
 
-        let vertices_slice = quad_meta.vertices.buffer().unwrap().slice(..);
-        pass.set_vertex_buffer(0, vertices_slice);
+        // Offset the buffer rather than the instances since WebGL doesn't support a first instance
+        let batch_range = item.batch_range();
+        let instance_size = std::mem::size_of::<QuadInstance>() as u64;
+        let instances_slice = quad_meta
+            .instances
+            .buffer()
+            .unwrap()
+            .slice(batch_range.start as u64 * instance_size..);
+        pass.set_vertex_buffer(0, instances_slice);
 
         pass.set_bind_group(
             2,
@@ -1513,16 +1601,19 @@ impl<T: PhaseItem + TransparentUIGeneric> RenderCommand<T> for DrawUIDraw<T> {
             } else {
                 pass.set_bind_group(1, &unified_pipeline.default_image.1, &[]);
             }
-        } else if let Some(bind_group) = batch
-            .font_handle_id
-            .as_ref()
-            .and_then(|h| image_bind_groups.font_values.get(h))
-        {
+        } else if let Some((_, bind_group)) = batch.font_handle_id.as_ref().and_then(|h| {
+            image_bind_groups
+                .font_values
+                .get(&(h.clone_weak(), batch.font_page))
+        }) {
             pass.set_bind_group(1, bind_group, &[]);
         } else {
             pass.set_bind_group(1, &unified_pipeline.default_image.1, &[]);
         }
-        pass.draw(item.batch_range().clone(), 0..1);
+        pass.draw(
+            0..QUAD_INSTANCE_VERTICES,
+            0..(batch_range.end - batch_range.start),
+        );
 
         RenderCommandResult::Success
     }
@@ -10,6 +10,34 @@ fn sdRoundBox(p: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
     return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - r;
 }
 
+// How much of the pixel at P (relative to the top left corner) is covered by a rounded box of the
+// given size, anti-aliased over a single pixel. Boxes without a radius keep their sharp edges.
+fn rounded_box_alpha(p: vec2<f32>, size: vec2<f32>, radius: f32) -> f32 {
+    let half_size = size * 0.5;
+    // Lock border to max size. This is similar to how HTML/CSS handles border radius.
+    let r = clamp(radius, 0.0, min(half_size.x, half_size.y));
+    let dist = sdRoundBox(p - half_size, half_size, r);
+    let pixel = max(length(vec2(dpdx(dist), dpdy(dist))), 0.0001);
+    let alpha = clamp(0.5 - dist / pixel, 0.0, 1.0);
+    return select(alpha, step(dist, 0.0), r <= 0.0);
+}
+
+// How much of the fragment lies within the clip it's drawn in. Only the rounded corners of the
+// clip are taken into account, anti-aliased over a single pixel.
+fn clip_alpha(in: VertexOutput) -> f32 {
+    let size = in.clip.zw - in.clip.xy;
+    let half_size = size * 0.5;
+    let p = in.world_position - in.clip.xy - half_size;
+    let radius = select(
+        select(in.clip_radius.x, in.clip_radius.y, p.x > 0.0),
+        select(in.clip_radius.w, in.clip_radius.z, p.x > 0.0),
+        p.y > 0.0,
+    );
+    let r = clamp(radius, 0.0, min(half_size.x, half_size.y));
+    let dist = sdRoundBox(p, half_size, r);
+    return select(clamp(0.5 - dist, 0.0, 1.0), 1.0, r <= 0.0);
+}
+
 fn median_three(v: vec3<f32>) -> f32 {
     return max(min(v.x, v.y), min(max(v.x, v.y), v.z));
 }
@@ -31,17 +59,8 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
     var output_color = vec4<f32>(0.0);
     // Quad
     if quad_type.t == 0 {
-        var size = in.size;
-        var pos = in.pos.xy * 2.0;
-        // Lock border to max size. This is similar to how HTML/CSS handles border radius.
-        var bs = min(in.border_radius * 2.0, min(size.x, size.y));
-        var rect_dist = sdRoundBox(
-            pos - size,
-            size,
-            bs,
-        );
-        rect_dist = 1.0 - smoothstep(0.0, fwidth(rect_dist), rect_dist);
-        output_color = vec4<f32>(in.color.rgb, rect_dist * in.color.a);
+        let alpha = rounded_box_alpha(in.pos.xy, in.size, in.border_radius);
+        output_color = vec4<f32>(in.color.rgb, alpha * in.color.a);
     }
     // Subpixel text
     if quad_type.t == 1 {
@@ -66,8 +85,9 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         var tex_dimensions = textureDimensions(font_texture);
         let sd = sample_sdf(vec2(in.uv.x, 1.0 - in.uv.y), i32(in.uv.z), 0.0);
         let dxdy = fwidth(in.uv.xy) * vec2(f32(tex_dimensions.x), f32(tex_dimensions.y));
-        let dist = sd + min(0.001, 0.5 - 1.0 / px_range) - 0.5;
-        let alpha = clamp(dist * px_range / length(dxdy) + 0.5, 0.0, 1.0);
+        let dist = sd + min(0.001, 0.5 - 1.0 / px_range) - 0.5 + in.outline_width;
+        // Blurring widens the transition from inside to outside the glyph
+        let alpha = clamp(dist * px_range / length(dxdy) / (1.0 + in.blur) + 0.5, 0.0, 1.0);
         output_color = vec4(in.color.rgb, in.color.a * alpha);
     }
     // Image
@@ -96,6 +116,14 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         let color = in.color.rgb;// - vec3(rect_dist);
         output_color = vec4(color, in.color.a * rect_dist * 1.42);
     }
+    // Backdrop
+    if quad_type.t == 5 {
+        let mask = rounded_box_alpha(in.pos.xy, in.size, in.border_radius);
+        // The blurred backdrop covers the whole render target, so it's sampled at this fragment
+        let uv = in.position.xy / vec2<f32>(textureDimensions(image_texture));
+        let color = textureSample(image_texture, image_sampler, uv);
+        output_color = vec4<f32>(color.rgb * in.color.rgb, in.color.a * mask);
+    }
 
     return output_color;
 }
@@ -2,27 +2,87 @@
 
 #import kayak_ui::vertex_output::VertexOutput
 
+// Every quad is drawn as one instance of six vertices (two triangles).
+// SVG meshes are drawn one triangle per instance, see `QuadInstance` for the layout.
 @vertex
 fn vertex(
-    @location(0) vertex_position: vec3<f32>,
-    @location(1) vertex_color: vec4<f32>,
-    @location(2) vertex_uv: vec4<f32>,
-    @location(3) vertex_pos_size: vec4<f32>,
+    @builtin(vertex_index) vertex_index: u32,
+    // xy: the top left corner of the quad, zw: its size
+    @location(0) instance_pos_size: vec4<f32>,
+    @location(1) instance_color: vec4<f32>,
+    // xy: the min uv, zw: the max uv
+    @location(2) instance_uv: vec4<f32>,
+    // The border radius of the top left, top right, bottom right and bottom left corners
+    @location(3) instance_border_radius: vec4<f32>,
+    // x: the layer of the glyph within the font texture array, y: 1.0 for triangles
+    // z: how far the edge of the glyph is pushed outward, in SDF units
+    // w: how far the edge of the glyph is softened, in pixels
+    @location(4) instance_data: vec4<f32>,
+    // xy: the top left corner of the clip the quad is drawn in, zw: its bottom right corner
+    @location(5) instance_clip: vec4<f32>,
+    // The border radius of the top left, top right, bottom right and bottom left corners of the clip
+    @location(6) instance_clip_radius: vec4<f32>,
 ) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+
     var out: VertexOutput;
-    out.color = vertex_color;
-    out.pos = (vertex_position.xy - vertex_pos_size.xy);
-    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
+    var position: vec2<f32>;
+    if instance_data.y > 0.5 {
+        // The three corners of the triangle are stored in place of the uvs and border radius.
+        // The remaining vertices collapse onto the first one so nothing is drawn for them.
+        var triangle = array<vec2<f32>, 3>(
+            instance_uv.xy,
+            instance_uv.zw,
+            instance_border_radius.xy,
+        );
+        position = triangle[select(0u, vertex_index, vertex_index < 3u)];
+        out.uv = vec3<f32>(0.0);
+        out.border_radius = 0.0;
+    } else {
+        let corner = corners[vertex_index];
+        position = instance_pos_size.xy + corner * instance_pos_size.zw;
+        out.uv = vec3<f32>(
+            mix(instance_uv.x, instance_uv.z, corner.x),
+            mix(instance_uv.w, instance_uv.y, corner.y),
+            instance_data.x,
+        );
+        out.border_radius = select(
+            select(instance_border_radius.x, instance_border_radius.y, corner.x > 0.5),
+            select(instance_border_radius.w, instance_border_radius.z, corner.x > 0.5),
+            corner.y > 0.5,
+        );
+    }
+
+    out.color = instance_color;
+    out.outline_width = instance_data.z;
+    out.blur = instance_data.w;
+    out.pos = position - instance_pos_size.xy;
+    out.position = view.view_proj * vec4<f32>(position, 0.0, 1.0);
     out.pixel_position = out.position.xy;
-    out.uv = vertex_uv.xyz;
-    out.size = vertex_pos_size.zw;
-    out.border_radius = vertex_uv.w;
+    out.size = instance_pos_size.zw;
+    out.clip = instance_clip;
+    out.clip_radius = instance_clip_radius;
+    out.world_position = position;
     return out;
 }
 
-#import kayak_ui::sample_quad::sample_quad
+#import kayak_ui::sample_quad::{sample_quad, clip_alpha}
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    return sample_quad(in);
+    let color = sample_quad(in);
+    // The scissor rect already cuts off everything outside the clip's bounds,
+    // this only has to take care of its rounded corners.
+    let clip = clip_alpha(in);
+    if clip <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(color.rgb, color.a * clip);
 }
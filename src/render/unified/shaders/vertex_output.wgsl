@@ -1,11 +1,31 @@
 #define_import_path kayak_ui::vertex_output
 
+// The data every quad passes from the vertex to the fragment shader.
+// Custom `MaterialUI` fragment shaders receive this as their input.
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
+    // The background color of the quad (or the tint of an image, the color of a glyph)
     @location(0) color: vec4<f32>,
+    // xy: texture coordinates from 0.0 to 1.0 across images and glyphs
+    // z: the layer of the glyph within the font texture array
     @location(1) uv: vec3<f32>,
+    // The position of the fragment relative to the top left corner of the quad, in UI units
+    // `pos / size` goes from 0.0 to 1.0 across the quad.
     @location(2) pos: vec2<f32>,
+    // The size of the quad in UI units
     @location(3) size: vec2<f32>,
+    // The border radius of the quad
     @location(4) border_radius: f32,
+    // The clip space position of the vertex
     @location(5) pixel_position: vec2<f32>,
+    // How far the edge of a glyph is pushed outward, in SDF units (used to draw text outlines)
+    @location(6) outline_width: f32,
+    // How far the edge of a glyph is softened, in pixels (used to draw text shadows)
+    @location(7) blur: f32,
+    // xy: the top left corner of the clip the quad is drawn in, zw: its bottom right corner
+    @location(8) clip: vec4<f32>,
+    // The border radius of the top left, top right, bottom right and bottom left corners of the clip
+    @location(9) clip_radius: vec4<f32>,
+    // The position of the fragment in the same units as `clip`
+    @location(10) world_position: vec2<f32>,
 };
\ No newline at end of file
@@ -46,20 +46,15 @@ impl RenderPrimitive for KStyle {
         let background_color = self.background_color.resolve();
         let render_command = self.render_command.resolve();
         let material = self.material.resolve_as_option();
+        let tint = self.tint.resolve_or(Color::WHITE);
         match render_command {
             RenderCommand::Clip => {
-                let mut rect = Rect {
+                let rect = Rect {
                     min: Vec2::new(layout.posx, layout.posy) * dpi,
                     max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height) * dpi,
                 };
-                if let Some(prev_clip) = prev_clip {
-                    let y1 = rect.max.y;
-                    let y2 = prev_clip.rect.max.y;
-                    rect.max.y = y1.min(y2);
-                    if prev_clip.rect.min.y > rect.min.y {
-                        rect.min.y = prev_clip.rect.min.y;
-                    }
-                }
+                let (rect, border_radius) =
+                    intersect_clip(rect, self.border_radius.resolve(), prev_clip.as_ref());
 
                 // println!("New Clip: {:?}", (rect, layout.z_index));
 
@@ -72,7 +67,7 @@ impl RenderPrimitive for KStyle {
                     font_handle: None,
                     quad_type: UIQuadType::Clip,
                     type_index: 0,
-                    border_radius: Corner::default(),
+                    border_radius,
                     image: None,
                     uv_min: None,
                     uv_max: None,
@@ -91,6 +86,7 @@ impl RenderPrimitive for KStyle {
                 let border_radius = self.border_radius.resolve();
                 let border = self.border.resolve();
                 let box_shadow = self.box_shadow.resolve();
+                let backdrop_blur = self.backdrop_blur.resolve();
                 let mut quads = crate::render::quad::extract_quads(
                     camera_entity,
                     background_color,
@@ -100,9 +96,23 @@ impl RenderPrimitive for KStyle {
                     border,
                     opacity_layer,
                     box_shadow,
+                    backdrop_blur,
                     1.0,
                 );
 
+                if let Some(background_image) = self.background_image.resolve_as_option() {
+                    quads.extend(crate::render::image::extract_background_image(
+                        camera_entity,
+                        *layout,
+                        border,
+                        border_radius,
+                        background_image,
+                        self.background_size.resolve(),
+                        opacity_layer,
+                        images,
+                    ));
+                }
+
                 for quad in quads.iter_mut() {
                     quad.org_entity = current_node;
                 }
@@ -126,20 +136,25 @@ impl RenderPrimitive for KStyle {
             RenderCommand::Text {
                 subpixel,
                 text_layout,
-                properties,
                 ..
             } => {
                 let font = self
                     .font
                     .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
                 let color = self.color.resolve_or(Color::WHITE);
+                let outline = self.text_outline.resolve_or((Color::NONE, 0.0));
+                let shadow = self.text_shadow.resolve_as_option();
+                let decoration = self.text_decoration.resolve();
                 let text = crate::render::font::extract_texts(
                     camera_entity,
                     color,
+                    outline,
+                    shadow,
+                    decoration,
                     text_layout,
                     *layout,
                     font,
-                    properties,
+                    self.font_weight.resolve_as_option(),
                     subpixel,
                     opacity_layer,
                     fonts,
@@ -168,6 +183,7 @@ impl RenderPrimitive for KStyle {
                     border_radius,
                     *layout,
                     handle,
+                    tint,
                     opacity_layer,
                     dpi,
                 );
@@ -200,6 +216,7 @@ impl RenderPrimitive for KStyle {
                     position,
                     *layout,
                     handle,
+                    tint,
                     opacity_layer,
                     images,
                     dpi,
@@ -227,6 +244,7 @@ impl RenderPrimitive for KStyle {
                 border,
                 handle,
                 scale,
+                mode,
             } => {
                 let mut nines = crate::render::nine_patch::extract_nine_patch(
                     camera_entity,
@@ -234,6 +252,8 @@ impl RenderPrimitive for KStyle {
                     handle,
                     border,
                     scale,
+                    mode,
+                    tint,
                     opacity_layer,
                     images,
                     dpi,
@@ -257,6 +277,41 @@ impl RenderPrimitive for KStyle {
                     );
                 }
             }
+            RenderCommand::Canvas { shapes } => {
+                let mut canvas_quads = crate::render::canvas::extract_canvas(
+                    camera_entity,
+                    *layout,
+                    shapes,
+                    opacity_layer,
+                    dpi,
+                );
+                if canvas_quads.is_empty() {
+                    // Nothing was drawn but we still need a quad to apply z-values correctly
+                    canvas_quads.push(ExtractedQuad {
+                        camera_entity,
+                        quad_type: UIQuadType::None,
+                        ..Default::default()
+                    });
+                }
+                for quad in canvas_quads.iter_mut() {
+                    quad.org_entity = current_node;
+                }
+                if let Some(material) = material {
+                    for extracted in canvas_quads {
+                        let id = commands.spawn(extracted).id();
+                        material.run(commands, id);
+                        extracted_quads.push(QuadOrMaterial::Material(id));
+                    }
+                    return None;
+                } else {
+                    extracted_quads.extend(
+                        canvas_quads
+                            .into_iter()
+                            .map(QuadOrMaterial::Quad)
+                            .collect::<Vec<_>>(),
+                    );
+                }
+            }
             #[cfg(feature = "svg")]
             RenderCommand::Svg { handle } => {
                 let mut svgs = crate::render::svg::extract_svg(
@@ -304,3 +359,128 @@ impl RenderPrimitive for KStyle {
         None
     }
 }
+
+/// Narrows a clip down to the part of it that's within its parent clip
+///
+/// Corners of the clip that end up on the corners of a rounded parent clip are rounded at least as
+/// much as the parent, so nested clips can't let content poke out of the parent's corners.
+fn intersect_clip(
+    rect: Rect,
+    border_radius: Corner<f32>,
+    parent: Option<&ExtractedQuad>,
+) -> (Rect, Corner<f32>) {
+    let Some(parent) = parent else {
+        return (rect, border_radius);
+    };
+
+    let min = rect.min.max(parent.rect.min);
+    let max = rect.max.min(parent.rect.max).max(min);
+    let corner = |radius: f32, parent_radius: f32, x: f32, parent_x: f32, y: f32, parent_y: f32| {
+        if x == parent_x && y == parent_y {
+            radius.max(parent_radius)
+        } else {
+            radius
+        }
+    };
+    let parent_radius = parent.border_radius;
+    let (parent_min, parent_max) = (parent.rect.min, parent.rect.max);
+    let border_radius = Corner {
+        top_left: corner(
+            border_radius.top_left,
+            parent_radius.top_left,
+            min.x,
+            parent_min.x,
+            min.y,
+            parent_min.y,
+        ),
+        top_right: corner(
+            border_radius.top_right,
+            parent_radius.top_right,
+            max.x,
+            parent_max.x,
+            min.y,
+            parent_min.y,
+        ),
+        bottom_left: corner(
+            border_radius.bottom_left,
+            parent_radius.bottom_left,
+            min.x,
+            parent_min.x,
+            max.y,
+            parent_max.y,
+        ),
+        bottom_right: corner(
+            border_radius.bottom_right,
+            parent_radius.bottom_right,
+            max.x,
+            parent_max.x,
+            max.y,
+            parent_max.y,
+        ),
+    };
+
+    (Rect { min, max }, border_radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{Rect, Vec2};
+
+    use super::intersect_clip;
+    use crate::{render::unified::pipeline::ExtractedQuad, styles::Corner};
+
+    fn clip(min: Vec2, max: Vec2, border_radius: Corner<f32>) -> ExtractedQuad {
+        ExtractedQuad {
+            rect: Rect { min, max },
+            border_radius,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nested_clips_should_intersect_on_both_axes() {
+        let parent = clip(
+            Vec2::new(10.0, 10.0),
+            Vec2::new(110.0, 60.0),
+            Corner::all(0.0),
+        );
+        let rect = Rect {
+            min: Vec2::new(0.0, 20.0),
+            max: Vec2::new(80.0, 100.0),
+        };
+
+        let (rect, _) = intersect_clip(rect, Corner::all(0.0), Some(&parent));
+
+        assert_eq!(Vec2::new(10.0, 20.0), rect.min);
+        assert_eq!(Vec2::new(80.0, 60.0), rect.max);
+    }
+
+    #[test]
+    fn nested_clips_should_keep_the_rounded_corners_they_share_with_their_parent() {
+        let parent = clip(Vec2::ZERO, Vec2::new(100.0, 100.0), Corner::all(12.0));
+        let rect = Rect {
+            min: Vec2::new(-10.0, -10.0),
+            max: Vec2::new(50.0, 50.0),
+        };
+
+        let (_, border_radius) = intersect_clip(rect, Corner::all(4.0), Some(&parent));
+
+        assert_eq!(12.0, border_radius.top_left);
+        assert_eq!(4.0, border_radius.top_right);
+        assert_eq!(4.0, border_radius.bottom_left);
+        assert_eq!(4.0, border_radius.bottom_right);
+    }
+
+    #[test]
+    fn nested_clips_outside_their_parent_should_be_empty() {
+        let parent = clip(Vec2::ZERO, Vec2::new(100.0, 100.0), Corner::all(0.0));
+        let rect = Rect {
+            min: Vec2::new(200.0, 0.0),
+            max: Vec2::new(300.0, 50.0),
+        };
+
+        let (rect, _) = intersect_clip(rect, Corner::all(0.0), Some(&parent));
+
+        assert_eq!(0.0, rect.width());
+    }
+}
@@ -0,0 +1,158 @@
+use bevy::prelude::KeyCode;
+
+use crate::{handler::Handler, keyboard_event::KeyboardModifiers};
+
+/// A key along with the modifiers that must be held for it to trigger a shortcut
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombination {
+    pub key: KeyCode,
+    pub modifiers: KeyboardModifiers,
+}
+
+impl KeyCombination {
+    /// Creates a combination of the given key without any modifiers
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            modifiers: KeyboardModifiers::default(),
+        }
+    }
+
+    /// Requires one of the Control keys to be held
+    pub fn ctrl(mut self) -> Self {
+        self.modifiers.is_ctrl_pressed = true;
+        self
+    }
+
+    /// Requires one of the Shift keys to be held
+    pub fn shift(mut self) -> Self {
+        self.modifiers.is_shift_pressed = true;
+        self
+    }
+
+    /// Requires one of the Alt (or "Option") keys to be held
+    pub fn alt(mut self) -> Self {
+        self.modifiers.is_alt_pressed = true;
+        self
+    }
+
+    /// Requires one of the Meta keys to be held
+    pub fn meta(mut self) -> Self {
+        self.modifiers.is_meta_pressed = true;
+        self
+    }
+
+    /// Returns true if pressing `key` while holding `modifiers` triggers this combination
+    ///
+    /// Extra modifiers are allowed, so Ctrl+Shift+S matches both Ctrl+S and Ctrl+Shift+S.
+    pub fn matches(&self, key: KeyCode, modifiers: KeyboardModifiers) -> bool {
+        self.key == key
+            && (!self.modifiers.is_ctrl_pressed || modifiers.is_ctrl_pressed)
+            && (!self.modifiers.is_shift_pressed || modifiers.is_shift_pressed)
+            && (!self.modifiers.is_alt_pressed || modifiers.is_alt_pressed)
+            && (!self.modifiers.is_meta_pressed || modifiers.is_meta_pressed)
+    }
+
+    /// The number of modifiers required by this combination
+    fn modifier_count(&self) -> usize {
+        [
+            self.modifiers.is_ctrl_pressed,
+            self.modifiers.is_shift_pressed,
+            self.modifiers.is_alt_pressed,
+            self.modifiers.is_meta_pressed,
+        ]
+        .into_iter()
+        .filter(|pressed| *pressed)
+        .count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Shortcut {
+    pub combo: KeyCombination,
+    pub handler: Handler,
+    /// If true, the key press isn't sent to the focused widget
+    pub consume: bool,
+}
+
+/// The shortcuts registered on a [`KayakRootContext`](crate::prelude::KayakRootContext)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Shortcuts {
+    shortcuts: Vec<Shortcut>,
+}
+
+impl Shortcuts {
+    /// Adds a shortcut, replacing any shortcut with the same combination
+    pub fn register(&mut self, shortcut: Shortcut) {
+        self.unregister(shortcut.combo);
+        self.shortcuts.push(shortcut);
+    }
+
+    pub fn unregister(&mut self, combo: KeyCombination) {
+        self.shortcuts.retain(|shortcut| shortcut.combo != combo);
+    }
+
+    /// Finds the shortcut triggered by pressing `key` while holding `modifiers`
+    ///
+    /// When several combinations match, the one requiring the most modifiers wins.
+    pub fn find(&self, key: KeyCode, modifiers: KeyboardModifiers) -> Option<&Shortcut> {
+        self.shortcuts
+            .iter()
+            .filter(|shortcut| shortcut.combo.matches(key, modifiers))
+            .max_by_key(|shortcut| shortcut.combo.modifier_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::KeyCode;
+
+    use super::{KeyCombination, Shortcut, Shortcuts};
+    use crate::{handler::Handler, keyboard_event::KeyboardModifiers};
+
+    fn shortcut(combo: KeyCombination) -> Shortcut {
+        Shortcut {
+            combo,
+            handler: Handler::default(),
+            consume: true,
+        }
+    }
+
+    #[test]
+    fn most_specific_shortcut_should_win() {
+        let mut shortcuts = Shortcuts::default();
+        let save = KeyCombination::new(KeyCode::KeyS).ctrl();
+        let save_as = KeyCombination::new(KeyCode::KeyS).ctrl().shift();
+        shortcuts.register(shortcut(save_as));
+        shortcuts.register(shortcut(save));
+
+        let ctrl = KeyboardModifiers {
+            is_ctrl_pressed: true,
+            ..Default::default()
+        };
+        let ctrl_shift = KeyboardModifiers {
+            is_shift_pressed: true,
+            ..ctrl
+        };
+        let ctrl_alt = KeyboardModifiers {
+            is_alt_pressed: true,
+            ..ctrl
+        };
+
+        assert_eq!(
+            Some(save),
+            shortcuts.find(KeyCode::KeyS, ctrl).map(|s| s.combo)
+        );
+        assert_eq!(
+            Some(save_as),
+            shortcuts.find(KeyCode::KeyS, ctrl_shift).map(|s| s.combo)
+        );
+        assert_eq!(
+            Some(save),
+            shortcuts.find(KeyCode::KeyS, ctrl_alt).map(|s| s.combo)
+        );
+        assert!(shortcuts
+            .find(KeyCode::KeyS, KeyboardModifiers::default())
+            .is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::World;
+use serde::Serialize;
+
+use crate::{
+    context::WidgetName,
+    layout::Rect,
+    node::{Node, WrappedIndex},
+    styles::{ComputedStyles, KStyle},
+    tree::Tree,
+};
+
+/// A serializable copy of a widget and its descendants
+///
+/// Entity ids are left out on purpose, so snapshots of the same UI match across runs.
+/// Children are listed in the same order as they are in the widget tree.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WidgetSnapshot {
+    /// The type name of the widget
+    pub name: String,
+    /// The layout of the widget as of the last layout pass
+    pub layout: Option<Rect>,
+    /// The fully resolved styles of the widget
+    ///
+    /// Falls back to the widget's [`ComputedStyles`] if its styles haven't been resolved yet.
+    /// Image handles, text layouts and materials are not included.
+    pub styles: Option<KStyle>,
+    pub children: Vec<WidgetSnapshot>,
+}
+
+impl WidgetSnapshot {
+    pub(crate) fn new(
+        world: &World,
+        tree: &Tree,
+        get_layout: &impl Fn(&WrappedIndex) -> Option<Rect>,
+        index: WrappedIndex,
+    ) -> Self {
+        let entity_ref = world.get_entity(index.0);
+        let name = entity_ref
+            .and_then(|entity_ref| entity_ref.get::<WidgetName>())
+            .map(|name| name.0.clone())
+            .unwrap_or("Unknown".into());
+        let styles = entity_ref.and_then(|entity_ref| {
+            if let Some(node) = entity_ref.get::<Node>() {
+                Some(node.resolved_styles.clone())
+            } else {
+                entity_ref
+                    .get::<ComputedStyles>()
+                    .map(|styles| styles.0.clone())
+            }
+        });
+        let children = tree
+            .children
+            .get(&index)
+            .map(|children| {
+                children
+                    .iter()
+                    .map(|child| Self::new(world, tree, get_layout, *child))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            name,
+            layout: get_layout(&index),
+            styles,
+            children,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::World;
+
+    use crate::{
+        context::{KayakRootContext, WidgetName},
+        layout::Rect,
+        node::{NodeBuilder, WrappedIndex},
+        styles::{KStyle, StyleProp, Units},
+    };
+
+    /// Builds a panel with a title and a body, spawning the widgets in the given order after
+    /// `skipped` unrelated entities
+    fn build_tree(spawn_order: [usize; 3], skipped: usize) -> (World, KayakRootContext) {
+        let mut world = World::new();
+        for _ in 0..skipped {
+            world.spawn_empty();
+        }
+
+        let names = ["Panel", "Title", "Body"];
+        let mut widgets = [None; 3];
+        for index in spawn_order {
+            let entity = world.spawn(WidgetName(names[index].into())).id();
+            let styles = KStyle {
+                width: StyleProp::Value(Units::Pixels(10.0 * (index + 1) as f32)),
+                ..Default::default()
+            };
+            world
+                .entity_mut(entity)
+                .insert(NodeBuilder::new(WrappedIndex(entity), styles).build());
+            widgets[index] = Some(entity);
+        }
+        let [panel, title, body] = widgets.map(Option::unwrap);
+
+        let mut context = KayakRootContext::new(world.spawn_empty().id());
+        context.add_widget(None, panel);
+        context.add_widget(Some(panel), title);
+        context.add_widget(Some(panel), body);
+        if let Ok(mut cache) = context.layout_cache.write() {
+            let rect = Rect {
+                posx: 0.0,
+                posy: 0.0,
+                width: 20.0,
+                height: 10.0,
+                z_index: Some(1.0),
+            };
+            cache.rect.insert(WrappedIndex(title), rect);
+        }
+        (world, context)
+    }
+
+    #[test]
+    fn dumps_should_not_depend_on_entity_ids() {
+        let (world, context) = build_tree([0, 1, 2], 0);
+        let dump = context.dump_tree(&world).unwrap();
+        assert_eq!(Some(&dump), context.dump_tree(&world).as_ref());
+        assert!(dump.find("Title") < dump.find("Body"));
+
+        for (spawn_order, skipped) in [([2, 1, 0], 0), ([1, 2, 0], 5)] {
+            let (world, context) = build_tree(spawn_order, skipped);
+            assert_eq!(Some(&dump), context.dump_tree(&world).as_ref());
+        }
+    }
+
+    #[test]
+    fn empty_trees_should_not_be_dumped() {
+        let mut world = World::new();
+        let context = KayakRootContext::new(world.spawn_empty().id());
+        assert_eq!(None, context.dump_tree(&world));
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::{
+    asset::{
+        io::Reader, Asset, AssetEvent, AssetId, AssetLoader, Assets, AsyncReadExt, LoadContext,
+    },
+    prelude::{EventReader, Local, Res, ResMut},
+    reflect::TypePath,
+    utils::{BoxedFuture, HashMap},
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{styles::KStyle, theme::Theme};
+
+/// A file of named styles which are added to the [`Theme`] when loaded
+///
+/// Stylesheets are written in RON (`.style.ron`) or JSON (`.style.json`) as a map from
+/// style names to [`KStyle`]s. Properties that are left out are
+/// [`StyleProp::Unset`](crate::styles::StyleProp::Unset). The `render_command`,
+/// `background_image` and `material` properties can't be set from a stylesheet.
+///
+/// ```ron
+/// {
+///     "panel": (
+///         background_color: Value(Rgba(red: 0.1, green: 0.1, blue: 0.1, alpha: 1.0)),
+///         border_radius: Value((top_left: 8.0, top_right: 8.0, bottom_left: 8.0, bottom_right: 8.0)),
+///         padding: Value((top: Pixels(10.0), right: Pixels(10.0), bottom: Pixels(10.0), left: Pixels(10.0))),
+///     ),
+/// }
+/// ```
+///
+/// Keep the [`Handle`](bevy::asset::Handle) returned by the `AssetServer` alive for as long
+/// as the styles should be updated. With Bevy's `file_watcher` feature enabled, saving the
+/// file re-applies its styles while the app is running. If the file can't be parsed, the
+/// error is logged and the previously loaded styles are kept.
+#[derive(Asset, TypePath, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct StyleSheet {
+    pub styles: HashMap<String, KStyle>,
+}
+
+#[derive(Debug, Error)]
+pub enum StyleSheetLoaderError {
+    #[error("Could not read stylesheet: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse RON stylesheet: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("Could not parse JSON stylesheet: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Default)]
+pub struct StyleSheetLoader;
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+
+    type Settings = ();
+
+    type Error = StyleSheetLoaderError;
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes).await?;
+
+            let is_json = load_context
+                .path()
+                .extension()
+                .map(|extension| extension == "json")
+                .unwrap_or(false);
+            parse_style_sheet(&bytes, is_json)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["style.ron", "style.json"];
+        EXTENSIONS
+    }
+}
+
+/// Parses the contents of a RON or JSON stylesheet
+fn parse_style_sheet(bytes: &[u8], is_json: bool) -> Result<StyleSheet, StyleSheetLoaderError> {
+    if is_json {
+        Ok(serde_json::from_slice(bytes)?)
+    } else {
+        Ok(ron::de::from_bytes(bytes)?)
+    }
+}
+
+/// Adds the styles of newly loaded or modified stylesheets to the [`Theme`]
+///
+/// Styles that were removed from a stylesheet are removed from the theme as well, as are all
+/// the styles of a stylesheet that was unloaded.
+pub(crate) fn update_theme_from_style_sheets(
+    mut events: EventReader<AssetEvent<StyleSheet>>,
+    style_sheets: Res<Assets<StyleSheet>>,
+    mut theme: ResMut<Theme>,
+    mut loaded_styles: Local<HashMap<AssetId<StyleSheet>, Vec<String>>>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                if let Some(style_sheet) = style_sheets.get(*id) {
+                    let names = loaded_styles.entry(*id).or_default();
+                    for name in names.drain(..) {
+                        if !style_sheet.styles.contains_key(&name) {
+                            theme.remove(&name);
+                        }
+                    }
+
+                    for (name, style) in style_sheet.styles.iter() {
+                        if theme.get(name) != Some(style) {
+                            theme.insert(name.clone(), style.clone());
+                        }
+                        names.push(name.clone());
+                    }
+                }
+            }
+            AssetEvent::Removed { id } => {
+                for name in loaded_styles.remove(id).unwrap_or_default() {
+                    theme.remove(&name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::{AssetEvent, Assets},
+        ecs::event::Events,
+        prelude::{Color, IntoSystem, System, World},
+    };
+
+    use super::{parse_style_sheet, update_theme_from_style_sheets, StyleSheet};
+    use crate::{
+        styles::{Corner, Edge, StyleProp, Units},
+        theme::Theme,
+    };
+
+    const EXAMPLE: &str = r#"{
+        "panel": (
+            background_color: Value(Rgba(red: 0.1, green: 0.1, blue: 0.1, alpha: 1.0)),
+            border_radius: Value((top_left: 8.0, top_right: 8.0, bottom_left: 8.0, bottom_right: 8.0)),
+            padding: Value((top: Pixels(10.0), right: Pixels(10.0), bottom: Pixels(10.0), left: Pixels(10.0))),
+        ),
+    }"#;
+
+    #[test]
+    fn documented_example_should_parse() {
+        let style_sheet = parse_style_sheet(EXAMPLE.as_bytes(), false).unwrap();
+        let panel = &style_sheet.styles["panel"];
+
+        assert_eq!(
+            StyleProp::Value(Color::rgba(0.1, 0.1, 0.1, 1.0)),
+            panel.background_color
+        );
+        assert_eq!(StyleProp::Value(Corner::all(8.0)), panel.border_radius);
+        assert_eq!(
+            StyleProp::Value(Edge::all(Units::Pixels(10.0))),
+            panel.padding
+        );
+        assert_eq!(StyleProp::Unset, panel.width);
+    }
+
+    #[test]
+    fn theme_should_follow_loaded_style_sheets() {
+        let mut world = World::new();
+        world.init_resource::<Theme>();
+        world.init_resource::<Events<AssetEvent<StyleSheet>>>();
+        let mut style_sheets = Assets::<StyleSheet>::default();
+        let mut style_sheet = parse_style_sheet(EXAMPLE.as_bytes(), false).unwrap();
+        let id = style_sheets.add(style_sheet.clone()).id();
+        world.insert_resource(style_sheets);
+
+        let mut system = IntoSystem::into_system(update_theme_from_style_sheets);
+        system.initialize(&mut world);
+        let mut update = |world: &mut World, event: AssetEvent<StyleSheet>| {
+            world.send_event(event);
+            system.run((), world);
+        };
+
+        update(&mut world, AssetEvent::Added { id });
+        assert!(world.resource::<Theme>().get("panel").is_some());
+
+        // Renaming a style replaces it in the theme
+        let panel = style_sheet.styles.remove("panel").unwrap();
+        style_sheet.styles.insert("card".into(), panel);
+        world
+            .resource_mut::<Assets<StyleSheet>>()
+            .insert(id, style_sheet);
+        update(&mut world, AssetEvent::Modified { id });
+        assert!(world.resource::<Theme>().get("panel").is_none());
+        assert!(world.resource::<Theme>().get("card").is_some());
+
+        world.resource_mut::<Assets<StyleSheet>>().remove(id);
+        update(&mut world, AssetEvent::Removed { id });
+        assert_eq!(Theme::default(), *world.resource::<Theme>());
+    }
+}
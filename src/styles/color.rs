@@ -0,0 +1,52 @@
+use bevy::prelude::Color;
+
+use super::style::hsv_lerp;
+
+/// Helpers for working with [`Color`]s in widgets
+///
+/// Bevy already provides hex parsing through [`Color::hex`] (accepting the `RGB`, `RGBA`,
+/// `RRGGBB` and `RRGGBBAA` forms, with or without a leading `#`), transparency through
+/// [`Color::with_a`] and named constants such as [`Color::WHITE`].
+pub trait ColorExt {
+    /// Interpolates between this color and `other`, with `x` ranging from `0.0` to `1.0`
+    ///
+    /// This is the interpolation used when animating the colors of a [`KStyle`](super::KStyle).
+    fn lerp(self, other: Color, x: f32) -> Color;
+}
+
+impl ColorExt for Color {
+    fn lerp(self, other: Color, x: f32) -> Color {
+        hsv_lerp(&self, &other, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use super::ColorExt;
+
+    fn assert_color_eq(expected: Color, actual: Color) {
+        let expected = expected.as_rgba_f32();
+        let actual = actual.as_rgba_f32();
+        for (expected, actual) in expected.iter().zip(actual.iter()) {
+            assert!(
+                (expected - actual).abs() < 1e-4,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn lerp_should_interpolate_between_colors() {
+        let from = Color::rgba(1.0, 0.0, 0.0, 0.0);
+        let to = Color::rgba(0.0, 0.0, 1.0, 1.0);
+
+        assert_color_eq(from, from.lerp(to, 0.0));
+        assert_color_eq(to, from.lerp(to, 1.0));
+        assert!((from.lerp(to, 0.5).a() - 0.5).abs() < 1e-4);
+        assert_color_eq(Color::WHITE, Color::WHITE.lerp(Color::WHITE, 0.5));
+    }
+}
@@ -1,11 +1,12 @@
 use std::ops::{Mul, MulAssign};
 
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
 /// A struct for defining properties related to the corners of widgets
 ///
 /// This is useful for things like border radii, etc.
-#[derive(Debug, Default, Reflect, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Reflect, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
 pub struct Corner<T>
 where
     T: Copy + Default + PartialEq + Reflect,
@@ -26,12 +27,15 @@ where
 {
     /// Creates a new `Corner` with values individually specified for each corner
     ///
+    /// Note that the corners are given row by row, which differs from the clockwise order of
+    /// the CSS `border-radius` property (see [`Corner::into_clockwise_array`]).
+    ///
     /// # Arguments
     ///
     /// * `top_left`: The top-left corner value
-    /// * `top_right`: The top_-right corner value
-    /// * `bottom_left`: The bottom_-left corner value
-    /// * `bottom_right`: The bottom_-right corner value
+    /// * `top_right`: The top-right corner value
+    /// * `bottom_left`: The bottom-left corner value
+    /// * `bottom_right`: The bottom-right corner value
     ///
     pub fn new(top_left: T, top_right: T, bottom_left: T, bottom_right: T) -> Self {
         Self {
@@ -90,6 +94,26 @@ where
         }
     }
 
+    /// Creates a new `Corner` with only the top corners having the given value
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: The value of the top corners
+    ///
+    pub fn top(value: T) -> Self {
+        Self::vertical(value, T::default())
+    }
+
+    /// Creates a new `Corner` with only the bottom corners having the given value
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: The value of the bottom corners
+    ///
+    pub fn bottom(value: T) -> Self {
+        Self::vertical(T::default(), value)
+    }
+
     /// Creates a new `Corner` with all corners having the same value
     ///
     /// # Arguments
@@ -114,6 +138,18 @@ where
             self.bottom_right,
         )
     }
+
+    /// Converts this `Corner` into an array matching `[Top Left, Top Right, Bottom Right, Bottom Left]`
+    ///
+    /// This is the clockwise order used by CSS and by the shaders.
+    pub fn into_clockwise_array(self) -> [T; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
 }
 
 impl<T> From<Corner<T>> for (T, T, T, T)
@@ -220,6 +256,24 @@ mod tests {
         assert_eq!(expected, corner.into_tuple());
     }
 
+    #[test]
+    fn constructors_should_map_to_the_visual_corners() {
+        let corner = Corner::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(1.0, corner.top_left);
+        assert_eq!(2.0, corner.top_right);
+        assert_eq!(3.0, corner.bottom_left);
+        assert_eq!(4.0, corner.bottom_right);
+        assert_eq!([1.0, 2.0, 4.0, 3.0], corner.into_clockwise_array());
+
+        assert_eq!(Corner::new(1.0, 1.0, 0.0, 0.0), Corner::top(1.0));
+        assert_eq!(Corner::new(0.0, 0.0, 1.0, 1.0), Corner::bottom(1.0));
+        assert_eq!(Corner::new(1.0, 1.0, 2.0, 2.0), Corner::vertical(1.0, 2.0));
+        assert_eq!(
+            Corner::new(1.0, 2.0, 1.0, 2.0),
+            Corner::horizontal(1.0, 2.0)
+        );
+    }
+
     #[test]
     fn multiplication_should_work_on_corners() {
         let expected = (10.0, 20.0, 30.0, 40.0);
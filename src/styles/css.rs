@@ -0,0 +1,243 @@
+use bevy::{
+    prelude::Color,
+    reflect::{FromReflect, Reflect},
+};
+
+use super::{is_rgba, parse_rgba, BoxShadow, Corner, Edge, KStyle, StyleProp, Units};
+
+impl KStyle {
+    /// Parses a CSS declaration block into a style
+    ///
+    /// ```ignore
+    /// let style = KStyle::from_css("width: 100px; background-color: #fff; padding: 4px 8px;");
+    /// ```
+    ///
+    /// Supported values are lengths (`px`, `%` and `auto`, with unitless numbers treated as
+    /// pixels) and colors (`#rgb`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()` and `transparent`).
+    /// `padding`, `margin` (the [`offset`](KStyle::offset) of the widget) and `border-width`
+    /// accept one to four values and `border-radius` one to four corners, expanded the same way
+    /// CSS does. Unknown properties and values that can't be parsed are skipped with a warning,
+    /// leaving the property [`Unset`](StyleProp::Unset).
+    pub fn from_css(css: &str) -> KStyle {
+        let mut style = KStyle::default();
+        for declaration in css.split(';') {
+            let declaration = declaration.trim();
+            if declaration.is_empty() {
+                continue;
+            }
+            let Some((property, value)) = declaration.split_once(':') else {
+                log::warn!(
+                    "Skipping CSS declaration without a value: {:?}",
+                    declaration
+                );
+                continue;
+            };
+            let (property, value) = (property.trim(), value.trim());
+            if !style.set_css_property(property, value) {
+                log::warn!("Skipping unsupported CSS property: {}: {}", property, value);
+            }
+        }
+        style
+    }
+
+    /// Sets a single CSS property, returning false if it couldn't be set
+    fn set_css_property(&mut self, property: &str, value: &str) -> bool {
+        fn set<T: Default + Clone + Reflect + FromReflect>(
+            prop: &mut StyleProp<T>,
+            value: Option<T>,
+        ) -> bool {
+            if let Some(value) = &value {
+                *prop = StyleProp::Value(value.clone());
+            }
+            value.is_some()
+        }
+
+        match property {
+            "width" => set(&mut self.width, parse_length(value)),
+            "height" => set(&mut self.height, parse_length(value)),
+            "min-width" => set(&mut self.min_width, parse_length(value)),
+            "min-height" => set(&mut self.min_height, parse_length(value)),
+            "max-width" => set(&mut self.max_width, parse_length(value)),
+            "max-height" => set(&mut self.max_height, parse_length(value)),
+            "top" => set(&mut self.top, parse_length(value)),
+            "right" => set(&mut self.right, parse_length(value)),
+            "bottom" => set(&mut self.bottom, parse_length(value)),
+            "left" => set(&mut self.left, parse_length(value)),
+            "padding" => set(&mut self.padding, parse_edge(value, parse_length)),
+            "padding-top" => set(&mut self.padding_top, parse_length(value)),
+            "padding-right" => set(&mut self.padding_right, parse_length(value)),
+            "padding-bottom" => set(&mut self.padding_bottom, parse_length(value)),
+            "padding-left" => set(&mut self.padding_left, parse_length(value)),
+            "margin" => set(&mut self.offset, parse_edge(value, parse_length)),
+            "gap" => set(&mut self.gap, parse_length(value)),
+            "row-gap" => set(&mut self.row_between, parse_length(value)),
+            "column-gap" => set(&mut self.col_between, parse_length(value)),
+            "border-width" => set(&mut self.border, parse_edge(value, parse_pixels)),
+            "border-radius" => set(&mut self.border_radius, parse_corner(value)),
+            "border-color" => set(&mut self.border_color, parse_color(value)),
+            "background-color" => set(&mut self.background_color, parse_color(value)),
+            "color" => set(&mut self.color, parse_color(value)),
+            "font-size" => set(&mut self.font_size, parse_pixels(value)),
+            "line-height" => set(&mut self.line_height, parse_pixels(value)),
+            "letter-spacing" => set(&mut self.letter_spacing, parse_pixels(value)),
+            "opacity" => set(&mut self.opacity, value.parse().ok()),
+            "z-index" => set(&mut self.z_index, value.parse().ok()),
+            "box-shadow" => set(&mut self.box_shadow, Some(BoxShadow::from_string(value))),
+            _ => false,
+        }
+    }
+}
+
+/// Parses a length such as `10px`, `50%` or `auto`
+fn parse_length(value: &str) -> Option<Units> {
+    if value == "auto" {
+        Some(Units::Auto)
+    } else if let Some(percentage) = value.strip_suffix('%') {
+        percentage.trim().parse().ok().map(Units::Percentage)
+    } else {
+        parse_pixels(value).map(Units::Pixels)
+    }
+}
+
+/// Parses a number of pixels such as `10px` or `10`
+fn parse_pixels(value: &str) -> Option<f32> {
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parses a color such as `#fff`, `rgb(255, 255, 255)` or `transparent`
+fn parse_color(value: &str) -> Option<Color> {
+    if value == "transparent" {
+        Some(Color::NONE)
+    } else if is_rgba(value) {
+        Some(parse_rgba(value))
+    } else if value.starts_with('#') {
+        Color::hex(value).ok()
+    } else {
+        None
+    }
+}
+
+/// Expands the one to four values of an edge shorthand (`top right bottom left`)
+fn parse_edge<T>(value: &str, parse: impl Fn(&str) -> Option<T>) -> Option<Edge<T>>
+where
+    T: Copy + Default + PartialEq + Reflect + FromReflect,
+{
+    let values = value
+        .split_whitespace()
+        .map(parse)
+        .collect::<Option<Vec<_>>>()?;
+    let (top, right, bottom, left) = match values[..] {
+        [all] => (all, all, all, all),
+        [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+        [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return None,
+    };
+    Some(Edge {
+        top,
+        right,
+        bottom,
+        left,
+    })
+}
+
+/// Expands the one to four values of a `border-radius` (`top-left top-right bottom-right bottom-left`)
+fn parse_corner(value: &str) -> Option<Corner<f32>> {
+    let values = value
+        .split_whitespace()
+        .map(parse_pixels)
+        .collect::<Option<Vec<_>>>()?;
+    let (top_left, top_right, bottom_right, bottom_left) = match values[..] {
+        [all] => (all, all, all, all),
+        [main, cross] => (main, cross, main, cross),
+        [top_left, cross, bottom_right] => (top_left, cross, bottom_right, cross),
+        [top_left, top_right, bottom_right, bottom_left] => {
+            (top_left, top_right, bottom_right, bottom_left)
+        }
+        _ => return None,
+    };
+    Some(Corner {
+        top_left,
+        top_right,
+        bottom_left,
+        bottom_right,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use crate::styles::{Corner, Edge, KStyle, StyleProp, Units};
+
+    #[test]
+    fn edge_shorthands_should_expand_like_css() {
+        let px = Units::Pixels;
+        let cases = [
+            ("padding: 4px", Edge::all(px(4.0))),
+            ("padding: 4px 8px", Edge::axis(px(4.0), px(8.0))),
+            (
+                "padding: 1px 2px 3px",
+                Edge::new(px(1.0), px(2.0), px(3.0), px(2.0)),
+            ),
+            (
+                "padding: 1px 2px 3px 4px",
+                Edge::new(px(1.0), px(2.0), px(3.0), px(4.0)),
+            ),
+        ];
+
+        for (css, expanded) in cases {
+            assert_eq!(StyleProp::Value(expanded), KStyle::from_css(css).padding);
+        }
+    }
+
+    #[test]
+    fn border_radius_shorthands_should_expand_like_css() {
+        let cases = [
+            ("border-radius: 4px", Corner::all(4.0)),
+            ("border-radius: 1px 2px", Corner::new(1.0, 2.0, 2.0, 1.0)),
+            (
+                "border-radius: 1px 2px 3px",
+                Corner::new(1.0, 2.0, 2.0, 3.0),
+            ),
+            (
+                "border-radius: 1px 2px 3px 4px",
+                Corner::new(1.0, 2.0, 4.0, 3.0),
+            ),
+        ];
+
+        for (css, expanded) in cases {
+            assert_eq!(
+                StyleProp::Value(expanded),
+                KStyle::from_css(css).border_radius
+            );
+        }
+    }
+
+    #[test]
+    fn declarations_should_be_parsed_into_a_style() {
+        let style = KStyle::from_css(
+            "width: 100px; height: 50%; left: auto; background-color: #fff; \
+             color: rgba(255, 0, 0, 0.5); margin: 0 10px; unknown: 1px; opacity: oops",
+        );
+
+        assert_eq!(StyleProp::Value(Units::Pixels(100.0)), style.width);
+        assert_eq!(StyleProp::Value(Units::Percentage(50.0)), style.height);
+        assert_eq!(StyleProp::Value(Units::Auto), style.left);
+        assert_eq!(StyleProp::Value(Color::WHITE), style.background_color);
+        assert_eq!(
+            StyleProp::Value(Color::rgba(1.0, 0.0, 0.0, 0.5)),
+            style.color
+        );
+        assert_eq!(
+            StyleProp::Value(Edge::axis(Units::Pixels(0.0), Units::Pixels(10.0))),
+            style.offset
+        );
+        assert_eq!(StyleProp::Unset, style.opacity);
+    }
+}
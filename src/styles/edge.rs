@@ -1,11 +1,12 @@
 use std::ops::{Mul, MulAssign};
 
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
 /// A struct for defining properties related to the edges of widgets
 ///
 /// This is useful for things like borders, padding, etc.
-#[derive(Debug, Default, Reflect, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Reflect, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
 pub struct Edge<T>
 where
     T: Copy + Default + PartialEq + Reflect,
@@ -26,6 +27,9 @@ where
 {
     /// Creates a new `Edge` with values individually specified for each edge
     ///
+    /// The edges are given clockwise starting from the top, matching the order of CSS
+    /// shorthands like `padding` and `border-width`.
+    ///
     /// # Arguments
     ///
     /// * `top`: The top edge value
@@ -194,6 +198,19 @@ mod tests {
         assert_eq!(expected, edge.into_tuple());
     }
 
+    #[test]
+    fn constructors_should_map_to_the_visual_edges() {
+        let edge = Edge::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(1.0, edge.top);
+        assert_eq!(2.0, edge.right);
+        assert_eq!(3.0, edge.bottom);
+        assert_eq!(4.0, edge.left);
+
+        let edge = Edge::axis(1.0, 2.0);
+        assert_eq!((1.0, 1.0), (edge.top, edge.bottom));
+        assert_eq!((2.0, 2.0), (edge.left, edge.right));
+    }
+
     #[test]
     fn multiplication_should_work_on_edges() {
         let expected = (10.0, 20.0, 30.0, 40.0);
@@ -1,20 +1,24 @@
 use bevy::{
-    prelude::{Color, Component, Vec2},
+    prelude::{Color, Component, Handle, Image, Vec2},
     reflect::Reflect,
 };
+use serde::{Deserialize, Serialize};
 
+mod color;
 mod corner;
+mod css;
 mod edge;
 mod options_ref;
 mod render_command;
 mod style;
 mod units;
 
+pub use color::ColorExt;
 pub use corner::Corner;
 pub use edge::Edge;
 use fancy_regex::Matches;
 pub use options_ref::AsRefOption;
-pub use render_command::RenderCommand;
+pub use render_command::{CanvasShape, NinePatchMode, RenderCommand};
 pub use style::*;
 pub use units::*;
 
@@ -27,7 +31,7 @@ impl From<KStyle> for ComputedStyles {
     }
 }
 
-#[derive(Reflect, Clone, Copy, Default, Debug, PartialEq)]
+#[derive(Reflect, Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq)]
 pub struct BoxShadow {
     pub color: Color,
     pub radius: f32,
@@ -35,6 +39,58 @@ pub struct BoxShadow {
     pub spread: Vec2,
 }
 
+/// A soft shadow drawn behind the glyphs of a text widget
+#[derive(Reflect, Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct TextShadow {
+    /// The color of the shadow
+    pub color: Color,
+    /// How far the shadow is moved from the text (in pixels)
+    pub offset: Vec2,
+    /// How far the edges of the shadow are softened (in pixels)
+    ///
+    /// The blur is limited by the distance field of each glyph, so large values are cut off.
+    pub blur: f32,
+}
+
+/// A line drawn along the text of a widget
+#[derive(Reflect, Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TextDecoration {
+    /// No line is drawn
+    #[default]
+    None,
+    /// A line below the baseline
+    Underline,
+    /// A line above the tallest glyphs
+    Overline,
+    /// A line through the middle of lowercase letters
+    LineThrough,
+}
+
+/// An image drawn in place of the system cursor
+#[derive(Reflect, Clone, Default, Debug, PartialEq)]
+pub struct CursorImage {
+    /// The image to draw
+    pub handle: Handle<Image>,
+    /// The point of the image (in pixels from its top-left corner) placed at the pointer position
+    pub hotspot: Vec2,
+    /// The size to draw the image at, defaults to the size of the image
+    pub size: Option<Vec2>,
+}
+
+/// How a background image is sized to fit its widget
+#[derive(Reflect, Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum BackgroundSize {
+    /// Stretch the image to fill the widget, ignoring its aspect ratio
+    #[default]
+    Stretch,
+    /// Scale the image to fill the widget while keeping its aspect ratio, cropping the overflow
+    Cover,
+    /// Scale the image to fit within the widget while keeping its aspect ratio
+    Contain,
+    /// Repeat the image at its original size, starting from the top-left corner
+    Tile,
+}
+
 fn is_length(v: &str) -> bool {
     v == "0"
         || fancy_regex::Regex::new(r"^[0-9]+[a-zA-Z%]+?$")
@@ -1,14 +1,15 @@
 use bevy::{
-    prelude::{Handle, Image, Vec2},
+    prelude::{Color, Handle, Image, Vec2},
     reflect::Reflect,
 };
 #[cfg(feature = "svg")]
 use bevy_svg::prelude::Svg;
-use kayak_font::{Alignment, TextLayout, TextProperties};
+use kayak_font::{Alignment, TextDirection, TextLayout, TextProperties, TextSpan};
+use serde::Serialize;
 
 use super::Edge;
 
-#[derive(Debug, Reflect, Clone, PartialEq)]
+#[derive(Debug, Reflect, Serialize, Clone, PartialEq)]
 pub enum RenderCommand {
     Empty,
     /// Represents a node that has no renderable object but contributes to the layout.
@@ -17,31 +18,88 @@ pub enum RenderCommand {
     Quad,
     Text {
         content: String,
+        #[serde(skip)]
         alignment: Alignment,
+        #[serde(skip)]
+        direction: TextDirection,
         word_wrap: bool,
         subpixel: bool,
+        #[serde(skip)]
         text_layout: TextLayout,
+        #[serde(skip)]
         properties: TextProperties,
+        /// Runs of the content drawn with a different size or baseline, like superscripts
+        #[serde(skip)]
+        spans: Vec<TextSpan>,
     },
     Image {
+        #[serde(skip)]
         handle: Handle<Image>,
     },
     TextureAtlas {
         position: Vec2,
         size: Vec2,
+        #[serde(skip)]
         handle: Handle<Image>,
     },
     NinePatch {
         border: Edge<f32>,
+        #[serde(skip)]
         handle: Handle<Image>,
         scale: f32,
+        mode: NinePatchMode,
+    },
+    /// A list of shapes drawn by a [`Canvas`](crate::widgets::Canvas)
+    Canvas {
+        shapes: Vec<CanvasShape>,
     },
     #[cfg(feature = "svg")]
     Svg {
+        #[serde(skip)]
         handle: Handle<Svg>,
     },
 }
 
+/// How the edges and center of a nine-patch fill their area
+#[derive(Debug, Reflect, Serialize, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NinePatchMode {
+    /// Stretch a single copy of each segment to fill its area
+    #[default]
+    Stretch,
+    /// Repeat each segment at its original size, cutting off the last partial tile
+    Tile,
+}
+
+/// A shape drawn by a [`Canvas`](crate::widgets::Canvas)
+///
+/// All positions are relative to the top-left corner of the canvas.
+#[derive(Debug, Reflect, Serialize, Clone, PartialEq)]
+pub enum CanvasShape {
+    Line {
+        start: Vec2,
+        end: Vec2,
+        width: f32,
+        color: Color,
+    },
+    Rect {
+        position: Vec2,
+        size: Vec2,
+        color: Color,
+        radius: f32,
+    },
+    Circle {
+        center: Vec2,
+        radius: f32,
+        color: Color,
+    },
+    Triangle {
+        a: Vec2,
+        b: Vec2,
+        c: Vec2,
+        color: Color,
+    },
+}
+
 impl Default for RenderCommand {
     fn default() -> Self {
         Self::Empty
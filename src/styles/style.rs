@@ -2,10 +2,14 @@
 
 use std::ops::Add;
 
-pub use super::units::{KPositionType, LayoutType, Units};
-use super::BoxShadow;
+pub use super::units::{
+    AlignItems, KPositionType, KVisibility, LayoutDirection, LayoutType, Units,
+};
+use super::{BackgroundSize, BoxShadow, CursorImage, TextDecoration, TextShadow};
 use bevy::prelude::Color;
 use bevy::prelude::Component;
+use bevy::prelude::Handle;
+use bevy::prelude::Image;
 use bevy::prelude::ReflectComponent;
 use bevy::prelude::Vec2;
 use bevy::prelude::Vec3;
@@ -13,6 +17,7 @@ use bevy::prelude::Vec4;
 use bevy::reflect::FromReflect;
 use bevy::reflect::Reflect;
 use bevy::window::CursorIcon;
+use serde::{Deserialize, Serialize};
 
 use crate::cursor::PointerEvents;
 use crate::render::material::MaterialHandle;
@@ -23,7 +28,7 @@ pub use super::Edge;
 use super::RenderCommand;
 
 /// Just a wrapper around bevy's CursorIcon so we can define a default.
-#[derive(Debug, Reflect, Clone, PartialEq, Eq)]
+#[derive(Debug, Reflect, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct KCursorIcon(#[reflect(ignore)] pub CursorIcon);
 
 impl Default for KCursorIcon {
@@ -35,7 +40,7 @@ impl Default for KCursorIcon {
 /// The base container of all style properties
 ///
 /// The default value for this enum is [`StyleProp::Unset`].
-#[derive(Debug, Reflect, Clone, PartialEq, Eq)]
+#[derive(Debug, Reflect, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub enum StyleProp<T: Default + Clone + Reflect + FromReflect> {
     /// This prop is unset, meaning its actual value is not determined until style resolution,
     /// wherein it will be set to the property's default value.
@@ -259,13 +264,25 @@ define_styles! {
     ///   // Applied second (sets any remaining `StyleProp::Unset` fields)
     ///   .with_style(&style_b);
     /// ```
-    #[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+    #[derive(Component, Reflect, Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
     #[reflect(Component)]
+    #[serde(default)]
     pub struct KStyle {
         /// The background color of this widget
         ///
         /// Only applies to widgets marked [`RenderCommand::Quad`]
         pub background_color : StyleProp<Color>,
+        /// An image drawn over the background color of this widget
+        ///
+        /// The image is drawn within the border and clipped by the `border_radius`.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`]
+        #[serde(skip)]
+        pub background_image: StyleProp<Handle<Image>>,
+        /// How the `background_image` is sized to fit this widget
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`]
+        pub background_size: StyleProp<BackgroundSize>,
         /// The color of the border around this widget
         ///
         /// Currently, this controls all border sides.
@@ -295,21 +312,90 @@ define_styles! {
         pub color: StyleProp<Color>,
         /// The spacing between child widgets along the horizontal axis
         pub col_between: StyleProp<Units>,
+        /// The spacing between child widgets along both axes
+        ///
+        /// This is overridden by `col_between` and `row_between` when they are set.
+        pub gap: StyleProp<Units>,
         /// The cursor icon to display when hovering this widget
         #[reflect(ignore)]
         pub cursor: StyleProp<KCursorIcon>,
+        /// An image to display in place of the cursor when hovering this widget
+        ///
+        /// The system cursor is hidden while the image is shown. This takes precedence over
+        /// `cursor`, set it to [`StyleProp::Default`] to show the `cursor` icon again.
+        #[serde(skip)]
+        pub cursor_image: StyleProp<CursorImage>,
         /// The font name for this widget
         ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
         /// Only applies to [`RenderCommand::Text`]
         pub font: StyleProp<String>,
         /// The font size for this widget, in pixels
         ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`, so a theme or container
+        /// can set a base size which individual widgets (such as headings) override. Text widgets
+        /// only use their [`size`](crate::widgets::TextProps::size) prop when neither their inline
+        /// styles nor their theme style set this.
+        ///
         /// Only applies to [`RenderCommand::Text`]
         pub font_size: StyleProp<f32>,
+        /// The weight of the font along its `wght` axis, like `400.0` for regular or `700.0` for bold
+        ///
+        /// Only variable fonts loaded lazily from a `.kttf` file can change their weight; other
+        /// fonts ignore this. Every weight used generates its own glyphs and atlas the first time
+        /// it's drawn, so prefer a few fixed weights over animating this.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
+        /// Only applies to [`RenderCommand::Text`]
+        pub font_weight: StyleProp<f32>,
+        /// Extra space added after every grapheme of the text, in pixels
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
+        /// Only applies to [`RenderCommand::Text`]
+        pub letter_spacing: StyleProp<f32>,
+        /// A line drawn along the text, in the text color
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
+        /// Only applies to [`RenderCommand::Text`]
+        pub text_decoration: StyleProp<TextDecoration>,
+        /// An outline drawn around the glyphs of this widget, behind the text
+        ///
+        /// The float is the width of the outline in SDF units, where `0.0` draws no outline and
+        /// widths are limited to `0.4` (the edge of the distance field).
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Text`]
+        pub text_outline: StyleProp<(Color, f32)>,
+        /// A shadow drawn behind the glyphs (and outline) of this widget
+        ///
+        /// The shadow doesn't affect the size of the widget.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Text`]
+        pub text_shadow: StyleProp<TextShadow>,
         /// The height of this widget
         pub height: StyleProp<Units>,
         /// The layout method for children of this widget
         pub layout_type: StyleProp<LayoutType>,
+        /// How the children of this widget are aligned with each other
+        ///
+        /// Only applies when `layout_type` is [`LayoutType::Row`]. Aligning children doesn't
+        /// change the size of this widget, so an auto-sized row doesn't grow to fit moved children.
+        pub align_items: StyleProp<AlignItems>,
+        /// The horizontal direction children of this widget are laid out in
+        ///
+        /// With [`LayoutDirection::Rtl`], rows start on the right and the meaning of `left` and
+        /// `right` (including padding and [`SelfDirected`](KPositionType::SelfDirected) offsets)
+        /// is swapped, mirroring the whole subtree.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`], like `color`.
+        pub layout_direction: StyleProp<LayoutDirection>,
         /// The distance between the left edge of this widget and the left edge of its containing widget
         pub left: StyleProp<Units>,
         /// The line height for this widget, in pixels
@@ -374,6 +460,7 @@ define_styles! {
         /// The render method for this widget
         ///
         /// This controls what actually gets rendered and how it's rendered.
+        #[serde(skip_deserializing)]
         pub render_command: StyleProp<RenderCommand>,
         /// The distance between the right edge of this widget and the right edge of its containing widget
         pub right: StyleProp<Units>,
@@ -395,11 +482,13 @@ define_styles! {
         pub grid_cols: StyleProp<Vec<Units>>,
         /// The row index of this widget when using the grid layout
         ///
-        /// This references the `grid_rows` property of the parent widget.
+        /// This references the `grid_rows` property of the parent widget. If neither this nor
+        /// `col_index` is set, the widget is placed in the next free cell, filling the grid row by row.
         pub row_index: StyleProp<usize>,
         /// The column index of this widget when using the grid layout
         ///
-        /// This references the `grid_cols` property of the parent widget.
+        /// This references the `grid_cols` property of the parent widget. If neither this nor
+        /// `row_index` is set, the widget is placed in the next free cell, filling the grid row by row.
         pub col_index: StyleProp<usize>,
         /// The number rows that this widget spans when using the grid layout
         ///
@@ -417,8 +506,44 @@ define_styles! {
         /// Box shadow
         /// Currently only applied to quads
         pub box_shadow: StyleProp<Vec<BoxShadow>>,
+        /// The radius (in pixels) of a blur applied to whatever is drawn behind this widget
+        ///
+        /// This gives a frosted-glass look to widgets with a translucent `background_color`. The
+        /// radius is limited to [`MAX_BACKDROP_BLUR`](crate::prelude::MAX_BACKDROP_BLUR) and `0.0`
+        /// disables the blur.
+        ///
+        /// Every blurred widget splits the UI render pass and adds two blur passes over its area,
+        /// whose cost grows with the radius. It isn't supported within widgets with an `opacity`
+        /// below `1.0`.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`]
+        pub backdrop_blur: StyleProp<f32>,
+        /// Whether this widget and its children are drawn
+        ///
+        /// Unlike not rendering the widget at all, a hidden widget still takes up space in the layout.
+        pub visibility: StyleProp<KVisibility>,
+        /// The ratio of width to height this widget should keep
+        ///
+        /// When only one of `width` or `height` is set, the other is derived from it. If neither is
+        /// set, the height is derived from the width. The ratio is ignored when both are set.
+        ///
+        /// A derived dimension is still limited by its own min/max constraints, which take
+        /// precedence over the ratio.
+        pub aspect_ratio: StyleProp<f32>,
+        /// The name of a style in the [`Theme`](crate::prelude::Theme) to apply to this widget
+        ///
+        /// The theme style takes precedence over the widget's own styles but not over its inline styles.
+        pub theme: StyleProp<String>,
+        /// The color multiplied with the image of this widget
+        ///
+        /// Defaults to white, which leaves the image unchanged.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Image`], [`RenderCommand::TextureAtlas`]
+        /// and [`RenderCommand::NinePatch`]
+        pub tint: StyleProp<Color>,
         /// Overrides the default renderer with a custom material
         #[reflect(ignore)]
+        #[serde(skip)]
         pub material: StyleProp<MaterialHandle>,
     }
 }
@@ -431,17 +556,28 @@ impl KStyle {
     pub fn initial() -> Self {
         Self {
             background_color: StyleProp::Default,
+            background_image: StyleProp::Default,
+            background_size: StyleProp::Default,
             border: StyleProp::Default,
             border_color: StyleProp::Default,
             border_radius: StyleProp::Default,
             bottom: StyleProp::Default,
             color: StyleProp::Inherit,
             cursor: StyleProp::Inherit,
+            cursor_image: StyleProp::Inherit,
             col_between: StyleProp::Default,
+            gap: StyleProp::Default,
             font: StyleProp::Inherit,
             font_size: StyleProp::Inherit,
+            font_weight: StyleProp::Inherit,
+            letter_spacing: StyleProp::Inherit,
+            text_decoration: StyleProp::Inherit,
+            text_outline: StyleProp::Inherit,
+            text_shadow: StyleProp::Inherit,
             height: StyleProp::Default,
             layout_type: StyleProp::Default,
+            align_items: StyleProp::Default,
+            layout_direction: StyleProp::Inherit,
             line_height: StyleProp::Inherit,
             left: StyleProp::Default,
             max_height: StyleProp::Default,
@@ -470,6 +606,11 @@ impl KStyle {
             col_span: StyleProp::Default,
             opacity: StyleProp::Value(1.0),
             box_shadow: StyleProp::Default,
+            backdrop_blur: StyleProp::Default,
+            visibility: StyleProp::Default,
+            aspect_ratio: StyleProp::Default,
+            theme: StyleProp::Default,
+            tint: StyleProp::Default,
             material: StyleProp::Default,
         }
     }
@@ -528,6 +669,7 @@ impl KStyle {
         };
 
         new_styles.bottom = lerp_units(&self.bottom, &b.bottom, x);
+        new_styles.gap = lerp_units(&self.gap, &b.gap, x);
 
         new_styles.color = if let StyleProp::Value(color_a) = self.color {
             if let StyleProp::Value(color_b) = b.color {
@@ -540,6 +682,7 @@ impl KStyle {
         };
 
         new_styles.font_size = lerp_f32(&new_styles.font_size, &b.font_size, x);
+        new_styles.letter_spacing = lerp_f32(&new_styles.letter_spacing, &b.letter_spacing, x);
         new_styles.height = lerp_units(&self.height, &b.height, x);
         new_styles.line_height = lerp_f32(&new_styles.line_height, &b.line_height, x);
         new_styles.left = lerp_units(&self.left, &b.left, x);
@@ -618,6 +761,27 @@ impl KStyle {
         new_styles.top = lerp_units(&self.top, &b.top, x);
         new_styles.width = lerp_units(&self.width, &b.width, x);
         new_styles.opacity = lerp_f32(&self.opacity, &b.opacity, x);
+        new_styles.aspect_ratio = lerp_f32(&self.aspect_ratio, &b.aspect_ratio, x);
+
+        new_styles.text_outline = if let StyleProp::Value((color_a, width_a)) = self.text_outline {
+            if let StyleProp::Value((color_b, width_b)) = b.text_outline {
+                StyleProp::Value((hsv_lerp(&color_a, &color_b, x), lerp(width_a, width_b, x)))
+            } else {
+                StyleProp::Value((color_a, width_a))
+            }
+        } else {
+            self.text_outline.clone()
+        };
+
+        new_styles.tint = if let StyleProp::Value(color_a) = self.tint {
+            if let StyleProp::Value(color_b) = b.tint {
+                StyleProp::Value(hsv_lerp(&color_a, &color_b, x))
+            } else {
+                StyleProp::Value(color_a)
+            }
+        } else {
+            self.tint.clone()
+        };
 
         new_styles
     }
@@ -721,6 +885,13 @@ fn rgb_to_hsv(from: &Color) -> Vec3 {
         return res;
     }
 
+    // Grays have no hue
+    if delta == 0.0 {
+        res.x = -1.0;
+
+        return res;
+    }
+
     // calc Hue
     if r == max {
         // between Yellow & Magenta
@@ -784,7 +955,7 @@ fn hsv_to_rgb(from: &Vec3) -> Color {
     Color::rgba_from_array(res)
 }
 
-fn hsv_lerp(from: &Color, to: &Color, amount: f32) -> Color {
+pub(super) fn hsv_lerp(from: &Color, to: &Color, amount: f32) -> Color {
     let from_a = from.a();
     let to_a = to.a();
     let from = rgb_to_hsv(from);
@@ -792,7 +963,9 @@ fn hsv_lerp(from: &Color, to: &Color, amount: f32) -> Color {
     let mut res = from.lerp(to, amount);
 
     if from.x < 0.0 {
-        res.x = to.x;
+        res.x = to.x.max(0.0);
+    } else if to.x < 0.0 {
+        res.x = from.x;
     }
     let mut color = hsv_to_rgb(&res);
     color.set_a(lerp(from_a, to_a, amount).clamp(0.0, 1.0));
@@ -817,7 +990,9 @@ impl Add for KStyle {
 
 #[cfg(test)]
 mod tests {
-    use super::{Edge, KStyle, StyleProp, Units};
+    use bevy::prelude::Color;
+
+    use super::{hsv_lerp, Edge, KStyle, StyleProp, Units};
 
     #[test]
     fn styles_should_equal() {
@@ -1003,4 +1178,30 @@ mod tests {
         assert_eq!(expected, property.resolve_or_else(|| expected));
         assert_eq!(f32::default(), property.resolve_or_default());
     }
+
+    #[test]
+    fn hsv_lerp_should_keep_the_hue_of_the_chromatic_color() {
+        let cases = [
+            // Fading in from black
+            (Color::BLACK, Color::RED, [0.5, 0.25, 0.25]),
+            // Fading out to white
+            (Color::BLUE, Color::WHITE, [0.5, 0.5, 1.0]),
+            // Neither color has a hue
+            (Color::WHITE, Color::BLACK, [0.5, 0.5, 0.5]),
+        ];
+
+        for (from, to, expected) in cases {
+            let actual = hsv_lerp(&from, &to, 0.5).as_rgba_f32();
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                assert!(
+                    (expected - actual).abs() < 1e-4,
+                    "{:?} -> {:?}: expected {:?}, got {:?}",
+                    from,
+                    to,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
 }
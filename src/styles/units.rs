@@ -1,7 +1,8 @@
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
 /// The layout type determines how nodes will be positioned when directed by the parent
-#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum LayoutType {
     /// Stack child elements horizontally
     Row,
@@ -23,7 +24,7 @@ impl From<LayoutType> for morphorm::LayoutType {
 }
 
 /// The position type determines whether a node will be positioned in-line with its siblings or seperate
-#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum KPositionType {
     /// Node is positioned relative to parent but ignores its siblings
     SelfDirected,
@@ -41,8 +42,42 @@ impl From<KPositionType> for morphorm::PositionType {
     }
 }
 
+/// The horizontal direction children are laid out in
+///
+/// This is separate from the direction of text, which is set on each text widget.
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutDirection {
+    #[default]
+    /// Rows start on the left
+    Ltr,
+    /// Rows start on the right, and children are mirrored horizontally within their parent
+    Rtl,
+}
+
+/// How the children of a row are aligned with each other
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum AlignItems {
+    #[default]
+    /// Children are placed by their own spacing, such as `top` and `bottom`
+    Normal,
+    /// Children are moved so the baselines of their first lines of text line up
+    ///
+    /// Children that aren't text have their bottom edge placed on the baseline instead.
+    Baseline,
+}
+
+/// Whether a node and its children are drawn
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum KVisibility {
+    #[default]
+    /// Node is drawn as usual
+    Visible,
+    /// Node and its children are not drawn and don't receive pointer events, but still take up space in the layout
+    Hidden,
+}
+
 /// Units which describe spacing and size
-#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Reflect, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum Units {
     /// A number of pixels
     Pixels(f32),
@@ -52,6 +87,9 @@ pub enum Units {
     Stretch(f32),
     #[default]
     /// Automatically determine the value
+    ///
+    /// As a width or height, the node wraps around its children, including the measured size of
+    /// any text.
     Auto,
 }
 
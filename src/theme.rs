@@ -0,0 +1,109 @@
+use bevy::{
+    prelude::{Commands, DetectChanges, Entity, Query, Res, Resource},
+    utils::HashMap,
+};
+
+use crate::{
+    node::DirtyNode,
+    styles::{ComputedStyles, KStyle, StyleProp},
+};
+
+/// A collection of named styles shared across widgets
+///
+/// Widgets use a theme style by setting their [`theme`](KStyle::theme) style to its name.
+/// The styles are merged in the following order (highest precedence first):
+/// 1. The widget's inline `styles`
+/// 2. The named theme style
+/// 3. The styles the widget sets for itself (such as a button's default background)
+///
+/// Changing this resource restyles all widgets using a theme style without re-rendering them.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn setup(mut theme: ResMut<Theme>) {
+///     theme.insert(
+///         "panel",
+///         KStyle {
+///             background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+///             ..Default::default()
+///         },
+///     );
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct Theme {
+    styles: HashMap<String, KStyle>,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a named style, replacing any existing style with the same name
+    pub fn insert(&mut self, name: impl Into<String>, style: KStyle) {
+        self.styles.insert(name.into(), style);
+    }
+
+    /// Adds a named style and returns the updated theme
+    pub fn with_style(mut self, name: impl Into<String>, style: KStyle) -> Self {
+        self.insert(name, style);
+        self
+    }
+
+    /// Returns the style with the given name, if any
+    pub fn get(&self, name: &str) -> Option<&KStyle> {
+        self.styles.get(name)
+    }
+
+    /// Removes the style with the given name, returning it if it existed
+    pub fn remove(&mut self, name: &str) -> Option<KStyle> {
+        self.styles.remove(name)
+    }
+
+    /// Removes all styles
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Merges the theme style named by `computed` into it
+    ///
+    /// Returns `None` if the styles don't use a theme style.
+    pub(crate) fn resolve(&self, inline: Option<&KStyle>, computed: &KStyle) -> Option<KStyle> {
+        if let StyleProp::Value(name) = &computed.theme {
+            if let Some(theme_style) = self.get(name) {
+                return Some(
+                    inline
+                        .cloned()
+                        .unwrap_or_default()
+                        .with_style(theme_style)
+                        .with_style(computed),
+                );
+            } else {
+                log::warn!("No theme style named: {}", name);
+            }
+        }
+
+        None
+    }
+}
+
+/// Marks all widgets using a theme style as dirty whenever the [`Theme`] changes
+pub(crate) fn restyle_on_theme_change(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    query: Query<(Entity, &ComputedStyles)>,
+) {
+    if !theme.is_changed() || theme.is_added() {
+        return;
+    }
+
+    for (entity, styles) in query.iter() {
+        if matches!(styles.0.theme, StyleProp::Value(..)) {
+            commands.entity(entity).insert(DirtyNode);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::Resource;
+
+/// A multiplier for the size of every Kayak UI, applied on top of the window's scale factor
+///
+/// Use this to offer a "UI scale" setting: layout sizes, font sizes and quad positions are all
+/// scaled together, so a scale of `2.0` draws everything twice as large while leaving half the
+/// room (in each direction) for the UI to be laid out in. Text stays crisp at any scale since it's
+/// rendered from signed distance fields.
+///
+/// Positions and sizes Kayak hands to widgets (layouts, cursor positions and [`WindowSize`](crate::WindowSize))
+/// are in UI units, which are logical pixels divided by this scale.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct KUiScale(pub f32);
+
+impl Default for KUiScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
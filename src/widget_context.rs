@@ -1,12 +1,17 @@
 use std::sync::{Arc, RwLock};
 
-use bevy::prelude::{BuildChildren, Commands, Component, Entity, Resource};
-use dashmap::DashMap;
+use bevy::prelude::{BuildChildren, Commands, Component, Entity, Query, Resource};
+use dashmap::{DashMap, DashSet};
 use morphorm::Hierarchy;
 
 use crate::{
-    context_entities::ContextEntities, layout::LayoutCache, node::WrappedIndex, prelude::Tree,
+    breakpoints::{Breakpoint, Breakpoints},
+    context_entities::ContextEntities,
+    layout::LayoutCache,
+    node::WrappedIndex,
+    prelude::Tree,
     widget_state::WidgetState,
+    WindowSize,
 };
 
 /// KayakWidgetContext manages tree, state, and context updates within a single widget.
@@ -30,6 +35,10 @@ pub struct KayakWidgetContext {
     // The 2nd hashmap is a list of keys and their entities.
     unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
     unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+    // The keys spawned during this render, along with their parent widget.
+    spawned_keys: Arc<DashSet<(Entity, String)>>,
+    // The entities holding the previous values of each widget.
+    cloned_widget_entities: Arc<DashMap<Entity, Entity>>,
 }
 
 impl KayakWidgetContext {
@@ -43,6 +52,7 @@ impl KayakWidgetContext {
         camera_entity: Option<Entity>,
         unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
         unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+        cloned_widget_entities: Arc<DashMap<Entity, Entity>>,
     ) -> Self {
         Self {
             old_tree,
@@ -55,6 +65,8 @@ impl KayakWidgetContext {
             camera_entity,
             unique_ids,
             unique_ids_parents,
+            spawned_keys: Arc::new(DashSet::new()),
+            cloned_widget_entities,
         }
     }
 
@@ -109,6 +121,50 @@ impl KayakWidgetContext {
         None
     }
 
+    /// Finds the closest value of type `T` provided by an ancestor (or by this widget itself).
+    ///
+    /// Values are usually provided with a [`ContextProvider`](crate::widgets::ContextProvider).
+    /// Register the widget with [`widget_update_with_context`](crate::prelude::widget_update_with_context)
+    /// so it re-renders when the value changes.
+    pub fn get_context<'a, T: Component + Default>(
+        &self,
+        current_entity: Entity,
+        query: &'a Query<&T>,
+    ) -> Option<&'a T> {
+        self.get_context_entity::<T>(current_entity)
+            .and_then(|context_entity| query.get(context_entity).ok())
+    }
+
+    /// Returns the [`PreviousWidget`](crate::prelude::PreviousWidget) entity holding the values
+    /// the given widget had when it was last rendered.
+    ///
+    /// This is the same entity the widget's update system receives as its second input.
+    pub fn previous_entity(&self, widget_entity: Entity) -> Option<Entity> {
+        self.cloned_widget_entities
+            .get(&widget_entity)
+            .map(|entity| *entity.value())
+    }
+
+    /// Returns the props the given widget had when it was last rendered.
+    ///
+    /// Only props registered with [`KayakRootContext::add_widget_data`](crate::prelude::KayakRootContext::add_widget_data)
+    /// are kept. They're copied when the widget's update system decides to re-render it, right
+    /// before its render system runs, so:
+    /// - In an update system these are the props from the last render, which can be compared to
+    ///   the current props to skip rendering (or other expensive work) when nothing changed.
+    /// - In a render system these already match the current props.
+    ///
+    /// Returns `None` before the widget's first render. The props are borrowed from the given
+    /// query, so they can't outlive the system they were read in.
+    pub fn previous_props<'a, T: Component>(
+        &self,
+        widget_entity: Entity,
+        query: &'a Query<&T>,
+    ) -> Option<&'a T> {
+        self.previous_entity(widget_entity)
+            .and_then(|previous_entity| query.get(previous_entity).ok())
+    }
+
     pub(crate) fn copy_from_point(&self, other_tree: &Arc<RwLock<Tree>>, entity: WrappedIndex) {
         if let Ok(other_tree) = other_tree.read() {
             if let Ok(mut tree) = self.new_tree.write() {
@@ -162,6 +218,13 @@ impl KayakWidgetContext {
     }
 
     /// Creates or grabs the existing state entity
+    ///
+    /// Widgets are diffed against their previous state once per frame (in `PostUpdate`), so any
+    /// number of changes made to the state during a frame, such as by several event handlers,
+    /// only re-render the widget once with the final value. See [`KayakRootContext::flush`] to
+    /// apply pending changes sooner.
+    ///
+    /// [`KayakRootContext::flush`]: crate::prelude::KayakRootContext::flush
     pub fn use_state<State: Component + PartialEq + Clone + Default>(
         &self,
         commands: &mut Commands,
@@ -177,10 +240,150 @@ impl KayakWidgetContext {
         self.widget_state.get(widget_entity)
     }
 
+    /// Uses the global (app-wide) value `T`, re-rendering the widget whenever it changes
+    ///
+    /// Global values are plain bevy resources, inserted with their default value if they don't
+    /// exist yet. Unlike [`use_state`](Self::use_state), which gives each widget its own state
+    /// entity that's diffed against its previous value, a global is shared by every widget using
+    /// it and any change to it (as detected by bevy's change detection) re-renders all of them.
+    ///
+    /// Since the default value is only inserted once the render system's commands are applied,
+    /// read the global with `Option<Res<T>>` in the render system unless the app inserts it
+    /// beforehand. It can be changed from any system or event handler through `ResMut<T>`; bevy's
+    /// scheduler takes care of access across threads, so no locking is needed. Use
+    /// [`set_if_neq`](bevy::prelude::DetectChangesMut::set_if_neq) to avoid re-rendering when
+    /// the value stays the same.
+    ///
+    /// Usage:
+    /// ```ignore
+    /// fn my_widget_render(
+    ///     In(entity): In<Entity>,
+    ///     widget_context: Res<KayakWidgetContext>,
+    ///     mut commands: Commands,
+    ///     dark_mode: Option<Res<DarkMode>>,
+    /// ) -> bool {
+    ///     widget_context.use_global::<DarkMode>(&mut commands, entity);
+    ///     let is_dark = dark_mode.is_some_and(|dark_mode| dark_mode.0);
+    ///     // ...
+    ///     true
+    /// }
+    /// ```
+    pub fn use_global<T: Resource + Default>(
+        &self,
+        commands: &mut Commands,
+        widget_entity: Entity,
+    ) {
+        commands.init_resource::<T>();
+        self.widget_state.add_global::<T>(widget_entity);
+    }
+
+    /// Uses a value derived from the global value `T`, re-rendering the widget only when the
+    /// derived value changes
+    ///
+    /// `global` is the current value of the global (usually read with `Option<Res<T>>`), its
+    /// default value is used if it doesn't exist yet. The derived value is returned, and
+    /// whenever the global changes `map` is run again to check if the widget needs to be
+    /// re-rendered. Keep `map` cheap and pure, as it may be run several times per frame.
+    ///
+    /// Usage:
+    /// ```ignore
+    /// fn my_widget_render(
+    ///     In(entity): In<Entity>,
+    ///     widget_context: Res<KayakWidgetContext>,
+    ///     mut commands: Commands,
+    ///     score: Option<Res<Score>>,
+    /// ) -> bool {
+    ///     // Only re-renders when the text changes, not for every point scored
+    ///     let text = widget_context.use_global_map(
+    ///         &mut commands,
+    ///         entity,
+    ///         score.as_deref(),
+    ///         |score: &Score| format!("Level {}", score.0 / 1000),
+    ///     );
+    ///     // ...
+    ///     true
+    /// }
+    /// ```
+    pub fn use_global_map<T, U, F>(
+        &self,
+        commands: &mut Commands,
+        widget_entity: Entity,
+        global: Option<&T>,
+        map: F,
+    ) -> U
+    where
+        T: Resource + Default,
+        U: Clone + PartialEq + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        commands.init_resource::<T>();
+        let value = match global {
+            Some(global) => map(global),
+            None => map(&T::default()),
+        };
+        self.widget_state
+            .add_derived_global(widget_entity, value.clone(), map);
+        value
+    }
+
+    /// Returns the [`Breakpoint`] the window's width falls into, re-rendering the widget
+    /// whenever it changes
+    ///
+    /// Resizing the window within a breakpoint doesn't re-render the widget. The thresholds
+    /// are read from the [`Breakpoints`] resource.
+    ///
+    /// Usage:
+    /// ```ignore
+    /// fn my_widget_render(
+    ///     In(entity): In<Entity>,
+    ///     widget_context: Res<KayakWidgetContext>,
+    ///     mut commands: Commands,
+    ///     breakpoints: Res<Breakpoints>,
+    ///     window_size: Res<WindowSize>,
+    /// ) -> bool {
+    ///     let layout_type = match widget_context.use_breakpoint(
+    ///         &mut commands,
+    ///         entity,
+    ///         &breakpoints,
+    ///         &window_size,
+    ///     ) {
+    ///         Breakpoint::Sm => LayoutType::Column,
+    ///         Breakpoint::Md | Breakpoint::Lg => LayoutType::Row,
+    ///     };
+    ///     // ...
+    ///     true
+    /// }
+    /// ```
+    pub fn use_breakpoint(
+        &self,
+        commands: &mut Commands,
+        widget_entity: Entity,
+        breakpoints: &Breakpoints,
+        window_size: &WindowSize,
+    ) -> Breakpoint {
+        self.use_global::<Breakpoints>(commands, widget_entity);
+        let breakpoints = *breakpoints;
+        self.use_global_map(
+            commands,
+            widget_entity,
+            Some(window_size),
+            move |window_size: &WindowSize| breakpoints.get(window_size.0),
+        )
+    }
+
     /// Returns a new/existing widget entity.
     /// Because a re-render can potentially spawn new entities it's advised to use this
     /// to avoid creating a new entity.
     ///
+    /// Children spawned without a key are matched to the previous render by their position among
+    /// the other unkeyed children of the parent. Children spawned with a key are matched by that
+    /// key instead, so they keep their entity (and with it their state, focus and scroll
+    /// position) when the list they're in gets reordered, filtered or grown. A key that wasn't
+    /// spawned on the previous render gets a new entity, and an entity whose key isn't spawned
+    /// anymore is despawned along with its state. Keys only need to be unique among the children
+    /// of a parent; spawning the same key twice in one render logs a warning and treats the
+    /// second child as unkeyed.
+    ///
     /// Usage:
     /// ```rust
     /// fn setup() {
@@ -198,6 +401,20 @@ impl KayakWidgetContext {
         parent_id: Option<Entity>,
     ) -> Entity {
         let mut entity = None;
+        let key = key.filter(|key| match parent_id {
+            Some(parent_entity) => {
+                let is_unique = self.spawned_keys.insert((parent_entity, key.to_string()));
+                if !is_unique {
+                    log::warn!(
+                        "Widget key {:?} was used more than once with parent: {:?}!",
+                        key,
+                        parent_entity.index()
+                    );
+                }
+                is_unique
+            }
+            None => true,
+        });
         if let Some(parent_entity) = parent_id {
             if let Some(key) = key.map(|key| key.to_string()) {
                 if let Some(key_hashmap) = self.unique_ids.get(&parent_entity) {
@@ -321,6 +538,11 @@ impl KayakWidgetContext {
 
     /// Attempts to get the layout rect for the widget with the given ID
     ///
+    /// Widgets are rendered before the layout is calculated, so this returns the layout from the
+    /// previous frame (or `None` for widgets that were just spawned). See
+    /// [`KayakRootContext::layout`](crate::prelude::KayakRootContext::layout) for reading layouts
+    /// from regular systems.
+    ///
     /// # Arguments
     ///
     /// * `id`: The ID of the widget
@@ -1,6 +1,18 @@
-use bevy::prelude::{BuildChildren, Commands, Component, Entity, Resource};
+use bevy::{
+    ecs::component::Tick,
+    prelude::{BuildChildren, Commands, Component, DetectChanges, Entity, Resource, World},
+};
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::{any::TypeId, fmt, sync::Arc};
+
+/// Returns true if a global has changed since the given tick
+struct GlobalChanged(Box<dyn Fn(&World, Tick) -> bool + Send + Sync>);
+
+impl fmt::Debug for GlobalChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GlobalChanged")
+    }
+}
 
 /// Stores mappings between widget entities and their corresponding state entities.
 #[derive(Resource, Default, Debug, Clone)]
@@ -9,6 +21,10 @@ pub struct WidgetState {
     mapping: Arc<DashMap<Entity, Entity>>,
     // State entity to widget entity
     reverse_mapping: Arc<DashMap<Entity, Entity>>,
+    // Widget entity to the globals it re-renders for
+    globals: Arc<DashMap<Entity, Vec<(TypeId, GlobalChanged)>>>,
+    // Widget entity to the change tick it was last rendered at
+    rendered: Arc<DashMap<Entity, Tick>>,
 }
 
 impl WidgetState {
@@ -46,7 +62,78 @@ impl WidgetState {
             .map(|entry| *entry.value())
     }
 
+    /// Re-renders the widget whenever the global resource `T` changes
+    pub fn add_global<T: Resource>(&self, widget_entity: Entity) {
+        let mut globals = self.globals.entry(widget_entity).or_default();
+        if !globals
+            .iter()
+            .any(|(type_id, _)| *type_id == TypeId::of::<T>())
+        {
+            globals.push((
+                TypeId::of::<T>(),
+                GlobalChanged(Box::new(global_changed::<T>)),
+            ));
+        }
+    }
+
+    /// Re-renders the widget whenever the value derived from the global resource `T` changes
+    ///
+    /// `value` is the derived value the widget is rendered with. Each derivation is told apart
+    /// by the type of `map`, so calling this again with the same closure replaces the value.
+    pub fn add_derived_global<T, U, F>(&self, widget_entity: Entity, value: U, map: F)
+    where
+        T: Resource,
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let has_changed = GlobalChanged(Box::new(move |world: &World, _| {
+            world
+                .get_resource::<T>()
+                .is_some_and(|global| map(global) != value)
+        }));
+        let mut globals = self.globals.entry(widget_entity).or_default();
+        if let Some((_, existing)) = globals
+            .iter_mut()
+            .find(|(type_id, _)| *type_id == TypeId::of::<F>())
+        {
+            *existing = has_changed;
+        } else {
+            globals.push((TypeId::of::<F>(), has_changed));
+        }
+    }
+
+    /// Returns true if any of the globals used by the widget changed since `last_run`
+    ///
+    /// Changes the widget was already rendered with (e.g. earlier in the same update) are
+    /// ignored, so several changes only re-render it once.
+    pub(crate) fn has_global_changed(
+        &self,
+        widget_entity: Entity,
+        world: &World,
+        last_run: Tick,
+    ) -> bool {
+        let this_run = world.read_change_tick();
+        let last_run = self
+            .rendered
+            .get(&widget_entity)
+            .map(|rendered| *rendered)
+            .filter(|rendered| rendered.is_newer_than(last_run, this_run))
+            .unwrap_or(last_run);
+        self.globals.get(&widget_entity).is_some_and(|globals| {
+            globals
+                .iter()
+                .any(|(_, has_changed)| (has_changed.0)(world, last_run))
+        })
+    }
+
+    /// Records the change tick the widget was rendered at
+    pub(crate) fn set_rendered(&self, widget_entity: Entity, tick: Tick) {
+        self.rendered.insert(widget_entity, tick);
+    }
+
     pub fn remove(&self, widget_entity: Entity) -> Option<Entity> {
+        self.globals.remove(&widget_entity);
+        self.rendered.remove(&widget_entity);
         let state_entity = self.mapping.remove(&widget_entity).map(|(_, v)| v);
         if let Some(state_entity) = state_entity {
             self.reverse_mapping.remove(&state_entity);
@@ -54,3 +141,69 @@ impl WidgetState {
         state_entity
     }
 }
+
+fn global_changed<T: Resource>(world: &World, last_run: Tick) -> bool {
+    world.get_resource_ref::<T>().is_some_and(|global| {
+        global
+            .last_changed()
+            .is_newer_than(last_run, world.read_change_tick())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::component::Tick,
+        prelude::{Resource, World},
+    };
+
+    use super::WidgetState;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn is_even(counter: &Counter) -> bool {
+        counter.0 % 2 == 0
+    }
+
+    #[test]
+    fn derived_globals_should_only_rerender_when_the_derived_value_changes() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let widget = world.spawn_empty().id();
+        let widget_state = WidgetState::default();
+        widget_state.add_derived_global(widget, true, is_even);
+        let last_run = Tick::new(0);
+
+        world.resource_mut::<Counter>().0 = 2;
+        assert!(!widget_state.has_global_changed(widget, &world, last_run));
+
+        world.resource_mut::<Counter>().0 = 3;
+        assert!(widget_state.has_global_changed(widget, &world, last_run));
+
+        widget_state.add_derived_global(widget, false, is_even);
+        assert!(!widget_state.has_global_changed(widget, &world, last_run));
+    }
+
+    #[test]
+    fn globals_should_only_rerender_once_per_change() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let widget = world.spawn_empty().id();
+        let widget_state = WidgetState::default();
+        widget_state.add_global::<Counter>(widget);
+        let last_run = Tick::new(0);
+
+        world.increment_change_tick();
+        world.resource_mut::<Counter>().0 += 1;
+        world.resource_mut::<Counter>().0 += 1;
+        assert!(widget_state.has_global_changed(widget, &world, last_run));
+
+        widget_state.set_rendered(widget, world.read_change_tick());
+        assert!(!widget_state.has_global_changed(widget, &world, last_run));
+
+        world.increment_change_tick();
+        world.resource_mut::<Counter>().0 += 1;
+        assert!(widget_state.has_global_changed(widget, &world, last_run));
+    }
+}
@@ -1,4 +1,4 @@
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{prelude::*, utils::HashSet};
 
 use crate::{
     children::KChildren,
@@ -10,38 +10,62 @@ use crate::{
 
 #[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
 pub struct AccordionContext {
-    allow_one: bool,
-    accordions: HashMap<usize, bool>,
+    allow_multiple: bool,
+    open: HashSet<usize>,
 }
 
 impl AccordionContext {
     pub fn is_open(&self, index: usize) -> bool {
-        self.accordions.get(&index).copied().unwrap_or(false)
+        self.open.contains(&index)
+    }
+
+    /// Returns the indices of all currently open sections
+    pub fn open_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.open.iter().copied()
     }
 
     pub fn toggle_current(&mut self, index: usize) {
-        if self.allow_one {
-            self.accordions
-                .iter_mut()
-                .filter(|(e, _)| **e != index)
-                .for_each(|(_, v)| {
-                    *v = false;
-                });
-        }
-        if let Some(open) = self.accordions.get_mut(&index) {
-            *open = !*open;
-        } else {
-            self.accordions.insert(index, true);
+        if !self.open.remove(&index) {
+            if !self.allow_multiple {
+                self.open.clear();
+            }
+            self.open.insert(index);
         }
     }
 }
 
-#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub struct AccordionContextProvider {
+    /// If true (the default), several sections can be open at the same time
+    ///
+    /// Otherwise opening a section closes any other open section.
+    pub allow_multiple: bool,
+    /// If true, opening a section closes any other open section
+    #[deprecated(note = "set `allow_multiple` to false instead")]
     pub allow_only_one: bool,
     pub default_open: Option<usize>,
 }
 
+#[allow(deprecated)]
+impl Default for AccordionContextProvider {
+    fn default() -> Self {
+        Self {
+            allow_multiple: true,
+            allow_only_one: false,
+            default_open: None,
+        }
+    }
+}
+
+impl AccordionContextProvider {
+    /// Returns true if several sections can be open at the same time
+    pub(crate) fn allows_multiple(&self) -> bool {
+        #[allow(deprecated)]
+        let allow_only_one = self.allow_only_one;
+        self.allow_multiple && !allow_only_one
+    }
+}
+
 impl Widget for AccordionContextProvider {}
 
 #[derive(Bundle, Debug, Clone, PartialEq)]
@@ -81,7 +105,7 @@ pub fn render(
             context_entity
         } else {
             let mut accordion_context = AccordionContext {
-                allow_one: accordion.allow_only_one,
+                allow_multiple: accordion.allows_multiple(),
                 ..AccordionContext::default()
             };
             if let Some(default_open) = accordion.default_open {
@@ -95,3 +119,56 @@ pub fn render(
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AccordionContext, AccordionContextProvider};
+
+    #[test]
+    fn opening_should_close_others_when_single() {
+        let mut context = AccordionContext::default();
+        context.toggle_current(0);
+        context.toggle_current(1);
+
+        assert!(!context.is_open(0));
+        assert!(context.is_open(1));
+
+        context.toggle_current(1);
+        assert!(!context.is_open(1));
+    }
+
+    #[test]
+    fn opening_should_keep_others_when_multiple() {
+        let mut context = AccordionContext {
+            allow_multiple: true,
+            ..Default::default()
+        };
+        context.toggle_current(0);
+        context.toggle_current(1);
+
+        assert!(context.is_open(0));
+        assert!(context.is_open(1));
+
+        context.toggle_current(0);
+        assert!(!context.is_open(0));
+        assert!(context.is_open(1));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn providers_should_allow_multiple_unless_told_otherwise() {
+        assert!(AccordionContextProvider::default().allows_multiple());
+
+        let single = AccordionContextProvider {
+            allow_multiple: false,
+            ..Default::default()
+        };
+        assert!(!single.allows_multiple());
+
+        let only_one = AccordionContextProvider {
+            allow_only_one: true,
+            ..Default::default()
+        };
+        assert!(!only_one.allows_multiple());
+    }
+}
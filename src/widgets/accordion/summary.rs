@@ -78,6 +78,9 @@ pub fn render(
                           mut event: ResMut<KEvent>,
                           mut query: Query<&mut AccordionContext>| {
                         if let Ok(mut context) = query.get_mut(context_entity) {
+                            if event.is_default_prevented() {
+                                return;
+                            }
                             event.stop_propagation();
                             event.prevent_default();
                             if let EventType::Click(..) = event.event_type {
@@ -92,7 +95,7 @@ pub fn render(
                     <BackgroundBundle
                         styles={KStyle {
                             background_color: Color::rgba(0.133, 0.145, 0.2, 1.0).into(),
-                            border_radius: if accordion.index > 0 { StyleProp::Unset } else { Corner::new(5.0, 5.0, 0.0, 0.0).into() },
+                            border_radius: if accordion.index > 0 { StyleProp::Unset } else { Corner::top(5.0).into() },
                             width: Units::Stretch(1.0).into(),
                             height: Units::Auto.into(),
                             padding: Edge::new(Units::Pixels(12.0), Units::Pixels(16.0), Units::Pixels(16.0), Units::Pixels(16.0)).into(),
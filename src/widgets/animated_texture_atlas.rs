@@ -0,0 +1,206 @@
+use bevy::prelude::{
+    Bundle, Commands, Component, Entity, Handle, Image, In, Query, Rect, Res, Time, Vec2,
+};
+
+use crate::{
+    context::WidgetName,
+    handler::Handler,
+    node::DirtyNode,
+    styles::{ComputedStyles, KStyle, RenderCommand, StyleProp},
+    widget::Widget,
+};
+
+/// A widget that plays an animation made up of cells of a texture atlas
+///
+/// Frames are advanced without re-rendering the widget, only the displayed cell is updated.
+///
+/// # Props
+///
+/// __Type:__ [`AnimatedTextureAtlasProps`]
+///
+/// | Common Prop | Accepted |
+/// | :---------: | :------: |
+/// | `children`  |           |
+/// | `styles`    | ✅        |
+/// | `on_event`  |           |
+/// | `on_layout` |           |
+/// | `focusable` |           |
+///
+#[derive(Component, PartialEq, Clone, Debug)]
+pub struct AnimatedTextureAtlasProps {
+    /// The handle to image
+    pub handle: Handle<Image>,
+    /// The cells of the image (in pixels) to display, in order
+    pub frames: Vec<Rect>,
+    /// How long each frame is displayed (in seconds)
+    pub frame_duration: f32,
+    /// Whether the animation starts over after the last frame
+    /// defaults to true
+    pub looping: bool,
+    /// Whether the animation is currently playing
+    /// defaults to true
+    pub playing: bool,
+    /// Called each time a looping animation starts over
+    pub on_loop: Handler,
+    /// Called when a non-looping animation reaches its last frame
+    pub on_end: Handler,
+}
+
+impl Default for AnimatedTextureAtlasProps {
+    fn default() -> Self {
+        Self {
+            handle: Default::default(),
+            frames: Vec::new(),
+            frame_duration: 0.1,
+            looping: true,
+            playing: true,
+            on_loop: Default::default(),
+            on_end: Default::default(),
+        }
+    }
+}
+
+impl AnimatedTextureAtlasProps {
+    /// Creates a list of frames from a sprite sheet laid out in a grid, read row by row
+    pub fn grid_frames(tile_size: Vec2, columns: usize, rows: usize) -> Vec<Rect> {
+        (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| {
+                    let min = Vec2::new(column as f32, row as f32) * tile_size;
+                    Rect {
+                        min,
+                        max: min + tile_size,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl Widget for AnimatedTextureAtlasProps {}
+
+/// The playback position of an [`AnimatedTextureAtlasProps`] widget
+#[derive(Component, Default, Clone, Debug, PartialEq)]
+pub struct TextureAtlasAnimation {
+    /// The index of the frame being displayed
+    pub frame: usize,
+    /// The time the current frame has been displayed for (in seconds)
+    pub elapsed: f32,
+    /// Whether a non-looping animation has reached its last frame
+    pub finished: bool,
+}
+
+impl TextureAtlasAnimation {
+    /// Starts the animation over from the first frame
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// A widget that plays a sprite sheet animation
+#[derive(Bundle)]
+pub struct AnimatedTextureAtlasBundle {
+    pub atlas: AnimatedTextureAtlasProps,
+    pub animation: TextureAtlasAnimation,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for AnimatedTextureAtlasBundle {
+    fn default() -> Self {
+        Self {
+            atlas: Default::default(),
+            animation: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: AnimatedTextureAtlasProps::default().get_name(),
+        }
+    }
+}
+
+pub fn animated_texture_atlas_render(
+    In(entity): In<Entity>,
+    mut query: Query<(
+        &KStyle,
+        &mut ComputedStyles,
+        &AnimatedTextureAtlasProps,
+        &mut TextureAtlasAnimation,
+    )>,
+) -> bool {
+    if let Ok((styles, mut computed_styles, atlas, mut animation)) = query.get_mut(entity) {
+        if animation.frame >= atlas.frames.len() {
+            // The frames were changed
+            animation.restart();
+        }
+
+        let frame = atlas
+            .frames
+            .get(animation.frame)
+            .copied()
+            .unwrap_or_default();
+        *computed_styles = KStyle {
+            render_command: RenderCommand::TextureAtlas {
+                position: frame.min,
+                size: frame.size(),
+                handle: atlas.handle.clone_weak(),
+            }
+            .into(),
+            ..Default::default()
+        }
+        .with_style(styles)
+        .into();
+    }
+
+    true
+}
+
+/// Advances the frames of all playing [`AnimatedTextureAtlasProps`] widgets
+pub fn update_animated_texture_atlases(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &AnimatedTextureAtlasProps,
+        &mut TextureAtlasAnimation,
+        &mut ComputedStyles,
+    )>,
+) {
+    for (entity, atlas, mut animation, mut computed_styles) in query.iter_mut() {
+        if !atlas.playing
+            || animation.finished
+            || atlas.frames.is_empty()
+            || atlas.frame_duration <= 0.0
+        {
+            continue;
+        }
+
+        animation.elapsed += time.delta_seconds();
+        let previous_frame = animation.frame;
+        while animation.elapsed >= atlas.frame_duration {
+            animation.elapsed -= atlas.frame_duration;
+            if animation.frame + 1 < atlas.frames.len() {
+                animation.frame += 1;
+            } else if atlas.looping {
+                animation.frame = 0;
+                atlas.on_loop.call(&mut commands, entity, ());
+            } else {
+                animation.elapsed = 0.0;
+                animation.finished = true;
+                atlas.on_end.call(&mut commands, entity, ());
+                break;
+            }
+        }
+
+        if animation.frame != previous_frame {
+            if let StyleProp::Value(RenderCommand::TextureAtlas { position, size, .. }) =
+                &mut computed_styles.0.render_command
+            {
+                let frame = atlas.frames[animation.frame];
+                *position = frame.min;
+                *size = frame.size();
+                commands.entity(entity).insert(DirtyNode);
+            }
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     prelude::KayakWidgetContext,
     styles::{ComputedStyles, KStyle, RenderCommand, StyleProp, Units},
     widget::{EmptyState, Widget, WidgetParam},
-    CameraUIKayak,
+    CameraUIKayak, KUiScale,
 };
 
 use super::ClipBundle;
@@ -47,30 +47,29 @@ pub fn app_update(
     widget_param: WidgetParam<KayakApp, EmptyState>,
     camera: Query<&Camera, With<CameraUIKayak>>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<KUiScale>,
 ) -> bool {
     let mut window_change = false;
 
     if let Ok(app_style) = widget_param.computed_style_query.get(entity) {
         if let Some(camera_entity) = widget_context.camera_entity {
             if let Ok(camera) = camera.get(camera_entity) {
-                if let Some(size) = camera.logical_viewport_size() {
+                let size = if let Some(size) = camera.logical_viewport_size() {
+                    Some(size)
+                } else {
+                    // There might not be a window at all when only rendering to images
+                    windows.get_single().ok().map(|primary_window| {
+                        Vec2::new(primary_window.width(), primary_window.height())
+                    })
+                };
+                if let Some(size) = size {
+                    let size = size / ui_scale.0;
                     if app_style.0.width != StyleProp::Value(Units::Pixels(size.x)) {
                         window_change = true;
                     }
                     if app_style.0.height != StyleProp::Value(Units::Pixels(size.y)) {
                         window_change = true;
                     }
-                } else {
-                    let primary_window = windows.single();
-                    if app_style.0.width != StyleProp::Value(Units::Pixels(primary_window.width()))
-                    {
-                        window_change = true;
-                    }
-                    if app_style.0.height
-                        != StyleProp::Value(Units::Pixels(primary_window.height()))
-                    {
-                        window_change = true;
-                    }
                 }
             }
         }
@@ -79,13 +78,13 @@ pub fn app_update(
     widget_param.has_changed(&widget_context, entity, previous_props_entity) || window_change
 }
 
-/// TODO: USE CAMERA INSTEAD OF WINDOW!!
 pub fn app_render(
     In(entity): In<Entity>,
     widget_context: Res<KayakWidgetContext>,
     mut commands: Commands,
     mut query: Query<(&KStyle, &mut ComputedStyles, &KChildren)>,
     camera: Query<&Camera, With<CameraUIKayak>>,
+    ui_scale: Res<KUiScale>,
 ) -> bool {
     let (mut width, mut height) = (0.0, 0.0);
 
@@ -100,6 +99,9 @@ pub fn app_render(
             }
         }
     }
+    // The root is laid out in UI units
+    width /= ui_scale.0;
+    height /= ui_scale.0;
 
     if let Ok((app_style, mut computed_styles, children)) = query.get_mut(entity) {
         *computed_styles = KStyle::default()
@@ -2,8 +2,11 @@ use bevy::{prelude::*, window::CursorIcon};
 use kayak_font::Alignment;
 use kayak_ui_macros::rsx;
 
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
 use crate::{
     context::WidgetName,
+    disabled::set_disabled,
     event::{EventType, KEvent},
     on_event::OnEvent,
     prelude::{KChildren, KayakWidgetContext, Units},
@@ -16,6 +19,8 @@ use super::{ElementBundle, TextProps, TextWidgetBundle};
 #[derive(Component, PartialEq, Clone, Default)]
 pub struct KButton {
     pub text: String,
+    /// If true, the button is dimmed and doesn't receive any events (such as clicks)
+    pub disabled: bool,
 }
 
 /// Default button widget
@@ -56,6 +61,13 @@ pub fn button_render(
     state_query: Query<&ButtonState>,
 ) -> bool {
     if let Ok((button, styles, mut computed_styles)) = query.get_mut(entity) {
+        #[cfg(feature = "accessibility")]
+        commands.entity(entity).insert(
+            AccessibilityInfo::new(Role::Button)
+                .with_label(button.text.clone())
+                .with_disabled(button.disabled),
+        );
+
         let hover_color = Color::rgba(0.592, 0.627, 0.749, 1.0); //Color::rgba(0.549, 0.666, 0.933, 1.0);
 
         let font_size = styles.font_size.resolve_or(16.);
@@ -64,16 +76,21 @@ pub fn button_render(
         let state_entity =
             widget_context.use_state(&mut commands, entity, ButtonState { hovering: false });
 
+        let disabled_styles = set_disabled(&mut commands, entity, button.disabled);
+
         if let Ok(state) = state_query.get(state_entity) {
+            // Disabled buttons don't receive the event that would end their hover
+            let hovering = state.hovering && !button.disabled;
             *computed_styles = KStyle::default()
                 .with_style(KStyle {
                     render_command: StyleProp::Value(RenderCommand::Quad),
                     ..Default::default()
                 })
                 .with_style(styles)
+                .with_style(disabled_styles)
                 .with_style(KStyle {
                     background_color: Color::rgba(0.254, 0.270, 0.349, 1.0).into(),
-                    border_color: if state.hovering {
+                    border_color: if hovering {
                         hover_color.into()
                     } else {
                         Color::rgba(0.254, 0.270, 0.349, 1.0).into()
@@ -83,7 +100,11 @@ pub fn button_render(
                     font_size: StyleProp::Value(font_size),
                     height: StyleProp::Value(height),
                     width: Units::Stretch(1.0).into(),
-                    cursor: StyleProp::Value(KCursorIcon(CursorIcon::Pointer)),
+                    cursor: StyleProp::Value(KCursorIcon(if button.disabled {
+                        CursorIcon::Default
+                    } else {
+                        CursorIcon::Pointer
+                    })),
                     ..Default::default()
                 })
                 .into();
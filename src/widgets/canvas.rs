@@ -0,0 +1,237 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    context::WidgetName,
+    layout::{GeometryChanged, LayoutEvent},
+    on_event::OnEvent,
+    on_layout::OnLayout,
+    prelude::KayakWidgetContext,
+    styles::{CanvasShape, ComputedStyles, KStyle, RenderCommand},
+    widget::Widget,
+};
+
+/// Collects the shapes drawn by a [`CanvasDraw`] function
+///
+/// All positions are relative to the top-left corner of the canvas. Shapes are drawn in the
+/// order they're added, so later shapes appear on top of earlier ones.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CanvasPainter {
+    shapes: Vec<CanvasShape>,
+}
+
+impl CanvasPainter {
+    /// Draws a line between two points
+    ///
+    /// Lines that aren't horizontal or vertical are approximated with a series of dots, one
+    /// quad each, spaced half the line width apart. Long lines are capped at 256 dots, which
+    /// spreads them further apart and can make the edges look beaded.
+    pub fn line(&mut self, start: Vec2, end: Vec2, width: f32, color: Color) {
+        self.shapes.push(CanvasShape::Line {
+            start,
+            end,
+            width,
+            color,
+        });
+    }
+
+    /// Draws a filled rectangle
+    pub fn rect(&mut self, position: Vec2, size: Vec2, color: Color) {
+        self.rounded_rect(position, size, 0.0, color);
+    }
+
+    /// Draws a filled rectangle with rounded corners
+    pub fn rounded_rect(&mut self, position: Vec2, size: Vec2, radius: f32, color: Color) {
+        self.shapes.push(CanvasShape::Rect {
+            position,
+            size,
+            color,
+            radius,
+        });
+    }
+
+    /// Draws a filled circle
+    pub fn circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        self.shapes.push(CanvasShape::Circle {
+            center,
+            radius,
+            color,
+        });
+    }
+
+    /// Draws a filled triangle
+    ///
+    /// Triangles are filled with one quad per pixel row, up to 256 rows. Prefer rects when
+    /// drawing many shapes.
+    pub fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.shapes.push(CanvasShape::Triangle { a, b, c, color });
+    }
+
+    /// Removes all previously drawn shapes
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// The shapes drawn so far
+    pub fn shapes(&self) -> &[CanvasShape] {
+        &self.shapes
+    }
+}
+
+/// A container for a canvas draw function
+///
+/// The function receives a [`CanvasPainter`] along with the bounds of the canvas in its
+/// local coordinate space (`posx` and `posy` are always zero).
+#[derive(Clone)]
+pub struct CanvasDraw {
+    draw: Arc<dyn Fn(&mut CanvasPainter, crate::layout::Rect) + Send + Sync>,
+}
+
+impl CanvasDraw {
+    pub fn new(
+        draw: impl Fn(&mut CanvasPainter, crate::layout::Rect) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            draw: Arc::new(draw),
+        }
+    }
+
+    fn paint(&self, rect: crate::layout::Rect) -> Vec<CanvasShape> {
+        let mut painter = CanvasPainter::default();
+        (self.draw)(&mut painter, rect);
+        painter.shapes
+    }
+}
+
+impl Default for CanvasDraw {
+    fn default() -> Self {
+        Self::new(|_, _| {})
+    }
+}
+
+impl Debug for CanvasDraw {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CanvasDraw").finish()
+    }
+}
+
+impl PartialEq for CanvasDraw {
+    fn eq(&self, other: &Self) -> bool {
+        // A new draw function might draw something different so the canvas must re-render
+        Arc::ptr_eq(&self.draw, &other.draw)
+    }
+}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct CanvasProps {
+    /// The function used to draw the contents of the canvas
+    pub draw: CanvasDraw,
+}
+
+impl Widget for CanvasProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct CanvasState {
+    /// The width of the canvas at the time it was last drawn
+    pub width: f32,
+    /// The height of the canvas at the time it was last drawn
+    pub height: f32,
+}
+
+///
+/// A widget for drawing custom shapes such as charts and graphs
+///
+/// The draw function is called whenever the props change or the canvas is resized.
+///
+/// Shapes are drawn at the z-index of the canvas itself, in the order they were added.
+/// They are not clipped to the bounds of the canvas, only to those of any
+/// [`Clip`](crate::widgets::Clip) it's contained in. Wrap the canvas in a
+/// [`ClipBundle`](crate::widgets::ClipBundle) to keep its shapes within its bounds.
+///
+#[derive(Bundle)]
+pub struct CanvasBundle {
+    pub canvas: CanvasProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub on_layout: OnLayout,
+    pub widget_name: WidgetName,
+}
+
+impl Default for CanvasBundle {
+    fn default() -> Self {
+        Self {
+            canvas: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: OnEvent::default(),
+            on_layout: OnLayout::default(),
+            widget_name: CanvasProps::default().get_name(),
+        }
+    }
+}
+
+pub fn canvas_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&KStyle, &mut ComputedStyles, &CanvasProps, &mut OnLayout)>,
+    state_query: Query<&CanvasState>,
+) -> bool {
+    if let Ok((style, mut computed_styles, canvas, mut on_layout)) = query.get_mut(entity) {
+        let layout = widget_context.get_layout(entity).unwrap_or_default();
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            CanvasState {
+                width: layout.width,
+                height: layout.height,
+            },
+        );
+
+        // Redraw whenever the canvas is resized
+        *on_layout = OnLayout::new(
+            move |In((event, _entity)): In<(LayoutEvent, Entity)>,
+                  mut state_query: Query<&mut CanvasState>| {
+                if event
+                    .flags
+                    .intersects(GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED)
+                {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        state.width = event.layout.width;
+                        state.height = event.layout.height;
+                    }
+                }
+
+                event
+            },
+        );
+
+        let (width, height) = state_query
+            .get(state_entity)
+            .map(|state| (state.width, state.height))
+            .unwrap_or((layout.width, layout.height));
+
+        let shapes = canvas.draw.paint(crate::layout::Rect {
+            posx: 0.0,
+            posy: 0.0,
+            width,
+            height,
+            z_index: layout.z_index,
+        });
+
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Canvas { shapes }.into(),
+                ..Default::default()
+            })
+            .with_style(style)
+            .into();
+    }
+
+    true
+}
@@ -18,6 +18,10 @@ impl Widget for Clip {}
 /// overflow out of the widget's bounds. This widget will cut or clip
 /// the text.
 /// Note: Clips roughly translate to wGPU scissor commands.
+///
+/// Clips are rectangular unless a `border_radius` is set in their styles, in which case
+/// anything drawn outside of the rounded corners is cut away too. Nested clips only show what's
+/// inside of both clips, keeping the rounded corners of the outer clip.
 #[derive(Bundle)]
 pub struct ClipBundle {
     pub clip: Clip,
@@ -0,0 +1,445 @@
+use bevy::{
+    prelude::{
+        Bundle, Camera, Color, Commands, Component, Entity, In, KeyCode, Query, Res, ResMut,
+    },
+    window::CursorIcon,
+};
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    focus_tree::FocusTree,
+    handler::Handler,
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{
+        ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, RenderCommand, StyleProp,
+        Units,
+    },
+    widget::Widget,
+    KUiScale, WindowSize,
+};
+
+use super::{
+    background::BackgroundBundle,
+    text::{TextProps, TextWidgetBundle},
+    ElementBundle,
+};
+
+/// The z-index of an open context menu, which places it above windows and other widgets
+const CONTEXT_MENU_Z_INDEX: i32 = 100_000;
+/// The vertical padding of the menu in pixels
+const MENU_PADDING: f32 = 4.0;
+/// The height of a separator (including the space around its line) in pixels
+const SEPARATOR_HEIGHT: f32 = 9.0;
+
+/// An entry of a [`ContextMenu`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextMenuItem {
+    /// An item that calls `on_select` when clicked
+    Action {
+        label: String,
+        /// Called when the item is clicked (unless it's disabled)
+        on_select: Handler,
+        /// If true, the item is greyed out and can't be clicked
+        disabled: bool,
+    },
+    /// A horizontal line between groups of items
+    Separator,
+}
+
+impl ContextMenuItem {
+    /// Creates an enabled item
+    pub fn action(label: impl Into<String>, on_select: Handler) -> Self {
+        Self::Action {
+            label: label.into(),
+            on_select,
+            disabled: false,
+        }
+    }
+
+    /// Creates an item that is greyed out and can't be clicked
+    pub fn disabled(label: impl Into<String>) -> Self {
+        Self::Action {
+            label: label.into(),
+            on_select: Handler::default(),
+            disabled: true,
+        }
+    }
+
+    fn height(&self, item_height: f32) -> f32 {
+        match self {
+            Self::Action { .. } => item_height,
+            Self::Separator => SEPARATOR_HEIGHT,
+        }
+    }
+}
+
+/// Props used by the context menu widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ContextMenu {
+    /// The entries of the menu, from top to bottom
+    pub items: Vec<ContextMenuItem>,
+    /// The width of the menu in pixels
+    pub width: f32,
+    /// The height of each (non-separator) item in pixels
+    pub item_height: f32,
+}
+
+impl Default for ContextMenu {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            width: 180.0,
+            item_height: 24.0,
+        }
+    }
+}
+
+impl ContextMenu {
+    /// The total height of the menu in pixels
+    fn height(&self) -> f32 {
+        self.items
+            .iter()
+            .map(|item| item.height(self.item_height))
+            .sum::<f32>()
+            + MENU_PADDING * 2.0
+    }
+
+    /// Places the menu at the cursor, flipping it to the other side of the cursor on either
+    /// axis where it would leave the window
+    fn position(&self, cursor: (f32, f32), window_size: &WindowSize) -> (f32, f32) {
+        let flip = |cursor: f32, size: f32, window: f32| {
+            if cursor + size > window && cursor - size >= 0.0 {
+                cursor - size
+            } else {
+                cursor.min(window - size).max(0.0)
+            }
+        };
+
+        (
+            flip(cursor.0, self.width, window_size.0),
+            flip(cursor.1, self.height(), window_size.1),
+        )
+    }
+}
+
+impl Widget for ContextMenu {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ContextMenuState {
+    /// Is the menu open?
+    pub open: bool,
+    /// The position of the cursor when the menu was opened
+    pub position: (f32, f32),
+}
+
+///
+/// A widget that opens a menu of actions at the cursor when its children are right-clicked
+///
+/// Clicking an item calls its handler and closes the menu. Clicking outside of the menu
+/// or pressing Escape closes it without doing anything. While the menu is open, it covers the
+/// rest of the window, so the click that closes it isn't received by any other widget.
+///
+/// # Props
+///
+/// __Type:__ [`ContextMenu`]
+///
+/// | Common Prop | Accepted |
+/// | :---------: | :------: |
+/// | `children`  | ✅        |
+/// | `styles`    | ✅        |
+/// | `on_event`  |           |
+/// | `on_layout` |           |
+/// | `focusable` |           |
+///
+#[derive(Bundle)]
+pub struct ContextMenuBundle {
+    pub context_menu: ContextMenu,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub on_event: OnEvent,
+    pub widget_name: WidgetName,
+}
+
+impl Default for ContextMenuBundle {
+    fn default() -> Self {
+        Self {
+            context_menu: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            children: Default::default(),
+            on_event: Default::default(),
+            widget_name: ContextMenu::default().get_name(),
+        }
+    }
+}
+
+pub fn context_menu_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    window_size: Res<WindowSize>,
+    ui_scale: Res<KUiScale>,
+    cameras: Query<&Camera>,
+    mut query: Query<(
+        &KStyle,
+        &mut ComputedStyles,
+        &KChildren,
+        &ContextMenu,
+        &mut OnEvent,
+    )>,
+    state_query: Query<&ContextMenuState>,
+) -> bool {
+    if let Ok((styles, mut computed_styles, context_menu_children, context_menu, mut on_event)) =
+        query.get_mut(entity)
+    {
+        let state_entity =
+            widget_context.use_state(&mut commands, entity, ContextMenuState::default());
+        // The menu is kept within the area rendered by this UI's camera (a window or an image)
+        let window_size = widget_context
+            .camera_entity
+            .and_then(|camera_entity| cameras.get(camera_entity).ok())
+            .and_then(|camera| WindowSize::of_camera(camera, *ui_scale))
+            .unwrap_or(*window_size);
+
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                width: Units::Auto.into(),
+                height: Units::Auto.into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        *on_event = OnEvent::new(
+            move |In(entity): In<Entity>,
+                  mut event: ResMut<KEvent>,
+                  focus_tree: Res<FocusTree>,
+                  mut query: Query<&mut ContextMenuState>| {
+                if let Ok(mut state) = query.get_mut(state_entity) {
+                    match event.event_type {
+                        EventType::RightClick(cursor_event) if !event.is_default_prevented() => {
+                            event.stop_propagation();
+                            event.prevent_default();
+                            state.open = true;
+                            state.position = cursor_event.position;
+                            // Take focus so that Escape reaches the menu
+                            focus_tree.focus(entity);
+                        }
+                        EventType::KeyDown(key_event)
+                            if !event.is_default_prevented()
+                                && state.open
+                                && key_event.key() == KeyCode::Escape =>
+                        {
+                            event.prevent_default();
+                            state.open = false;
+                        }
+                        _ => {}
+                    }
+                }
+            },
+        );
+
+        if let Ok(state) = state_query.get(state_entity) {
+            // Self-directed widgets are placed relative to their parent, not the window
+            let layout = widget_context.get_layout(entity).unwrap_or_default();
+            let (x, y) = context_menu.position(state.position, &window_size);
+
+            let close = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut query: Query<&mut ContextMenuState>| {
+                    if let EventType::Click(..) | EventType::RightClick(..) = event.event_type {
+                        event.stop_propagation();
+                        if let Ok(mut state) = query.get_mut(state_entity) {
+                            state.open = false;
+                        }
+                    }
+                },
+            );
+
+            // Swallow clicks on the menu itself so they don't reach the backdrop or reopen it
+            let menu_on_event =
+                OnEvent::new(move |In(_entity): In<Entity>, mut event: ResMut<KEvent>| {
+                    if let EventType::Click(..) | EventType::RightClick(..) = event.event_type {
+                        event.stop_propagation();
+                    }
+                });
+
+            let open = state.open;
+            let item_height = context_menu.item_height;
+            let parent_id = Some(entity);
+            rsx! {
+                <ElementBundle
+                    styles={KStyle {
+                        width: Units::Auto.into(),
+                        height: Units::Auto.into(),
+                        ..Default::default()
+                    }}
+                >
+                    <ElementBundle
+                        styles={KStyle {
+                            width: Units::Auto.into(),
+                            height: Units::Auto.into(),
+                            ..Default::default()
+                        }}
+                        children={context_menu_children.clone()}
+                    />
+                    {
+                        if open {
+                            constructor! {
+                                <BackgroundBundle
+                                    styles={KStyle {
+                                        position_type: KPositionType::SelfDirected.into(),
+                                        z_index: StyleProp::Value(CONTEXT_MENU_Z_INDEX),
+                                        left: Units::Pixels(-layout.posx).into(),
+                                        top: Units::Pixels(-layout.posy).into(),
+                                        width: Units::Pixels(window_size.0).into(),
+                                        height: Units::Pixels(window_size.1).into(),
+                                        background_color: Color::NONE.into(),
+                                        ..Default::default()
+                                    }}
+                                    on_event={close.clone()}
+                                />
+                            }
+                            constructor! {
+                                <BackgroundBundle
+                                    styles={KStyle {
+                                        position_type: KPositionType::SelfDirected.into(),
+                                        z_index: StyleProp::Value(CONTEXT_MENU_Z_INDEX),
+                                        left: Units::Pixels(x - layout.posx).into(),
+                                        top: Units::Pixels(y - layout.posy).into(),
+                                        width: Units::Pixels(context_menu.width).into(),
+                                        height: Units::Auto.into(),
+                                        padding_top: Units::Pixels(MENU_PADDING).into(),
+                                        padding_bottom: Units::Pixels(MENU_PADDING).into(),
+                                        background_color: Color::rgba(0.188, 0.203, 0.274, 1.0).into(),
+                                        border_color: Color::rgba(0.239, 0.258, 0.337, 1.0).into(),
+                                        border: Edge::all(1.0).into(),
+                                        border_radius: Corner::all(4.0).into(),
+                                        ..Default::default()
+                                    }}
+                                    on_event={menu_on_event}
+                                >
+                                    {
+                                        for item in context_menu.items.iter() {
+                                            menu_item(
+                                                item,
+                                                item_height,
+                                                entity,
+                                                state_entity,
+                                                &widget_context,
+                                                &mut commands,
+                                                &mut children,
+                                                parent_id,
+                                            );
+                                        }
+                                    }
+                                </BackgroundBundle>
+                            }
+                        }
+                    }
+                </ElementBundle>
+            };
+        }
+    }
+
+    true
+}
+
+/// Adds an entry of a context menu to `children`
+#[allow(clippy::too_many_arguments)]
+fn menu_item(
+    item: &ContextMenuItem,
+    item_height: f32,
+    menu_entity: Entity,
+    state_entity: Entity,
+    widget_context: &KayakWidgetContext,
+    mut commands: &mut Commands,
+    children: &mut KChildren,
+    parent_id: Option<Entity>,
+) {
+    match item {
+        ContextMenuItem::Action {
+            label,
+            on_select,
+            disabled,
+        } => {
+            let disabled = *disabled;
+            let on_select = on_select.clone();
+            let on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut commands: Commands,
+                      mut query: Query<&mut ContextMenuState>| {
+                    if let EventType::Click(..) = event.event_type {
+                        event.stop_propagation();
+                        if !disabled {
+                            on_select.call(&mut commands, menu_entity, ());
+                            if let Ok(mut state) = query.get_mut(state_entity) {
+                                state.open = false;
+                            }
+                        }
+                    }
+                },
+            );
+
+            constructor! {
+                <ElementBundle
+                    styles={KStyle {
+                        cursor: if disabled {
+                            StyleProp::Default
+                        } else {
+                            StyleProp::Value(KCursorIcon(CursorIcon::Pointer))
+                        },
+                        width: Units::Stretch(1.0).into(),
+                        height: Units::Pixels(item_height).into(),
+                        padding_left: Units::Pixels(10.0).into(),
+                        padding_right: Units::Pixels(10.0).into(),
+                        padding_top: Units::Stretch(1.0).into(),
+                        padding_bottom: Units::Stretch(1.0).into(),
+                        ..Default::default()
+                    }}
+                    on_event={on_event}
+                >
+                    <TextWidgetBundle
+                        styles={KStyle {
+                            color: if disabled {
+                                Color::rgba(0.592, 0.627, 0.749, 0.5)
+                            } else {
+                                Color::WHITE
+                            }
+                            .into(),
+                            ..Default::default()
+                        }}
+                        text={TextProps {
+                            content: label.clone(),
+                            size: 14.0,
+                            word_wrap: false,
+                            ..Default::default()
+                        }}
+                    />
+                </ElementBundle>
+            }
+        }
+        ContextMenuItem::Separator => {
+            constructor! {
+                <BackgroundBundle
+                    styles={KStyle {
+                        background_color: Color::rgba(0.239, 0.258, 0.337, 1.0).into(),
+                        width: Units::Stretch(1.0).into(),
+                        height: Units::Pixels(1.0).into(),
+                        top: Units::Pixels((SEPARATOR_HEIGHT - 1.0) / 2.0).into(),
+                        bottom: Units::Pixels((SEPARATOR_HEIGHT - 1.0) / 2.0).into(),
+                        ..Default::default()
+                    }}
+                />
+            }
+        }
+    }
+}
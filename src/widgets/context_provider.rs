@@ -0,0 +1,96 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{
+    children::KChildren,
+    context::{KayakRootContext, WidgetName},
+    prelude::KayakWidgetContext,
+    widget::{widget_update, Widget},
+    KayakUIPlugin,
+};
+
+/// Provides a value of type `T` to every descendant of this widget
+///
+/// Descendants can read the nearest provided value with
+/// [`KayakWidgetContext::get_context`]. Widgets registered with
+/// [`widget_update_with_context`](crate::prelude::widget_update_with_context) re-render whenever
+/// the provided value changes.
+///
+/// The value is owned by the provider's props, so change it by re-rendering the provider with a
+/// new `value` rather than by mutating the context directly.
+///
+/// Each provided type needs to be registered once with [`ContextProviderPlugin`].
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ContextProvider<T: Component + Clone + PartialEq + Default> {
+    /// The value passed down to descendants
+    pub value: T,
+}
+
+impl<T: Component + Clone + PartialEq + Default> Widget for ContextProvider<T> {}
+
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct ContextProviderBundle<T: Component + Clone + PartialEq + Default> {
+    pub context_provider: ContextProvider<T>,
+    pub children: KChildren,
+    pub widget_name: WidgetName,
+}
+
+impl<T: Component + Clone + PartialEq + Default> Default for ContextProviderBundle<T> {
+    fn default() -> Self {
+        Self {
+            context_provider: Default::default(),
+            children: Default::default(),
+            widget_name: ContextProvider::<T>::default().get_name(),
+        }
+    }
+}
+
+/// Registers the [`ContextProvider`] widget for values of type `T`
+///
+/// ```ignore
+/// widget_context.add_plugin(ContextProviderPlugin::<Theme>::default());
+/// ```
+pub struct ContextProviderPlugin<T>(PhantomData<T>);
+
+impl<T> Default for ContextProviderPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Component + Clone + PartialEq + Default> KayakUIPlugin for ContextProviderPlugin<T> {
+    fn build(&self, context: &mut KayakRootContext) {
+        context.add_widget_data::<ContextProvider<T>, T>();
+        context.add_widget_system(
+            ContextProvider::<T>::default().get_name(),
+            widget_update::<ContextProvider<T>, T>,
+            context_provider_render::<T>,
+        );
+    }
+}
+
+pub fn context_provider_render<T: Component + Clone + PartialEq + Default>(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    query: Query<(&ContextProvider<T>, &KChildren)>,
+    mut context_query: Query<&mut T>,
+) -> bool {
+    if let Ok((provider, children)) = query.get(entity) {
+        // The provided value lives on the widget's state entity so it's cleaned up along with it
+        let context_entity =
+            widget_context.use_state(&mut commands, entity, provider.value.clone());
+        if let Ok(mut value) = context_query.get_mut(context_entity) {
+            // Only touch the value when it differs so consumers aren't re-rendered needlessly
+            if *value != provider.value {
+                *value = provider.value.clone();
+            }
+        }
+
+        widget_context.set_context_entity::<T>(Some(entity), context_entity);
+        children.process(&widget_context, &mut commands, Some(entity));
+    }
+
+    true
+}
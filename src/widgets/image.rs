@@ -8,6 +8,7 @@ use crate::{
 
 /// Renders a bevy image asset within the GUI
 /// The rendered image respects the styles.
+/// Use the [`tint`](KStyle::tint) style to recolor the image.
 #[derive(Component, PartialEq, Eq, Clone, Default)]
 pub struct KImage(pub Handle<bevy::prelude::Image>);
 
@@ -5,14 +5,22 @@
 //!
 //! - KayakApp
 //! - Background
+//! - Canvas
 //! - Clip
+//! - Context Menu
+//! - Context Provider
 //! - Element
 //! - Image
 //! - NinePatch
+//! - NumberInput
+//! - Radio
 //! - TextBox
 //! - Text
 //! - Texture Atlas
+//! - Animated Texture Atlas
 //! - Scroll
+//! - Split Pane
+//! - Table
 //!
 //! Widgets like:
 //! - Window
@@ -24,19 +32,27 @@ use bevy::prelude::*;
 
 #[cfg(feature = "svg")]
 mod accordion;
+mod animated_texture_atlas;
 mod app;
 mod background;
 mod button;
+mod canvas;
 mod clip;
+mod context_menu;
+mod context_provider;
 mod element;
 #[cfg(feature = "svg")]
 mod icons;
 mod image;
 mod modal;
 mod nine_patch;
+mod number_input;
+mod radio;
 mod scroll;
+mod split_pane;
 #[cfg(feature = "svg")]
 mod svg;
+mod table;
 mod text;
 mod text_box;
 mod texture_atlas;
@@ -46,26 +62,37 @@ mod window_context_provider;
 
 #[cfg(feature = "svg")]
 pub use accordion::*;
+pub use animated_texture_atlas::{
+    AnimatedTextureAtlasBundle, AnimatedTextureAtlasProps, TextureAtlasAnimation,
+};
 pub use app::{KayakApp, KayakAppBundle};
 pub use background::{Background, BackgroundBundle};
 pub use button::{ButtonState, KButton, KButtonBundle};
+pub use canvas::{CanvasBundle, CanvasDraw, CanvasPainter, CanvasProps, CanvasState};
 pub use clip::{Clip, ClipBundle};
+pub use context_menu::{ContextMenu, ContextMenuBundle, ContextMenuItem, ContextMenuState};
+pub use context_provider::{ContextProvider, ContextProviderBundle, ContextProviderPlugin};
 pub use element::{Element, ElementBundle};
 #[cfg(feature = "svg")]
 pub use icons::*;
 pub use image::{KImage, KImageBundle};
 pub use modal::{Modal, ModalBundle};
 pub use nine_patch::{NinePatch, NinePatchBundle};
+pub use number_input::{NumberInputBundle, NumberInputProps, NumberInputState};
+pub use radio::{Radio, RadioBundle, RadioGroup, RadioGroupBundle, RadioGroupContext, RadioPlugin};
 pub use scroll::{
     scroll_bar::{ScrollBarBundle, ScrollBarProps},
     scroll_box::{ScrollBoxBundle, ScrollBoxProps},
     scroll_content::{ScrollContentBundle, ScrollContentProps},
     scroll_context::{
-        ScrollContext, ScrollContextProvider, ScrollContextProviderBundle, ScrollMode,
+        ScrollAlignment, ScrollContext, ScrollContextProvider, ScrollContextProviderBundle,
+        ScrollMode,
     },
 };
+pub use split_pane::{SplitDirection, SplitPaneBundle, SplitPaneProps, SplitPaneState};
 #[cfg(feature = "svg")]
 pub use svg::{KSvg, KSvgBundle, Svg};
+pub use table::{TableBundle, TableColumn, TableProps, TableRowRenderer};
 pub use text::{TextProps, TextWidgetBundle};
 pub use text_box::{TextBoxBundle, TextBoxProps, TextBoxState};
 pub use texture_atlas::{TextureAtlasBundle, TextureAtlasProps};
@@ -78,19 +105,25 @@ pub use window_context_provider::{
     WindowContext, WindowContextProvider, WindowContextProviderBundle,
 };
 
+use animated_texture_atlas::animated_texture_atlas_render;
 use app::{app_render, app_update};
 use background::background_render;
 use button::button_render;
+use canvas::canvas_render;
 use clip::clip_render;
+use context_menu::context_menu_render;
 use element::element_render;
 use image::image_render;
 use nine_patch::nine_patch_render;
+use number_input::number_input_render;
 use scroll::{
     scroll_bar::scroll_bar_render, scroll_box::scroll_box_render,
     scroll_content::scroll_content_render, scroll_context::scroll_context_render,
 };
+use split_pane::split_pane_render;
 #[cfg(feature = "svg")]
 use svg::svg_render;
+use table::table_render;
 use text::text_render;
 use text_box::text_box_render;
 use texture_atlas::texture_atlas_render;
@@ -114,7 +147,15 @@ impl Plugin for KayakWidgets {
             PostUpdate,
             transition::update_transitions.after(update_widgets_sys),
         )
-        .add_systems(Update, text_box::cursor_animation_system);
+        .add_systems(
+            Update,
+            (
+                text_box::cursor_animation_system,
+                animated_texture_atlas::update_animated_texture_atlases,
+                scroll::scroll_context::update_scroll_momentum,
+                scroll::scroll_context::scroll_focused_into_view,
+            ),
+        );
     }
 }
 
@@ -124,6 +165,7 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
     fn build(&self, context: &mut KayakRootContext) {
         #[cfg(feature = "svg")]
         context.add_plugin(AccordionPlugin);
+        context.add_plugin(RadioPlugin);
         context.add_widget_data::<KayakApp, EmptyState>();
         context.add_widget_data::<KButton, ButtonState>();
         context.add_widget_data::<TextProps, EmptyState>();
@@ -131,9 +173,12 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
         context.add_widget_data::<WindowContextProvider, EmptyState>();
         context.add_widget_data::<Background, EmptyState>();
         context.add_widget_data::<Clip, EmptyState>();
+        context.add_widget_data::<ContextMenu, ContextMenuState>();
         context.add_widget_data::<KImage, EmptyState>();
         context.add_widget_data::<TextureAtlasProps, EmptyState>();
+        context.add_widget_data::<AnimatedTextureAtlasProps, EmptyState>();
         context.add_widget_data::<NinePatch, EmptyState>();
+        context.add_widget_data::<CanvasProps, CanvasState>();
         #[cfg(feature = "svg")]
         context.add_widget_data::<KSvg, EmptyState>();
         context.add_widget_data::<Element, EmptyState>();
@@ -141,7 +186,10 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
         context.add_widget_data::<ScrollContentProps, EmptyState>();
         context.add_widget_data::<ScrollBoxProps, EmptyState>();
         context.add_widget_data::<ScrollContextProvider, EmptyState>();
+        context.add_widget_data::<SplitPaneProps, SplitPaneState>();
+        context.add_widget_data::<TableProps, EmptyState>();
         context.add_widget_data::<TextBoxProps, TextBoxState>();
+        context.add_widget_data::<NumberInputProps, NumberInputState>();
         context.add_widget_data::<TransitionProps, TransitionState>();
         context.add_widget_data::<Modal, TransitionState>();
 
@@ -176,6 +224,11 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<Clip, EmptyState>,
             clip_render,
         );
+        context.add_widget_system(
+            ContextMenu::default().get_name(),
+            widget_update::<ContextMenu, ContextMenuState>,
+            context_menu_render,
+        );
         context.add_widget_system(
             KImage::default().get_name(),
             widget_update::<KImage, EmptyState>,
@@ -186,11 +239,21 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<TextureAtlasProps, EmptyState>,
             texture_atlas_render,
         );
+        context.add_widget_system(
+            AnimatedTextureAtlasProps::default().get_name(),
+            widget_update::<AnimatedTextureAtlasProps, EmptyState>,
+            animated_texture_atlas_render,
+        );
         context.add_widget_system(
             NinePatch::default().get_name(),
             widget_update::<NinePatch, EmptyState>,
             nine_patch_render,
         );
+        context.add_widget_system(
+            CanvasProps::default().get_name(),
+            widget_update::<CanvasProps, CanvasState>,
+            canvas_render,
+        );
         #[cfg(feature = "svg")]
         context.add_widget_system(
             KSvg::default().get_name(),
@@ -222,11 +285,26 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<ScrollContextProvider, EmptyState>,
             scroll_context_render,
         );
+        context.add_widget_system(
+            SplitPaneProps::default().get_name(),
+            widget_update::<SplitPaneProps, SplitPaneState>,
+            split_pane_render,
+        );
+        context.add_widget_system(
+            TableProps::default().get_name(),
+            widget_update::<TableProps, EmptyState>,
+            table_render,
+        );
         context.add_widget_system(
             TextBoxProps::default().get_name(),
             widget_update::<TextBoxProps, TextBoxState>,
             text_box_render,
         );
+        context.add_widget_system(
+            NumberInputProps::default().get_name(),
+            widget_update::<NumberInputProps, NumberInputState>,
+            number_input_render,
+        );
         context.add_widget_system(
             TransitionProps::default().get_name(),
             widget_update::<TransitionProps, TransitionState>,
@@ -1,5 +1,5 @@
 use bevy::{
-    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res},
+    prelude::{Bundle, Color, Commands, Component, Entity, In, KeyCode, Query, Res, ResMut},
     window::CursorIcon,
 };
 use kayak_ui_macros::rsx;
@@ -7,6 +7,11 @@ use kayak_ui_macros::rsx;
 use crate::{
     children::KChildren,
     context::WidgetName,
+    event::{EventType, KEvent},
+    focus_tree::{FocusTrap, FocusTree},
+    handler::Handler,
+    node::WrappedIndex,
+    on_event::OnEvent,
     prelude::KayakWidgetContext,
     styles::{
         ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, RenderCommand, StyleProp,
@@ -14,6 +19,7 @@ use crate::{
     },
     widget::Widget,
     widgets::{create_transition, Transition, TransitionEasing, TransitionProps},
+    Focusable,
 };
 
 use super::{
@@ -35,6 +41,14 @@ pub struct Modal {
     pub timeout: f32,
     /// The overlay background alpha value
     pub overlay_alpha: f32,
+    /// If true, clicking the backdrop (outside of the dialog) requests the modal to close
+    pub dismiss_on_backdrop: bool,
+    /// If true, pressing Escape while the modal is focused requests the modal to close
+    pub dismiss_on_escape: bool,
+    /// Called when the modal requests to be closed
+    ///
+    /// The modal is controlled, so this should set [`visible`](Self::visible) to false.
+    pub on_request_close: Handler,
 }
 
 impl Default for Modal {
@@ -45,6 +59,9 @@ impl Default for Modal {
             visible: Default::default(),
             timeout: 250.0,
             overlay_alpha: 0.95,
+            dismiss_on_backdrop: false,
+            dismiss_on_escape: false,
+            on_request_close: Default::default(),
         }
     }
 }
@@ -80,6 +97,7 @@ pub fn render(
     mut commands: Commands,
     mut query: Query<(&KStyle, &KChildren, &Modal, &mut ComputedStyles)>,
     mut transition_state_query: Query<&mut TransitionState>,
+    focus_tree: Res<FocusTree>,
 ) -> bool {
     if let Ok((modal_styles, modal_children, modal, mut computed_styles)) =
         query.get_mut(modal_entity)
@@ -126,11 +144,53 @@ pub fn render(
                 *computed_styles = ComputedStyles(new_styles);
             }
 
+            // Only an open modal can hold focus, which stays trapped inside of it
+            if modal.visible {
+                commands.entity(modal_entity).insert((Focusable, FocusTrap));
+            } else if focus_tree.contains(modal_entity) {
+                commands
+                    .entity(modal_entity)
+                    .remove::<(Focusable, FocusTrap)>();
+                focus_tree.release_trap(WrappedIndex(modal_entity));
+                focus_tree.remove(WrappedIndex(modal_entity));
+            }
+
             // Don't render if nothing is visible.
             if !transition_state.transition.is_playing() && !modal.visible {
                 return true;
             }
 
+            let dismiss_on_escape = modal.dismiss_on_escape;
+            let on_request_close = modal.on_request_close.clone();
+            commands.entity(modal_entity).insert(OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut commands: Commands| {
+                    if let EventType::KeyDown(evt) = event.event_type {
+                        if dismiss_on_escape
+                            && evt.key() == KeyCode::Escape
+                            && !event.is_default_prevented()
+                        {
+                            event.prevent_default();
+                            on_request_close.call(&mut commands, modal_entity, ());
+                        }
+                    }
+                },
+            ));
+
+            // The backdrop is a sibling of the dialog, so clicks within the dialog never reach it
+            let dismiss_on_backdrop = modal.dismiss_on_backdrop;
+            let on_request_close = modal.on_request_close.clone();
+            let backdrop_on_event = OnEvent::new(
+                move |In(_entity): In<Entity>, event: Res<KEvent>, mut commands: Commands| {
+                    if let EventType::Click(..) = event.event_type {
+                        if dismiss_on_backdrop {
+                            on_request_close.call(&mut commands, modal_entity, ());
+                        }
+                    }
+                },
+            );
+
             let title = modal.title.clone();
             let parent_id = Some(modal_entity);
             rsx! {
@@ -140,6 +200,7 @@ pub fn render(
                             background_color: Color::rgba(0.0, 0.0, 0.0, modal.overlay_alpha).into(),
                             ..Default::default()
                         }}
+                        on_event={backdrop_on_event}
                     />
                     <ElementBundle
                         styles={KStyle {
@@ -5,7 +5,7 @@ use crate::{
     context::WidgetName,
     on_event::OnEvent,
     prelude::KayakWidgetContext,
-    styles::{ComputedStyles, Edge, KStyle, RenderCommand},
+    styles::{ComputedStyles, Edge, KStyle, NinePatchMode, RenderCommand},
     widget::Widget,
 };
 
@@ -13,11 +13,14 @@ use crate::{
 pub struct NinePatch {
     /// The handle to image
     pub handle: Handle<Image>,
-    /// The size of each edge (in pixels)
+    /// The size of each edge (in pixels of the source image)
     pub border: Edge<f32>,
-    /// Scale of the nine patch
+    /// Scale of the nine patch, the rendered edges are `border * scale` pixels wide
     /// defaults to 1.0
     pub scale: f32,
+    /// Whether the edges and center are stretched or tiled
+    /// defaults to [`NinePatchMode::Stretch`]
+    pub mode: NinePatchMode,
 }
 
 impl Default for NinePatch {
@@ -26,6 +29,7 @@ impl Default for NinePatch {
             handle: Default::default(),
             border: Default::default(),
             scale: 1.0,
+            mode: NinePatchMode::Stretch,
         }
     }
 }
@@ -36,7 +40,9 @@ impl Widget for NinePatch {}
 /// Render's a nine-patch image as a UI widget.
 ///
 /// Also know as 9-slicing. This 2D technique allows users to render UI images at multiple
-/// resolutions while maintaining a level of quality. The image in the middle is repeated.
+/// resolutions while maintaining a level of quality. The edges and the image in the middle
+/// are stretched, or repeated when using [`NinePatchMode::Tile`]. Tiling keeps pixel-art
+/// borders crisp.
 ///
 /// Accepts Children and Styles.
 ///
@@ -97,6 +103,7 @@ pub fn nine_patch_render(
                     border: nine_patch.border,
                     handle: nine_patch.handle.clone_weak(),
                     scale: nine_patch.scale,
+                    mode: nine_patch.mode,
                 }
                 .into(),
                 ..Default::default()
@@ -0,0 +1,404 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
+use crate::{
+    context::WidgetName,
+    event::{EventType, KEvent},
+    handler::Handler,
+    on_change::{OnChange, ValueChange},
+    on_event::OnEvent,
+    prelude::{KChildren, KayakWidgetContext},
+    styles::{ComputedStyles, KStyle, LayoutType, RenderCommand, Units},
+    widget::Widget,
+};
+
+use super::{ElementBundle, KButton, KButtonBundle, TextBoxBundle, TextBoxProps};
+
+/// Props used by the [`NumberInput`] widget
+#[derive(Component, PartialEq, Debug, Clone)]
+pub struct NumberInputProps {
+    /// The current value
+    ///
+    /// This is a controlled state. You _must_ set this to the value you wish to be displayed.
+    /// You can use the [`on_change`](Self::on_change) callback to update this prop.
+    pub value: f64,
+    /// The smallest allowed value
+    pub min: f64,
+    /// The largest allowed value
+    pub max: f64,
+    /// The amount to add or subtract when stepping up or down
+    pub step: f64,
+    /// If true, the field and its stepper buttons are dimmed and can't be edited
+    pub disabled: bool,
+    /// Called with the new value whenever a valid number is entered or stepped to
+    pub on_change: OnChange<f64>,
+}
+
+impl Default for NumberInputProps {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            min: f64::MIN,
+            max: f64::MAX,
+            step: 1.0,
+            disabled: false,
+            on_change: Default::default(),
+        }
+    }
+}
+
+impl Widget for NumberInputProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct NumberInputState {
+    /// The text currently entered (which may not yet be a valid number)
+    pub text: String,
+    /// The last valid value
+    pub value: f64,
+}
+
+/// A widget that displays a numeric input field with up/down stepper buttons
+///
+/// Up and down arrow keys also step the value while the field is focused.
+#[derive(Bundle)]
+pub struct NumberInputBundle {
+    pub number_input: NumberInputProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub widget_name: WidgetName,
+}
+
+impl Default for NumberInputBundle {
+    fn default() -> Self {
+        Self {
+            number_input: Default::default(),
+            styles: Default::default(),
+            computed_styles: Default::default(),
+            on_event: Default::default(),
+            widget_name: NumberInputProps::default().get_name(),
+        }
+    }
+}
+
+pub fn number_input_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &NumberInputProps,
+        &KStyle,
+        &mut ComputedStyles,
+        &mut OnEvent,
+    )>,
+    mut state_query: Query<&mut NumberInputState>,
+) -> bool {
+    if let Ok((number_input, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        #[cfg(feature = "accessibility")]
+        commands.entity(entity).insert(
+            AccessibilityInfo::new(Role::SpinButton)
+                .with_value(format_value(number_input.value))
+                .with_disabled(number_input.disabled),
+        );
+
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            NumberInputState {
+                text: format_value(number_input.value),
+                value: number_input.value,
+            },
+        );
+
+        if let Ok(mut state) = state_query.get_mut(state_entity) {
+            if state.value != number_input.value {
+                // The value was changed (or a change was rejected) by the user of this widget
+                state.value = number_input.value;
+                state.text = format_value(number_input.value);
+            }
+
+            *computed_styles = KStyle::default()
+                // Required styles
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    ..Default::default()
+                })
+                // Apply any prop-given styles
+                .with_style(styles)
+                // If not set by props, apply these styles
+                .with_style(KStyle {
+                    height: Units::Pixels(26.0).into(),
+                    ..Default::default()
+                })
+                .into();
+
+            let NumberInputProps {
+                min,
+                max,
+                step,
+                disabled,
+                ref on_change,
+                ..
+            } = *number_input;
+            // `clamp` panics on a reversed range
+            let (min, max) = (min.min(max), min.max(max));
+
+            let cloned_on_change = on_change.clone();
+            let cloned_widget_context = widget_context.clone();
+            let on_text_change = OnChange::new(
+                move |In((_, change)): In<(Entity, ValueChange<String>)>,
+                      mut commands: Commands,
+                      mut state_query: Query<&mut NumberInputState>| {
+                    let text = change.value;
+                    if !is_numeric_text(&text) {
+                        // Leaving the state untouched makes the text box revert the keystroke
+                        return;
+                    }
+
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        state.text = text.clone();
+                        if let Ok(value) = text.parse::<f64>() {
+                            if value >= min && value <= max {
+                                let previous = state.value;
+                                state.value = value;
+                                send_change(
+                                    &mut commands,
+                                    &cloned_on_change,
+                                    entity,
+                                    previous,
+                                    value,
+                                    &cloned_widget_context,
+                                );
+                            }
+                        }
+                    }
+                },
+            );
+
+            let cloned_on_change = on_change.clone();
+            let cloned_widget_context = widget_context.clone();
+            let on_blur = Handler::new(
+                move |In(_): In<(Entity, ())>,
+                      mut commands: Commands,
+                      mut state_query: Query<&mut NumberInputState>| {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        let value = state.text.parse::<f64>().unwrap_or(state.value);
+                        let value = value.clamp(min, max);
+                        state.text = format_value(value);
+                        if value != state.value {
+                            let previous = state.value;
+                            state.value = value;
+                            send_change(
+                                &mut commands,
+                                &cloned_on_change,
+                                entity,
+                                previous,
+                                value,
+                                &cloned_widget_context,
+                            );
+                        }
+                    }
+                },
+            );
+
+            let cloned_on_change = on_change.clone();
+            let cloned_widget_context = widget_context.clone();
+            *on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut commands: Commands,
+                      mut state_query: Query<&mut NumberInputState>| {
+                    if let EventType::KeyDown(key_event) = event.event_type {
+                        let direction = match key_event.key() {
+                            KeyCode::ArrowUp => 1.0,
+                            KeyCode::ArrowDown => -1.0,
+                            _ => return,
+                        };
+                        // Keep a surrounding scroll box from scrolling as well
+                        event.prevent_default();
+                        if let Ok(mut state) = state_query.get_mut(state_entity) {
+                            let previous = state.value;
+                            step_value(&mut state, direction * step, min, max);
+                            send_change(
+                                &mut commands,
+                                &cloned_on_change,
+                                entity,
+                                previous,
+                                state.value,
+                                &cloned_widget_context,
+                            );
+                        }
+                    }
+                },
+            );
+
+            let stepper_styles = KStyle {
+                width: Units::Pixels(26.0).into(),
+                height: Units::Pixels(26.0).into(),
+                left: Units::Pixels(2.0).into(),
+                ..Default::default()
+            };
+
+            let parent_id = Some(entity);
+            rsx! {
+                <ElementBundle
+                    styles={KStyle {
+                        layout_type: LayoutType::Row.into(),
+                        ..Default::default()
+                    }}
+                >
+                    <TextBoxBundle
+                        styles={KStyle {
+                            width: Units::Stretch(1.0).into(),
+                            ..Default::default()
+                        }}
+                        text_box={TextBoxProps {
+                            value: state.text.clone(),
+                            disabled,
+                            on_blur,
+                            ..Default::default()
+                        }}
+                        on_change={on_text_change}
+                    />
+                    <KButtonBundle
+                        styles={stepper_styles.clone()}
+                        button={KButton { text: "-".into(), disabled }}
+                        on_event={stepper_event(entity, state_entity, -step, min, max, on_change.clone(), widget_context.clone())}
+                    />
+                    <KButtonBundle
+                        styles={stepper_styles}
+                        button={KButton { text: "+".into(), disabled }}
+                        on_event={stepper_event(entity, state_entity, step, min, max, on_change.clone(), widget_context.clone())}
+                    />
+                </ElementBundle>
+            };
+        }
+    }
+
+    true
+}
+
+/// Creates the click handler for a stepper button
+fn stepper_event(
+    entity: Entity,
+    state_entity: Entity,
+    step: f64,
+    min: f64,
+    max: f64,
+    on_change: OnChange<f64>,
+    widget_context: KayakWidgetContext,
+) -> OnEvent {
+    OnEvent::new(
+        move |In(_entity): In<Entity>,
+              event: Res<KEvent>,
+              mut commands: Commands,
+              mut state_query: Query<&mut NumberInputState>| {
+            if let EventType::Click(..) = event.event_type {
+                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                    let previous = state.value;
+                    step_value(&mut state, step, min, max);
+                    send_change(
+                        &mut commands,
+                        &on_change,
+                        entity,
+                        previous,
+                        state.value,
+                        &widget_context,
+                    );
+                }
+            }
+        },
+    )
+}
+
+/// Adds the given step to the current value, keeping it within range
+fn step_value(state: &mut NumberInputState, step: f64, min: f64, max: f64) {
+    let value = state.text.parse::<f64>().unwrap_or(state.value);
+    state.value = (value + step).clamp(min, max);
+    state.text = format_value(state.value);
+}
+
+/// Queues the `on_change` callback to be called with the given value
+fn send_change(
+    commands: &mut Commands,
+    on_change: &OnChange<f64>,
+    entity: Entity,
+    previous: f64,
+    value: f64,
+    widget_context: &KayakWidgetContext,
+) {
+    on_change.set_value(previous, value);
+    let on_change = on_change.clone();
+    let widget_context = widget_context.clone();
+    commands.add(move |world: &mut World| on_change.try_call(entity, world, widget_context));
+}
+
+fn format_value(value: f64) -> String {
+    value.to_string()
+}
+
+/// Checks if the given text is a number (or could become one with further typing)
+fn is_numeric_text(text: &str) -> bool {
+    text.chars().filter(|c| *c == '.').count() <= 1
+        && text
+            .chars()
+            .enumerate()
+            .all(|(index, c)| c.is_ascii_digit() || c == '.' || (c == '-' && index == 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_value, is_numeric_text, step_value, NumberInputState};
+
+    #[test]
+    fn stepping_should_stay_within_range() {
+        let mut state = NumberInputState {
+            text: "4".into(),
+            value: 4.0,
+        };
+        step_value(&mut state, 1.0, 0.0, 5.0);
+        assert_eq!(5.0, state.value);
+        assert_eq!("5", state.text);
+
+        step_value(&mut state, 1.0, 0.0, 5.0);
+        assert_eq!(5.0, state.value);
+
+        step_value(&mut state, -10.0, 0.0, 5.0);
+        assert_eq!(0.0, state.value);
+        assert_eq!("0", state.text);
+    }
+
+    #[test]
+    fn stepping_should_start_from_valid_text_or_last_value() {
+        let mut state = NumberInputState {
+            text: "2.5".into(),
+            value: 1.0,
+        };
+        step_value(&mut state, 1.0, f64::MIN, f64::MAX);
+        assert_eq!(3.5, state.value);
+
+        state.text = "-".into();
+        step_value(&mut state, 1.0, f64::MIN, f64::MAX);
+        assert_eq!(4.5, state.value);
+        assert_eq!("4.5", state.text);
+    }
+
+    #[test]
+    fn values_should_format_without_trailing_zeros() {
+        assert_eq!("1", format_value(1.0));
+        assert_eq!("-0.5", format_value(-0.5));
+        assert_eq!("12.25", format_value(12.25));
+    }
+
+    #[test]
+    fn numeric_text_should_allow_partial_numbers() {
+        for text in ["", "-", "12", "-1.", ".5", "0.25"] {
+            assert!(is_numeric_text(text), "{:?} should be numeric", text);
+        }
+        for text in ["1-", "1.2.3", "abc", "1e5", "--1", " 1"] {
+            assert!(!is_numeric_text(text), "{:?} should not be numeric", text);
+        }
+    }
+}
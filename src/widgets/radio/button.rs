@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use kayak_ui_macros::{constructor, rsx};
+
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
+use crate::{
+    context::WidgetName,
+    disabled::{set_disabled, Disabled},
+    event::{EventType, KEvent},
+    focus_tree::{FocusTree, Focusable},
+    on_event::OnEvent,
+    prelude::{KChildren, KayakWidgetContext},
+    styles::{
+        ComputedStyles, Corner, Edge, KCursorIcon, KStyle, LayoutType, RenderCommand, StyleProp,
+        Units,
+    },
+    widget::Widget,
+    widgets::{BackgroundBundle, ElementBundle, TextProps, TextWidgetBundle},
+};
+
+use super::RadioGroupContext;
+
+/// Props used by the radio widget
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct Radio {
+    /// The value the radio group is set to when this radio is selected
+    pub value: String,
+    /// The text displayed next to the radio
+    pub label: String,
+    /// If true, the radio is dimmed and can't be selected or focused
+    pub disabled: bool,
+}
+
+impl Widget for Radio {}
+
+///
+/// A widget for selecting one of several options within a [`RadioGroup`](super::RadioGroup)
+///
+/// Radios are selected by clicking them or by pressing Space while they're focused.
+/// The arrow keys move the selection (and focus) to the previous or next radio in the group,
+/// skipping disabled radios.
+///
+#[derive(Bundle)]
+pub struct RadioBundle {
+    pub radio: Radio,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for RadioBundle {
+    fn default() -> Self {
+        Self {
+            radio: Default::default(),
+            styles: Default::default(),
+            computed_styles: Default::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: Radio::default().get_name(),
+        }
+    }
+}
+
+pub fn render(
+    In(radio_entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&Radio, &KStyle, &mut ComputedStyles, &mut OnEvent)>,
+    mut context_query: Query<&mut RadioGroupContext>,
+) -> bool {
+    if let Ok((radio, styles, mut computed_styles, mut on_event)) = query.get_mut(radio_entity) {
+        let disabled_styles = set_disabled(&mut commands, radio_entity, radio.disabled);
+        let cursor = if radio.disabled {
+            CursorIcon::Default
+        } else {
+            CursorIcon::Pointer
+        };
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                cursor: KCursorIcon(cursor).into(),
+                width: Units::Auto.into(),
+                height: Units::Auto.into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .with_style(disabled_styles)
+            .into();
+
+        if let Some(context_entity) =
+            widget_context.get_context_entity::<RadioGroupContext>(radio_entity)
+        {
+            if let Ok(mut context) = context_query.get_mut(context_entity) {
+                // Only touch the context when needed since changing it re-renders every radio
+                if !context.is_registered(&radio.value, radio_entity) {
+                    context.register(&radio.value, radio_entity);
+                }
+                let selected = context.is_selected(&radio.value);
+
+                #[cfg(feature = "accessibility")]
+                commands.entity(radio_entity).insert(
+                    AccessibilityInfo::new(Role::RadioButton)
+                        .with_label(radio.label.clone())
+                        .with_checked(selected)
+                        .with_disabled(radio.disabled),
+                );
+
+                let value = radio.value.clone();
+                *on_event = OnEvent::new(
+                    move |In(entity): In<Entity>,
+                          mut event: ResMut<KEvent>,
+                          focus_tree: Res<FocusTree>,
+                          mut query: Query<&mut RadioGroupContext>,
+                          disabled: Query<(), With<Disabled>>| {
+                        let is_disabled = |entity: Entity| disabled.contains(entity);
+                        if let Ok(mut context) = query.get_mut(context_entity) {
+                            let target = match event.event_type {
+                                EventType::Click(..) => Some((value.clone(), entity)),
+                                EventType::KeyDown(key_event) => match key_event.key() {
+                                    KeyCode::Space => Some((value.clone(), entity)),
+                                    KeyCode::ArrowDown | KeyCode::ArrowRight => {
+                                        context.neighbor(entity, 1, is_disabled)
+                                    }
+                                    KeyCode::ArrowUp | KeyCode::ArrowLeft => {
+                                        context.neighbor(entity, -1, is_disabled)
+                                    }
+                                    _ => None,
+                                },
+                                _ => None,
+                            };
+
+                            if let Some((target_value, target)) = target {
+                                event.stop_propagation();
+                                event.prevent_default();
+                                if target != entity {
+                                    focus_tree.focus(target);
+                                }
+                                if let Some(previous) = context.select(&target_value) {
+                                    let on_change = context.on_change().clone();
+                                    on_change.set_value(previous, target_value);
+                                    event.add_system(on_change);
+                                }
+                            }
+                        }
+                    },
+                );
+
+                let parent_id = Some(radio_entity);
+                rsx! {
+                    <ElementBundle
+                        styles={KStyle {
+                            layout_type: LayoutType::Row.into(),
+                            width: Units::Auto.into(),
+                            height: Units::Auto.into(),
+                            col_between: Units::Pixels(8.0).into(),
+                            ..Default::default()
+                        }}
+                    >
+                        <BackgroundBundle
+                            styles={KStyle {
+                                background_color: Color::rgba(0.133, 0.145, 0.2, 1.0).into(),
+                                border_color: Color::rgba(0.592, 0.627, 0.749, 1.0).into(),
+                                border: Edge::all(2.0).into(),
+                                border_radius: Corner::all(8.0).into(),
+                                width: Units::Pixels(16.0).into(),
+                                height: Units::Pixels(16.0).into(),
+                                top: Units::Stretch(1.0).into(),
+                                bottom: Units::Stretch(1.0).into(),
+                                padding: StyleProp::Value(Edge::all(Units::Stretch(1.0))),
+                                ..Default::default()
+                            }}
+                        >
+                            {
+                                if selected {
+                                    constructor! {
+                                        <BackgroundBundle
+                                            styles={KStyle {
+                                                background_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
+                                                border_radius: Corner::all(3.0).into(),
+                                                width: Units::Pixels(6.0).into(),
+                                                height: Units::Pixels(6.0).into(),
+                                                ..Default::default()
+                                            }}
+                                        />
+                                    }
+                                }
+                            }
+                        </BackgroundBundle>
+                        <TextWidgetBundle
+                            styles={KStyle {
+                                top: Units::Stretch(1.0).into(),
+                                bottom: Units::Stretch(1.0).into(),
+                                ..Default::default()
+                            }}
+                            text={TextProps {
+                                content: radio.label.clone(),
+                                size: 14.0,
+                                ..Default::default()
+                            }}
+                        />
+                    </ElementBundle>
+                };
+            }
+        }
+    }
+
+    true
+}
@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    on_change::OnChange,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KStyle, RenderCommand, Units},
+    widget::Widget,
+};
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct RadioGroupContext {
+    value: Option<String>,
+    /// The value and entity of each radio in the group, in the order they were first rendered
+    radios: Vec<(String, Entity)>,
+    on_change: OnChange,
+}
+
+impl RadioGroupContext {
+    /// The value of the selected radio (if any)
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    pub fn is_selected(&self, value: &str) -> bool {
+        self.value.as_deref() == Some(value)
+    }
+
+    /// Selects the radio with the given value
+    ///
+    /// Returns the previously selected value (or an empty string if there was none) if the
+    /// selection changed.
+    pub fn select(&mut self, value: &str) -> Option<String> {
+        if self.is_selected(value) {
+            return None;
+        }
+
+        Some(self.value.replace(value.to_string()).unwrap_or_default())
+    }
+
+    pub(super) fn on_change(&self) -> &OnChange {
+        &self.on_change
+    }
+
+    pub(super) fn is_registered(&self, value: &str, entity: Entity) -> bool {
+        self.radios
+            .iter()
+            .any(|(other_value, other_entity)| *other_entity == entity && other_value == value)
+    }
+
+    /// Adds a radio to the group (or updates its value if it was already added)
+    pub(super) fn register(&mut self, value: &str, entity: Entity) {
+        if let Some(radio) = self.radios.iter_mut().find(|(_, other)| *other == entity) {
+            radio.0 = value.to_string();
+        } else {
+            self.radios.push((value.to_string(), entity));
+        }
+    }
+
+    /// Returns the next radio in the direction of `offset` from the given one, wrapping around
+    /// the group
+    ///
+    /// Radios for which `is_disabled` returns true are skipped.
+    pub(super) fn neighbor(
+        &self,
+        entity: Entity,
+        offset: isize,
+        is_disabled: impl Fn(Entity) -> bool,
+    ) -> Option<(String, Entity)> {
+        let index = self.radios.iter().position(|(_, other)| *other == entity)? as isize;
+        let len = self.radios.len() as isize;
+        (1..len)
+            .map(|step| (index + offset.signum() * step).rem_euclid(len) as usize)
+            .map(|neighbor| &self.radios[neighbor])
+            .find(|(_, other)| !is_disabled(*other))
+            .cloned()
+    }
+}
+
+/// Props used by the radio group widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RadioGroup {
+    /// The value of the radio selected when the group is first rendered
+    pub default_value: Option<String>,
+}
+
+impl Widget for RadioGroup {}
+
+///
+/// A widget that makes sure only one of the [`Radio`](super::Radio) widgets within it is selected
+///
+/// The `on_change` handler is called with the selected value whenever the selection changes.
+///
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct RadioGroupBundle {
+    pub radio_group: RadioGroup,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub on_change: OnChange,
+    pub widget_name: WidgetName,
+}
+
+impl Default for RadioGroupBundle {
+    fn default() -> Self {
+        Self {
+            radio_group: Default::default(),
+            styles: Default::default(),
+            computed_styles: Default::default(),
+            children: Default::default(),
+            on_change: Default::default(),
+            widget_name: RadioGroup::default().get_name(),
+        }
+    }
+}
+
+pub fn render(
+    In(widget_entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &RadioGroup,
+        &KChildren,
+        &KStyle,
+        &mut ComputedStyles,
+        &OnChange,
+    )>,
+    mut context_query: Query<&mut RadioGroupContext>,
+) -> bool {
+    if let Ok((radio_group, children, styles, mut computed_styles, on_change)) =
+        query.get_mut(widget_entity)
+    {
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                height: Units::Auto.into(),
+                width: Units::Stretch(1.0).into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        let context_entity = if let Some(context_entity) =
+            widget_context.get_context_entity::<RadioGroupContext>(widget_entity)
+        {
+            // Handlers are recreated every render, swapping them shouldn't re-render the radios
+            if let Ok(mut context) = context_query.get_mut(context_entity) {
+                context.bypass_change_detection().on_change = on_change.clone();
+            }
+            context_entity
+        } else {
+            commands
+                .spawn(RadioGroupContext {
+                    value: radio_group.default_value.clone(),
+                    on_change: on_change.clone(),
+                    ..Default::default()
+                })
+                .id()
+        };
+        widget_context.set_context_entity::<RadioGroupContext>(Some(widget_entity), context_entity);
+        children.process(&widget_context, &mut commands, Some(widget_entity));
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Entity;
+
+    use super::RadioGroupContext;
+
+    #[test]
+    fn selecting_should_replace_previous_value() {
+        let mut context = RadioGroupContext::default();
+
+        assert_eq!(Some(String::new()), context.select("a"));
+        assert_eq!(Some("a".to_string()), context.select("b"));
+        assert_eq!(None, context.select("b"));
+        assert!(context.is_selected("b"));
+        assert!(!context.is_selected("a"));
+    }
+
+    #[test]
+    fn neighbor_should_wrap_around() {
+        let mut context = RadioGroupContext::default();
+        let (a, b, c) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        );
+        context.register("a", a);
+        context.register("b", b);
+        context.register("c", c);
+
+        assert_eq!(
+            Some(("b".to_string(), b)),
+            context.neighbor(a, 1, |_| false)
+        );
+        assert_eq!(
+            Some(("a".to_string(), a)),
+            context.neighbor(c, 1, |_| false)
+        );
+        assert_eq!(
+            Some(("c".to_string(), c)),
+            context.neighbor(a, -1, |_| false)
+        );
+    }
+
+    #[test]
+    fn neighbor_should_skip_disabled_radios() {
+        let mut context = RadioGroupContext::default();
+        let (a, b, c) = (
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        );
+        context.register("a", a);
+        context.register("b", b);
+        context.register("c", c);
+
+        assert_eq!(
+            Some(("c".to_string(), c)),
+            context.neighbor(a, 1, |e| e == b)
+        );
+        assert_eq!(
+            Some(("a".to_string(), a)),
+            context.neighbor(c, -1, |e| e == b)
+        );
+        assert_eq!(None, context.neighbor(a, 1, |e| e != a));
+    }
+}
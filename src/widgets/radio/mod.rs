@@ -0,0 +1,29 @@
+use crate::{
+    widget::{widget_update, widget_update_with_context, EmptyState, Widget},
+    KayakUIPlugin,
+};
+
+mod button;
+mod group;
+
+pub use button::{Radio, RadioBundle};
+pub use group::{RadioGroup, RadioGroupBundle, RadioGroupContext};
+
+pub struct RadioPlugin;
+impl KayakUIPlugin for RadioPlugin {
+    fn build(&self, context: &mut crate::context::KayakRootContext) {
+        context.add_widget_data::<RadioGroup, EmptyState>();
+        context.add_widget_system(
+            RadioGroup::default().get_name(),
+            widget_update::<RadioGroup, EmptyState>,
+            group::render,
+        );
+
+        context.add_widget_data::<Radio, EmptyState>();
+        context.add_widget_system(
+            Radio::default().get_name(),
+            widget_update_with_context::<Radio, EmptyState, RadioGroupContext>,
+            button::render,
+        );
+    }
+}
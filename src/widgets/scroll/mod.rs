@@ -4,7 +4,7 @@ pub mod scroll_content;
 pub mod scroll_context;
 
 /// Maps a value from one range to another range
-fn map_range(value: f32, from_range: (f32, f32), to_range: (f32, f32)) -> f32 {
+pub(crate) fn map_range(value: f32, from_range: (f32, f32), to_range: (f32, f32)) -> f32 {
     let from_diff = from_range.1 - from_range.0;
     if from_diff <= f32::EPSILON {
         value
@@ -1,11 +1,15 @@
-use bevy::prelude::{Bundle, Color, Commands, Component, Entity, In, ParamSet, Query, Res, ResMut};
+use bevy::prelude::{
+    Bundle, Color, Commands, Component, Entity, In, ParamSet, Query, Res, ResMut, Time, Vec2,
+};
 
 use crate::{
     children::KChildren,
     context::WidgetName,
     cursor::ScrollUnit,
     event::{EventType, KEvent},
+    focus_tree::{FocusTree, Focusable},
     layout::{GeometryChanged, LayoutEvent},
+    node::WrappedIndex,
     on_event::OnEvent,
     on_layout::OnLayout,
     prelude::{constructor, rsx, KayakWidgetContext},
@@ -49,10 +53,26 @@ pub struct ScrollBoxProps {
     pub track_color: Option<Color>,
     /// The styles of the scrollbar track
     pub track_styles: Option<KStyle>,
+    /// If true, the content can be dragged and will keep moving after being flung
+    pub momentum: bool,
+    /// How quickly flung content decelerates (per second) when [`momentum`](Self::momentum) is enabled
+    pub friction: Option<f32>,
+    /// If true, the scroll box itself can be focused by clicking it or with Tab
+    ///
+    /// While the scroll box (or a focusable widget inside it) is focused, PageUp and PageDown
+    /// scroll by the height of the viewport, Home and End jump to the top and bottom, and the
+    /// arrow keys scroll by [`scroll_line`](Self::scroll_line). Widgets that use these keys
+    /// themselves can keep the scroll box from reacting with [`KEvent::prevent_default`].
+    pub focusable: bool,
 }
 
 impl Widget for ScrollBoxProps {}
 
+/// The default deceleration applied to flung content
+const DEFAULT_FRICTION: f32 = 5.0;
+/// Releasing a drag after holding still for this long (in seconds) won't fling the content
+const FLING_WINDOW: f32 = 0.1;
+
 #[derive(Bundle)]
 pub struct ScrollBoxBundle {
     pub scroll_box_props: ScrollBoxProps,
@@ -88,6 +108,7 @@ pub fn scroll_box_render(
         &mut OnLayout,
     )>,
     mut context_query: ParamSet<(Query<&ScrollContext>, Query<&mut ScrollContext>)>,
+    focus_tree: Res<FocusTree>,
 ) -> bool {
     if let Ok((scroll_box, styles, mut computed_styles, scroll_box_children, mut on_layout)) =
         query.get_mut(entity)
@@ -106,6 +127,9 @@ pub fn scroll_box_render(
                 let thumb_styles = scroll_box.thumb_styles.clone();
                 let track_color = scroll_box.track_color;
                 let track_styles = scroll_box.track_styles.clone();
+                let momentum = scroll_box.momentum;
+                let focusable = scroll_box.focusable;
+                let friction = scroll_box.friction.unwrap_or(DEFAULT_FRICTION);
 
                 let scroll_x = scroll_context.scroll_x();
                 let scroll_y = scroll_context.scroll_y();
@@ -130,6 +154,16 @@ pub fn scroll_box_render(
                     }
                 }
 
+                if momentum != scroll_context.momentum || friction != scroll_context.friction {
+                    if let Ok(mut scroll_context_mut) = context_query.p1().get_mut(context_entity) {
+                        scroll_context_mut.momentum = momentum;
+                        scroll_context_mut.friction = friction;
+                        if !momentum {
+                            scroll_context_mut.cancel_momentum();
+                        }
+                    }
+                }
+
                 *on_layout = OnLayout::new(
                     move |In((event, _entity)): In<(LayoutEvent, Entity)>,
                           mut query: Query<&mut ScrollContext>| {
@@ -182,29 +216,97 @@ pub fn scroll_box_render(
 
                 let event_handler = OnEvent::new(
                     move |In(_entity): In<Entity>,
+                          time: Res<Time>,
                           mut event: ResMut<KEvent>,
                           mut query: Query<&mut ScrollContext>| {
                         if let Ok(mut scroll_context) = query.get_mut(context_entity) {
-                            if let EventType::Scroll(evt) = event.event_type {
-                                match evt.delta {
-                                    ScrollUnit::Line { x, y } => {
-                                        if !disable_horizontal {
-                                            scroll_context.set_scroll_x(scroll_x - x * scroll_line);
-                                        }
-                                        if !disable_vertical {
-                                            scroll_context.set_scroll_y(scroll_y + y * scroll_line);
-                                        }
+                            match event.event_type {
+                                EventType::MouseDown(data) if momentum => {
+                                    // Grabbing the content stops any existing momentum
+                                    if scroll_context.velocity != Vec2::ZERO {
+                                        scroll_context.cancel_momentum();
+                                    }
+                                    // The scrollbar thumb takes priority over panning
+                                    if !scroll_context.is_dragging {
+                                        scroll_context.is_panning = true;
+                                        scroll_context.last_pan_pos = data.position.into();
+                                        scroll_context.last_pan_time = time.elapsed_seconds();
                                     }
-                                    ScrollUnit::Pixel { x, y } => {
-                                        if !disable_horizontal {
-                                            scroll_context.set_scroll_x(scroll_x - x);
+                                }
+                                EventType::Hover(data) if scroll_context.is_panning => {
+                                    if !data.pressed {
+                                        // The release happened outside of this widget
+                                        scroll_context.is_panning = false;
+                                        scroll_context.cancel_momentum();
+                                        return;
+                                    }
+
+                                    let now = time.elapsed_seconds();
+                                    let position: Vec2 = data.position.into();
+                                    let mut delta = position - scroll_context.last_pan_pos;
+                                    if disable_horizontal {
+                                        delta.x = 0.0;
+                                    }
+                                    if disable_vertical {
+                                        delta.y = 0.0;
+                                    }
+
+                                    let current_x = scroll_context.scroll_x();
+                                    let current_y = scroll_context.scroll_y();
+                                    scroll_context.set_scroll_x(current_x + delta.x);
+                                    scroll_context.set_scroll_y(current_y + delta.y);
+
+                                    // Smooth the velocity over the last few moves
+                                    let dt = (now - scroll_context.last_pan_time).max(f32::EPSILON);
+                                    let velocity = scroll_context.velocity;
+                                    scroll_context.velocity = velocity.lerp(delta / dt, 0.5);
+                                    scroll_context.last_pan_pos = position;
+                                    scroll_context.last_pan_time = now;
+                                }
+                                EventType::MouseUp(..) if scroll_context.is_panning => {
+                                    scroll_context.is_panning = false;
+                                    if time.elapsed_seconds() - scroll_context.last_pan_time
+                                        > FLING_WINDOW
+                                    {
+                                        scroll_context.cancel_momentum();
+                                    }
+                                }
+                                // A descendant that handles scrolling itself can keep the box still
+                                EventType::Scroll(evt) if !event.is_default_prevented() => {
+                                    match evt.delta {
+                                        ScrollUnit::Line { x, y } => {
+                                            if !disable_horizontal {
+                                                scroll_context
+                                                    .set_scroll_x(scroll_x - x * scroll_line);
+                                            }
+                                            if !disable_vertical {
+                                                scroll_context
+                                                    .set_scroll_y(scroll_y + y * scroll_line);
+                                            }
                                         }
-                                        if !disable_vertical {
-                                            scroll_context.set_scroll_y(scroll_y + y);
+                                        ScrollUnit::Pixel { x, y } => {
+                                            if !disable_horizontal {
+                                                scroll_context.set_scroll_x(scroll_x - x);
+                                            }
+                                            if !disable_vertical {
+                                                scroll_context.set_scroll_y(scroll_y + y);
+                                            }
                                         }
                                     }
+                                    event.stop_propagation();
                                 }
-                                event.stop_propagation();
+                                EventType::KeyDown(evt)
+                                    if !event.is_default_prevented()
+                                        && scroll_context.scroll_by_key(
+                                            evt.key(),
+                                            scroll_line,
+                                            disable_horizontal,
+                                            disable_vertical,
+                                        ) =>
+                                {
+                                    event.stop_propagation();
+                                }
+                                _ => {}
                             }
                         }
                     },
@@ -212,7 +314,19 @@ pub fn scroll_box_render(
 
                 let parent_id = Some(entity);
                 rsx! {
-                    <ElementBundle on_event={event_handler} styles={hbox_styles}>
+                    <ElementBundle
+                        id={"event_entity"}
+                        on_event={event_handler}
+                        styles={hbox_styles}
+                    >
+                        {
+                            if focusable {
+                                commands.entity(event_entity).insert(Focusable);
+                            } else if focus_tree.contains(event_entity) {
+                                commands.entity(event_entity).remove::<Focusable>();
+                                focus_tree.remove(WrappedIndex(event_entity));
+                            }
+                        }
                         <ElementBundle styles={vbox_styles}>
                             <ClipBundle>
                                 <ScrollContentBundle
@@ -258,3 +372,78 @@ pub fn scroll_box_render(
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Entity, KeyCode, Time, With, World};
+
+    use super::{ScrollBoxBundle, ScrollBoxProps};
+    use crate::{
+        children::KChildren,
+        context::{KayakRootContext, Mounted},
+        event_dispatcher::EventDispatcher,
+        focus_tree::Focusable,
+        input_event::InputEvent,
+        node::WrappedIndex,
+        widgets::{KayakWidgetsContextPlugin, ScrollContextProviderBundle},
+        KayakUIPlugin,
+    };
+
+    #[test]
+    fn focusable_scroll_boxes_should_receive_focus() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let camera = world.spawn_empty().id();
+        let scroll_box = world
+            .spawn(ScrollBoxBundle {
+                scroll_box_props: ScrollBoxProps {
+                    focusable: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let mut children = KChildren::new();
+        children.add(scroll_box);
+        let provider = world
+            .spawn((
+                ScrollContextProviderBundle {
+                    children,
+                    ..Default::default()
+                },
+                Mounted,
+            ))
+            .id();
+
+        let mut context = KayakRootContext::new(camera);
+        KayakWidgetsContextPlugin.build(&mut context);
+        context.add_widget(None, provider);
+        world.entity_mut(camera).insert(context);
+        KayakRootContext::flush(&mut world);
+
+        let focusables = world
+            .query_filtered::<Entity, With<Focusable>>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        assert_eq!(1, focusables.len());
+        let event_entity = focusables[0];
+
+        let mut context = world.entity_mut(camera).take::<KayakRootContext>().unwrap();
+        assert_eq!(
+            Some(WrappedIndex(scroll_box)),
+            context
+                .tree
+                .read()
+                .unwrap()
+                .get_parent(WrappedIndex(event_entity))
+        );
+
+        let mut dispatcher = EventDispatcher::new();
+        let tab = InputEvent::Keyboard {
+            key: KeyCode::Tab,
+            is_pressed: true,
+        };
+        dispatcher.process_events(&[tab], &mut context, &mut world);
+        assert_eq!(Some(event_entity), context.focus_tree.current());
+    }
+}
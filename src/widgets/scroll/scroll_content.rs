@@ -69,6 +69,15 @@ pub fn scroll_content_render(
                             }
                         }
 
+                        if event.flags.intersects(
+                            GeometryChanged::POSX_CHANGED | GeometryChanged::POSY_CHANGED,
+                        ) {
+                            if let Ok(mut scroll) = query.get_mut(context_entity) {
+                                scroll.content_posx = event.layout.x;
+                                scroll.content_posy = event.layout.y;
+                            }
+                        }
+
                         event
                     },
                 );
@@ -1,8 +1,16 @@
-use bevy::prelude::{BuildChildren, Bundle, Commands, Component, Entity, In, Query, Res, Vec2};
+use bevy::{
+    prelude::{
+        BuildChildren, Bundle, Commands, Component, Entity, In, KeyCode, Local, Query, Res, Time,
+        Vec2,
+    },
+    utils::HashMap,
+};
 
 use crate::{
     children::KChildren,
-    context::WidgetName,
+    context::{KayakRootContext, WidgetName},
+    layout::Rect,
+    node::WrappedIndex,
     prelude::KayakWidgetContext,
     styles::{ComputedStyles, KStyle},
     widget::Widget,
@@ -15,6 +23,8 @@ pub struct ScrollContext {
     pub(super) scroll_y: f32,
     pub(super) content_width: f32,
     pub(super) content_height: f32,
+    pub(super) content_posx: f32,
+    pub(super) content_posy: f32,
     pub(super) scrollbox_width: f32,
     pub(super) scrollbox_height: f32,
     pub(super) pad_x: f32,
@@ -23,6 +33,12 @@ pub struct ScrollContext {
     pub(super) is_dragging: bool,
     pub(super) start_pos: Vec2,
     pub(super) start_offset: Vec2,
+    pub(super) momentum: bool,
+    pub(super) friction: f32,
+    pub(super) velocity: Vec2,
+    pub(super) is_panning: bool,
+    pub(super) last_pan_pos: Vec2,
+    pub(super) last_pan_time: f32,
 }
 
 #[non_exhaustive]
@@ -35,6 +51,18 @@ pub enum ScrollMode {
     Infinite,
 }
 
+/// Where a target should end up when scrolled into view
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScrollAlignment {
+    /// Scrolls the least amount needed to make the target fully visible
+    #[default]
+    Nearest,
+    /// Aligns the target with the start (top/left) of the viewport
+    Start,
+    /// Aligns the target with the center of the viewport
+    Center,
+}
+
 impl ScrollContext {
     /// Get the current x-axis scroll offset
     pub fn scroll_x(&self) -> f32 {
@@ -64,6 +92,18 @@ impl ScrollContext {
         }
     }
 
+    /// The width of the visible part of the content (i.e. excluding the scrollbar)
+    pub fn viewport_width(&self) -> f32 {
+        (self.scrollbox_width - self.pad_x).max(0.0)
+    }
+
+    /// The height of the visible part of the content (i.e. excluding the scrollbar)
+    ///
+    /// This is how far PageUp and PageDown scroll.
+    pub fn viewport_height(&self) -> f32 {
+        (self.scrollbox_height - self.pad_y).max(0.0)
+    }
+
     /// The total amount that can be scrolled along the x-axis
     pub fn scrollable_width(&self) -> f32 {
         (self.content_width() - self.scrollbox_width).max(0.0)
@@ -79,6 +119,21 @@ impl ScrollContext {
         self.mode
     }
 
+    /// Returns true if flinging the content will keep it moving after release
+    pub fn momentum(&self) -> bool {
+        self.momentum
+    }
+
+    /// The current momentum velocity in pixels per second
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// Stops any momentum that is currently being applied
+    pub fn cancel_momentum(&mut self) {
+        self.velocity = Vec2::ZERO;
+    }
+
     /// Set the scroll offset along the x-axis
     ///
     /// This automatically accounts for the scroll mode
@@ -101,6 +156,110 @@ impl ScrollContext {
         };
     }
 
+    /// Scrolls so that the given rect becomes visible within the viewport
+    ///
+    /// The rect should be relative to the scroll content (i.e. a rect at `(0, 0)` is the very
+    /// top-left of the content, regardless of the current scroll offset).
+    pub fn scroll_to(&mut self, target_layout: Rect, alignment: ScrollAlignment) {
+        let view_width = self.viewport_width();
+        let view_height = self.viewport_height();
+        let x = Self::aligned_offset(
+            self.scroll_x,
+            target_layout.posx,
+            target_layout.width,
+            view_width,
+            alignment,
+        );
+        let y = Self::aligned_offset(
+            self.scroll_y,
+            target_layout.posy,
+            target_layout.height,
+            view_height,
+            alignment,
+        );
+        self.set_scroll_x(x);
+        self.set_scroll_y(y);
+    }
+
+    /// Scrolls so that the given rect becomes visible within the viewport
+    ///
+    /// Unlike [`scroll_to`](Self::scroll_to), the rect should be the computed layout of a widget
+    /// (as returned by [`KayakWidgetContext::get_layout`]) within the scroll content.
+    pub fn scroll_into_view(&mut self, layout: Rect, alignment: ScrollAlignment) {
+        self.scroll_to(
+            Rect {
+                posx: layout.posx - self.content_posx,
+                posy: layout.posy - self.content_posy,
+                ..layout
+            },
+            alignment,
+        );
+    }
+
+    /// Scrolls in response to a navigation key
+    ///
+    /// PageUp and PageDown scroll by a viewport height, Home and End jump to the top and bottom,
+    /// and the arrow keys scroll by `line`. Returns false if the key doesn't scroll along an
+    /// enabled axis, so it can be left to other widgets.
+    pub(super) fn scroll_by_key(
+        &mut self,
+        key: KeyCode,
+        line: f32,
+        disable_horizontal: bool,
+        disable_vertical: bool,
+    ) -> bool {
+        let y = match key {
+            KeyCode::PageUp => self.scroll_y + self.viewport_height(),
+            KeyCode::PageDown => self.scroll_y - self.viewport_height(),
+            KeyCode::Home => 0.0,
+            KeyCode::End => -self.scrollable_height(),
+            KeyCode::ArrowUp => self.scroll_y + line,
+            KeyCode::ArrowDown => self.scroll_y - line,
+            KeyCode::ArrowLeft | KeyCode::ArrowRight if !disable_horizontal => {
+                let x = if key == KeyCode::ArrowLeft {
+                    self.scroll_x + line
+                } else {
+                    self.scroll_x - line
+                };
+                self.cancel_momentum();
+                self.set_scroll_x(x);
+                return true;
+            }
+            _ => return false,
+        };
+        if disable_vertical {
+            return false;
+        }
+
+        self.cancel_momentum();
+        self.set_scroll_y(y);
+        true
+    }
+
+    /// Calculates the scroll offset along a single axis needed to show the target
+    fn aligned_offset(
+        current: f32,
+        target_pos: f32,
+        target_size: f32,
+        view_size: f32,
+        alignment: ScrollAlignment,
+    ) -> f32 {
+        let start = -target_pos;
+        match alignment {
+            ScrollAlignment::Start => start,
+            ScrollAlignment::Center => -(target_pos + target_size / 2.0 - view_size / 2.0),
+            ScrollAlignment::Nearest => {
+                if target_pos < -current || target_size > view_size {
+                    start
+                } else if target_pos + target_size > -current + view_size {
+                    -(target_pos + target_size - view_size)
+                } else {
+                    current
+                }
+            }
+        }
+    }
+
     /// The percent scrolled along the x-axis
     pub fn percent_x(&self) -> f32 {
         let width = self.scrollable_width();
@@ -129,6 +288,80 @@ impl ScrollContext {
     }
 }
 
+/// Velocity (in pixels per second) below which momentum is stopped
+const MOMENTUM_THRESHOLD: f32 = 5.0;
+
+/// Applies momentum to flung [`ScrollContext`]s, decelerating them each frame
+pub fn update_scroll_momentum(time: Res<Time>, mut query: Query<&mut ScrollContext>) {
+    let dt = time.delta_seconds();
+    for mut scroll_context in query.iter_mut() {
+        if !scroll_context.momentum
+            || scroll_context.is_panning
+            || scroll_context.velocity == Vec2::ZERO
+        {
+            continue;
+        }
+
+        let velocity = scroll_context.velocity;
+        let x = scroll_context.scroll_x + velocity.x * dt;
+        let y = scroll_context.scroll_y + velocity.y * dt;
+        scroll_context.set_scroll_x(x);
+        scroll_context.set_scroll_y(y);
+
+        let mut velocity = velocity * (-scroll_context.friction * dt).exp();
+        // Hitting the content bounds kills the momentum along that axis
+        if scroll_context.scroll_x != x {
+            velocity.x = 0.0;
+        }
+        if scroll_context.scroll_y != y {
+            velocity.y = 0.0;
+        }
+        if velocity.length() < MOMENTUM_THRESHOLD {
+            velocity = Vec2::ZERO;
+        }
+        scroll_context.velocity = velocity;
+    }
+}
+
+/// Scrolls newly focused widgets into view if they're within a scroll box
+pub fn scroll_focused_into_view(
+    mut last_focus: Local<HashMap<Entity, Entity>>,
+    context_query: Query<(Entity, &KayakRootContext)>,
+    mut scroll_query: Query<&mut ScrollContext>,
+) {
+    for (root, context) in context_query.iter() {
+        if let Some(focused) = context.focus_tree.current() {
+            if last_focus.insert(root, focused) == Some(focused) {
+                continue;
+            }
+
+            if let (Some(layout), Ok(tree)) = (
+                context.get_layout(&WrappedIndex(focused)),
+                context.tree.try_read(),
+            ) {
+                // Find the closest scroll context above the focused widget
+                let mut current = tree.get_parent(WrappedIndex(focused));
+                while let Some(parent) = current {
+                    if let Some(context_entity) = context
+                        .context_entities
+                        .get_context_entity::<ScrollContext>(Some(parent.0))
+                    {
+                        if let Ok(mut scroll_context) = scroll_query.get_mut(context_entity) {
+                            let mut scrolled = *scroll_context;
+                            scrolled.scroll_into_view(layout, ScrollAlignment::Nearest);
+                            if scrolled != *scroll_context {
+                                *scroll_context = scrolled;
+                            }
+                        }
+                        break;
+                    }
+                    current = tree.get_parent(parent);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Component, Default, PartialEq, Clone)]
 pub struct ScrollContextProvider {
     initial_value: ScrollContext,
@@ -180,3 +413,53 @@ pub fn scroll_context_render(
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::KeyCode;
+
+    use super::ScrollContext;
+
+    fn scroll_context() -> ScrollContext {
+        ScrollContext {
+            content_height: 1000.0,
+            scrollbox_width: 200.0,
+            scrollbox_height: 300.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn page_keys_should_scroll_by_the_viewport_height() {
+        let mut context = scroll_context();
+
+        assert!(context.scroll_by_key(KeyCode::PageDown, 16.0, false, false));
+        assert_eq!(-300.0, context.scroll_y());
+        assert!(context.scroll_by_key(KeyCode::PageUp, 16.0, false, false));
+        assert_eq!(0.0, context.scroll_y());
+    }
+
+    #[test]
+    fn keyboard_scrolling_should_stay_within_the_content() {
+        let mut context = scroll_context();
+
+        context.scroll_by_key(KeyCode::PageUp, 16.0, false, false);
+        assert_eq!(0.0, context.scroll_y());
+        context.scroll_by_key(KeyCode::End, 16.0, false, false);
+        assert_eq!(-700.0, context.scroll_y());
+        context.scroll_by_key(KeyCode::ArrowDown, 16.0, false, false);
+        assert_eq!(-700.0, context.scroll_y());
+        context.scroll_by_key(KeyCode::Home, 16.0, false, false);
+        assert_eq!(0.0, context.scroll_y());
+    }
+
+    #[test]
+    fn keys_of_disabled_axes_should_be_ignored() {
+        let mut context = scroll_context();
+
+        assert!(!context.scroll_by_key(KeyCode::PageDown, 16.0, false, true));
+        assert!(!context.scroll_by_key(KeyCode::ArrowRight, 16.0, true, false));
+        assert!(!context.scroll_by_key(KeyCode::Enter, 16.0, false, false));
+        assert_eq!(0.0, context.scroll_y());
+    }
+}
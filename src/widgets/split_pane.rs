@@ -0,0 +1,307 @@
+use bevy::{
+    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res, ResMut},
+    window::CursorIcon,
+};
+use kayak_ui_macros::rsx;
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    drag::Draggable,
+    event::{EventType, KEvent},
+    layout::{LayoutEvent, Rect},
+    on_event::OnEvent,
+    on_layout::OnLayout,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KCursorIcon, KStyle, LayoutType, RenderCommand, StyleProp, Units},
+    widget::Widget,
+};
+
+use super::{background::BackgroundBundle, clip::ClipBundle, scroll::map_range, ElementBundle};
+
+/// The direction in which a [`SplitPaneProps`] widget places its panes
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The panes are placed side by side, separated by a vertical divider
+    #[default]
+    Horizontal,
+    /// The panes are placed on top of each other, separated by a horizontal divider
+    Vertical,
+}
+
+/// Props used by the split pane widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SplitPaneProps {
+    /// The direction in which the panes are placed
+    pub direction: SplitDirection,
+    /// The portion of the space given to the first pane when the widget is first rendered (0.0 to 1.0)
+    pub initial_ratio: f32,
+    /// The minimum size of the first and second pane in pixels
+    pub min_px: (f32, f32),
+    /// The thickness of the divider in pixels
+    pub divider_thickness: f32,
+    /// The color of the divider
+    pub divider_color: Color,
+}
+
+impl Default for SplitPaneProps {
+    fn default() -> Self {
+        Self {
+            direction: SplitDirection::Horizontal,
+            initial_ratio: 0.5,
+            min_px: (0.0, 0.0),
+            divider_thickness: 6.0,
+            divider_color: Color::rgba(0.239, 0.258, 0.337, 1.0),
+        }
+    }
+}
+
+impl SplitPaneProps {
+    /// Clamps a split ratio so that both panes stay at or above their minimum size
+    ///
+    /// If both minimums don't fit, the first pane's minimum wins.
+    fn clamp_ratio(&self, ratio: f32, available: f32) -> f32 {
+        if available <= 0.0 {
+            return ratio.clamp(0.0, 1.0);
+        }
+
+        let min = (self.min_px.0 / available).clamp(0.0, 1.0);
+        let max = (1.0 - self.min_px.1 / available).clamp(min, 1.0);
+        ratio.clamp(min, max)
+    }
+
+    /// The space shared by the two panes, given the layout of the widget
+    fn available_space(&self, layout: &Rect) -> f32 {
+        let size = match self.direction {
+            SplitDirection::Horizontal => layout.width,
+            SplitDirection::Vertical => layout.height,
+        };
+        (size - self.divider_thickness).max(0.0)
+    }
+}
+
+impl Widget for SplitPaneProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct SplitPaneState {
+    /// The portion of the space given to the first pane (0.0 to 1.0)
+    pub ratio: f32,
+    /// The layout of the split pane as of the last layout pass
+    pub layout: Rect,
+}
+
+///
+/// A widget that splits its space between two panes with a draggable divider
+///
+/// The first child is placed in the first pane and the second child in the second pane.
+/// Any other children are ignored.
+///
+/// # Props
+///
+/// __Type:__ [`SplitPaneProps`]
+///
+/// | Common Prop | Accepted |
+/// | :---------: | :------: |
+/// | `children`  | ✅        |
+/// | `styles`    | ✅        |
+/// | `on_event`  |           |
+/// | `on_layout` |           |
+/// | `focusable` |           |
+///
+#[derive(Bundle)]
+pub struct SplitPaneBundle {
+    pub split_pane: SplitPaneProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub on_layout: OnLayout,
+    pub widget_name: WidgetName,
+}
+
+impl Default for SplitPaneBundle {
+    fn default() -> Self {
+        Self {
+            split_pane: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            children: Default::default(),
+            on_layout: OnLayout::default(),
+            widget_name: SplitPaneProps::default().get_name(),
+        }
+    }
+}
+
+pub fn split_pane_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &KStyle,
+        &mut ComputedStyles,
+        &KChildren,
+        &SplitPaneProps,
+        &mut OnLayout,
+    )>,
+    state_query: Query<&SplitPaneState>,
+) -> bool {
+    if let Ok((styles, mut computed_styles, children, split_pane, mut on_layout)) =
+        query.get_mut(entity)
+    {
+        let layout = widget_context.get_layout(entity).unwrap_or_default();
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            SplitPaneState {
+                ratio: split_pane.initial_ratio,
+                layout,
+            },
+        );
+
+        // Keep track of the layout so the divider can convert pointer positions to ratios
+        *on_layout = OnLayout::new(
+            move |In((event, _entity)): In<(LayoutEvent, Entity)>,
+                  mut state_query: Query<&mut SplitPaneState>| {
+                if !event.flags.is_empty() {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        state.layout = event.layout.into();
+                    }
+                }
+
+                event
+            },
+        );
+
+        if let Ok(state) = state_query.get(state_entity) {
+            let horizontal = split_pane.direction == SplitDirection::Horizontal;
+            let thickness = split_pane.divider_thickness;
+            let ratio =
+                split_pane.clamp_ratio(state.ratio, split_pane.available_space(&state.layout));
+
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Stretch(1.0).into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .into();
+
+            let container_style = KStyle {
+                layout_type: if horizontal {
+                    LayoutType::Row
+                } else {
+                    LayoutType::Column
+                }
+                .into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            // The panes share the space left over by the divider
+            let pane_style = |ratio: f32, min_px: f32| {
+                if horizontal {
+                    KStyle {
+                        width: Units::Stretch(ratio).into(),
+                        height: Units::Stretch(1.0).into(),
+                        min_width: Units::Pixels(min_px).into(),
+                        ..Default::default()
+                    }
+                } else {
+                    KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        height: Units::Stretch(ratio).into(),
+                        min_height: Units::Pixels(min_px).into(),
+                        ..Default::default()
+                    }
+                }
+            };
+            let first_style = pane_style(ratio, split_pane.min_px.0);
+            let second_style = pane_style(1.0 - ratio, split_pane.min_px.1);
+
+            let divider_style = KStyle {
+                background_color: StyleProp::Value(split_pane.divider_color),
+                cursor: StyleProp::Value(KCursorIcon(if horizontal {
+                    CursorIcon::ColResize
+                } else {
+                    CursorIcon::RowResize
+                })),
+                width: if horizontal {
+                    Units::Pixels(thickness)
+                } else {
+                    Units::Stretch(1.0)
+                }
+                .into(),
+                height: if horizontal {
+                    Units::Stretch(1.0)
+                } else {
+                    Units::Pixels(thickness)
+                }
+                .into(),
+                ..Default::default()
+            };
+
+            let mut first_children = KChildren::new();
+            if let Some(child) = children.get(0) {
+                first_children.add(child);
+            }
+            let mut second_children = KChildren::new();
+            if let Some(child) = children.get(1) {
+                second_children.add(child);
+            }
+
+            let props = split_pane.clone();
+            let on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut query: Query<&mut SplitPaneState>| {
+                    if let Ok(mut state) = query.get_mut(state_entity) {
+                        match &event.event_type {
+                            EventType::DragStart(data) | EventType::Drag(data) => {
+                                // Center the divider on the cursor
+                                let (pointer, start) = if horizontal {
+                                    (data.position.0, state.layout.posx)
+                                } else {
+                                    (data.position.1, state.layout.posy)
+                                };
+                                let available = props.available_space(&state.layout);
+                                let ratio = map_range(
+                                    pointer - thickness / 2.0,
+                                    (start, start + available),
+                                    (0.0, 1.0),
+                                );
+                                let ratio = props.clamp_ratio(ratio, available);
+                                // Only touch the state when the ratio changes to avoid needless re-renders
+                                if ratio != state.ratio {
+                                    state.ratio = ratio;
+                                }
+                                event.stop_propagation();
+                            }
+                            EventType::DragEnd(..) => {
+                                event.stop_propagation();
+                            }
+                            _ => {}
+                        }
+                    }
+                },
+            );
+
+            let parent_id = Some(entity);
+            rsx! {
+                <ElementBundle styles={container_style}>
+                    <ClipBundle styles={first_style} children={first_children} />
+                    <BackgroundBundle
+                        id={"divider_entity"}
+                        styles={divider_style}
+                        on_event={on_event}
+                    />
+                    {commands.entity(divider_entity).insert(Draggable)}
+                    <ClipBundle styles={second_style} children={second_children} />
+                </ElementBundle>
+            };
+        }
+    }
+
+    true
+}
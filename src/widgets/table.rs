@@ -0,0 +1,314 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+use bevy::{
+    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res, ResMut},
+    window::CursorIcon,
+};
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    handler::Handler,
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KCursorIcon, KStyle, LayoutType, RenderCommand, StyleProp, Units},
+    widget::Widget,
+};
+
+use super::{
+    background::BackgroundBundle,
+    clip::ClipBundle,
+    scroll::{scroll_box::ScrollBoxBundle, scroll_context::ScrollContextProviderBundle},
+    text::{TextProps, TextWidgetBundle},
+    ElementBundle,
+};
+
+/// A column of a [`TableProps`] widget
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableColumn {
+    /// The text displayed in the column's header
+    pub label: String,
+    /// The width of the column
+    ///
+    /// Both fixed (`Pixels`) and `Stretch` widths are supported.
+    pub width: Units,
+}
+
+impl TableColumn {
+    pub fn new(label: impl Into<String>, width: Units) -> Self {
+        Self {
+            label: label.into(),
+            width,
+        }
+    }
+}
+
+impl Default for TableColumn {
+    fn default() -> Self {
+        Self::new("", Units::Stretch(1.0))
+    }
+}
+
+/// A container for the function that provides the cells of each table row
+///
+/// The function receives the index of a row and returns the text of its cells, in column order.
+#[derive(Clone)]
+pub struct TableRowRenderer {
+    render: Arc<dyn Fn(usize) -> Vec<String> + Send + Sync>,
+}
+
+impl TableRowRenderer {
+    pub fn new(render: impl Fn(usize) -> Vec<String> + Send + Sync + 'static) -> Self {
+        Self {
+            render: Arc::new(render),
+        }
+    }
+}
+
+impl Default for TableRowRenderer {
+    fn default() -> Self {
+        Self::new(|_| Vec::new())
+    }
+}
+
+impl Debug for TableRowRenderer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TableRowRenderer").finish()
+    }
+}
+
+impl PartialEq for TableRowRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        // A new render function might return different cells so the table must re-render
+        Arc::ptr_eq(&self.render, &other.render)
+    }
+}
+
+/// Props used by the table widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TableProps {
+    /// The columns of the table
+    pub columns: Vec<TableColumn>,
+    /// The number of rows in the body of the table
+    pub row_count: usize,
+    /// Provides the cells of each row
+    pub render_row: TableRowRenderer,
+    /// The height of the header and of each row in pixels
+    pub row_height: f32,
+    /// If true, the header stays in place while the body scrolls
+    ///
+    /// Note that a sticky header doesn't follow the body when it's scrolled horizontally.
+    pub sticky_header: bool,
+    /// Called with the index of a column when its header is clicked
+    ///
+    /// Headers are only clickable if this is set.
+    pub on_sort: Option<Handler<usize>>,
+}
+
+impl Default for TableProps {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            row_count: 0,
+            render_row: Default::default(),
+            row_height: 24.0,
+            sticky_header: true,
+            on_sort: None,
+        }
+    }
+}
+
+impl Widget for TableProps {}
+
+///
+/// A widget that displays rows of text in aligned columns
+///
+/// Each cell is clipped to its column. Cell text isn't wrapped, so text that doesn't fit
+/// is cut off at the edge of the column. Missing cells are left empty and extra cells are ignored.
+///
+/// # Props
+///
+/// __Type:__ [`TableProps`]
+///
+/// | Common Prop | Accepted |
+/// | :---------: | :------: |
+/// | `children`  |           |
+/// | `styles`    | ✅        |
+/// | `on_event`  |           |
+/// | `on_layout` |           |
+/// | `focusable` |           |
+///
+#[derive(Bundle)]
+pub struct TableBundle {
+    pub table: TableProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for TableBundle {
+    fn default() -> Self {
+        Self {
+            table: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: TableProps::default().get_name(),
+        }
+    }
+}
+
+pub fn table_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&KStyle, &mut ComputedStyles, &TableProps)>,
+) -> bool {
+    if let Ok((styles, mut computed_styles, table)) = query.get_mut(entity) {
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        let row_styles = row_styles(table);
+        let sticky_header = table.sticky_header;
+
+        let parent_id = Some(entity);
+        rsx! {
+            <ScrollContextProviderBundle>
+                {
+                    if sticky_header {
+                        header_row(table, entity, &widget_context, &mut commands, &mut children, parent_id);
+                    }
+                }
+                <ScrollBoxBundle>
+                    {
+                        if !sticky_header {
+                            header_row(table, entity, &widget_context, &mut commands, &mut children, parent_id);
+                        }
+                    }
+                    {
+                        for row in 0..table.row_count {
+                            let mut cells = (table.render_row.render)(row);
+                            cells.resize(table.columns.len(), String::new());
+                            constructor! {
+                                <ElementBundle styles={row_styles.clone()}>
+                                    {
+                                        for cell in cells {
+                                            constructor! {
+                                                <ClipBundle styles={cell_styles()}>
+                                                    <TextWidgetBundle
+                                                        text={TextProps {
+                                                            content: cell,
+                                                            size: 14.0,
+                                                            word_wrap: false,
+                                                            ..Default::default()
+                                                        }}
+                                                    />
+                                                </ClipBundle>
+                                            }
+                                        }
+                                    }
+                                </ElementBundle>
+                            }
+                        }
+                    }
+                </ScrollBoxBundle>
+            </ScrollContextProviderBundle>
+        };
+    }
+
+    true
+}
+
+/// The styles of the header and body rows, which lay their cells out in the table's columns
+fn row_styles(table: &TableProps) -> KStyle {
+    KStyle {
+        layout_type: StyleProp::Value(LayoutType::Grid),
+        grid_rows: StyleProp::Value(vec![Units::Pixels(table.row_height)]),
+        grid_cols: StyleProp::Value(table.columns.iter().map(|column| column.width).collect()),
+        width: Units::Stretch(1.0).into(),
+        height: Units::Pixels(table.row_height).into(),
+        ..Default::default()
+    }
+}
+
+fn cell_styles() -> KStyle {
+    KStyle {
+        padding_left: Units::Pixels(5.0).into(),
+        padding_right: Units::Pixels(5.0).into(),
+        padding_top: Units::Stretch(1.0).into(),
+        padding_bottom: Units::Stretch(1.0).into(),
+        ..Default::default()
+    }
+}
+
+/// Adds the header row of a table to `children`
+fn header_row(
+    table: &TableProps,
+    table_entity: Entity,
+    widget_context: &KayakWidgetContext,
+    mut commands: &mut Commands,
+    children: &mut KChildren,
+    parent_id: Option<Entity>,
+) {
+    let header_styles = KStyle {
+        background_color: StyleProp::Value(Color::rgba(0.239, 0.258, 0.337, 1.0)),
+        ..row_styles(table)
+    };
+
+    constructor! {
+        <BackgroundBundle styles={header_styles}>
+            {
+                for (index, column) in table.columns.iter().enumerate() {
+                    let on_sort = table.on_sort.clone();
+                    let sortable = on_sort.is_some();
+                    let on_event = OnEvent::new(
+                        move |In(_entity): In<Entity>, mut commands: Commands, mut event: ResMut<KEvent>| {
+                            if let EventType::Click(..) = event.event_type {
+                                if let Some(on_sort) = &on_sort {
+                                    event.stop_propagation();
+                                    on_sort.call(&mut commands, table_entity, index);
+                                }
+                            }
+                        },
+                    );
+
+                    constructor! {
+                        <BackgroundBundle
+                            styles={KStyle {
+                                cursor: if sortable {
+                                    StyleProp::Value(KCursorIcon(CursorIcon::Pointer))
+                                } else {
+                                    StyleProp::Default
+                                },
+                                ..Default::default()
+                            }}
+                            on_event={on_event}
+                        >
+                            <ClipBundle styles={cell_styles()}>
+                                <TextWidgetBundle
+                                    text={TextProps {
+                                        content: column.label.clone(),
+                                        size: 14.0,
+                                        word_wrap: false,
+                                        ..Default::default()
+                                    }}
+                                />
+                            </ClipBundle>
+                        </BackgroundBundle>
+                    }
+                }
+            }
+        </BackgroundBundle>
+    }
+}
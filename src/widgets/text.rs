@@ -1,8 +1,11 @@
 use bevy::prelude::*;
-use kayak_font::Alignment;
+use kayak_font::{Alignment, TextDirection, TextSpan};
 
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
 use crate::{
     context::WidgetName,
+    localization::Localization,
     styles::{ComputedStyles, KCursorIcon, KStyle, RenderCommand, StyleProp},
     widget::Widget,
 };
@@ -10,7 +13,13 @@ use crate::{
 #[derive(Component, Debug, PartialEq, Clone)]
 pub struct TextProps {
     /// The string to display
+    ///
+    /// Used as the fallback when a `content_key` is set but has no string in the active language.
     pub content: String,
+    /// The key of the [`Localization`] string to display instead of `content`
+    pub content_key: Option<String>,
+    /// The values of the `{name}` placeholders in the localized string
+    pub content_args: Vec<(String, String)>,
     /// The name of the font to use
     ///
     /// The given font must already be loaded into the [`KayakContext`](kayak_core::KayakContext)
@@ -23,32 +32,67 @@ pub struct TextProps {
     pub show_cursor: bool,
     /// The font size (in pixels)
     ///
-    /// Negative values have no effect
+    /// Negative values have no effect, leaving the size to the `font_size` style.
+    ///
+    /// The size is taken from the first of these that sets it:
+    /// 1. The `font_size` of the widget's inline styles
+    /// 2. The `font_size` of its [`Theme`](crate::prelude::Theme) style
+    /// 3. This prop
+    /// 4. The `font_size` inherited from its parent
     pub size: f32,
+    /// The weight of a variable font, like `700.0` for bold
+    ///
+    /// Only used when the widget's styles don't set a `font_weight`. See [`KStyle::font_weight`]
+    /// for which fonts support it.
+    pub weight: Option<f32>,
     /// Text alignment.
     pub alignment: Alignment,
+    /// The base direction lines are laid out in.
+    ///
+    /// Mixed left-to-right and right-to-left text is reordered automatically. Right-to-left
+    /// paragraphs align to the right with [`Alignment::Start`].
+    pub direction: TextDirection,
     /// Basic word wrapping.
     /// Defautls to true
     pub word_wrap: bool,
     /// Enables subpixel rendering of text. This is useful on smaller low-dpi screens.
     pub subpixel: bool,
+    /// Runs of the content drawn smaller and raised or lowered, like superscripts and subscripts
+    ///
+    /// Span ranges are byte ranges into `content`.
+    pub spans: Vec<TextSpan>,
 }
 
 impl Default for TextProps {
     fn default() -> Self {
         Self {
             content: String::new(),
+            content_key: None,
+            content_args: Vec::new(),
             font: None,
             line_height: None,
             show_cursor: false,
             size: -1.0,
+            weight: None,
             alignment: Alignment::Start,
+            direction: TextDirection::LeftToRight,
             word_wrap: true,
             subpixel: false,
+            spans: Vec::new(),
         }
     }
 }
 
+impl TextProps {
+    /// The string to display, resolved through the given localization
+    pub fn localized_content(&self, localization: &Localization) -> String {
+        self.content_key
+            .as_ref()
+            .and_then(|key| localization.format(key, &self.content_args))
+            .unwrap_or_else(|| self.content.clone())
+    }
+}
+
 impl Widget for TextProps {}
 
 /// A widget that renders text
@@ -74,19 +118,30 @@ impl Default for TextWidgetBundle {
 
 pub fn text_render(
     In(entity): In<Entity>,
+    #[cfg(feature = "accessibility")] mut commands: Commands,
     mut query: Query<(&KStyle, &mut ComputedStyles, &TextProps)>,
+    localization: Res<Localization>,
 ) -> bool {
     if let Ok((styles, mut computed_styles, text)) = query.get_mut(entity) {
+        let content = text.localized_content(&localization);
+
+        #[cfg(feature = "accessibility")]
+        commands
+            .entity(entity)
+            .insert(AccessibilityInfo::new(Role::StaticText).with_label(content.clone()));
+
         *computed_styles = KStyle::default()
             .with_style(styles)
             .with_style(KStyle {
                 render_command: StyleProp::Value(RenderCommand::Text {
-                    content: text.content.clone(),
+                    content,
                     alignment: text.alignment,
+                    direction: text.direction,
                     word_wrap: text.word_wrap,
                     subpixel: text.subpixel,
                     text_layout: Default::default(),
                     properties: Default::default(),
+                    spans: text.spans.clone(),
                 }),
                 font: if let Some(ref font) = text.font {
                     StyleProp::Value(font.clone())
@@ -108,6 +163,11 @@ pub fn text_render(
                 } else {
                     StyleProp::Inherit
                 },
+                font_weight: if let Some(weight) = text.weight {
+                    StyleProp::Value(weight)
+                } else {
+                    StyleProp::Inherit
+                },
                 ..Default::default()
             })
             .into();
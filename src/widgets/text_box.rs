@@ -1,12 +1,19 @@
 use instant::Instant;
 
-use bevy::prelude::*;
-use kayak_font::{KayakFont, TextProperties};
+use bevy::{prelude::*, window::PrimaryWindow};
+use kayak_font::{KayakFont, TextLayout, TextProperties};
 use kayak_ui_macros::{constructor, rsx};
 
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
 use crate::{
+    clipboard::Clipboard,
     context::WidgetName,
+    disabled::set_disabled,
     event::{EventType, KEvent},
+    event_dispatcher::EventDispatcherContext,
+    focus_tree::FocusTree,
+    handler::Handler,
     on_event::OnEvent,
     on_layout::OnLayout,
     prelude::{KChildren, KayakWidgetContext, OnChange},
@@ -25,7 +32,7 @@ use super::ElementBundle;
 /// Props used by the [`TextBox`] widget
 #[derive(Component, PartialEq, Eq, Default, Debug, Clone)]
 pub struct TextBoxProps {
-    /// If true, prevents the widget from being focused (and consequently edited)
+    /// If true, prevents the widget from being focused (and consequently edited) and dims it
     pub disabled: bool,
     /// The text to display when the user input is empty
     pub placeholder: Option<String>,
@@ -34,6 +41,12 @@ pub struct TextBoxProps {
     /// This is a controlled state. You _must_ set this to the value to you wish to be displayed.
     /// You can use the [`on_change`] callback to update this prop as the user types.
     pub value: String,
+    /// If true, allows multiple lines of text to be entered
+    ///
+    /// Pressing Enter inserts a new line and the text wraps at the width of the widget.
+    pub multiline: bool,
+    /// Called when the text box loses focus
+    pub on_blur: Handler,
 }
 
 #[derive(Component, Clone, PartialEq)]
@@ -41,10 +54,47 @@ pub struct TextBoxState {
     pub focused: bool,
     pub graphemes: Vec<String>,
     pub cursor_x: f32,
+    pub cursor_y: f32,
+    /// The distance from the top of the cursor's line to its baseline
+    pub cursor_baseline: f32,
     pub cursor_position: usize,
+    /// The grapheme index where the current selection started
+    ///
+    /// The selection spans from here to the `cursor_position`. It is empty when this is
+    /// `None` or equal to the `cursor_position`.
+    pub selection_anchor: Option<usize>,
+    /// True while the user is dragging the mouse to select text
+    pub is_selecting: bool,
+    /// True if the text box accepts (and wraps) multiple lines of text
+    pub multiline: bool,
+    /// The width at which lines wrap when `multiline` is enabled
+    pub wrap_width: f32,
     pub cursor_visible: bool,
     pub cursor_last_update: Instant,
     pub current_value: String,
+    /// The text being composed by an IME
+    ///
+    /// It's shown (underlined) at the cursor but isn't part of the value until it's committed.
+    pub preedit: String,
+}
+
+impl TextBoxState {
+    /// Returns the selected grapheme range (`start..end`), if anything is selected
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.selection_anchor {
+            Some(anchor) if anchor != self.cursor_position => Some((
+                anchor.min(self.cursor_position),
+                anchor.max(self.cursor_position),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the currently selected text, if anything is selected
+    pub fn selected_text(&self) -> Option<String> {
+        self.selection()
+            .map(|(start, end)| self.graphemes[start..end].join(""))
+    }
 }
 
 impl Default for TextBoxState {
@@ -53,10 +103,17 @@ impl Default for TextBoxState {
             focused: Default::default(),
             graphemes: Default::default(),
             cursor_x: 0.0,
+            cursor_y: 0.0,
+            cursor_baseline: 0.0,
             cursor_position: Default::default(),
+            selection_anchor: None,
+            is_selecting: false,
+            multiline: false,
+            wrap_width: 10000.0,
             cursor_visible: Default::default(),
             cursor_last_update: Instant::now(),
             current_value: String::new(),
+            preedit: String::new(),
         }
     }
 }
@@ -114,11 +171,26 @@ pub fn text_box_render(
     if let Ok((styles, mut computed_styles, text_box, mut on_event, on_change)) =
         query.get_mut(entity)
     {
+        #[cfg(feature = "accessibility")]
+        {
+            let role = if text_box.multiline {
+                Role::MultilineTextInput
+            } else {
+                Role::TextInput
+            };
+            let mut info = AccessibilityInfo::new(role)
+                .with_value(text_box.value.clone())
+                .with_disabled(text_box.disabled);
+            info.label = text_box.placeholder.clone();
+            commands.entity(entity).insert(info);
+        }
+
         let state_entity = widget_context.use_state::<TextBoxState>(
             &mut commands,
             entity,
             TextBoxState {
                 current_value: text_box.value.clone(),
+                multiline: text_box.multiline,
                 ..TextBoxState::default()
             },
         );
@@ -139,11 +211,55 @@ pub fn text_box_render(
                 set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
 
                 state.cursor_position = state.graphemes.len();
+                state.selection_anchor = None;
+
+                set_new_cursor_position(&mut state, &font_assets, &font_mapping, &style_font);
+            }
+        }
+
+        // Lines wrap within the padding of the text box
+        let wrap_width = widget_context
+            .get_layout(entity)
+            .map(|layout| (layout.width - 10.0).max(0.0))
+            .unwrap_or(10000.0);
+
+        let mut is_wrap_different = false;
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            if state.multiline != text_box.multiline
+                || (text_box.multiline && state.wrap_width != wrap_width)
+            {
+                is_wrap_different = true;
+            }
+        }
 
+        if is_wrap_different {
+            if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+                state.multiline = text_box.multiline;
+                state.wrap_width = wrap_width;
                 set_new_cursor_position(&mut state, &font_assets, &font_mapping, &style_font);
             }
         }
 
+        let disabled_styles = set_disabled(&mut commands, entity, text_box.disabled);
+        if text_box.disabled {
+            // Disabled text boxes lose focus without receiving a blur event
+            if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+                if state.focused || state.is_selecting {
+                    state.focused = false;
+                    state.is_selecting = false;
+                    state.selection_anchor = None;
+                    state.preedit.clear();
+                }
+            }
+        }
+
+        let default_height = if text_box.multiline { 80.0 } else { 26.0 };
+        let inner_height = if text_box.multiline {
+            Units::Stretch(1.0)
+        } else {
+            Units::Pixels(26.0)
+        };
+
         if let Ok(state) = state_query.p0().get(state_entity) {
             *computed_styles = KStyle::default()
                 // Required styles
@@ -153,11 +269,12 @@ pub fn text_box_render(
                 })
                 // Apply any prop-given styles
                 .with_style(styles)
+                .with_style(disabled_styles)
                 // If not set by props, apply these styles
                 .with_style(KStyle {
                     top: Units::Pixels(0.0).into(),
                     bottom: Units::Pixels(0.0).into(),
-                    height: Units::Pixels(26.0).into(),
+                    height: Units::Pixels(default_height).into(),
                     // cursor: CursorIcon::Text.into(),
                     ..Default::default()
                 })
@@ -172,40 +289,263 @@ pub fn text_box_render(
                     Color::rgba(0.360, 0.380, 0.474, 1.0).into()
                 },
                 border: Edge::new(0.0, 0.0, 0.0, 2.0).into(),
-                height: Units::Pixels(26.0).into(),
+                height: inner_height.into(),
                 padding_left: Units::Pixels(5.0).into(),
                 padding_right: Units::Pixels(5.0).into(),
                 ..Default::default()
             };
 
+            let shift = if text_box.multiline {
+                0.0
+            } else if let Some(layout) = widget_context.get_layout(entity) {
+                let font_handle = match &styles.font {
+                    StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
+                    _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
+                };
+                if let Some(font) = font_assets.get(&font_handle) {
+                    let string_to_cursor = state.graphemes[0..state.cursor_position].join("");
+                    let measurement = font.measure(
+                        &string_to_cursor,
+                        TextProperties {
+                            font_size: 14.0,
+                            line_height: 18.0,
+                            max_size: (10000.0, 18.0),
+                            alignment: kayak_font::Alignment::Start,
+                            tab_size: 4,
+                            kerning: true,
+                            direction: kayak_font::TextDirection::LeftToRight,
+                            letter_spacing: 0.0,
+                        },
+                    );
+                    if measurement.size().0 > layout.width {
+                        (layout.width - measurement.size().0) - 20.0
+                    } else {
+                        0.0
+                    }
+                } else {
+                    0.0
+                }
+            } else {
+                0.0
+            };
+
+            // Scroll vertically to keep the cursor in view
+            let shift_y = if text_box.multiline {
+                if let Some(layout) = widget_context.get_layout(entity) {
+                    let visible_height = layout.height - 8.0;
+                    let cursor_bottom = state.cursor_y + 18.0;
+                    if cursor_bottom > visible_height {
+                        visible_height - cursor_bottom
+                    } else {
+                        0.0
+                    }
+                } else {
+                    0.0
+                }
+            } else {
+                0.0
+            };
+
+            // The absolute position of the start of the text (after padding and scrolling)
+            let layout = widget_context.get_layout(entity).unwrap_or_default();
+            let text_x = layout.posx + 5.0 + shift;
+            let text_y = layout.posy + 4.0 + shift_y;
+
             let cloned_on_change = on_change.clone();
+            let on_blur = text_box.on_blur.clone();
 
             *on_event = OnEvent::new(
                 move |In(_entity): In<Entity>,
                       mut event: ResMut<KEvent>,
                       font_assets: Res<Assets<KayakFont>>,
                       font_mapping: Res<FontMapping>,
-                      mut state_query: Query<&mut TextBoxState>| {
+                      clipboard: Res<Clipboard>,
+                      mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+                      mut commands: Commands,
+                      mut state_query: Query<&mut TextBoxState>,
+                      mut windows: Query<&mut Window, With<PrimaryWindow>>,
+                      focus_tree: Res<FocusTree>,
+                      text_boxes: Query<(), With<TextBoxProps>>| {
                     match event.event_type {
+                        EventType::MouseDown(cursor_event) => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
+                                let index = get_grapheme_at_position(
+                                    &state,
+                                    (
+                                        cursor_event.position.0 - text_x,
+                                        cursor_event.position.1 - text_y,
+                                    ),
+                                    &font_assets,
+                                    &font_mapping,
+                                    &style_font,
+                                );
+                                state.cursor_position = index;
+                                state.selection_anchor = Some(index);
+                                state.is_selecting = true;
+                                set_new_cursor_position(
+                                    &mut state,
+                                    &font_assets,
+                                    &font_mapping,
+                                    &style_font,
+                                );
+                                event_dispatcher_context.capture_cursor(event.current_target);
+                            }
+                        }
+                        EventType::Hover(cursor_event) => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if state.is_selecting {
+                                    state.cursor_position = get_grapheme_at_position(
+                                        &state,
+                                        (
+                                            cursor_event.position.0 - text_x,
+                                            cursor_event.position.1 - text_y,
+                                        ),
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                    set_new_cursor_position(
+                                        &mut state,
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                }
+                            }
+                        }
+                        EventType::MouseUp(..) => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if state.is_selecting {
+                                    state.is_selecting = false;
+                                    event_dispatcher_context.release_cursor(event.current_target);
+                                }
+                            }
+                        }
                         EventType::KeyDown(key_event) => {
-                            if key_event.key() == KeyCode::ArrowRight {
+                            // Keys are handled by the IME while it's composing
+                            if state_query
+                                .get(state_entity)
+                                .is_ok_and(|state| !state.preedit.is_empty())
+                            {
+                                return;
+                            }
+                            if key_event.is_ctrl_pressed() || key_event.is_meta_pressed() {
                                 if let Ok(mut state) = state_query.get_mut(state_entity) {
-                                    if state.cursor_position < state.graphemes.len() {
-                                        state.cursor_position += 1;
-                                    }
+                                    let previous_value = state.current_value.clone();
+                                    let is_changed = match key_event.key() {
+                                        KeyCode::KeyA => {
+                                            state.selection_anchor = Some(0);
+                                            state.cursor_position = state.graphemes.len();
+                                            false
+                                        }
+                                        KeyCode::KeyC => {
+                                            if let Some(text) = state.selected_text() {
+                                                clipboard.set_text(text);
+                                            }
+                                            false
+                                        }
+                                        KeyCode::KeyX => {
+                                            if let Some(text) = state.selected_text() {
+                                                clipboard.set_text(text);
+                                            }
+                                            delete_selection(&mut state)
+                                        }
+                                        KeyCode::KeyV => {
+                                            if let Some(text) = clipboard.get_text() {
+                                                // Pasting replaces the selection (if any)
+                                                delete_selection(&mut state);
+                                                insert_text(
+                                                    &mut state,
+                                                    &text,
+                                                    &font_assets,
+                                                    &font_mapping,
+                                                    &style_font,
+                                                );
+                                                true
+                                            } else {
+                                                false
+                                            }
+                                        }
+                                        _ => false,
+                                    };
+
                                     set_new_cursor_position(
                                         &mut state,
                                         &font_assets,
                                         &font_mapping,
                                         &style_font,
                                     );
+
+                                    if is_changed {
+                                        let cloned_on_change = cloned_on_change.clone();
+                                        cloned_on_change
+                                            .set_value(previous_value, state.current_value.clone());
+                                        event.add_system(cloned_on_change);
+                                    }
                                 }
+                                return;
                             }
-                            if key_event.key() == KeyCode::ArrowLeft {
-                                if let Ok(mut state) = state_query.get_mut(state_entity) {
-                                    if state.cursor_position > 0 {
-                                        state.cursor_position -= 1;
+                            let key = key_event.key();
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                let is_horizontal = matches!(
+                                    key,
+                                    KeyCode::ArrowLeft
+                                        | KeyCode::ArrowRight
+                                        | KeyCode::Home
+                                        | KeyCode::End
+                                );
+                                let is_vertical =
+                                    matches!(key, KeyCode::ArrowUp | KeyCode::ArrowDown);
+                                if is_horizontal || (is_vertical && state.multiline) {
+                                    // Keep a surrounding scroll box from scrolling as well
+                                    event.prevent_default();
+                                    let is_shift_pressed = key_event.is_shift_pressed();
+                                    let selection = state.selection();
+                                    if is_shift_pressed {
+                                        if state.selection_anchor.is_none() {
+                                            state.selection_anchor = Some(state.cursor_position);
+                                        }
+                                    } else {
+                                        state.selection_anchor = None;
                                     }
+
+                                    state.cursor_position = match (key, selection) {
+                                        // Collapse an existing selection to the side being moved towards
+                                        (KeyCode::ArrowLeft, Some((start, _)))
+                                            if !is_shift_pressed =>
+                                        {
+                                            start
+                                        }
+                                        (KeyCode::ArrowRight, Some((_, end)))
+                                            if !is_shift_pressed =>
+                                        {
+                                            end
+                                        }
+                                        (KeyCode::ArrowLeft, _) => {
+                                            state.cursor_position.saturating_sub(1)
+                                        }
+                                        (KeyCode::ArrowRight, _) => {
+                                            (state.cursor_position + 1).min(state.graphemes.len())
+                                        }
+                                        (KeyCode::ArrowUp, _) => get_vertical_cursor_position(
+                                            &state,
+                                            -1,
+                                            &font_assets,
+                                            &font_mapping,
+                                            &style_font,
+                                        ),
+                                        (KeyCode::ArrowDown, _) => get_vertical_cursor_position(
+                                            &state,
+                                            1,
+                                            &font_assets,
+                                            &font_mapping,
+                                            &style_font,
+                                        ),
+                                        (KeyCode::Home, _) => 0,
+                                        _ => state.graphemes.len(),
+                                    };
+
                                     set_new_cursor_position(
                                         &mut state,
                                         &font_assets,
@@ -218,34 +558,49 @@ pub fn text_box_render(
                         EventType::CharInput { ref c } => {
                             if let Ok(mut state) = state_query.get_mut(state_entity) {
                                 let cloned_on_change = cloned_on_change.clone();
-                                if !state.focused {
+                                if !state.focused || !state.preedit.is_empty() {
                                     return;
                                 }
-                                let cursor_pos = state.cursor_position;
+                                let previous_value = state.current_value.clone();
+                                // Typing replaces the selection (if any)
+                                let mut had_selection = delete_selection(&mut state);
+                                // Track the cursor as a byte offset since typed characters may
+                                // merge with the grapheme cluster before them (e.g. combining marks)
+                                let mut byte_pos =
+                                    grapheme_byte_offset(&state.graphemes, state.cursor_position);
                                 for c in c.chars() {
                                     if is_backspace(c) {
-                                        if !state.current_value.is_empty() {
-                                            let char_pos: usize = state.graphemes
-                                                [0..cursor_pos - 1]
-                                                .iter()
-                                                .map(|g| g.len())
-                                                .sum();
-                                            state.current_value.remove(char_pos);
-                                            state.cursor_position -= 1;
+                                        if had_selection {
+                                            // The selection was already removed
+                                            had_selection = false;
+                                        } else if byte_pos > 0 {
+                                            // Remove the whole cluster before the cursor
+                                            let index =
+                                                grapheme_index_at(&state.graphemes, byte_pos);
+                                            let start =
+                                                grapheme_byte_offset(&state.graphemes, index - 1);
+                                            state.current_value.replace_range(start..byte_pos, "");
+                                            byte_pos = start;
                                         }
-                                    } else if !c.is_control() {
-                                        let char_pos: usize = state.graphemes[0..cursor_pos]
-                                            .iter()
-                                            .map(|g| g.len())
-                                            .sum();
-                                        state.current_value.insert(char_pos, c);
-
-                                        state.cursor_position += 1;
+                                    } else if !c.is_control()
+                                        || (state.multiline && (c == '\r' || c == '\n'))
+                                    {
+                                        // Enter inserts a new line in multiline mode
+                                        let c = if c == '\r' { '\n' } else { c };
+                                        state.current_value.insert(byte_pos, c);
+                                        byte_pos += c.len_utf8();
                                     }
-                                }
 
-                                // Update graphemes
-                                set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
+                                    // Update graphemes
+                                    set_graphemes(
+                                        &mut state,
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                }
+                                state.cursor_position =
+                                    grapheme_index_at(&state.graphemes, byte_pos);
 
                                 set_new_cursor_position(
                                     &mut state,
@@ -253,17 +608,69 @@ pub fn text_box_render(
                                     &font_mapping,
                                     &style_font,
                                 );
-                                cloned_on_change.set_value(state.current_value.clone());
+                                cloned_on_change
+                                    .set_value(previous_value, state.current_value.clone());
+                                event.add_system(cloned_on_change);
+                            }
+                        }
+                        EventType::ImePreedit { ref text, .. } => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if state.focused {
+                                    state.preedit = text.clone();
+                                    if let Ok(mut window) = windows.get_single_mut() {
+                                        window.ime_position = Vec2::new(
+                                            text_x + state.cursor_x,
+                                            text_y + state.cursor_y + 18.0,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        EventType::ImeCommit { ref text } => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if !state.focused {
+                                    return;
+                                }
+                                state.preedit.clear();
+                                let previous_value = state.current_value.clone();
+                                // Committed text replaces the selection (if any)
+                                delete_selection(&mut state);
+                                insert_text(
+                                    &mut state,
+                                    text,
+                                    &font_assets,
+                                    &font_mapping,
+                                    &style_font,
+                                );
+                                set_new_cursor_position(
+                                    &mut state,
+                                    &font_assets,
+                                    &font_mapping,
+                                    &style_font,
+                                );
+
+                                let cloned_on_change = cloned_on_change.clone();
+                                cloned_on_change
+                                    .set_value(previous_value, state.current_value.clone());
                                 event.add_system(cloned_on_change);
                             }
                         }
                         EventType::Focus => {
+                            // Let the user type with an IME (required for CJK input)
+                            if let Ok(mut window) = windows.get_single_mut() {
+                                window.ime_enabled = true;
+                            }
                             if let Ok(mut state) = state_query.get_mut(state_entity) {
                                 state.focused = true;
+                                if state.is_selecting {
+                                    // Focused by clicking -> keep the clicked cursor position
+                                    return;
+                                }
                                 // Update graphemes
                                 set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
 
                                 state.cursor_position = state.graphemes.len();
+                                state.selection_anchor = None;
 
                                 set_new_cursor_position(
                                     &mut state,
@@ -274,90 +681,146 @@ pub fn text_box_render(
                             }
                         }
                         EventType::Blur => {
+                            // Losing focus cancels any ongoing IME composition. The newly focused
+                            // widget has already received its focus event, so leave the IME
+                            // enabled if it's another text box.
+                            let focused_text_box = focus_tree
+                                .current()
+                                .is_some_and(|focused| text_boxes.contains(focused));
+                            if !focused_text_box {
+                                if let Ok(mut window) = windows.get_single_mut() {
+                                    window.ime_enabled = false;
+                                }
+                            }
                             if let Ok(mut state) = state_query.get_mut(state_entity) {
                                 state.focused = false;
+                                state.selection_anchor = None;
+                                state.preedit.clear();
                             }
+                            on_blur.call(&mut commands, entity, ());
                         }
                         _ => {}
                     }
                 },
             );
 
-            let cursor_styles = KStyle {
+            // The caret is placed after the text being composed by an IME (if any)
+            let preedit_width = get_preedit_width(state, &font_assets, &font_mapping, &styles.font);
+            let preedit_styles = KStyle {
                 background_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
                 position_type: KPositionType::SelfDirected.into(),
-                top: Units::Pixels(5.0).into(),
+                top: Units::Pixels(5.0 + state.cursor_y + state.cursor_baseline + 2.0).into(),
                 left: Units::Pixels(state.cursor_x).into(),
-                width: Units::Pixels(2.0).into(),
-                height: Units::Pixels(26.0 - 10.0).into(),
+                width: Units::Pixels(preedit_width).into(),
+                height: Units::Pixels(1.0).into(),
                 ..Default::default()
             };
+            let content = if state.preedit.is_empty() {
+                text_box.value.clone()
+            } else {
+                value_with_preedit(state)
+            };
 
-            let text_styles = KStyle {
-                top: Units::Stretch(1.0).into(),
-                bottom: Units::Stretch(1.0).into(),
+            let cursor_styles = KStyle {
+                background_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
+                position_type: KPositionType::SelfDirected.into(),
+                top: Units::Pixels(5.0 + state.cursor_y).into(),
+                left: Units::Pixels(state.cursor_x + preedit_width).into(),
+                width: Units::Pixels(2.0).into(),
+                height: Units::Pixels(26.0 - 10.0).into(),
                 ..Default::default()
             };
 
-            let shift = if let Some(layout) = widget_context.get_layout(entity) {
-                let font_handle = match &styles.font {
-                    StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
-                    _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
-                };
-                if let Some(font) = font_assets.get(&font_handle) {
-                    let string_to_cursor = state.graphemes[0..state.cursor_position].join("");
-                    let measurement = font.measure(
-                        &string_to_cursor,
-                        TextProperties {
-                            font_size: 14.0,
-                            line_height: 18.0,
-                            max_size: (10000.0, 18.0),
-                            alignment: kayak_font::Alignment::Start,
-                            tab_size: 4,
-                        },
-                    );
-                    if measurement.size().0 > layout.width {
-                        (layout.width - measurement.size().0) - 20.0
-                    } else {
-                        0.0
-                    }
-                } else {
-                    0.0
+            let text_styles = if text_box.multiline {
+                KStyle {
+                    top: Units::Pixels(4.0).into(),
+                    ..Default::default()
                 }
             } else {
-                0.0
+                KStyle {
+                    top: Units::Stretch(1.0).into(),
+                    bottom: Units::Stretch(1.0).into(),
+                    ..Default::default()
+                }
             };
 
-            let scroll_styles = KStyle {
-                position_type: KPositionType::SelfDirected.into(),
-                padding_left: StyleProp::Value(Units::Stretch(0.0)),
-                padding_right: StyleProp::Value(Units::Stretch(0.0)),
-                padding_bottom: StyleProp::Value(Units::Stretch(1.0)),
-                padding_top: StyleProp::Value(Units::Stretch(1.0)),
-                left: Units::Pixels(shift).into(),
-                ..Default::default()
+            let selection_styles = state
+                .selection()
+                .map(|(start, end)| {
+                    get_selection_rects(
+                        state,
+                        start,
+                        end,
+                        &font_assets,
+                        &font_mapping,
+                        &styles.font,
+                    )
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(x, y, width)| KStyle {
+                    background_color: Color::rgba(0.933, 0.745, 0.745, 0.35).into(),
+                    position_type: KPositionType::SelfDirected.into(),
+                    top: Units::Pixels(5.0 + y).into(),
+                    left: Units::Pixels(x).into(),
+                    width: Units::Pixels(width).into(),
+                    height: Units::Pixels(26.0 - 10.0).into(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            let scroll_styles = if text_box.multiline {
+                KStyle {
+                    position_type: KPositionType::SelfDirected.into(),
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Auto.into(),
+                    top: Units::Pixels(shift_y).into(),
+                    ..Default::default()
+                }
+            } else {
+                KStyle {
+                    position_type: KPositionType::SelfDirected.into(),
+                    padding_left: StyleProp::Value(Units::Stretch(0.0)),
+                    padding_right: StyleProp::Value(Units::Stretch(0.0)),
+                    padding_bottom: StyleProp::Value(Units::Stretch(1.0)),
+                    padding_top: StyleProp::Value(Units::Stretch(1.0)),
+                    left: Units::Pixels(shift).into(),
+                    ..Default::default()
+                }
             };
 
             let parent_id = Some(entity);
             rsx! {
                 <BackgroundBundle id={"background_event"} styles={background_styles}>
                     <ClipBundle styles={KStyle {
-                        height: Units::Pixels(26.0).into(),
+                        height: inner_height.into(),
                         padding_left: StyleProp::Value(Units::Stretch(0.0)),
                         padding_right: StyleProp::Value(Units::Stretch(0.0)),
                         ..Default::default()
                     }}>
                         <ElementBundle styles={scroll_styles}>
+                            {selection_styles.into_iter().for_each(|selection_styles| {
+                                constructor! {
+                                    <BackgroundBundle styles={selection_styles} />
+                                }
+                            })}
                             <TextWidgetBundle
                                 styles={text_styles}
                                 text={TextProps {
-                                    content: text_box.value.clone(),
+                                    content,
                                     size: 14.0,
                                     line_height: Some(18.0),
-                                    word_wrap: false,
+                                    word_wrap: text_box.multiline,
                                     ..Default::default()
                                 }}
                             />
+                            {
+                                if !state.preedit.is_empty() {
+                                    constructor! {
+                                        <BackgroundBundle styles={preedit_styles} />
+                                    }
+                                }
+                            }
                             {
                                 if state.focused && state.cursor_visible {
                                     constructor! {
@@ -402,6 +865,102 @@ fn set_graphemes(
     }
 }
 
+/// Returns the byte offset of the grapheme at the given index
+fn grapheme_byte_offset(graphemes: &[String], index: usize) -> usize {
+    graphemes[0..index].iter().map(|g| g.len()).sum()
+}
+
+/// Returns the index of the first grapheme starting at or after the given byte offset
+fn grapheme_index_at(graphemes: &[String], byte_pos: usize) -> usize {
+    let mut offset = 0;
+    graphemes
+        .iter()
+        .position(|g| {
+            let is_end = offset >= byte_pos;
+            offset += g.len();
+            is_end
+        })
+        .unwrap_or(graphemes.len())
+}
+
+/// Returns the value of the text box with the text being composed by an IME inserted at the cursor
+fn value_with_preedit(state: &TextBoxState) -> String {
+    let mut value = state.current_value.clone();
+    let char_pos = grapheme_byte_offset(&state.graphemes, state.cursor_position);
+    value.insert_str(char_pos, &state.preedit);
+    value
+}
+
+/// Measures the width of the text being composed by an IME
+fn get_preedit_width(
+    state: &TextBoxState,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> f32 {
+    if state.preedit.is_empty() {
+        return 0.0;
+    }
+
+    let font_handle = match style_font {
+        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
+        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
+    };
+
+    if let Some(font) = font_assets.get(&font_handle) {
+        let measurement = font.measure(
+            &state.preedit,
+            TextProperties {
+                max_size: (10000.0, 18.0),
+                ..text_properties(state)
+            },
+        );
+        return measurement.size().0;
+    }
+
+    0.0
+}
+
+/// Removes the selected text (if any), returning true if anything was removed
+fn delete_selection(state: &mut TextBoxState) -> bool {
+    let selection = state.selection();
+    state.selection_anchor = None;
+
+    if let Some((start, end)) = selection {
+        let start_pos = grapheme_byte_offset(&state.graphemes, start);
+        let end_pos = grapheme_byte_offset(&state.graphemes, end);
+        state.current_value.replace_range(start_pos..end_pos, "");
+        state.graphemes.drain(start..end);
+        state.cursor_position = start;
+        return true;
+    }
+
+    false
+}
+
+/// Inserts the given text at the cursor, moving the cursor to the end of it
+///
+/// Control characters are stripped, including line breaks unless the text box is multiline.
+fn insert_text(
+    state: &mut TextBoxState,
+    text: &str,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) {
+    let text = text
+        .replace("\r\n", "\n")
+        .chars()
+        .filter(|c| !c.is_control() || (state.multiline && *c == '\n'))
+        .collect::<String>();
+    let char_pos = grapheme_byte_offset(&state.graphemes, state.cursor_position);
+    state.current_value.insert_str(char_pos, &text);
+
+    set_graphemes(state, font_assets, font_mapping, style_font);
+
+    state.cursor_position = grapheme_index_at(&state.graphemes, char_pos + text.len());
+}
+
 fn get_single_grapheme_length(
     font_assets: &Res<Assets<KayakFont>>,
     font_mapping: &FontMapping,
@@ -427,25 +986,189 @@ fn set_new_cursor_position(
     font_mapping: &FontMapping,
     style_font: &StyleProp<String>,
 ) {
+    if state.multiline {
+        if let Some(layout) = measure_text(state, font_assets, font_mapping, style_font) {
+            (state.cursor_x, state.cursor_y) =
+                get_caret_position(&layout, &state.graphemes, state.cursor_position);
+        }
+    } else {
+        state.cursor_x = get_text_width(
+            state,
+            state.cursor_position,
+            font_assets,
+            font_mapping,
+            style_font,
+        );
+        state.cursor_y = 0.0;
+    }
+
+    if let Some(layout) = measure_text(state, font_assets, font_mapping, style_font) {
+        // Text boxes don't use spans, so every line's baseline is the same distance from its top
+        state.cursor_baseline = layout
+            .lines()
+            .first()
+            .map(|line| line.baseline())
+            .unwrap_or_default();
+    }
+}
+
+/// Measures the full text content, wrapping lines if the text box is multiline
+fn measure_text(
+    state: &TextBoxState,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> Option<TextLayout> {
+    let font_handle = match style_font {
+        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
+        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
+    };
+
+    font_assets
+        .get(&font_handle)
+        .map(|font| font.measure(&state.current_value, text_properties(state)))
+}
+
+/// Returns the position of the caret placed before the grapheme at the given index
+fn get_caret_position(layout: &TextLayout, graphemes: &[String], index: usize) -> (f32, f32) {
+    if let Some(row_col) = layout.find_grapheme(index) {
+        return row_col.grapheme.position;
+    }
+
+    // The caret is at the very end of the text
+    if let Some(row_col) = index.checked_sub(1).and_then(|i| layout.find_grapheme(i)) {
+        let (x, y) = row_col.grapheme.position;
+        if graphemes.last().map(|g| g == "\n").unwrap_or_default() {
+            return (0.0, y + layout.properties().line_height);
+        }
+        return (x + row_col.grapheme.size.0, y);
+    }
+
+    (0.0, 0.0)
+}
+
+/// Finds the cursor position on the line above (negative) or below (positive) the cursor
+fn get_vertical_cursor_position(
+    state: &TextBoxState,
+    lines: i32,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> usize {
+    if let Some(layout) = measure_text(state, font_assets, font_mapping, style_font) {
+        let line_height = layout.properties().line_height;
+        let target_y = state.cursor_y + line_height * (lines as f32 + 0.5);
+        if target_y < 0.0 {
+            return 0;
+        }
+        if target_y >= line_height * layout.total_lines() as f32 {
+            return state.graphemes.len();
+        }
+        return layout
+            .grapheme_at_position((state.cursor_x, target_y))
+            .min(state.graphemes.len());
+    }
+
+    state.cursor_position
+}
+
+/// Returns the `(x, y, width)` of each line's selection highlight
+fn get_selection_rects(
+    state: &TextBoxState,
+    start: usize,
+    end: usize,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> Vec<(f32, f32, f32)> {
+    if !state.multiline {
+        let start_x = get_text_width(state, start, font_assets, font_mapping, style_font);
+        let end_x = get_text_width(state, end, font_assets, font_mapping, style_font);
+        return vec![(start_x, 0.0, end_x - start_x)];
+    }
+
+    let mut rects = Vec::new();
+    if let Some(layout) = measure_text(state, font_assets, font_mapping, style_font) {
+        for line in layout.lines() {
+            let line_start = line.grapheme_index();
+            let line_end = line_start + line.total_graphemes();
+            let (first, last) = (start.max(line_start), end.min(line_end));
+            if first >= last {
+                continue;
+            }
+
+            let first = line.grapheme(first - line_start);
+            let last = line.grapheme(last - 1 - line_start);
+            let width = last.position.0 + last.size.0 - first.position.0;
+            rects.push((first.position.0, first.position.1, width));
+        }
+    }
+
+    rects
+}
+
+/// Measures the width of the text up to the given grapheme index
+fn get_text_width(
+    state: &TextBoxState,
+    index: usize,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> f32 {
     let font_handle = match style_font {
         StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
         _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
     };
 
     if let Some(font) = font_assets.get(&font_handle) {
-        let string_to_cursor = state.graphemes[0..state.cursor_position].join("");
-        let measurement = font.measure(
-            &string_to_cursor,
-            TextProperties {
-                font_size: 14.0,
-                line_height: 18.0,
-                max_size: (10000.0, 18.0),
-                alignment: kayak_font::Alignment::Start,
-                tab_size: 4,
-            },
-        );
+        let string_to_index = state.graphemes[0..index].join("");
+        let measurement = font.measure(&string_to_index, text_properties(state));
+
+        return measurement.size().0;
+    }
+
+    0.0
+}
+
+/// Finds the cursor position (as a grapheme index) closest to the given position
+///
+/// The position should be relative to the start of the text.
+fn get_grapheme_at_position(
+    state: &TextBoxState,
+    position: (f32, f32),
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> usize {
+    let font_handle = match style_font {
+        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
+        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
+    };
+
+    if let Some(font) = font_assets.get(&font_handle) {
+        let measurement = font.measure(&state.current_value, text_properties(state));
+        return measurement
+            .grapheme_at_position(position)
+            .min(state.graphemes.len());
+    }
+
+    state.cursor_position
+}
 
-        state.cursor_x = measurement.size().0;
+fn text_properties(state: &TextBoxState) -> TextProperties {
+    TextProperties {
+        font_size: 14.0,
+        line_height: 18.0,
+        max_size: if state.multiline {
+            (state.wrap_width, 100000.0)
+        } else {
+            (10000.0, 18.0)
+        },
+        alignment: kayak_font::Alignment::Start,
+        tab_size: 4,
+        kerning: true,
+        direction: kayak_font::TextDirection::LeftToRight,
+        letter_spacing: 0.0,
     }
 }
 
@@ -1,22 +1,28 @@
 use bevy::{
-    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res, ResMut, Vec2},
+    prelude::{
+        Bundle, Camera, Color, Commands, Component, Entity, In, Query, Rect, Res, ResMut, Vec2,
+    },
     window::CursorIcon,
 };
-use kayak_ui_macros::rsx;
+use kayak_ui_macros::{constructor, rsx};
 
+#[cfg(feature = "accessibility")]
+use crate::prelude::{AccessibilityInfo, Role};
 use crate::{
     children::KChildren,
     context::WidgetName,
+    drag::Draggable,
     event::{EventType, KEvent},
     event_dispatcher::EventDispatcherContext,
+    handler::Handler,
     on_event::OnEvent,
     prelude::KayakWidgetContext,
     styles::{
-        BoxShadow, ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, RenderCommand,
-        StyleProp, Units,
+        BoxShadow, ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, LayoutType,
+        RenderCommand, StyleProp, Units,
     },
     widget::Widget,
-    Focusable,
+    Focusable, KUiScale, WindowSize,
 };
 
 use super::{
@@ -34,7 +40,36 @@ pub struct KWindow {
     /// The initial position at which to display the window in pixels
     pub initial_position: Vec2,
     /// The size of the window in pixels
+    ///
+    /// For resizable windows, this is only the initial size.
     pub size: Vec2,
+    /// If true, allows the window to be resized by dragging its edges and corners
+    pub resizable: bool,
+    /// The minimum width of a resizable window in pixels
+    pub min_width: f32,
+    /// The minimum height of a resizable window in pixels
+    pub min_height: f32,
+    /// The maximum width of a resizable window in pixels
+    pub max_width: Option<f32>,
+    /// The maximum height of a resizable window in pixels
+    pub max_height: Option<f32>,
+    /// If true, shows a title bar button that calls `on_minimize`
+    pub minimizable: bool,
+    /// If true, shows a title bar button that maximizes and restores the window
+    pub maximizable: bool,
+    /// If true, shows a title bar button that calls `on_close`
+    pub closable: bool,
+    /// If true, dragging the title bar to the left or right edge of the screen snaps the window
+    /// to that half of the screen, and dragging it to the top edge maximizes it
+    ///
+    /// This assumes the window's parent covers the whole screen.
+    pub snap_to_edges: bool,
+    /// Called when the minimize button is clicked
+    pub on_minimize: Handler,
+    /// Called when the window is maximized (`true`) or restored (`false`)
+    pub on_maximize: Handler<bool>,
+    /// Called when the close button is clicked
+    pub on_close: Handler,
     /// The text to display in the window's title bar
     pub title: String,
     /// Styles for the main window quad.
@@ -48,11 +83,167 @@ pub struct KWindowState {
     pub is_dragging: bool,
     pub offset: Vec2,
     pub position: Vec2,
+    /// The current size of a resizable or snapped window in pixels
+    pub size: Vec2,
     pub focused: bool,
+    /// If true, the window fills its parent
+    pub maximized: bool,
+    /// The position and size to return to when a maximized or snapped window is restored
+    pub restore_rect: Option<Rect>,
+}
+
+impl KWindowState {
+    /// Makes the window fill its parent, remembering its current position and size
+    pub fn maximize(&mut self) {
+        self.save_rect();
+        self.maximized = true;
+    }
+
+    /// Returns a maximized or snapped window to its previous position and size
+    pub fn restore(&mut self) {
+        if let Some(rect) = self.restore_rect.take() {
+            self.position = rect.min;
+            self.size = rect.size();
+        }
+        self.maximized = false;
+    }
+
+    /// Moves and resizes the window, remembering its current position and size
+    fn snap(&mut self, position: Vec2, size: Vec2) {
+        self.save_rect();
+        self.position = position;
+        self.size = size;
+    }
+
+    fn save_rect(&mut self) {
+        // Keep the original rect when going from snapped to maximized
+        if self.restore_rect.is_none() {
+            self.restore_rect = Some(Rect::from_corners(self.position, self.position + self.size));
+        }
+    }
 }
 
 impl Widget for KWindow {}
 
+impl KWindow {
+    /// Clamps a size to the minimum and maximum size of the window
+    fn clamp_size(&self, size: Vec2) -> Vec2 {
+        let max_width = self.max_width.unwrap_or(f32::INFINITY).max(self.min_width);
+        let max_height = self
+            .max_height
+            .unwrap_or(f32::INFINITY)
+            .max(self.min_height);
+        Vec2::new(
+            size.x.clamp(self.min_width, max_width),
+            size.y.clamp(self.min_height, max_height),
+        )
+    }
+}
+
+/// How close (in pixels) the cursor must be to an edge of the screen to snap a window to it
+const SNAP_DISTANCE: f32 = 8.0;
+
+/// The diameter of the title bar buttons in pixels
+const TITLE_BAR_BUTTON_SIZE: f32 = 12.0;
+
+fn title_bar_button_styles(color: Color) -> KStyle {
+    KStyle {
+        render_command: StyleProp::Value(RenderCommand::Quad),
+        background_color: StyleProp::Value(color),
+        border_radius: StyleProp::Value(Corner::all(TITLE_BAR_BUTTON_SIZE / 2.0)),
+        width: StyleProp::Value(Units::Pixels(TITLE_BAR_BUTTON_SIZE)),
+        height: StyleProp::Value(Units::Pixels(TITLE_BAR_BUTTON_SIZE)),
+        ..Default::default()
+    }
+}
+
+/// The thickness of the invisible handles used to resize a window
+const RESIZE_HANDLE_THICKNESS: f32 = 6.0;
+
+/// An edge or corner of a window that can be dragged to resize it
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ResizeHandle {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ResizeHandle {
+    const ALL: [ResizeHandle; 8] = [
+        Self::Top,
+        Self::Bottom,
+        Self::Left,
+        Self::Right,
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+
+    /// The direction each axis grows in when the handle is dragged
+    /// (-1 for the left/top edges, 1 for the right/bottom edges, 0 if the axis isn't resized)
+    fn directions(&self) -> Vec2 {
+        match self {
+            Self::Top => Vec2::new(0.0, -1.0),
+            Self::Bottom => Vec2::new(0.0, 1.0),
+            Self::Left => Vec2::new(-1.0, 0.0),
+            Self::Right => Vec2::new(1.0, 0.0),
+            Self::TopLeft => Vec2::new(-1.0, -1.0),
+            Self::TopRight => Vec2::new(1.0, -1.0),
+            Self::BottomLeft => Vec2::new(-1.0, 1.0),
+            Self::BottomRight => Vec2::new(1.0, 1.0),
+        }
+    }
+
+    fn cursor(&self) -> CursorIcon {
+        match self {
+            Self::Top | Self::Bottom => CursorIcon::NsResize,
+            Self::Left | Self::Right => CursorIcon::EwResize,
+            Self::TopLeft | Self::BottomRight => CursorIcon::NwseResize,
+            Self::TopRight | Self::BottomLeft => CursorIcon::NeswResize,
+        }
+    }
+
+    /// The styles placing the handle along the edge of a window of the given size
+    fn styles(&self, size: Vec2) -> KStyle {
+        let directions = self.directions();
+        let half = RESIZE_HANDLE_THICKNESS / 2.0;
+        // Edges span the window between the corners, corners are squares
+        let place = |direction: f32, length: f32| match direction {
+            d if d < 0.0 => (-half, RESIZE_HANDLE_THICKNESS),
+            d if d > 0.0 => (length - half, RESIZE_HANDLE_THICKNESS),
+            _ => (half, (length - RESIZE_HANDLE_THICKNESS).max(0.0)),
+        };
+        let (left, width) = place(directions.x, size.x);
+        let (top, height) = place(directions.y, size.y);
+
+        KStyle {
+            cursor: StyleProp::Value(KCursorIcon(self.cursor())),
+            position_type: StyleProp::Value(KPositionType::SelfDirected),
+            left: StyleProp::Value(Units::Pixels(left)),
+            top: StyleProp::Value(Units::Pixels(top)),
+            width: StyleProp::Value(Units::Pixels(width)),
+            height: StyleProp::Value(Units::Pixels(height)),
+            ..Default::default()
+        }
+    }
+}
+
+/// The rect of a window and the cursor position when a resize started
+///
+/// This is attached to the drag as its payload.
+#[derive(Clone, Copy, Debug)]
+struct ResizeOrigin {
+    position: Vec2,
+    size: Vec2,
+    pointer: Vec2,
+}
+
 /// Default window widget
 /// A simple widget that renders a window.
 /// Does not support much customization.
@@ -88,6 +279,11 @@ pub fn window_render(
     if let Ok((window_style, mut computed_styles, window_children, window)) =
         query.get_mut(window_entity)
     {
+        #[cfg(feature = "accessibility")]
+        commands
+            .entity(window_entity)
+            .insert(AccessibilityInfo::new(Role::Window).with_label(window.title.clone()));
+
         let possible_context_entity =
             widget_context.get_context_entity::<WindowContext>(window_entity);
         let z_index = if let Some(window_context_entity) = possible_context_entity {
@@ -121,13 +317,37 @@ pub fn window_render(
             KWindowState {
                 position: window.initial_position,
                 offset: Vec2::ZERO,
+                size: window.size,
                 is_dragging: false,
                 focused: false,
+                maximized: false,
+                restore_rect: None,
             },
         );
 
         if let Ok(state) = state_query.get(state_entity) {
             let parent_id = Some(window_entity);
+            let size = if window.resizable || state.restore_rect.is_some() {
+                state.size
+            } else {
+                window.size
+            };
+            // A maximized window fills its parent
+            let (left, top, width, height) = if state.maximized {
+                (
+                    Units::Pixels(0.0),
+                    Units::Pixels(0.0),
+                    Units::Percentage(100.0),
+                    Units::Percentage(100.0),
+                )
+            } else {
+                (
+                    Units::Pixels(state.position.x),
+                    Units::Pixels(state.position.y),
+                    Units::Pixels(size.x),
+                    Units::Pixels(size.y),
+                )
+            };
 
             let focus_event = OnEvent::new(
                 move |In(_entity): In<Entity>,
@@ -158,6 +378,133 @@ pub fn window_render(
                 },
             );
 
+            let snap_to_edges = window.snap_to_edges;
+            let on_maximize = window.on_maximize.clone();
+            let camera_entity = widget_context.camera_entity;
+            let title_bar_event = OnEvent::new(
+                move |In(entity): In<Entity>,
+                      mut commands: Commands,
+                      mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+                      mut event: ResMut<KEvent>,
+                      window_size: Res<WindowSize>,
+                      ui_scale: Res<KUiScale>,
+                      cameras: Query<&Camera>,
+                      mut query: Query<&mut KWindowState>| {
+                    if let Ok(mut window) = query.get_mut(state_entity) {
+                        event.prevent_default();
+                        event.stop_propagation();
+                        match event.event_type {
+                            EventType::MouseDown(data) if !window.maximized => {
+                                let pointer = Vec2::from(data.position);
+                                if window.restore_rect.is_some() {
+                                    // Dragging a snapped window restores its size, keeping the cursor
+                                    // over the same part of the title bar
+                                    let relative_x =
+                                        (pointer.x - window.position.x) / window.size.x.max(1.0);
+                                    let top = window.position.y;
+                                    window.restore();
+                                    window.position =
+                                        Vec2::new(pointer.x - relative_x * window.size.x, top);
+                                }
+
+                                event_dispatcher_context.capture_cursor(entity);
+                                window.is_dragging = true;
+                                window.offset = window.position - pointer;
+                            }
+                            EventType::MouseUp(data) => {
+                                event_dispatcher_context.release_cursor(entity);
+                                if window.is_dragging && snap_to_edges {
+                                    let WindowSize(screen_width, screen_height) = camera_entity
+                                        .and_then(|camera_entity| cameras.get(camera_entity).ok())
+                                        .and_then(|camera| WindowSize::of_camera(camera, *ui_scale))
+                                        .unwrap_or(*window_size);
+                                    let (x, y) = data.position;
+                                    if y <= SNAP_DISTANCE {
+                                        window.maximize();
+                                        on_maximize.call(&mut commands, window_entity, true);
+                                    } else if x <= SNAP_DISTANCE {
+                                        window.snap(
+                                            Vec2::ZERO,
+                                            Vec2::new(screen_width / 2.0, screen_height),
+                                        );
+                                    } else if x >= screen_width - SNAP_DISTANCE {
+                                        window.snap(
+                                            Vec2::new(screen_width / 2.0, 0.0),
+                                            Vec2::new(screen_width / 2.0, screen_height),
+                                        );
+                                    }
+                                }
+                                window.is_dragging = false;
+                            }
+                            EventType::Hover(data) => {
+                                if window.is_dragging {
+                                    window.position = Vec2::new(
+                                        window.offset.x + data.position.0,
+                                        window.offset.y + data.position.1,
+                                    );
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                },
+            );
+
+            // Title bar buttons handle their own presses so they don't start a drag
+            let on_minimize = window.on_minimize.clone();
+            let minimize_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut commands: Commands,
+                      mut event: ResMut<KEvent>| {
+                    match event.event_type {
+                        EventType::MouseDown(..) => event.stop_propagation(),
+                        EventType::Click(..) => {
+                            event.stop_propagation();
+                            on_minimize.call(&mut commands, window_entity, ());
+                        }
+                        _ => {}
+                    }
+                },
+            );
+            let on_maximize = window.on_maximize.clone();
+            let maximize_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut commands: Commands,
+                      mut event: ResMut<KEvent>,
+                      mut query: Query<&mut KWindowState>| {
+                    match event.event_type {
+                        EventType::MouseDown(..) => event.stop_propagation(),
+                        EventType::Click(..) => {
+                            event.stop_propagation();
+                            if let Ok(mut window) = query.get_mut(state_entity) {
+                                if window.maximized {
+                                    window.restore();
+                                } else {
+                                    window.maximize();
+                                }
+                                on_maximize.call(&mut commands, window_entity, window.maximized);
+                            }
+                        }
+                        _ => {}
+                    }
+                },
+            );
+            let on_close = window.on_close.clone();
+            let close_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut commands: Commands,
+                      mut event: ResMut<KEvent>| {
+                    match event.event_type {
+                        EventType::MouseDown(..) => event.stop_propagation(),
+                        EventType::Click(..) => {
+                            event.stop_propagation();
+                            on_close.call(&mut commands, window_entity, ());
+                        }
+                        _ => {}
+                    }
+                },
+            );
+
             rsx! {
                 <ElementBundle
                     id={"window_entity"}
@@ -168,12 +515,12 @@ pub fn window_render(
                         border_radius: StyleProp::Value(Corner::all(10.0)),
                         render_command: StyleProp::Value(RenderCommand::Quad),
                         position_type: StyleProp::Value(KPositionType::SelfDirected),
-                        left: StyleProp::Value(Units::Pixels(state.position.x)),
-                        top: StyleProp::Value(Units::Pixels(state.position.y)),
-                        width: StyleProp::Value(Units::Pixels(window.size.x)),
-                        height: StyleProp::Value(Units::Pixels(window.size.y)),
-                        min_width: StyleProp::Value(Units::Pixels(window.size.x)),
-                        min_height: StyleProp::Value(Units::Pixels(window.size.y)),
+                        left: StyleProp::Value(left),
+                        top: StyleProp::Value(top),
+                        width: StyleProp::Value(width),
+                        height: StyleProp::Value(height),
+                        min_width: StyleProp::Value(width),
+                        min_height: StyleProp::Value(height),
                         box_shadow: vec![BoxShadow {
                             color: Color::rgba(0.0, 0.0, 0.0, 0.5),
                             radius: 8.0,
@@ -216,48 +563,58 @@ pub fn window_render(
                                 ..Default::default()
                             }}
                         />
+                        <ElementBundle
+                            styles={KStyle {
+                                layout_type: StyleProp::Value(LayoutType::Row),
+                                position_type: StyleProp::Value(KPositionType::SelfDirected),
+                                left: Units::Stretch(1.0).into(),
+                                right: Units::Pixels(8.0).into(),
+                                top: Units::Stretch(1.0).into(),
+                                bottom: Units::Stretch(1.0).into(),
+                                width: Units::Auto.into(),
+                                height: Units::Pixels(TITLE_BAR_BUTTON_SIZE).into(),
+                                col_between: Units::Pixels(6.0).into(),
+                                ..Default::default()
+                            }}
+                        >
+                            {
+                                if window.minimizable {
+                                    constructor! {
+                                        <BackgroundBundle
+                                            styles={title_bar_button_styles(Color::rgba(0.949, 0.749, 0.266, 1.0))}
+                                            on_event={minimize_event}
+                                        />
+                                    }
+                                }
+                            }
+                            {
+                                if window.maximizable {
+                                    constructor! {
+                                        <BackgroundBundle
+                                            styles={title_bar_button_styles(Color::rgba(0.313, 0.788, 0.345, 1.0))}
+                                            on_event={maximize_event}
+                                        />
+                                    }
+                                }
+                            }
+                            {
+                                if window.closable {
+                                    constructor! {
+                                        <BackgroundBundle
+                                            styles={title_bar_button_styles(Color::rgba(0.929, 0.352, 0.333, 1.0))}
+                                            on_event={close_event}
+                                        />
+                                    }
+                                }
+                            }
+                        </ElementBundle>
                     </BackgroundBundle>
                     {
                         // This code needs to go after the closing tag for the background bundle as that is when the
                         // widget is "spawned". Adding this code after just the starting tag will cause this OnEvent
                         // to be wiped out with a default version.
                         if window.draggable {
-                            commands
-                                .entity(title_bar_entity)
-                                .insert(OnEvent::new(
-                                    move |In(entity): In<Entity>,
-                                    mut event_dispatcher_context: ResMut<EventDispatcherContext>,
-                                    mut event: ResMut<KEvent>,
-                                        mut query: Query<&mut KWindowState>| {
-                                        if let Ok(mut window) = query.get_mut(state_entity) {
-                                            event.prevent_default();
-                                            event.stop_propagation();
-                                            match event.event_type {
-                                                EventType::MouseDown(data) => {
-                                                    event_dispatcher_context.capture_cursor(entity);
-                                                    window.is_dragging = true;
-                                                    window.offset = Vec2::new(
-                                                        window.position.x - data.position.0,
-                                                        window.position.y - data.position.1,
-                                                    );
-                                                }
-                                                EventType::MouseUp(..) => {
-                                                    event_dispatcher_context.release_cursor(entity);
-                                                    window.is_dragging = false;
-                                                }
-                                                EventType::Hover(data) => {
-                                                    if window.is_dragging {
-                                                        window.position = Vec2::new(
-                                                            window.offset.x + data.position.0,
-                                                            window.offset.y + data.position.1,
-                                                        );
-                                                    }
-                                                }
-                                                _ => {}
-                                            }
-                                        }
-                                    },
-                                ));
+                            commands.entity(title_bar_entity).insert(title_bar_event);
                         }
                     }
                     <BackgroundBundle
@@ -278,6 +635,79 @@ pub fn window_render(
                         })}
                         children={window_children.clone()}
                     />
+                    {
+                        if window.resizable && !state.maximized {
+                            for handle in ResizeHandle::ALL {
+                                let window = window.clone();
+                                let on_event = OnEvent::new(
+                                    move |In(_entity): In<Entity>,
+                                          mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+                                          mut event: ResMut<KEvent>,
+                                          mut query: Query<&mut KWindowState>| {
+                                        if let Ok(mut state) = query.get_mut(state_entity) {
+                                            if event.event_type.drag_event().is_some() {
+                                                event.stop_propagation();
+                                            }
+                                            let (origin, pointer) = match &event.event_type {
+                                                EventType::DragStart(data) => {
+                                                    // The drag starts where the cursor was pressed
+                                                    let origin = ResizeOrigin {
+                                                        position: state.position,
+                                                        size: state.size,
+                                                        pointer: Vec2::new(
+                                                            data.position.0 - data.delta.0,
+                                                            data.position.1 - data.delta.1,
+                                                        ),
+                                                    };
+                                                    event_dispatcher_context.set_drag_payload(origin);
+                                                    (Some(origin), Vec2::from(data.position))
+                                                }
+                                                EventType::Drag(data) => (
+                                                    data.payload
+                                                        .as_ref()
+                                                        .and_then(|payload| payload.get::<ResizeOrigin>())
+                                                        .copied(),
+                                                    Vec2::from(data.position),
+                                                ),
+                                                _ => (None, Vec2::ZERO),
+                                            };
+
+                                            if let Some(origin) = origin {
+                                                let directions = handle.directions();
+                                                let size = window.clamp_size(
+                                                    origin.size + (pointer - origin.pointer) * directions,
+                                                );
+                                                // Dragging the left or top edge keeps the opposite edge in place
+                                                let mut position = origin.position;
+                                                if directions.x < 0.0 {
+                                                    position.x += origin.size.x - size.x;
+                                                }
+                                                if directions.y < 0.0 {
+                                                    position.y += origin.size.y - size.y;
+                                                }
+
+                                                if state.size != size || state.position != position {
+                                                    state.size = size;
+                                                    state.position = position;
+                                                }
+                                            }
+                                        }
+                                    },
+                                );
+
+                                let handle_entity = widget_context.spawn_widget(&mut commands, None, parent_id);
+                                commands.entity(handle_entity).insert((
+                                    ElementBundle {
+                                        styles: handle.styles(size),
+                                        on_event,
+                                        ..Default::default()
+                                    },
+                                    Draggable,
+                                ));
+                                children.add(handle_entity);
+                            }
+                        }
+                    }
                 </ElementBundle>
             };
         }
@@ -3,14 +3,33 @@ use bevy::{
     window::{WindowCreated, WindowResized},
 };
 
-/// Tracks the bevy window size.
+use crate::KUiScale;
+
+/// Tracks the bevy window size in UI units (logical pixels divided by the [`KUiScale`]).
+///
+/// The resource always holds the size of the whole window, even when several
+/// [`KayakRootContext`](crate::prelude::KayakRootContext)s share it through cameras with
+/// different viewports. Each context lays out its widgets relative to its camera's viewport, so
+/// widgets that need the size of the area they're shown in (or UIs rendered to an image) should
+/// use [`WindowSize::of_camera`] instead.
 #[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
 pub struct WindowSize(pub f32, pub f32);
 
+impl WindowSize {
+    /// The size of the area the given camera renders to, be it a window, part of a window
+    /// (when the camera has a viewport) or an image, in UI units
+    pub fn of_camera(camera: &Camera, ui_scale: KUiScale) -> Option<Self> {
+        camera
+            .logical_viewport_size()
+            .map(|size| Self(size.x / ui_scale.0, size.y / ui_scale.0))
+    }
+}
+
 pub fn update_window_size(
     mut window_resized_events: EventReader<WindowResized>,
     mut window_created_events: EventReader<WindowCreated>,
-    windows: Query<&Window>,
+    windows: Query<(Entity, &Window)>,
+    ui_scale: Res<KUiScale>,
     mut window_size: ResMut<WindowSize>,
 ) {
     let mut changed_windows = Vec::new();
@@ -44,11 +63,21 @@ pub fn update_window_size(
         changed_windows.push(event.window);
     }
 
+    // Changing the UI scale changes the size of every window in UI units
+    if ui_scale.is_changed() {
+        for (window_entity, _) in windows.iter() {
+            if !changed_windows.contains(&window_entity) {
+                changed_windows.push(window_entity);
+            }
+        }
+    }
+
     for window_entity in changed_windows {
-        if let Ok(window) = windows.get(window_entity) {
-            let width = window.width();
-            let height = window.height();
-            *window_size = WindowSize(width, height);
+        if let Ok((_, window)) = windows.get(window_entity) {
+            let width = window.width() / ui_scale.0;
+            let height = window.height() / ui_scale.0;
+            // Only flag an actual change, see `OnResize`
+            window_size.set_if_neq(WindowSize(width, height));
         }
     }
 }